@@ -0,0 +1,233 @@
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use crate::Scalar;
+
+/// An angle, stored in radians. Use `Angle::from_degrees` and `Angle::degrees` to work
+/// in degrees without having to convert by hand.
+///
+/// A plain scalar converts into an `Angle` as a value in radians, so any function taking
+/// `impl Into<Angle>` also accepts a radian `f64` directly.
+/// ```
+/// use rsgeo::Angle;
+/// let a = Angle::from_degrees(90.0);
+/// assert_eq!(a.radians(), std::f64::consts::PI / 2.0);
+/// assert_eq!((a * 3.0).normalize_signed().degrees(), -90.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Angle<T = f64> {
+    radians: T,
+}
+
+impl<T: Scalar> Angle<T> {
+    /// Create an angle from a value in radians
+    pub fn from_radians(radians: T) -> Angle<T> {
+        Angle { radians }
+    }
+
+    /// Create an angle from a value in degrees
+    pub fn from_degrees(degrees: T) -> Angle<T> {
+        Angle {
+            radians: degrees.to_radians(),
+        }
+    }
+
+    /// The angle in radians
+    pub fn radians(&self) -> T {
+        self.radians
+    }
+
+    /// The angle in degrees
+    pub fn degrees(&self) -> T {
+        self.radians.to_degrees()
+    }
+
+    /// normalize wraps the angle into the range [0, 2pi)
+    /// ```
+    /// let a = rsgeo::Angle::from_degrees(-90.0);
+    /// assert_eq!(a.normalize().degrees(), 270.0);
+    /// ```
+    pub fn normalize(&self) -> Angle<T> {
+        let tau = T::TAU();
+        let r = self.radians % tau;
+        let r = if r < T::zero() { r + tau } else { r };
+        // Adding tau can round up to exactly tau for tiny negative inputs
+        Angle {
+            radians: if r >= tau { T::zero() } else { r },
+        }
+    }
+
+    /// normalize_signed wraps the angle into the range (-pi, pi]
+    /// ```
+    /// let a = rsgeo::Angle::from_degrees(270.0);
+    /// assert_eq!(a.normalize_signed().degrees(), -90.0);
+    /// ```
+    pub fn normalize_signed(&self) -> Angle<T> {
+        let r = self.normalize().radians;
+        Angle {
+            radians: if r > T::PI() { r - T::TAU() } else { r },
+        }
+    }
+
+    /// The sine of the angle
+    pub fn sin(&self) -> T {
+        self.radians.sin()
+    }
+
+    /// The cosine of the angle
+    pub fn cos(&self) -> T {
+        self.radians.cos()
+    }
+
+    /// The tangent of the angle
+    pub fn tan(&self) -> T {
+        self.radians.tan()
+    }
+
+    /// The sine and cosine of the angle, computed together
+    pub fn sin_cos(&self) -> (T, T) {
+        self.radians.sin_cos()
+    }
+}
+
+impl<T: Scalar> From<T> for Angle<T> {
+    /// Interpret a scalar as an angle in radians
+    fn from(radians: T) -> Self {
+        Angle::from_radians(radians)
+    }
+}
+
+impl<T: Scalar> PartialEq<T> for Angle<T> {
+    /// Compare an angle against a value in radians
+    fn eq(&self, other: &T) -> bool {
+        self.radians == *other
+    }
+}
+
+impl<T: Scalar> Add for Angle<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Angle::from_radians(self.radians + rhs.radians)
+    }
+}
+
+impl<T: Scalar> Sub for Angle<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Angle::from_radians(self.radians - rhs.radians)
+    }
+}
+
+impl<T: Scalar> Neg for Angle<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Angle::from_radians(-self.radians)
+    }
+}
+
+impl<T: Scalar> Mul<T> for Angle<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Angle::from_radians(self.radians * rhs)
+    }
+}
+
+impl<T: Scalar> Div<T> for Angle<T> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Angle::from_radians(self.radians / rhs)
+    }
+}
+
+impl<T: Scalar> AddAssign for Angle<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.radians += rhs.radians;
+    }
+}
+
+impl<T: Scalar> SubAssign for Angle<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.radians -= rhs.radians;
+    }
+}
+
+macro_rules! impl_angle_into_scalar {
+    ($($t:ty),*) => {
+        $(
+            impl From<Angle<$t>> for $t {
+                /// Get the angle in radians
+                fn from(a: Angle<$t>) -> Self {
+                    a.radians
+                }
+            }
+        )*
+    };
+}
+
+impl_angle_into_scalar!(f32, f64);
+
+// As with Point, this is only implemented for f64 so that float literals stay unambiguous
+impl Mul<Angle<f64>> for f64 {
+    type Output = Angle<f64>;
+
+    fn mul(self, rhs: Angle<f64>) -> Self::Output {
+        rhs * self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar_isclose;
+    use std::f64::consts::{PI, TAU};
+
+    #[test]
+    fn test_degrees_round_trip() {
+        let a = Angle::from_degrees(123.0);
+        assert!(scalar_isclose(123.0, a.degrees()));
+        assert!(scalar_isclose(a.radians(), 123.0_f64.to_radians()))
+    }
+
+    #[test]
+    fn test_normalize_range() {
+        for &deg in &[-720.0, -450.0, -1e-12, 0.0, 360.0, 725.0] {
+            let n = Angle::from_degrees(deg).normalize().radians();
+            assert!((0.0..TAU).contains(&n), "{} normalized to {}", deg, n)
+        }
+        assert!(scalar_isclose(
+            5.0,
+            Angle::from_degrees(725.0).normalize().degrees()
+        ))
+    }
+
+    #[test]
+    fn test_normalize_signed_range() {
+        assert_eq!(Angle::from_radians(PI).normalize_signed(), PI);
+        assert_eq!(Angle::from_radians(-PI).normalize_signed(), PI);
+        assert!(scalar_isclose(
+            -10.0,
+            Angle::from_degrees(350.0).normalize_signed().degrees()
+        ))
+    }
+
+    #[test]
+    fn test_f32_normalize() {
+        let a = Angle::from_degrees(-90.0_f32).normalize();
+        assert!(scalar_isclose(270.0, a.degrees()))
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut a = Angle::from_degrees(30.0) + Angle::from_degrees(60.0);
+        assert!(scalar_isclose(90.0, a.degrees()));
+        a -= Angle::from_degrees(45.0);
+        assert!(scalar_isclose(45.0, a.degrees()));
+        assert!(scalar_isclose(-90.0, (-(2.0 * a)).degrees()));
+        assert!(scalar_isclose(15.0, (a / 3.0).degrees()))
+    }
+}
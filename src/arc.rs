@@ -0,0 +1,271 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{Angle, Circle, LineSegment, Point, Rect, Scalar};
+
+/// Which way an arc turns as it runs from its start to its end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArcDirection {
+    /// Decreasing angles, like a G2 move
+    Clockwise,
+    /// Increasing angles, like a G3 move
+    CounterClockwise,
+}
+
+/// A circular arc, running around the circle with the given center and radius from
+/// `start_angle` to `end_angle` in `direction`. Equal start and end angles describe a
+/// full circle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Arc<T = f64> {
+    pub center: Point<T>,
+    pub radius: T,
+    pub start_angle: Angle<T>,
+    pub end_angle: Angle<T>,
+    pub direction: ArcDirection,
+}
+
+impl<T: Scalar> Arc<T> {
+    pub fn new(
+        center: Point<T>,
+        radius: T,
+        start_angle: impl Into<Angle<T>>,
+        end_angle: impl Into<Angle<T>>,
+        direction: ArcDirection,
+    ) -> Arc<T> {
+        Arc {
+            center,
+            radius,
+            start_angle: start_angle.into(),
+            end_angle: end_angle.into(),
+            direction,
+        }
+    }
+
+    /// The signed angle swept from start to end, positive for counter-clockwise arcs.
+    /// Its magnitude is in (0, 2pi].
+    /// ```
+    /// use rsgeo::{Angle, Arc, ArcDirection, Point};
+    /// let origin = Point{x: 0.0, y: 0.0};
+    /// let ccw: Arc = Arc::new(origin, 1.0, Angle::from_degrees(350.0), Angle::from_degrees(10.0),
+    ///     ArcDirection::CounterClockwise);
+    /// assert!((ccw.sweep().degrees() - 20.0).abs() < 1e-9);
+    /// let cw = Arc { direction: ArcDirection::Clockwise, ..ccw };
+    /// assert!((cw.sweep().degrees() + 340.0).abs() < 1e-9);
+    /// ```
+    pub fn sweep(&self) -> Angle<T> {
+        let ccw = match self.direction {
+            ArcDirection::CounterClockwise => self.end_angle - self.start_angle,
+            ArcDirection::Clockwise => self.start_angle - self.end_angle,
+        }
+        .normalize()
+        .radians();
+        let magnitude = if ccw == T::zero() { T::TAU() } else { ccw };
+        match self.direction {
+            ArcDirection::CounterClockwise => Angle::from_radians(magnitude),
+            ArcDirection::Clockwise => Angle::from_radians(-magnitude),
+        }
+    }
+
+    /// The distance along the arc from start to end
+    pub fn length(&self) -> T {
+        self.radius * self.sweep().radians().abs()
+    }
+
+    /// The point a fraction `t` of the way along the arc, so that zero is the start
+    /// point and one the end point
+    pub fn point_at(&self, t: T) -> Point<T> {
+        self.center + Point::from_polar(self.radius, self.start_angle + self.sweep() * t)
+    }
+
+    pub fn start_point(&self) -> Point<T> {
+        self.center + Point::from_polar(self.radius, self.start_angle)
+    }
+
+    pub fn end_point(&self) -> Point<T> {
+        self.center + Point::from_polar(self.radius, self.end_angle)
+    }
+
+    /// Whether the ray from the center at `angle` passes through the arc, including its
+    /// endpoints
+    pub fn contains_angle(&self, angle: impl Into<Angle<T>>) -> bool {
+        let angle = angle.into();
+        let from_start = match self.direction {
+            ArcDirection::CounterClockwise => angle - self.start_angle,
+            ArcDirection::Clockwise => self.start_angle - angle,
+        };
+        from_start.normalize().radians() <= self.sweep().radians().abs()
+    }
+
+    /// The smallest axis-aligned rectangle containing the arc. It is spanned by the
+    /// endpoints, along with the extreme points of the circle at the four axis
+    /// directions that the arc passes through.
+    pub fn bounding_rect(&self) -> Rect<T> {
+        let mut rect = Rect::from_corners(self.start_point(), self.end_point());
+        for quarter in 0..4 {
+            let angle = Angle::from_radians(T::from_f64(f64::from(quarter)) * T::FRAC_PI_2());
+            if self.contains_angle(angle) {
+                let p = self.center + Point::from_polar(self.radius, angle);
+                rect = rect.union(&Rect::from_corners(p, p));
+            }
+        }
+        rect
+    }
+
+    /// The points where the arc crosses the segment, in order from `p1` to `p2`. The
+    /// unused entries are None.
+    /// ```
+    /// use rsgeo::{Angle, Arc, ArcDirection, LineSegment, Point};
+    /// // The upper half of the unit circle
+    /// let arc = Arc::new(Point{x: 0.0, y: 0.0}, 1.0, 0.0, Angle::from_degrees(180.0),
+    ///     ArcDirection::CounterClockwise);
+    /// let vertical: LineSegment = "(0, -2) -> (0, 2)".parse().unwrap();
+    /// assert_eq!(arc.intersect_segment(&vertical), [Some(Point{x: 0.0, y: 1.0}), None]);
+    /// ```
+    pub fn intersect_segment(&self, seg: &LineSegment<T>) -> [Option<Point<T>>; 2] {
+        let mut found = [None, None];
+        let mut n = 0;
+        let circle = Circle::new(self.center, self.radius);
+        for &p in circle.intersect_segment(seg).iter().flatten() {
+            if self.contains_angle((p - self.center).angle()) {
+                found[n] = Some(p);
+                n += 1;
+            }
+        }
+        found
+    }
+
+    /// Approximate the arc by a polyline whose segments stray at most `tolerance` from
+    /// the true curve. The points lie on the arc and run from the start point to the
+    /// end point, both included. Panics if `tolerance` is not positive.
+    /// ```
+    /// use rsgeo::{Angle, Arc, ArcDirection, Point};
+    /// let arc = Arc::new(Point{x: 0.0, y: 0.0}, 10.0, 0.0, Angle::from_degrees(90.0),
+    ///     ArcDirection::CounterClockwise);
+    /// let pts = arc.approximate(0.1);
+    /// assert_eq!(pts[0], arc.start_point());
+    /// assert_eq!(*pts.last().unwrap(), arc.point_at(1.0));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn approximate(&self, tolerance: T) -> Vec<Point<T>> {
+        assert!(
+            tolerance > T::zero(),
+            "tolerance must be positive, got {}",
+            tolerance
+        );
+        // A chord subtending an angle of 2 * acos(1 - tolerance / radius) strays exactly
+        // `tolerance` from the circle at its middle
+        let cos_half_step = (T::one() - tolerance / self.radius).max(-T::one());
+        let max_step = T::from_f64(2.0) * cos_half_step.acos();
+        let segments = (self.sweep().radians().abs() / max_step)
+            .ceil()
+            .max(T::one());
+        let n = segments.as_f64() as usize;
+        (0..=n)
+            .map(|i| self.point_at(T::from_f64(i as f64) / segments))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quarter(direction: ArcDirection) -> Arc {
+        Arc::new(
+            Point { x: 1.0, y: 1.0 },
+            2.0,
+            0.0,
+            Angle::from_degrees(90.0),
+            direction,
+        )
+    }
+
+    #[test]
+    fn test_length_and_endpoints() {
+        let ccw = quarter(ArcDirection::CounterClockwise);
+        assert!(crate::scalar_isclose(core::f64::consts::PI, ccw.length()));
+        let cw = quarter(ArcDirection::Clockwise);
+        assert!(crate::scalar_isclose(
+            3.0 * core::f64::consts::PI,
+            cw.length()
+        ));
+        for arc in &[ccw, cw] {
+            assert!(arc.point_at(0.0).isclose(Point { x: 3.0, y: 1.0 }));
+            assert!(arc.point_at(1.0).isclose(Point { x: 1.0, y: 3.0 }));
+            assert!(arc.end_point().isclose(arc.point_at(1.0)));
+        }
+        // Halfway round the long way is opposite the short arc's midpoint
+        let opposite = cw.point_at(0.5) - cw.center;
+        let mid = ccw.point_at(0.5) - ccw.center;
+        assert!(opposite.isclose(-mid))
+    }
+
+    #[test]
+    fn test_full_circle() {
+        let arc = Arc::new(
+            Point { x: 0.0, y: 0.0 },
+            1.0,
+            1.0,
+            1.0,
+            ArcDirection::Clockwise,
+        );
+        assert!(crate::scalar_isclose(core::f64::consts::TAU, arc.length()));
+        assert!(arc.contains_angle(4.0));
+        let rect = arc.bounding_rect();
+        assert!(rect.min().isclose(Point { x: -1.0, y: -1.0 }));
+        assert!(rect.max().isclose(Point { x: 1.0, y: 1.0 }))
+    }
+
+    #[test]
+    fn test_contains_angle() {
+        let ccw = quarter(ArcDirection::CounterClockwise);
+        assert!(ccw.contains_angle(Angle::from_degrees(45.0)));
+        assert!(ccw.contains_angle(Angle::from_degrees(90.0)));
+        assert!(ccw.contains_angle(Angle::from_degrees(-360.0)));
+        assert!(!ccw.contains_angle(Angle::from_degrees(180.0)));
+        let cw = quarter(ArcDirection::Clockwise);
+        assert!(!cw.contains_angle(Angle::from_degrees(45.0)));
+        assert!(cw.contains_angle(Angle::from_degrees(180.0)))
+    }
+
+    #[test]
+    fn test_bounding_rect() {
+        // The short arc is spanned by its endpoints, and the long way round reaches the
+        // other three sides of the circle
+        let ccw = quarter(ArcDirection::CounterClockwise);
+        let rect = ccw.bounding_rect();
+        assert!(rect.min().isclose(Point { x: 1.0, y: 1.0 }));
+        assert!(rect.max().isclose(Point { x: 3.0, y: 3.0 }));
+        let rect = quarter(ArcDirection::Clockwise).bounding_rect();
+        assert!(rect.min().isclose(Point { x: -1.0, y: -1.0 }));
+        assert!(rect.max().isclose(Point { x: 3.0, y: 3.0 }))
+    }
+
+    #[test]
+    fn test_intersect_segment_only_on_arc() {
+        let arc = quarter(ArcDirection::CounterClockwise);
+        let chord: LineSegment = "(0, 2.5) -> (4, 2.5)".parse().unwrap();
+        let [first, second] = arc.intersect_segment(&chord);
+        let first = first.unwrap();
+        assert!(first.x > 1.0 && crate::scalar_isclose(2.5, first.y));
+        assert_eq!(None, second);
+        let other_side: LineSegment = "(-2, 0) -> (0, -2)".parse().unwrap();
+        assert_eq!([None, None], arc.intersect_segment(&other_side))
+    }
+
+    #[test]
+    fn test_approximate_respects_tolerance() {
+        let arc = quarter(ArcDirection::Clockwise);
+        let tolerance = 0.01;
+        let pts = arc.approximate(tolerance);
+        let n = pts.len() - 1;
+        for i in 0..n {
+            let seg = LineSegment::new(pts[i], pts[i + 1]);
+            let bulge = arc.point_at((i as f64 + 0.5) / n as f64);
+            assert!(seg.distance_to_point(bulge) <= tolerance * (1.0 + 1e-9));
+        }
+        assert!(pts[n].isclose(arc.end_point()))
+    }
+}
@@ -0,0 +1,462 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::{LineSegment, Polyline};
+use crate::{Point, Rect, Scalar};
+
+/// A quadratic Bézier curve, running from `p0` to `p2` and pulled toward the control
+/// point `p1`, as in TrueType outlines
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuadBezier<T = f64> {
+    pub p0: Point<T>,
+    pub p1: Point<T>,
+    pub p2: Point<T>,
+}
+
+/// A cubic Bézier curve, running from `p0` to `p3` and leaving and arriving in the
+/// directions of the control points `p1` and `p2`, as in SVG paths and PostScript
+/// outlines
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubicBezier<T = f64> {
+    pub p0: Point<T>,
+    pub p1: Point<T>,
+    pub p2: Point<T>,
+    pub p3: Point<T>,
+}
+
+impl<T: Scalar> QuadBezier<T> {
+    pub fn new(p0: Point<T>, p1: Point<T>, p2: Point<T>) -> QuadBezier<T> {
+        QuadBezier { p0, p1, p2 }
+    }
+
+    /// The point at parameter `t`, so that zero is the start point and one the end
+    /// point. The parameter is not in proportion to the distance along the curve.
+    /// ```
+    /// use rsgeo::{Point, QuadBezier};
+    /// let curve = QuadBezier::new(Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 2.0}, Point{x: 2.0, y: 0.0});
+    /// assert_eq!(curve.point_at(0.5), Point{x: 1.0, y: 1.0});
+    /// ```
+    pub fn point_at(&self, t: T) -> Point<T> {
+        let (a, b) = (self.p0.lerp(&self.p1, t), self.p1.lerp(&self.p2, t));
+        a.lerp(&b, t)
+    }
+
+    /// The derivative of the position with respect to the parameter at `t`, which
+    /// points along the curve and has the speed at which `point_at` moves as its
+    /// magnitude
+    pub fn derivative_at(&self, t: T) -> Point<T> {
+        let two = T::from_f64(2.0);
+        (self.p1 - self.p0).lerp(&(self.p2 - self.p1), t) * two
+    }
+
+    /// The smallest axis-aligned rectangle containing the curve, spanned by its ends
+    /// and the point where each coordinate turns back, if it does
+    pub fn bounding_rect(&self) -> Rect<T> {
+        let mut rect = Rect::from_corners(self.p0, self.p2);
+        let (a, b) = (self.p1 - self.p0, self.p2 - self.p1);
+        // Each coordinate of the derivative is linear in t, and zero at most once
+        for [start, end] in [[a.x, b.x], [a.y, b.y]] {
+            if let [Some(t), _] =
+                roots_between([T::zero(), end - start, start], T::zero(), T::one())
+            {
+                let p = self.point_at(t);
+                rect = rect.union(&Rect::from_corners(p, p));
+            }
+        }
+        rect
+    }
+
+    /// The two halves of the curve either side of the point at `t`, which together run
+    /// along the same path
+    /// ```
+    /// use rsgeo::{Point, QuadBezier};
+    /// let curve = QuadBezier::new(Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 2.0}, Point{x: 2.0, y: 0.0});
+    /// let (before, after) = curve.split(0.5);
+    /// assert_eq!(before.p2, Point{x: 1.0, y: 1.0});
+    /// assert_eq!(after.point_at(0.5), curve.point_at(0.75));
+    /// ```
+    pub fn split(&self, t: T) -> (QuadBezier<T>, QuadBezier<T>) {
+        let (a, b) = (self.p0.lerp(&self.p1, t), self.p1.lerp(&self.p2, t));
+        let mid = a.lerp(&b, t);
+        (
+            QuadBezier::new(self.p0, a, mid),
+            QuadBezier::new(mid, b, self.p2),
+        )
+    }
+
+    /// The same curve as a cubic, with its control points a third of the way from the
+    /// ends
+    pub fn to_cubic(&self) -> CubicBezier<T> {
+        let third = T::one() / T::from_f64(3.0);
+        CubicBezier::new(
+            self.p0,
+            self.p1.lerp(&self.p0, third),
+            self.p1.lerp(&self.p2, third),
+            self.p2,
+        )
+    }
+
+    /// The length of the curve, within `tolerance` of the true length. See
+    /// `CubicBezier::length` for how it is found. Panics if `tolerance` is not
+    /// positive.
+    pub fn length(&self, tolerance: T) -> T {
+        self.to_cubic().length(tolerance)
+    }
+
+    /// A polyline through points on the curve that no point of the curve strays more
+    /// than `tolerance` from. See `CubicBezier::flatten`. Panics if `tolerance` is not
+    /// positive.
+    #[cfg(feature = "alloc")]
+    pub fn flatten(&self, tolerance: T) -> Polyline<T> {
+        self.to_cubic().flatten(tolerance)
+    }
+}
+
+impl<T: Scalar> CubicBezier<T> {
+    pub fn new(p0: Point<T>, p1: Point<T>, p2: Point<T>, p3: Point<T>) -> CubicBezier<T> {
+        CubicBezier { p0, p1, p2, p3 }
+    }
+
+    /// The point at parameter `t`, so that zero is the start point and one the end
+    /// point. The parameter is not in proportion to the distance along the curve.
+    /// ```
+    /// use rsgeo::{CubicBezier, Point};
+    /// let curve = CubicBezier::new(
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 0.0, y: 4.0}, Point{x: 4.0, y: 4.0}, Point{x: 4.0, y: 0.0},
+    /// );
+    /// assert_eq!(curve.point_at(0.5), Point{x: 2.0, y: 3.0});
+    /// ```
+    pub fn point_at(&self, t: T) -> Point<T> {
+        let (before, _) = self.split(t);
+        before.p3
+    }
+
+    /// The derivative of the position with respect to the parameter at `t`, which
+    /// points along the curve and has the speed at which `point_at` moves as its
+    /// magnitude
+    pub fn derivative_at(&self, t: T) -> Point<T> {
+        self.derivative().point_at(t)
+    }
+
+    /// The curve traced by the derivative, whose control points are three times the
+    /// steps between those of this curve
+    pub fn derivative(&self) -> QuadBezier<T> {
+        let three = T::from_f64(3.0);
+        QuadBezier::new(
+            (self.p1 - self.p0) * three,
+            (self.p2 - self.p1) * three,
+            (self.p3 - self.p2) * three,
+        )
+    }
+
+    /// The smallest axis-aligned rectangle containing the curve, spanned by its ends
+    /// and the points where either coordinate turns back
+    /// ```
+    /// use rsgeo::{CubicBezier, Point, Rect};
+    /// let curve = CubicBezier::new(
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 0.0, y: 4.0}, Point{x: 4.0, y: 4.0}, Point{x: 4.0, y: 0.0},
+    /// );
+    /// assert_eq!(curve.bounding_rect(), Rect::from_corners(Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 3.0}));
+    /// ```
+    pub fn bounding_rect(&self) -> Rect<T> {
+        let mut rect = Rect::from_corners(self.p0, self.p3);
+        let d = self.derivative();
+        // Each coordinate of the derivative is a quadratic in t
+        for [a, b, c] in [[d.p0.x, d.p1.x, d.p2.x], [d.p0.y, d.p1.y, d.p2.y]] {
+            let two = T::from_f64(2.0);
+            let coefficients = [a - two * b + c, two * (b - a), a];
+            for t in roots_between(coefficients, T::zero(), T::one())
+                .iter()
+                .flatten()
+            {
+                let p = self.point_at(*t);
+                rect = rect.union(&Rect::from_corners(p, p));
+            }
+        }
+        rect
+    }
+
+    /// The two halves of the curve either side of the point at `t`, which together run
+    /// along the same path
+    pub fn split(&self, t: T) -> (CubicBezier<T>, CubicBezier<T>) {
+        let (a, b, c) = (
+            self.p0.lerp(&self.p1, t),
+            self.p1.lerp(&self.p2, t),
+            self.p2.lerp(&self.p3, t),
+        );
+        let (d, e) = (a.lerp(&b, t), b.lerp(&c, t));
+        let mid = d.lerp(&e, t);
+        (
+            CubicBezier::new(self.p0, a, d, mid),
+            CubicBezier::new(mid, e, c, self.p3),
+        )
+    }
+
+    /// The length of the curve, within `tolerance` of the true length. The length of a
+    /// piece of the curve lies between that of its chord and that of its control
+    /// polygon, so the curve is cut in half until the two are within twice the
+    /// tolerance left for each piece, and the halfway value between them is taken.
+    /// Panics if `tolerance` is not positive.
+    /// ```
+    /// use rsgeo::{CubicBezier, Point};
+    /// // Close to a quarter of the unit circle
+    /// let k = 0.5519150244935105;
+    /// let curve = CubicBezier::new(
+    ///     Point{x: 1.0, y: 0.0}, Point{x: 1.0, y: k}, Point{x: k, y: 1.0}, Point{x: 0.0, y: 1.0},
+    /// );
+    /// assert!((curve.length(1e-9) - std::f64::consts::FRAC_PI_2).abs() < 1e-4);
+    /// ```
+    pub fn length(&self, tolerance: T) -> T {
+        assert!(
+            tolerance > T::zero(),
+            "tolerance must be positive, got {}",
+            tolerance
+        );
+        self.length_within(tolerance, 0)
+    }
+
+    fn length_within(&self, tolerance: T, depth: u32) -> T {
+        let chord = self.p0.distance(&self.p3);
+        let hull =
+            self.p0.distance(&self.p1) + self.p1.distance(&self.p2) + self.p2.distance(&self.p3);
+        // Past this depth the pieces are too small for rounding to leave the bounds
+        // any closer
+        if hull - chord <= T::from_f64(2.0) * tolerance || depth >= 32 {
+            return (chord + hull) / T::from_f64(2.0);
+        }
+        let (before, after) = self.split(T::from_f64(0.5));
+        let half = tolerance / T::from_f64(2.0);
+        before.length_within(half, depth + 1) + after.length_within(half, depth + 1)
+    }
+
+    /// A polyline through points on the curve that no point of the curve strays more
+    /// than `tolerance` from, running from the start point to the end point. The curve
+    /// lies within the convex hull of its control points, so a piece whose inner
+    /// control points are both within `tolerance` of its chord is replaced by that
+    /// chord, and any other piece is cut in half. The points crowd together where the
+    /// curve bends sharply and spread out along flatter stretches, and a straight
+    /// curve gives a single segment. Panics if `tolerance` is not positive.
+    /// ```
+    /// use rsgeo::{CubicBezier, Point};
+    /// let curve = CubicBezier::new(
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 0.0, y: 4.0}, Point{x: 4.0, y: 4.0}, Point{x: 4.0, y: 0.0},
+    /// );
+    /// let line = curve.flatten(0.01);
+    /// assert_eq!(line.points()[0], curve.p0);
+    /// assert_eq!(*line.points().last().unwrap(), curve.p3);
+    /// let p = curve.point_at(0.3);
+    /// assert!(line.segments().any(|s| s.distance_to_point(p) <= 0.01));
+    /// let coarse = curve.flatten(0.5);
+    /// assert!(coarse.points().len() < line.points().len());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn flatten(&self, tolerance: T) -> Polyline<T> {
+        assert!(
+            tolerance > T::zero(),
+            "tolerance must be positive, got {}",
+            tolerance
+        );
+        let mut points = alloc::vec![self.p0];
+        // The pieces still to be flattened, with the last to come along the curve on
+        // top, and how many times each has been cut
+        let mut pieces: Vec<(CubicBezier<T>, u32)> = alloc::vec![(*self, 0)];
+        while let Some((piece, depth)) = pieces.pop() {
+            let chord = LineSegment::new(piece.p0, piece.p3);
+            let off = chord
+                .distance_squared_to_point(piece.p1)
+                .max(chord.distance_squared_to_point(piece.p2));
+            // As for `length`, rounding stops pieces past this depth getting flatter
+            if off <= tolerance * tolerance || depth >= 32 {
+                points.push(piece.p3);
+                continue;
+            }
+            let (before, after) = piece.split(T::from_f64(0.5));
+            pieces.push((after, depth + 1));
+            pieces.push((before, depth + 1));
+        }
+        Polyline::new(points)
+    }
+}
+
+/// The roots of `a t² + b t + c` that lie in `[lo, hi]`
+pub(crate) fn roots_between<T: Scalar>([a, b, c]: [T; 3], lo: T, hi: T) -> [Option<T>; 2] {
+    let found = if a == T::zero() {
+        if b == T::zero() {
+            [None, None]
+        } else {
+            [Some(-c / b), None]
+        }
+    } else {
+        let disc = b * b - T::from_f64(4.0) * a * c;
+        if disc < T::zero() {
+            [None, None]
+        } else {
+            let root = disc.sqrt();
+            let twice_a = T::from_f64(2.0) * a;
+            [Some((-b - root) / twice_a), Some((-b + root) / twice_a)]
+        }
+    };
+    let keep = |t: Option<T>| t.filter(|&t| t >= lo && t <= hi);
+    [keep(found[0]), keep(found[1])]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s_curve() -> CubicBezier {
+        CubicBezier::new(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 5.0, y: 3.0 },
+            Point { x: -2.0, y: 4.0 },
+            Point { x: 3.0, y: -1.0 },
+        )
+    }
+
+    fn arch() -> QuadBezier {
+        QuadBezier::new(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 3.0, y: 4.0 },
+            Point { x: -1.0, y: 1.0 },
+        )
+    }
+
+    #[test]
+    fn test_points_and_derivatives() {
+        let (cubic, quad) = (s_curve(), arch());
+        assert_eq!(cubic.p0, cubic.point_at(0.0));
+        assert_eq!(cubic.p3, cubic.point_at(1.0));
+        assert_eq!(quad.p2, quad.point_at(1.0));
+        let h = 1e-6;
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            // The derivative matches the slope between nearby points
+            let slope = (cubic.point_at(t + h) - cubic.point_at(t - h)) / (2.0 * h);
+            assert!(slope.isclose_with(cubic.derivative_at(t), 1e-6, 0.0));
+            let slope = (quad.point_at(t + h) - quad.point_at(t - h)) / (2.0 * h);
+            assert!(slope.isclose_with(quad.derivative_at(t), 1e-6, 0.0));
+            // Raised to a cubic, the quadratic runs the same way
+            assert!(quad.to_cubic().point_at(t).isclose(quad.point_at(t)));
+        }
+        assert_eq!(Point { x: 15.0, y: 9.0 }, cubic.derivative_at(0.0));
+    }
+
+    #[test]
+    fn test_bounding_rect_is_tight() {
+        for rect in [s_curve().bounding_rect(), arch().bounding_rect()] {
+            assert!(rect.width() > 0.0 && rect.height() > 0.0);
+        }
+        let sample = |f: &dyn Fn(f64) -> Point| {
+            Rect::from_points((0..=10000).map(|i| f(i as f64 / 10000.0))).unwrap()
+        };
+        let (cubic, quad) = (s_curve(), arch());
+        for (exact, sampled) in [
+            (cubic.bounding_rect(), sample(&|t| cubic.point_at(t))),
+            (quad.bounding_rect(), sample(&|t| quad.point_at(t))),
+        ] {
+            assert!(exact.contains(sampled.min()) && exact.contains(sampled.max()));
+            assert!(exact.min().isclose_with(sampled.min(), 1e-6, 0.0));
+            assert!(exact.max().isclose_with(sampled.max(), 1e-6, 0.0));
+        }
+        // The s-curve rises well above both of its ends
+        let rect = cubic.bounding_rect();
+        assert!(rect.max().y > 2.0 && rect.min().y == -1.0);
+    }
+
+    #[test]
+    fn test_split() {
+        let (cubic, quad) = (s_curve(), arch());
+        for &t in &[0.0, 0.3, 0.5, 1.0] {
+            let (before, after) = cubic.split(t);
+            assert_eq!(before.p3, after.p0);
+            assert!(before.point_at(0.5).isclose(cubic.point_at(t * 0.5)));
+            assert!(after
+                .point_at(0.5)
+                .isclose(cubic.point_at(t + (1.0 - t) * 0.5)));
+            let (before, after) = quad.split(t);
+            assert!(before.point_at(0.25).isclose(quad.point_at(t * 0.25)));
+            assert!(after
+                .point_at(0.25)
+                .isclose(quad.point_at(t + (1.0 - t) * 0.25)));
+        }
+        let (before, after) = cubic.split(0.4);
+        // The halves together are as long as the whole
+        let whole = cubic.length(1e-10);
+        assert!((before.length(1e-10) + after.length(1e-10) - whole).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_length() {
+        // The parabola y = x² from 0 to 1, whose length is known exactly
+        let parabola = QuadBezier::new(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 0.5, y: 0.0 },
+            Point { x: 1.0, y: 1.0 },
+        );
+        let exact = 5f64.sqrt() / 2.0 + 2f64.asinh() / 4.0;
+        for &tolerance in &[1e-2, 1e-5, 1e-10] {
+            assert!((parabola.length(tolerance) - exact).abs() <= tolerance);
+        }
+        let straight = CubicBezier::new(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 3.0, y: 3.0 },
+        );
+        assert!(crate::scalar_isclose(18f64.sqrt(), straight.length(1e-12)));
+        // A cusp, where the curve stops and turns back on itself
+        let cusp = CubicBezier::new(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 1.0 },
+            Point { x: 0.0, y: 1.0 },
+            Point { x: 2.0, y: 0.0 },
+        );
+        let sampled: f64 = (0..100_000)
+            .map(|i| {
+                let t = i as f64 / 100_000.0;
+                cusp.point_at(t).distance(&cusp.point_at(t + 1e-5))
+            })
+            .sum();
+        assert!((cusp.length(1e-6) - sampled).abs() < 1e-6);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_flatten_within_tolerance() {
+        let cubic = s_curve();
+        let mut last = 0;
+        for &tolerance in &[1.0, 0.1, 0.01, 1e-4] {
+            let line = cubic.flatten(tolerance);
+            assert_eq!(cubic.p0, line.points()[0]);
+            assert_eq!(cubic.p3, *line.points().last().unwrap());
+            // Each vertex is on the curve, and each point of the curve is near the line
+            for i in 0..=2000 {
+                let p = cubic.point_at(i as f64 / 2000.0);
+                let off = line
+                    .segments()
+                    .map(|s| s.distance_to_point(p))
+                    .fold(f64::INFINITY, f64::min);
+                assert!(off <= tolerance);
+            }
+            assert!(line.points().len() > last);
+            last = line.points().len();
+            assert!(line.length() <= cubic.length(1e-9));
+        }
+        // A hundredth of the tolerance takes about ten times the points
+        let (coarse, fine) = (cubic.flatten(1e-3), cubic.flatten(1e-5));
+        let ratio = fine.points().len() as f64 / coarse.points().len() as f64;
+        assert!(ratio > 5.0 && ratio < 20.0);
+        let straight = CubicBezier::new(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 3.0, y: 3.0 },
+        );
+        assert_eq!(2, straight.flatten(1e-9).points().len());
+        let quad = arch().flatten(0.01);
+        assert_eq!(arch().p2, *quad.points().last().unwrap());
+    }
+}
@@ -0,0 +1,558 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::polygon::{ring_edges, ring_orientation};
+use crate::predicates::{orient2d, Orientation};
+use crate::sweep::Status;
+use crate::{
+    segment_intersections, LineSegment, MultiPolygon, OrderedPoint, Point, Polygon, Scalar,
+    SegmentIntersection,
+};
+
+/// A set operation on the regions covered by two shapes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BooleanOp {
+    /// Everywhere covered by either shape
+    Union,
+    /// Everywhere covered by both shapes
+    Intersection,
+    /// Everywhere covered by the first shape but not the second
+    Difference,
+    /// Everywhere covered by exactly one of the shapes
+    Xor,
+}
+
+impl BooleanOp {
+    /// Whether a point inside the first shape or not, and the second, is in the result
+    fn keeps(self, a: bool, b: bool) -> bool {
+        match self {
+            BooleanOp::Union => a || b,
+            BooleanOp::Intersection => a && b,
+            BooleanOp::Difference => a && !b,
+            BooleanOp::Xor => a != b,
+        }
+    }
+}
+
+pub trait BooleanOps<T: Scalar, Rhs = Self> {
+    /// The region given by applying `op` to the regions covered by the two shapes. The
+    /// result's polygons wind counter-clockwise and their holes clockwise, and have no
+    /// vertices where their boundaries run straight on. A shape's rings may wind in
+    /// either direction, but each ring is expected not to cross itself. Where the
+    /// polygons of a multipolygon overlap, they cover the union of their regions.
+    ///
+    /// Works in the manner of Martinez and Rueda. The boundaries of both shapes are
+    /// cut wherever they meet, by a sweep line, so that the pieces meet only at their
+    /// ends, and a second sweep finds how many times each shape winds around the
+    /// regions either side of every piece. The pieces with the result on exactly one
+    /// side are then joined into rings. Where boundaries cross, the crossing is rounded,
+    /// and crossings that round to within a few units of each other are merged. The
+    /// result is exact otherwise.
+    /// ```
+    /// use rsgeo::{BooleanOps, Point, Polygon};
+    /// let square = |lo: f64, hi: f64| Polygon::new(vec![
+    ///     Point{x: lo, y: lo}, Point{x: hi, y: lo}, Point{x: hi, y: hi}, Point{x: lo, y: hi},
+    /// ]);
+    /// let (a, b) = (square(0.0, 2.0), square(1.0, 3.0));
+    /// assert_eq!(a.union(&b).area(), 7.0);
+    /// assert_eq!(a.intersection(&b).area(), 1.0);
+    /// assert_eq!(a.difference(&b).area(), 3.0);
+    /// assert_eq!(a.xor(&b).polygons().len(), 2);
+    /// // A square with a square hole
+    /// let frame = square(-1.0, 4.0).difference(&square(0.0, 3.0));
+    /// assert_eq!(frame.polygons()[0].holes().len(), 1);
+    /// ```
+    fn boolean_op(&self, other: &Rhs, op: BooleanOp) -> MultiPolygon<T>;
+
+    /// The region covered by either shape. See `boolean_op`.
+    fn union(&self, other: &Rhs) -> MultiPolygon<T> {
+        self.boolean_op(other, BooleanOp::Union)
+    }
+
+    /// The region covered by both shapes. See `boolean_op`.
+    fn intersection(&self, other: &Rhs) -> MultiPolygon<T> {
+        self.boolean_op(other, BooleanOp::Intersection)
+    }
+
+    /// The region covered by this shape but not `other`. See `boolean_op`.
+    fn difference(&self, other: &Rhs) -> MultiPolygon<T> {
+        self.boolean_op(other, BooleanOp::Difference)
+    }
+
+    /// The region covered by exactly one of the shapes. See `boolean_op`.
+    fn xor(&self, other: &Rhs) -> MultiPolygon<T> {
+        self.boolean_op(other, BooleanOp::Xor)
+    }
+}
+
+/// The edges of the polygons, each running with the region covered on its left
+pub(crate) fn oriented_edges<T: Scalar>(
+    polygons: &[Polygon<T>],
+) -> impl Iterator<Item = LineSegment<T>> + '_ {
+    polygons.iter().flat_map(|poly| {
+        poly.rings().enumerate().flat_map(|(i, ring)| {
+            let orientation = ring_orientation(ring);
+            // Holes have the polygon's interior on their outside
+            let reverse = (orientation == Orientation::CounterClockwise) != (i == 0);
+            let edges = if orientation == Orientation::Collinear {
+                &ring[..0]
+            } else {
+                ring
+            };
+            ring_edges(edges)
+                .filter(|e| e.p1() != e.p2())
+                .map(move |e| if reverse { e.reverse() } else { e })
+        })
+    })
+}
+
+/// A piece of boundary from its left end to its right, with how much the winding
+/// number of each shape rises from below it to above it
+pub(crate) type Piece<T> = ((Point<T>, Point<T>), [i32; 2]);
+
+/// The pieces of every edge between the points where it meets another, in sweep
+/// order. Pieces shared by several edges are merged, and those across which neither
+/// winding number changes are left out.
+pub(crate) fn cut_edges<T: Scalar>(edges: &[LineSegment<T>], shapes: &[usize]) -> Vec<Piece<T>> {
+    // Crossings are rounded, so where several edges cross at one point each pair can
+    // round it differently. A crossing within a few units of rounding of a vertex or an
+    // earlier crossing is moved onto it.
+    let scale = edges
+        .iter()
+        .flat_map(|e| [e.p1(), e.p2()])
+        .fold(T::zero(), |m, p| m.max(p.x.abs()).max(p.y.abs()));
+    let tol = scale * T::epsilon() * T::from_f64(16.0);
+    let mut known: BTreeSet<OrderedPoint<T>> = edges
+        .iter()
+        .flat_map(|e| [e.p1().into(), e.p2().into()])
+        .collect();
+    let mut snap = |p: Point<T>| {
+        let (lo, hi) = (
+            Point {
+                x: p.x - tol,
+                y: T::neg_infinity(),
+            },
+            Point {
+                x: p.x + tol,
+                y: T::infinity(),
+            },
+        );
+        let near = known
+            .range(OrderedPoint::new(lo)..=OrderedPoint::new(hi))
+            .map(OrderedPoint::point)
+            .find(|q| (q.y - p.y).abs() <= tol);
+        near.unwrap_or_else(|| {
+            known.insert(p.into());
+            p
+        })
+    };
+    let mut pieces: BTreeMap<(OrderedPoint<T>, OrderedPoint<T>), [i32; 2]> = BTreeMap::new();
+    for (e, &shape) in edges.iter().zip(shapes) {
+        // The region covered is on the edge's left, which is above it when it runs
+        // from left to right
+        let (a, b) = (OrderedPoint::new(e.p1()), OrderedPoint::new(e.p2()));
+        let (key, rise) = if a < b { ((a, b), 1) } else { ((b, a), -1) };
+        pieces.entry(key).or_insert([0, 0])[shape] += rise;
+    }
+    // Cutting a piece at a rounded point turns it slightly, which can take it across
+    // a vertex it passed close by, so the pieces are cut again until none crosses
+    // another
+    loop {
+        let segments: Vec<LineSegment<T>> = pieces
+            .keys()
+            .map(|(a, b)| LineSegment::new(a.point(), b.point()))
+            .collect();
+        let mut cuts = alloc::vec![Vec::new(); segments.len()];
+        for (i, j, hit) in segment_intersections(&segments) {
+            let (p, q) = match hit {
+                SegmentIntersection::None => continue,
+                SegmentIntersection::Point(p) => {
+                    let p = snap(p);
+                    (p, p)
+                }
+                SegmentIntersection::Segment(s) => (s.p1(), s.p2()),
+            };
+            cuts[i].extend([p, q]);
+            cuts[j].extend([p, q]);
+        }
+        let mut cut_any = false;
+        let mut next = BTreeMap::new();
+        for ((e, rise), cuts) in segments.iter().zip(pieces.values()).zip(cuts) {
+            // Points are ordered by how far along the piece they lie. Rounding can make
+            // that tie with an end for a point that is not on the piece, so ties are
+            // broken by sweep order. Cuts that round to beyond the ends are left to
+            // the other piece.
+            let along = |p: Point<T>| {
+                (
+                    (p - e.p1()).dot_product(&e.direction()),
+                    OrderedPoint::new(p),
+                )
+            };
+            let order = |a: &(T, OrderedPoint<T>), b: &(T, OrderedPoint<T>)| {
+                a.0.partial_cmp(&b.0)
+                    .unwrap_or(Ordering::Equal)
+                    .then(a.1.cmp(&b.1))
+            };
+            let (first, last) = (along(e.p1()), along(e.p2()));
+            let mut points: Vec<(T, OrderedPoint<T>)> = cuts
+                .into_iter()
+                .map(along)
+                .filter(|p| order(&first, p).is_lt() && order(p, &last).is_lt())
+                .collect();
+            cut_any |= !points.is_empty();
+            points.sort_by(order);
+            points.insert(0, first);
+            points.push(last);
+            points.dedup_by(|a, b| a.1 == b.1);
+            for pair in points.windows(2) {
+                let (a, b) = (pair[0].1, pair[1].1);
+                let (key, sign) = if a < b { ((a, b), 1) } else { ((b, a), -1) };
+                let entry = next.entry(key).or_insert([0, 0]);
+                entry[0] += sign * rise[0];
+                entry[1] += sign * rise[1];
+            }
+        }
+        pieces = next;
+        if !cut_any {
+            break;
+        }
+    }
+    pieces
+        .into_iter()
+        .filter(|&(_, rise)| rise != [0, 0])
+        .map(|((a, b), rise)| ((a.point(), b.point()), rise))
+        .collect()
+}
+
+/// The winding numbers of each shape just above every piece, found by sweeping over
+/// them. The region just above a new piece is the region just above the one below it.
+pub(crate) fn windings_above<T: Scalar>(pieces: &[Piece<T>]) -> Vec<[i32; 2]> {
+    let mut above = alloc::vec![[0, 0]; pieces.len()];
+    let mut status = Status::new(pieces.len());
+    let mut ending: BTreeMap<OrderedPoint<T>, Vec<usize>> = BTreeMap::new();
+    let mut start = 0;
+    // The pieces are in order of their left ends
+    while start < pieces.len() {
+        let p = pieces[start].0 .0;
+        let mut group: Vec<usize> = (start..pieces.len())
+            .take_while(|&i| pieces[i].0 .0 == p)
+            .collect();
+        start += group.len();
+        while let Some(entry) = ending.first_entry() {
+            if *entry.key() > OrderedPoint::new(p) {
+                break;
+            }
+            for s in entry.remove() {
+                status.remove(s);
+            }
+        }
+        group.sort_by(|&a, &b| {
+            0.0.partial_cmp(&orient2d(p, pieces[a].0 .1, pieces[b].0 .1))
+                .unwrap_or(Ordering::Equal)
+        });
+        let k = status.partition_point(|s| {
+            let (left, right) = pieces[s].0;
+            orient2d(left, right, p) > 0.0
+        });
+        let mut below = match k.checked_sub(1) {
+            Some(k) => above[status.get(k).unwrap()],
+            None => [0, 0],
+        };
+        for (offset, &s) in group.iter().enumerate() {
+            let rise = pieces[s].1;
+            above[s] = [below[0] + rise[0], below[1] + rise[1]];
+            below = above[s];
+            status.insert(k + offset, s);
+            ending
+                .entry(OrderedPoint::new(pieces[s].0 .1))
+                .or_default()
+                .push(s);
+        }
+    }
+    above
+}
+
+/// Leave out the vertices of a ring where it runs straight on, and start it from its
+/// first vertex in sweep order
+pub(crate) fn tidy_ring<T: Scalar>(ring: &[Point<T>]) -> Vec<Point<T>> {
+    let n = ring.len();
+    let mut kept: Vec<Point<T>> = (0..n)
+        .filter(|&i| orient2d(ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]) != 0.0)
+        .map(|i| ring[i])
+        .collect();
+    let first = (0..kept.len()).min_by_key(|&i| OrderedPoint::new(kept[i]));
+    kept.rotate_left(first.unwrap_or(0));
+    kept
+}
+
+fn overlay<T: Scalar>(a: &[Polygon<T>], b: &[Polygon<T>], op: BooleanOp) -> MultiPolygon<T> {
+    let (mut edges, mut shapes) = (Vec::new(), Vec::new());
+    for (shape, polygons) in [a, b].iter().enumerate() {
+        for e in oriented_edges(polygons) {
+            edges.push(e);
+            shapes.push(shape);
+        }
+    }
+    let pieces = cut_edges(&edges, &shapes);
+    let above = windings_above(&pieces);
+    let inside = |w: [i32; 2]| op.keeps(w[0] > 0, w[1] > 0);
+    let boundary: Vec<(Point<T>, Point<T>)> = pieces
+        .iter()
+        .zip(above)
+        .filter_map(|(&((left, right), rise), up)| {
+            let down = [up[0] - rise[0], up[1] - rise[1]];
+            match (inside(down), inside(up)) {
+                (false, true) => Some((left, right)),
+                (true, false) => Some((right, left)),
+                _ => None,
+            }
+        })
+        .collect();
+    MultiPolygon::from_boundary(&boundary)
+        .polygons()
+        .iter()
+        .map(|poly| {
+            let holes = poly.holes().iter().map(|h| tidy_ring(h)).collect();
+            Polygon::with_holes(tidy_ring(poly.vertices()), holes)
+        })
+        .collect()
+}
+
+/// The polygons making up a shape that covers an area
+trait Regions<T> {
+    fn regions(&self) -> &[Polygon<T>];
+}
+
+impl<T> Regions<T> for Polygon<T> {
+    fn regions(&self) -> &[Polygon<T>] {
+        core::slice::from_ref(self)
+    }
+}
+
+impl<T: Scalar> Regions<T> for MultiPolygon<T> {
+    fn regions(&self) -> &[Polygon<T>] {
+        self.polygons()
+    }
+}
+
+macro_rules! impl_boolean_ops {
+    ($a:ident, $b:ident) => {
+        impl<T: Scalar> BooleanOps<T, $b<T>> for $a<T> {
+            fn boolean_op(&self, other: &$b<T>, op: BooleanOp) -> MultiPolygon<T> {
+                overlay(self.regions(), other.regions(), op)
+            }
+        }
+    };
+}
+
+for_pairs!(impl_boolean_ops; [Polygon, MultiPolygon] x [Polygon, MultiPolygon]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lcg;
+
+    fn square(lo: f64, hi: f64) -> Vec<Point> {
+        vec![
+            Point { x: lo, y: lo },
+            Point { x: hi, y: lo },
+            Point { x: hi, y: hi },
+            Point { x: lo, y: hi },
+        ]
+    }
+
+    /// A random star-shaped polygon around `center`
+    fn star(center: Point, n: usize, rng: &mut Lcg) -> Polygon {
+        let mut next = || rng.unit();
+        let ring = (0..n)
+            .map(|i| {
+                let angle = (i as f64 + next() * 0.8) / n as f64 * core::f64::consts::TAU;
+                let r = 0.5 + next();
+                center
+                    + Point {
+                        x: r * angle.cos(),
+                        y: r * angle.sin(),
+                    }
+            })
+            .collect();
+        Polygon::new(ring)
+    }
+
+    fn assert_oriented(shape: &MultiPolygon) {
+        for poly in shape.polygons() {
+            assert_eq!(Orientation::CounterClockwise, poly.orientation());
+            for hole in poly.holes() {
+                assert_eq!(
+                    Orientation::Clockwise,
+                    ring_orientation(hole),
+                    "{:?}",
+                    shape
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_overlapping_squares() {
+        let a = Polygon::new(square(0.0, 2.0));
+        // Clockwise, which makes no difference
+        let mut b = Polygon::new(square(1.0, 3.0));
+        b.reverse();
+        let union = a.union(&b);
+        assert_oriented(&union);
+        assert_eq!(1, union.polygons().len());
+        assert_eq!(8, union.polygons()[0].vertices().len());
+        let both = a.intersection(&b);
+        assert_eq!(both, MultiPolygon::from(Polygon::new(square(1.0, 2.0))));
+        assert_eq!(3.0, a.difference(&b).area());
+        assert_eq!(3.0, b.difference(&a).area());
+        let xor = a.xor(&b);
+        assert_oriented(&xor);
+        // The two L shapes touch at opposite corners of the overlap
+        assert_eq!(2, xor.polygons().len());
+        assert_eq!(6.0, xor.area())
+    }
+
+    #[test]
+    fn test_holes_and_shared_edges() {
+        let frame = Polygon::with_holes(square(0.0, 4.0), vec![square(1.0, 3.0)]);
+        let plug = Polygon::new(square(1.0, 3.0));
+        // Filling the hole exactly leaves no trace of it
+        let filled = frame.union(&plug);
+        assert_eq!(filled, MultiPolygon::from(Polygon::new(square(0.0, 4.0))));
+        assert!(frame.intersection(&plug).polygons().is_empty());
+        assert_eq!(frame.difference(&plug).area(), 12.0);
+        // Side by side squares merge, dropping the vertices along their shared edge
+        let right = Polygon::new(vec![
+            Point { x: 4.0, y: 0.0 },
+            Point { x: 6.0, y: 0.0 },
+            Point { x: 6.0, y: 4.0 },
+            Point { x: 4.0, y: 4.0 },
+        ]);
+        let wide = frame.union(&right);
+        assert_eq!(1, wide.polygons().len());
+        assert_eq!(4, wide.polygons()[0].vertices().len());
+        assert_eq!(1, wide.polygons()[0].holes().len());
+        assert_eq!(20.0, wide.area());
+        // With the squares overlapping, the hole is only partly covered
+        let shifted = Polygon::new(square(2.0, 5.0));
+        let union = frame.union(&shifted);
+        assert_oriented(&union);
+        // The squares cover 21, less what is left of the hole
+        assert_eq!(16.0 + 9.0 - 4.0 - 3.0, union.area());
+        assert!(!union.contains(Point { x: 1.5, y: 1.5 }))
+    }
+
+    #[test]
+    fn test_multipolygons() {
+        let pair = MultiPolygon::new(vec![
+            Polygon::new(square(0.0, 1.0)),
+            Polygon::new(square(2.0, 3.0)),
+        ]);
+        // A bar across both squares
+        let bar = Polygon::new(vec![
+            Point { x: -1.0, y: 0.25 },
+            Point { x: 4.0, y: 0.25 },
+            Point { x: 4.0, y: 0.75 },
+            Point { x: -1.0, y: 0.75 },
+        ]);
+        let cut = pair.intersection(&bar);
+        assert_eq!(1, cut.polygons().len());
+        assert_eq!(0.5, cut.area());
+        let joined = pair.union(&bar);
+        assert_eq!(2, joined.polygons().len());
+        assert_eq!(2.0 + 2.5 - 0.5, joined.area());
+        assert_eq!(pair.union(&pair), pair);
+        assert!(pair.difference(&pair).polygons().is_empty());
+        // Empty shapes
+        let empty = MultiPolygon::new(Vec::new());
+        assert_eq!(pair.union(&empty), pair);
+        assert!(empty.intersection(&bar).polygons().is_empty())
+    }
+
+    #[test]
+    fn test_random_polygons() {
+        let mut rng = Lcg::new(7);
+        for round in 0..30 {
+            let offset = Point {
+                x: (round % 5) as f64 * 0.3,
+                y: (round % 3) as f64 * 0.2,
+            };
+            let a = star(Point::origin(), 12, &mut rng);
+            let b = star(offset, 9, &mut rng);
+            let (union, both) = (a.union(&b), a.intersection(&b));
+            let (diff, xor) = (a.difference(&b), a.xor(&b));
+            for shape in [&union, &both, &diff, &xor].iter() {
+                assert_oriented(shape);
+            }
+            let close = |x: f64, y: f64| (x - y).abs() < 1e-9;
+            assert!(close(union.area() + both.area(), a.area() + b.area()));
+            assert!(close(diff.area(), a.area() - both.area()));
+            assert!(close(xor.area(), union.area() - both.area()));
+            // Grid points, none of which lie on a boundary by chance
+            for i in 0..20 {
+                for j in 0..20 {
+                    let p = Point {
+                        x: -2.0 + i as f64 * 0.21,
+                        y: -2.0 + j as f64 * 0.23,
+                    };
+                    let (in_a, in_b) = (a.contains(p), b.contains(p));
+                    assert_eq!(in_a || in_b, union.contains(p));
+                    assert_eq!(in_a && in_b, both.contains(p));
+                    assert_eq!(in_a && !in_b, diff.contains(p));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_snapped_shapes() {
+        // Overlapping rectangles and triangles with corners on a coarse grid, so that
+        // edges often overlap, meet at corners and cross several at a point
+        let mut rng = Lcg::new(3);
+        let mut next = |m: u64| rng.below(m) as f64;
+        let mut shape = |triangles: bool| -> MultiPolygon {
+            let count = 1 + next(4) as usize;
+            (0..count)
+                .map(|_| {
+                    let (x, y) = (next(7), next(7));
+                    let (w, h) = (1.0 + next(4), 1.0 + next(4));
+                    let mut ring = vec![Point { x, y }, Point { x: x + w, y }];
+                    ring.push(Point { x: x + w, y: y + h });
+                    if triangles {
+                        ring[1].y += next(4);
+                    } else {
+                        ring.push(Point { x, y: y + h });
+                    }
+                    Polygon::new(ring)
+                })
+                .collect()
+        };
+        let ops = [
+            BooleanOp::Union,
+            BooleanOp::Intersection,
+            BooleanOp::Difference,
+            BooleanOp::Xor,
+        ];
+        for round in 0..100 {
+            let a = shape(round % 2 == 1);
+            let b = shape(round % 3 == 0);
+            for &op in ops.iter() {
+                let result = a.boolean_op(&b, op);
+                assert_oriented(&result);
+                // Points off the grid, which lie on no boundary
+                for i in 0..24 {
+                    for j in 0..24 {
+                        let p = Point {
+                            x: -0.5 + i as f64 * 0.5 + 0.137,
+                            y: -0.5 + j as f64 * 0.5 + 0.291,
+                        };
+                        let want = op.keeps(a.contains(p), b.contains(p));
+                        assert_eq!(want, result.contains(p));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{BoundingRect, Distance, Ray, RayCast, RayHit, Rect, Scalar};
+
+/// The most shapes kept together in a leaf
+const LEAF_SIZE: usize = 4;
+
+/// A bounding volume hierarchy over shapes: a binary tree of rectangles, each holding
+/// the rectangles of the two halves below it, with a few shapes in each leaf. It is
+/// built once over the shapes, splitting each group of them in two at the middle of
+/// its longer side, and answers ray casts, nearest shape queries and which shapes of
+/// two hierarchies overlap by looking only at the parts of the tree that could hold
+/// an answer. Shapes are named by their index among those the tree was built from,
+/// and those without a bounding rectangle are never found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bvh<S, T = f64> {
+    shapes: Vec<S>,
+    /// The nodes in the order they were made, each inner node followed by its first
+    /// half
+    nodes: Vec<Node<T>>,
+    /// The bounds and indices of the shapes, in the order the leaves hold them
+    order: Vec<(Rect<T>, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Node<T> {
+    bounds: Rect<T>,
+    content: Content,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Content {
+    /// The range of `order` holding the shapes in a leaf
+    Shapes(usize, usize),
+    /// The index of the second half of an inner node
+    Split(usize),
+}
+
+impl<S: BoundingRect<T>, T: Scalar> Bvh<S, T> {
+    /// The hierarchy over `shapes`, in O(n log n) time
+    /// ```
+    /// use rsgeo::{Bvh, Circle, LineSegment, Point, Ray};
+    /// let walls: Vec<LineSegment> = vec![
+    ///     "(4, -1) -> (4, 1)".parse().unwrap(),
+    ///     "(2, -1) -> (2, 1)".parse().unwrap(),
+    ///     "(0, 3) -> (5, 3)".parse().unwrap(),
+    /// ];
+    /// let bvh = Bvh::new(walls);
+    /// let (hit, i) = bvh.cast_ray(&Ray::new(Point::origin(), Point{x: 1.0, y: 0.0})).unwrap();
+    /// assert_eq!((hit.point, i), (Point{x: 2.0, y: 0.0}, 1));
+    /// assert_eq!(bvh.closest(&Point{x: 1.0, y: 2.5}), Some((0.5, 2)));
+    /// let disks = Bvh::new(vec![Circle::new(Point{x: 4.0, y: 3.0}, 0.5)]);
+    /// assert_eq!(bvh.overlaps(&disks), vec![(2, 0)]);
+    /// ```
+    pub fn new(shapes: Vec<S>) -> Bvh<S, T> {
+        let mut items: Vec<(Rect<T>, usize)> = shapes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| Some((s.bounding_rect()?, i)))
+            .collect();
+        let mut nodes = Vec::new();
+        if !items.is_empty() {
+            build(&mut nodes, &mut items, 0);
+        }
+        Bvh {
+            shapes,
+            nodes,
+            order: items,
+        }
+    }
+}
+
+impl<S, T: Scalar> Bvh<S, T> {
+    /// The shapes the hierarchy was built over, in their original order
+    pub fn shapes(&self) -> &[S] {
+        &self.shapes
+    }
+
+    /// The rectangle holding every shape, or None if there are none with bounds
+    pub fn bounds(&self) -> Option<Rect<T>> {
+        self.nodes.first().map(|node| node.bounds)
+    }
+
+    /// The first shape along `ray`, with where the ray hits it and the index of the
+    /// shape, or None if the ray misses them all
+    pub fn cast_ray(&self, ray: &Ray<T>) -> Option<(RayHit<T>, usize)>
+    where
+        S: RayCast<T>,
+    {
+        let mut best: Option<(RayHit<T>, usize)> = None;
+        let mut stack = Vec::new();
+        if let Some(entry) = self.nodes.first().and_then(|n| ray_entry(&n.bounds, ray)) {
+            stack.push((entry, 0));
+        }
+        while let Some((entry, i)) = stack.pop() {
+            if best.is_some_and(|(hit, _)| entry > hit.distance) {
+                continue;
+            }
+            match self.nodes[i].content {
+                Content::Shapes(start, end) => {
+                    for &(_, s) in &self.order[start..end] {
+                        let hit = match self.shapes[s].cast_ray(ray) {
+                            Some(hit) => hit,
+                            None => continue,
+                        };
+                        if best.is_none_or(|(b, _)| hit.distance < b.distance) {
+                            best = Some((hit, s));
+                        }
+                    }
+                }
+                // The nearer half goes on the stack last, so it is looked at first
+                Content::Split(right) => {
+                    let mut halves: Vec<(T, usize)> = [i + 1, right]
+                        .iter()
+                        .filter_map(|&c| Some((ray_entry(&self.nodes[c].bounds, ray)?, c)))
+                        .collect();
+                    halves.sort_by(|a, b| b.0.total_cmp(&a.0));
+                    stack.extend(halves);
+                }
+            }
+        }
+        best
+    }
+
+    /// The shape nearest `query`, with its distance and index, or None if there are no
+    /// shapes with bounds. Of shapes equally near, any one may be given.
+    pub fn closest<Q>(&self, query: &Q) -> Option<(T, usize)>
+    where
+        S: Distance<T, Q>,
+        Rect<T>: Distance<T, Q>,
+    {
+        let mut best: Option<(T, usize)> = None;
+        let mut stack = Vec::new();
+        if let Some(d) = self
+            .nodes
+            .first()
+            .and_then(|n| n.bounds.min_distance(query))
+        {
+            stack.push((d, 0));
+        }
+        while let Some((d, i)) = stack.pop() {
+            if best.is_some_and(|(b, _)| d >= b) {
+                continue;
+            }
+            match self.nodes[i].content {
+                Content::Shapes(start, end) => {
+                    for &(_, s) in &self.order[start..end] {
+                        let d = match self.shapes[s].min_distance(query) {
+                            Some(d) => d,
+                            None => continue,
+                        };
+                        if best.is_none_or(|(b, _)| d < b) {
+                            best = Some((d, s));
+                        }
+                    }
+                }
+                Content::Split(right) => {
+                    let mut halves: Vec<(T, usize)> = [i + 1, right]
+                        .iter()
+                        .filter_map(|&c| Some((self.nodes[c].bounds.min_distance(query)?, c)))
+                        .collect();
+                    halves.sort_by(|a, b| b.0.total_cmp(&a.0));
+                    stack.extend(halves);
+                }
+            }
+        }
+        best
+    }
+
+    /// The pairs of indices of a shape in `self` and a shape in `other` whose bounding
+    /// rectangles meet, found by walking down both trees together, in no particular
+    /// order. The shapes themselves might not meet, so this is a first pass that an
+    /// exact test of the pairs can follow.
+    pub fn overlaps<U>(&self, other: &Bvh<U, T>) -> Vec<(usize, usize)> {
+        let mut found = Vec::new();
+        if self.nodes.is_empty() || other.nodes.is_empty() {
+            return found;
+        }
+        let mut stack = alloc::vec![(0, 0)];
+        while let Some((a, b)) = stack.pop() {
+            let (na, nb) = (&self.nodes[a], &other.nodes[b]);
+            if !na.bounds.intersects(&nb.bounds) {
+                continue;
+            }
+            match (na.content, nb.content) {
+                (Content::Shapes(s0, s1), Content::Shapes(t0, t1)) => {
+                    for (bounds, s) in &self.order[s0..s1] {
+                        for (other_bounds, t) in &other.order[t0..t1] {
+                            if bounds.intersects(other_bounds) {
+                                found.push((*s, *t));
+                            }
+                        }
+                    }
+                }
+                // Go down the bigger of the two, or the one that can still be split
+                (Content::Split(right), Content::Shapes(..)) => {
+                    stack.extend([(a + 1, b), (right, b)]);
+                }
+                (Content::Shapes(..), Content::Split(right)) => {
+                    stack.extend([(a, b + 1), (a, right)]);
+                }
+                (Content::Split(ra), Content::Split(rb)) => {
+                    match na.bounds.area() >= nb.bounds.area() {
+                        true => stack.extend([(a + 1, b), (ra, b)]),
+                        false => stack.extend([(a, b + 1), (a, rb)]),
+                    }
+                }
+            }
+        }
+        found
+    }
+}
+
+/// Add the nodes over `items` to `nodes`, where the items start at `offset` in the
+/// order of all of them
+fn build<T: Scalar>(nodes: &mut Vec<Node<T>>, items: &mut [(Rect<T>, usize)], offset: usize) {
+    let bounds = items[1..]
+        .iter()
+        .fold(items[0].0, |acc, item| acc.union(&item.0));
+    let at = nodes.len();
+    if items.len() <= LEAF_SIZE {
+        nodes.push(Node {
+            bounds,
+            content: Content::Shapes(offset, offset + items.len()),
+        });
+        return;
+    }
+    nodes.push(Node {
+        bounds,
+        content: Content::Split(0),
+    });
+    let centers = Rect::from_points(items.iter().map(|item| item.0.center())).unwrap();
+    let by_x = centers.width() >= centers.height();
+    let mid = items.len() / 2;
+    items.select_nth_unstable_by(mid, |a, b| {
+        let (a, b) = (a.0.center(), b.0.center());
+        match by_x {
+            true => a.x.partial_cmp(&b.x),
+            false => a.y.partial_cmp(&b.y),
+        }
+        .unwrap_or(Ordering::Equal)
+    });
+    let (left, right) = items.split_at_mut(mid);
+    build(nodes, left, offset);
+    nodes[at].content = Content::Split(nodes.len());
+    build(nodes, right, offset + mid);
+}
+
+/// How far along `ray` it first meets `rect`, or None if it misses. The rectangle is
+/// taken as slightly bigger than it is, so that rounding can't lose a ray that only
+/// grazes a corner.
+fn ray_entry<T: Scalar>(rect: &Rect<T>, ray: &Ray<T>) -> Option<T> {
+    let (lo, hi) = (rect.min(), rect.max());
+    let (o, d) = (ray.origin, ray.direction);
+    let (mut enter, mut leave) = (T::zero(), T::infinity());
+    for (o, d, lo, hi) in [(o.x, d.x, lo.x, hi.x), (o.y, d.y, lo.y, hi.y)] {
+        if d == T::zero() {
+            if o < lo || o > hi {
+                return None;
+            }
+            continue;
+        }
+        let (t0, t1) = ((lo - o) / d, (hi - o) / d);
+        enter = enter.max(t0.min(t1));
+        leave = leave.min(t0.max(t1));
+    }
+    let slack = T::epsilon() * T::from_f64(16.0);
+    match enter <= leave + leave.abs() * slack {
+        true => Some(enter * d.magnitude() * (T::one() - slack)),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{scattered, Circle, LineSegment, Point};
+
+    fn segments(n: usize, seed: u64) -> Vec<LineSegment> {
+        scattered(2 * n, seed)
+            .chunks(2)
+            .map(|pair| LineSegment::new(pair[0], pair[0] + (pair[1] - pair[0]) * 0.1))
+            .collect()
+    }
+
+    #[test]
+    fn test_ray_casts_match_brute_force() {
+        let walls = segments(500, 2);
+        let bvh = Bvh::new(walls.clone());
+        for (i, p) in scattered(200, 5).into_iter().enumerate() {
+            let angle = i as f64 * 0.7;
+            let ray = Ray::new(
+                p,
+                Point {
+                    x: angle.cos(),
+                    y: angle.sin(),
+                },
+            );
+            let expected = walls
+                .iter()
+                .filter_map(|w| w.cast_ray(&ray))
+                .map(|hit| hit.distance)
+                .fold(f64::INFINITY, f64::min);
+            match bvh.cast_ray(&ray) {
+                Some((hit, w)) => {
+                    assert_eq!(expected, hit.distance);
+                    assert_eq!(Some(hit), walls[w].cast_ray(&ray));
+                }
+                None => assert_eq!(f64::INFINITY, expected),
+            }
+        }
+        // A ray along a grid line grazes the corners of the leaves
+        let grid: Vec<LineSegment> = (0..40)
+            .map(|i| {
+                let p = Point {
+                    x: i as f64,
+                    y: i as f64,
+                };
+                LineSegment::new(p, p + Point { x: 1.0, y: 0.0 })
+            })
+            .collect();
+        let diagonal = Ray::new(Point { x: -1.0, y: 1.0 }, Point { x: 1.0, y: -1.0 });
+        let (hit, _) = Bvh::new(grid).cast_ray(&diagonal).unwrap();
+        assert_eq!(Point::origin(), hit.point);
+    }
+
+    #[test]
+    fn test_closest_matches_brute_force() {
+        let walls = segments(500, 7);
+        let bvh = Bvh::new(walls.clone());
+        for q in scattered(200, 3) {
+            let expected = walls
+                .iter()
+                .map(|w| w.min_distance(&q).unwrap())
+                .fold(f64::INFINITY, f64::min);
+            let (d, w) = bvh.closest(&q).unwrap();
+            assert_eq!(expected, d);
+            assert_eq!(Some(d), walls[w].min_distance(&q));
+            // Other kinds of shape can be asked about too
+            let disk = Circle::new(q, 0.1);
+            let expected = walls
+                .iter()
+                .map(|w| w.min_distance(&disk).unwrap())
+                .fold(f64::INFINITY, f64::min);
+            assert_eq!(Some(expected), bvh.closest(&disk).map(|(d, _)| d));
+        }
+        assert_eq!(
+            None,
+            Bvh::<LineSegment>::new(vec![]).closest(&Point::origin())
+        );
+    }
+
+    #[test]
+    fn test_overlaps_match_brute_force() {
+        let a = segments(300, 4);
+        let b: Vec<Circle> = scattered(200, 9)
+            .into_iter()
+            .map(|p| Circle::new(p, 0.3))
+            .collect();
+        let (ta, tb) = (Bvh::new(a.clone()), Bvh::new(b.clone()));
+        let mut found = ta.overlaps(&tb);
+        found.sort_unstable();
+        let mut expected = Vec::new();
+        for (i, s) in a.iter().enumerate() {
+            for (j, c) in b.iter().enumerate() {
+                if s.bounding_rect().unwrap().intersects(&c.bounding_rect()) {
+                    expected.push((i, j));
+                }
+            }
+        }
+        assert!(!expected.is_empty());
+        assert_eq!(expected, found);
+        assert!(ta.overlaps(&Bvh::<Circle>::new(vec![])).is_empty());
+        assert_eq!(a.len(), ta.shapes().len());
+    }
+}
@@ -0,0 +1,335 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{convex_hull, LineSegment, Obb, Point, Polygon, Scalar};
+
+/// Rotating calipers over the exterior ring of a convex polygon. The polygon may wind
+/// either way, but is assumed to be convex: for other polygons the results describe
+/// no particular shape. Holes are ignored.
+impl<T: Scalar> Polygon<T> {
+    /// The indices of the vertices, in counter-clockwise order, skipping any that
+    /// repeat the vertex before them
+    fn ccw_order(&self) -> Vec<usize> {
+        let v = self.vertices();
+        let mut order: Vec<usize> = (0..v.len()).collect();
+        if self.signed_area() < T::zero() {
+            order.reverse();
+        }
+        order.dedup_by(|a, b| v[*a] == v[*b]);
+        if order.len() > 1 && v[order[0]] == v[order[order.len() - 1]] {
+            order.pop();
+        }
+        order
+    }
+
+    /// Walk a pair of parallel calipers once around the polygon, one resting along each
+    /// edge in turn and the other on the vertex farthest from it. `visit` is given the
+    /// positions in `order` of the edge's start, and of each vertex the other caliper
+    /// touches, along with that vertex's distance from the edge's line times the
+    /// edge's length.
+    fn rotate_calipers(order: &[usize], v: &[Point<T>], mut visit: impl FnMut(usize, usize, T)) {
+        let n = order.len();
+        let at = |i: usize| v[order[i % n]];
+        let height = |i: usize, j: usize| (at(i + 1) - at(i)).cross(&(at(j) - at(i)));
+        let mut j = 1;
+        for i in 0..n {
+            while height(i, j + 1) > height(i, j) {
+                j += 1;
+            }
+            visit(i, j % n, height(i, j));
+            // An edge parallel to this one is touched along its whole length
+            if height(i, j + 1) == height(i, j) {
+                visit(i, (j + 1) % n, height(i, j));
+            }
+        }
+    }
+
+    /// The pairs of vertices that can touch a pair of parallel lines with the polygon
+    /// between them, as indices into the vertices with the smaller index first. There
+    /// are at most about one and a half times as many pairs as vertices.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let square = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.0}, Point{x: 1.0, y: 1.0}, Point{x: 0.0, y: 1.0},
+    /// ]);
+    /// // Every pair of corners, since either pair of opposite sides will do
+    /// assert_eq!(square.antipodal_pairs(), [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+    /// ```
+    pub fn antipodal_pairs(&self) -> Vec<(usize, usize)> {
+        let order = self.ccw_order();
+        let n = order.len();
+        let mut pairs = Vec::new();
+        if n < 3 {
+            let order = &order;
+            pairs.extend((0..n).flat_map(|i| (i + 1..n).map(move |j| (order[i], order[j]))));
+        } else {
+            Self::rotate_calipers(&order, self.vertices(), |i, j, _| {
+                for k in [i, (i + 1) % n] {
+                    if k != j {
+                        pairs.push((order[k].min(order[j]), order[k].max(order[j])));
+                    }
+                }
+            });
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+
+    /// The two vertices farthest apart, and the distance between them. None if the
+    /// polygon has no vertices.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let rect = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 3.0}, Point{x: 0.0, y: 3.0},
+    /// ]);
+    /// let (d, a, b) = rect.diameter().unwrap();
+    /// assert_eq!((d, a, b), (5.0, Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 3.0}));
+    /// ```
+    pub fn diameter(&self) -> Option<(T, Point<T>, Point<T>)> {
+        let v = self.vertices();
+        let first = *v.first()?;
+        let mut best = (T::zero(), first, first);
+        for (i, j) in self.antipodal_pairs() {
+            let d = v[i].distance_squared(&v[j]);
+            if d > best.0 {
+                best = (d, v[i], v[j]);
+            }
+        }
+        Some((best.0.sqrt(), best.1, best.2))
+    }
+
+    /// The smallest distance between two parallel lines with the polygon between them,
+    /// along with the edge one of the lines runs along and the vertex the other touches.
+    /// None if the polygon has fewer than three distinct vertices.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let tri = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 1.0, y: 2.0},
+    /// ]);
+    /// let (width, edge, apex) = tri.width().unwrap();
+    /// assert_eq!((width, apex), (2.0, Point{x: 1.0, y: 2.0}));
+    /// assert_eq!(edge.p2(), Point{x: 4.0, y: 0.0});
+    /// ```
+    pub fn width(&self) -> Option<(T, LineSegment<T>, Point<T>)> {
+        let order = self.ccw_order();
+        let n = order.len();
+        if n < 3 {
+            return None;
+        }
+        let v = self.vertices();
+        let mut best: Option<(T, LineSegment<T>, Point<T>)> = None;
+        Self::rotate_calipers(&order, v, |i, j, height| {
+            let edge = LineSegment::new(v[order[i]], v[order[(i + 1) % n]]);
+            let width = height / edge.length();
+            if best.as_ref().is_none_or(|b| width < b.0) {
+                best = Some((width, edge, v[order[j]]));
+            }
+        });
+        best
+    }
+}
+
+/// The rectangles enclosing `points` that have a side along an edge of their convex
+/// hull, one for each edge. The smallest rectangle by area or perimeter is always one
+/// of these. With four calipers that each only move forward around the hull, finding
+/// them all takes linear time after the hull.
+fn hull_aligned_rects<T: Scalar>(points: &[Point<T>]) -> Vec<Obb<T>> {
+    let hull = convex_hull(points, false);
+    let h = hull.vertices();
+    let n = h.len();
+    match n {
+        0 => return Vec::new(),
+        1 => return alloc::vec![Obb::new(h[0], Point::origin(), T::zero())],
+        _ => {}
+    }
+    let at = |i: usize| h[i % n];
+    let (mut right, mut top, mut left) = (1, 1, 1);
+    let mut rects = Vec::with_capacity(n);
+    for i in 0..n {
+        let u = (at(i + 1) - at(i)).normalize();
+        let v = u.perp();
+        while (at(right + 1) - at(right)).dot_product(&u) > T::zero() {
+            right += 1;
+        }
+        if i == 0 {
+            top = right;
+        }
+        while (at(top + 1) - at(top)).dot_product(&v) > T::zero() {
+            top += 1;
+        }
+        if i == 0 {
+            left = top;
+        }
+        while (at(left + 1) - at(left)).dot_product(&u) < T::zero() {
+            left += 1;
+        }
+        let base = at(i);
+        let lo = (at(left) - base).dot_product(&u);
+        let hi = (at(right) - base).dot_product(&u);
+        let height = (at(top) - base).dot_product(&v);
+        let two = T::from_f64(2.0);
+        let center = base + u * ((lo + hi) / two) + v * (height / two);
+        let half = Point {
+            x: (hi - lo) / two,
+            y: height / two,
+        };
+        rects.push(Obb::new(center, half, u.angle()));
+    }
+    rects
+}
+
+/// The smallest rectangle by area, at any rotation, that contains every point, found
+/// with rotating calipers over their convex hull. The box's x axis runs along an edge
+/// of the hull. None if there are no points.
+/// ```
+/// use rsgeo::{min_area_rect, Obb, Point};
+/// // A diamond needs a box only half the size of its axis-aligned bounds
+/// let diamond = [
+///     Point{x: 1.0, y: 0.0}, Point{x: 0.0, y: 1.0}, Point{x: -1.0, y: 0.0}, Point{x: 0.0, y: -1.0},
+/// ];
+/// let obb: Obb = min_area_rect(&diamond).unwrap();
+/// assert!((obb.area() - 2.0).abs() < 1e-12);
+/// ```
+pub fn min_area_rect<T: Scalar>(points: &[Point<T>]) -> Option<Obb<T>> {
+    hull_aligned_rects(points)
+        .into_iter()
+        .min_by(|a, b| a.area().partial_cmp(&b.area()).unwrap_or(Ordering::Equal))
+}
+
+/// The rectangle of smallest perimeter, at any rotation, that contains every point.
+/// See `min_area_rect`.
+pub fn min_perimeter_rect<T: Scalar>(points: &[Point<T>]) -> Option<Obb<T>> {
+    let perimeter = |r: &Obb<T>| r.half_extents.x + r.half_extents.y;
+    hull_aligned_rects(points).into_iter().min_by(|a, b| {
+        perimeter(a)
+            .partial_cmp(&perimeter(b))
+            .unwrap_or(Ordering::Equal)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Angle, Lcg};
+
+    fn polygon(coords: &[(f64, f64)]) -> Polygon {
+        Polygon::new(coords.iter().map(|&(x, y)| Point { x, y }).collect())
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        // A hexagon of uneven sides, listed clockwise
+        let hexagon = polygon(&[
+            (0.0, 0.0),
+            (-1.0, 2.0),
+            (1.0, 5.0),
+            (5.0, 6.0),
+            (7.0, 3.0),
+            (4.0, -1.0),
+        ]);
+        let v = hexagon.vertices();
+        let farthest = (0..6)
+            .flat_map(|i| (0..6).map(move |j| v[i].distance(&v[j])))
+            .fold(0.0, f64::max);
+        assert_eq!(farthest, hexagon.diameter().unwrap().0);
+        let narrowest = hexagon
+            .edges()
+            .map(|e| {
+                v.iter()
+                    .map(|&p| e.direction().cross(&(p - e.p1())).abs() / e.length())
+                    .fold(0.0, f64::max)
+            })
+            .fold(f64::INFINITY, f64::min);
+        let (width, edge, far) = hexagon.width().unwrap();
+        assert!(crate::scalar_isclose(narrowest, width));
+        let height = edge.direction().cross(&(far - edge.p1())) / edge.length();
+        assert!(crate::scalar_isclose(width, height));
+        // The farthest pair is always antipodal
+        let (_, a, b) = hexagon.diameter().unwrap();
+        let index = |p: Point| v.iter().position(|&q| q == p).unwrap();
+        let pair = (index(a).min(index(b)), index(a).max(index(b)));
+        assert!(hexagon.antipodal_pairs().contains(&pair))
+    }
+
+    #[test]
+    fn test_regular_polygon() {
+        let octagon = Polygon::new(
+            (0..8)
+                .map(|i| Point::from_polar(1.0, Angle::from_degrees(45.0 * i as f64)))
+                .collect(),
+        );
+        let (d, a, b) = octagon.diameter().unwrap();
+        assert!(crate::scalar_isclose(2.0, d));
+        assert!((a + b).isclose(Point::origin()));
+        let (width, _, _) = octagon.width().unwrap();
+        assert!(crate::scalar_isclose(
+            2.0 * Angle::from_degrees(22.5).cos(),
+            width
+        ));
+        // Each vertex is antipodal to the opposite one and its two neighbours
+        assert_eq!(8 * 3 / 2, octagon.antipodal_pairs().len())
+    }
+
+    #[test]
+    fn test_degenerate_polygons() {
+        let repeated = polygon(&[(0.0, 0.0), (0.0, 0.0), (3.0, 0.0), (3.0, 1.0), (0.0, 0.0)]);
+        assert!(crate::scalar_isclose(
+            3.0 / 10.0_f64.sqrt(),
+            repeated.width().unwrap().0
+        ));
+        let segment = polygon(&[(0.0, 0.0), (3.0, 4.0)]);
+        assert_eq!(vec![(0, 1)], segment.antipodal_pairs());
+        assert_eq!(5.0, segment.diameter().unwrap().0);
+        assert_eq!(None, segment.width());
+        assert_eq!(None, polygon(&[]).diameter())
+    }
+
+    #[test]
+    fn test_min_rects_enclose_points() {
+        let mut rng = Lcg::new(5);
+        let mut coord = || rng.below(1000) as f64 / 100.0;
+        // A cloud stretched along a diagonal
+        let points: Vec<Point> = (0..100)
+            .map(|_| {
+                let (a, b) = (coord(), coord() / 5.0);
+                Point { x: a + b, y: a - b }
+            })
+            .collect();
+        let bounds = crate::Rect::from_points(points.iter().copied()).unwrap();
+        for obb in [min_area_rect(&points), min_perimeter_rect(&points)] {
+            let obb = obb.unwrap();
+            let grown = Obb::new(
+                obb.center,
+                obb.half_extents + Point { x: 1e-9, y: 1e-9 },
+                obb.rotation,
+            );
+            assert!(points.iter().all(|&p| grown.contains(p)));
+            assert!(obb.area() < bounds.area() / 2.0);
+        }
+        // No other hull edge gives a smaller box
+        let best = min_area_rect(&points).unwrap().area();
+        assert!(hull_aligned_rects(&points).iter().all(|r| r.area() >= best))
+    }
+
+    #[test]
+    fn test_min_rects_of_rotated_rectangle() {
+        // A 4 by 1 rectangle turned by 30 degrees
+        let corners: [Point; 4] = Obb::new(
+            Point { x: 2.0, y: 3.0 },
+            Point { x: 2.0, y: 0.5 },
+            Angle::from_degrees(30.0),
+        )
+        .corners();
+        let obb = min_area_rect(&corners).unwrap();
+        assert!(crate::scalar_isclose(4.0, obb.area()));
+        assert!(obb.center.isclose(Point { x: 2.0, y: 3.0 }));
+        let perimeter = min_perimeter_rect(&corners).unwrap();
+        let half = perimeter.half_extents;
+        assert!(crate::scalar_isclose(2.5, half.x + half.y));
+        let segment = min_area_rect(&corners[..2]).unwrap();
+        assert_eq!(0.0, segment.area());
+        assert!(crate::scalar_isclose(2.0, segment.half_extents.x));
+        assert_eq!(None, min_area_rect::<f64>(&[]))
+    }
+}
@@ -0,0 +1,622 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::predicates::orient2d;
+use crate::{Intersects, LineSegment, Point, Ray, RayCast, RayHit, Rect, Scalar};
+
+/// A circle, or the disk it encloses. The boundary counts as part of the circle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Circle<T = f64> {
+    pub center: Point<T>,
+    pub radius: T,
+}
+
+impl<T: Scalar> Circle<T> {
+    pub fn new(center: Point<T>, radius: T) -> Circle<T> {
+        Circle { center, radius }
+    }
+
+    /// The circumcircle, i.e. the unique circle passing through all three points.
+    /// Returns None if the points are collinear, which is decided exactly.
+    /// ```
+    /// use rsgeo::{Circle, Point};
+    /// let c = Circle::from_three_points(
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 6.0, y: 0.0}, Point{x: 0.0, y: 8.0}
+    /// ).unwrap();
+    /// assert_eq!(c.center, Point{x: 3.0, y: 4.0});
+    /// assert_eq!(c.radius, 5.0);
+    /// ```
+    pub fn from_three_points(a: Point<T>, b: Point<T>, c: Point<T>) -> Option<Circle<T>> {
+        if orient2d(a, b, c) == 0.0 {
+            return None;
+        }
+        // Work relative to `a` to limit the rounding error for points far from the
+        // origin
+        let ab = b - a;
+        let ac = c - a;
+        let d = T::from_f64(2.0) * ab.cross(&ac);
+        let (ab2, ac2) = (ab.magnitude_squared(), ac.magnitude_squared());
+        let offset = Point {
+            x: (ac.y * ab2 - ab.y * ac2) / d,
+            y: (ab.x * ac2 - ac.x * ab2) / d,
+        };
+        Some(Circle::new(a + offset, offset.magnitude()))
+    }
+
+    pub fn area(&self) -> T {
+        T::PI() * self.radius * self.radius
+    }
+
+    pub fn circumference(&self) -> T {
+        T::TAU() * self.radius
+    }
+
+    /// Whether `p` is inside the circle or on its boundary
+    pub fn contains(&self, p: Point<T>) -> bool {
+        self.center.distance_squared(&p) <= self.radius * self.radius
+    }
+
+    /// Whether the disk and `other`, which may be any shape implementing `Intersects`,
+    /// overlap. Circles that only touch intersect.
+    pub fn intersects<G>(&self, other: &G) -> bool
+    where
+        Self: Intersects<G>,
+    {
+        Intersects::intersects(self, other)
+    }
+
+    /// The points where the boundaries of two circles cross. Circles that touch at a
+    /// single point have one point, and circles that are apart, nested or concentric
+    /// have none. Of two points, the first lies to the right of the line from
+    /// `self.center` to `other.center` and the second to the left.
+    /// ```
+    /// use rsgeo::{Circle, Point};
+    /// let a = Circle::new(Point{x: 0.0, y: 0.0}, 5.0);
+    /// let b = Circle::new(Point{x: 6.0, y: 0.0}, 5.0);
+    /// assert_eq!(
+    ///     a.intersection_points(&b),
+    ///     [Some(Point{x: 3.0, y: -4.0}), Some(Point{x: 3.0, y: 4.0})]
+    /// );
+    /// ```
+    pub fn intersection_points(&self, other: &Circle<T>) -> [Option<Point<T>>; 2] {
+        let offset = other.center - self.center;
+        let d = offset.magnitude();
+        if d == T::zero()
+            || d > self.radius + other.radius
+            || d < (self.radius - other.radius).abs()
+        {
+            return [None, None];
+        }
+        let r1_sq = self.radius * self.radius;
+        // Distance from self.center to the chord joining the two points
+        let a = (d * d + r1_sq - other.radius * other.radius) / (T::from_f64(2.0) * d);
+        let h_sq = r1_sq - a * a;
+        let unit = offset / d;
+        let foot = self.center + unit * a;
+        if h_sq <= T::zero() {
+            return [Some(foot), None];
+        }
+        let side = unit.perp() * h_sq.sqrt();
+        [Some(foot - side), Some(foot + side)]
+    }
+
+    /// The area covered by both disks. This is the area of the lens between two
+    /// crossing circles, or the whole of the smaller disk when one lies inside the
+    /// other.
+    /// ```
+    /// use rsgeo::{Circle, Point};
+    /// let a = Circle::new(Point{x: 0.0, y: 0.0}, 1.0);
+    /// let b = Circle::new(Point{x: 1.0, y: 0.0}, 1.0);
+    /// let want = 2.0 * std::f64::consts::PI / 3.0 - 3.0_f64.sqrt() / 2.0;
+    /// assert!((a.overlap_area(&b) - want).abs() < 1e-12);
+    /// ```
+    pub fn overlap_area(&self, other: &Circle<T>) -> T {
+        let d = self.center.distance(&other.center);
+        let (r1, r2) = (self.radius, other.radius);
+        if d >= r1 + r2 {
+            return T::zero();
+        }
+        if d <= (r1 - r2).abs() {
+            let r = r1.min(r2);
+            return T::PI() * r * r;
+        }
+        let two = T::from_f64(2.0);
+        // Half the angle each circle's part of the lens subtends at its center
+        let half_angle = |r: T, r_other: T| {
+            let cos = (d * d + r * r - r_other * r_other) / (two * d * r);
+            cos.max(-T::one()).min(T::one()).acos()
+        };
+        let kite = (-d + r1 + r2) * (d + r1 - r2) * (d - r1 + r2) * (d + r1 + r2);
+        r1 * r1 * half_angle(r1, r2) + r2 * r2 * half_angle(r2, r1)
+            - kite.max(T::zero()).sqrt() / two
+    }
+
+    /// The two lines through `p` that just touch the circle, as segments from `p` to the
+    /// point of tangency. The first touches the right side of the circle as seen from
+    /// `p`, and the second the left. Returns None if `p` is inside the circle, and two
+    /// zero length segments if it is on the boundary.
+    /// ```
+    /// use rsgeo::{Circle, Point};
+    /// let c = Circle::new(Point{x: 0.0, y: 0.0}, 1.0);
+    /// let [right, left] = c.tangents_from_point(Point{x: 2.0, y: 0.0}).unwrap();
+    /// assert!(right.p2().isclose(Point{x: 0.5, y: 0.75_f64.sqrt()}));
+    /// assert!(left.p2().isclose(Point{x: 0.5, y: -0.75_f64.sqrt()}));
+    /// ```
+    pub fn tangents_from_point(&self, p: Point<T>) -> Option<[LineSegment<T>; 2]> {
+        let offset = self.center - p;
+        let d = offset.magnitude();
+        if d < self.radius {
+            return None;
+        }
+        // Touching the circle at r * n means n is a unit normal of the tangent line
+        let normals = tangent_normals(-offset / d, self.radius / d)?;
+        let touch = |n: Point<T>| LineSegment::new(p, self.center + n * self.radius);
+        Some([touch(normals[1]), touch(normals[0])])
+    }
+
+    /// The two lines touching both circles without passing between them, like a belt
+    /// wrapped around two pulleys. Each segment runs from the point of tangency on
+    /// `self` to the one on `other`. The first lies to the right of the line from
+    /// `self.center` to `other.center`, and the second to the left. Returns None if one
+    /// circle lies inside the other, or they are concentric.
+    /// ```
+    /// use rsgeo::{Circle, Point};
+    /// let a = Circle::new(Point{x: 0.0, y: 0.0}, 1.0);
+    /// let b = Circle::new(Point{x: 5.0, y: 0.0}, 1.0);
+    /// let [right, left] = a.external_tangents(&b).unwrap();
+    /// assert_eq!(right.to_string(), "(0.0, -1.0) -> (5.0, -1.0)");
+    /// assert_eq!(left.to_string(), "(0.0, 1.0) -> (5.0, 1.0)");
+    /// ```
+    pub fn external_tangents(&self, other: &Circle<T>) -> Option<[LineSegment<T>; 2]> {
+        self.common_tangents(other, T::one())
+    }
+
+    /// The two lines touching both circles and crossing between them, like a crossed
+    /// belt. Each segment runs from the point of tangency on `self` to the one on
+    /// `other`. The first touches `self` on the right of the line from `self.center` to
+    /// `other.center`, and the second on the left. Returns None if the circles overlap.
+    pub fn internal_tangents(&self, other: &Circle<T>) -> Option<[LineSegment<T>; 2]> {
+        self.common_tangents(other, -T::one())
+    }
+
+    /// Common tangents touching `other` on the same side as `self` when `sign` is one,
+    /// or the opposite side when it is minus one
+    fn common_tangents(&self, other: &Circle<T>, sign: T) -> Option<[LineSegment<T>; 2]> {
+        let offset = other.center - self.center;
+        let d = offset.magnitude();
+        if d == T::zero() {
+            return None;
+        }
+        let normals = tangent_normals(offset / d, (self.radius - sign * other.radius) / d)?;
+        let touch = |n: Point<T>| {
+            LineSegment::new(
+                self.center + n * self.radius,
+                other.center + n * (sign * other.radius),
+            )
+        };
+        Some([touch(normals[0]), touch(normals[1])])
+    }
+
+    /// The points where the boundary of the circle crosses the segment, in order from
+    /// `p1` to `p2`. A segment that just touches the circle has one point, and one that
+    /// enters but does not leave has one point. The unused entries are None.
+    /// ```
+    /// use rsgeo::{Circle, LineSegment, Point};
+    /// let c = Circle::new(Point{x: 0.0, y: 0.0}, 1.0);
+    /// let through: LineSegment = "(-2, 0) -> (2, 0)".parse().unwrap();
+    /// assert_eq!(
+    ///     c.intersect_segment(&through),
+    ///     [Some(Point{x: -1.0, y: 0.0}), Some(Point{x: 1.0, y: 0.0})]
+    /// );
+    /// let miss: LineSegment = "(-2, 3) -> (2, 3)".parse().unwrap();
+    /// assert_eq!(c.intersect_segment(&miss), [None, None]);
+    /// ```
+    pub fn intersect_segment(&self, seg: &LineSegment<T>) -> [Option<Point<T>>; 2] {
+        let mut found = [None, None];
+        let mut n = 0;
+        for &t in self
+            .line_parameters(seg.p1(), seg.direction())
+            .iter()
+            .flatten()
+        {
+            if t >= T::zero() && t <= T::one() {
+                found[n] = Some(seg.p1() + seg.direction() * t);
+                n += 1;
+            }
+        }
+        found
+    }
+
+    /// The values of `t`, in increasing order, at which `origin + direction * t` lies
+    /// on the boundary of the circle. A tangent line has a single value.
+    fn line_parameters(&self, origin: Point<T>, direction: Point<T>) -> [Option<T>; 2] {
+        let a = direction.magnitude_squared();
+        if a == T::zero() {
+            return [None, None];
+        }
+        let f = origin - self.center;
+        let half_b = f.dot_product(&direction);
+        let c = f.magnitude_squared() - self.radius * self.radius;
+        let disc = half_b * half_b - a * c;
+        if disc < T::zero() {
+            [None, None]
+        } else if disc == T::zero() {
+            [Some(-half_b / a), None]
+        } else {
+            let root = disc.sqrt();
+            [Some((-half_b - root) / a), Some((-half_b + root) / a)]
+        }
+    }
+
+    /// The smallest axis-aligned rectangle containing the circle
+    pub fn bounding_rect(&self) -> Rect<T> {
+        Rect::from_center_size(
+            self.center,
+            T::from_f64(2.0) * self.radius,
+            T::from_f64(2.0) * self.radius,
+        )
+    }
+
+    /// The smallest circle containing every point, by Welzl's algorithm, along with the
+    /// two or three points on its boundary that fix it, or the single point if there is
+    /// only one. The points are visited in a shuffled order, which makes the expected
+    /// time linear. The shuffle is seeded from the number of points, so the same input
+    /// always gives the same result. None if there are no points.
+    /// ```
+    /// use rsgeo::{Circle, Point};
+    /// let points = [
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 2.0, y: 1.0}, Point{x: 1.0, y: -1.0},
+    /// ];
+    /// let (circle, support) = Circle::min_enclosing(&points).unwrap();
+    /// assert_eq!(circle, Circle::new(Point{x: 2.0, y: 0.0}, 2.0));
+    /// assert_eq!(support.len(), 2);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn min_enclosing(points: &[Point<T>]) -> Option<(Circle<T>, Vec<Point<T>>)> {
+        let mut p = points.to_vec();
+        let mut state = p.len() as u64 ^ 0x9e37_79b9_7f4a_7c15;
+        for i in (1..p.len()).rev() {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            p.swap(i, (state % (i as u64 + 1)) as usize);
+        }
+        // Allow for the rounding in finding each circle, so that points it was built
+        // through still count as inside
+        let slack = T::one() + T::epsilon() * T::from_f64(16.0);
+        let inside = |c: &Circle<T>, q: Point<T>| c.center.distance(&q) <= c.radius * slack;
+        let diameter = |a: Point<T>, b: Point<T>| {
+            Circle::new(
+                a.lerp(&b, T::from_f64(0.5)),
+                a.distance(&b) / T::from_f64(2.0),
+            )
+        };
+        let mut circle = Circle::new(*p.first()?, T::zero());
+        let mut support = alloc::vec![p[0]];
+        for i in 1..p.len() {
+            if inside(&circle, p[i]) {
+                continue;
+            }
+            circle = Circle::new(p[i], T::zero());
+            support = alloc::vec![p[i]];
+            for j in 0..i {
+                if inside(&circle, p[j]) {
+                    continue;
+                }
+                circle = diameter(p[i], p[j]);
+                support = alloc::vec![p[i], p[j]];
+                for k in 0..j {
+                    if inside(&circle, p[k]) {
+                        continue;
+                    }
+                    // Three points on one line are spanned by the farthest two
+                    let (a, b, c) = (p[i], p[j], p[k]);
+                    match Circle::from_three_points(a, b, c) {
+                        Some(through) => {
+                            circle = through;
+                            support = alloc::vec![a, b, c];
+                        }
+                        None => {
+                            let pairs = [(a, b), (b, c), (a, c)];
+                            let &(x, y) = pairs
+                                .iter()
+                                .max_by(|l, r| {
+                                    l.0.distance_squared(&l.1)
+                                        .partial_cmp(&r.0.distance_squared(&r.1))
+                                        .unwrap_or(core::cmp::Ordering::Equal)
+                                })
+                                .unwrap();
+                            circle = diameter(x, y);
+                            support = alloc::vec![x, y];
+                        }
+                    }
+                }
+            }
+        }
+        Some((circle, support))
+    }
+}
+
+/// The unit vectors `n` with `n.dot_product(&v) == c`, for the unit vector `v`. The
+/// first is clockwise of `v` and the second counter-clockwise. None if `|c| > 1`.
+fn tangent_normals<T: Scalar>(v: Point<T>, c: T) -> Option<[Point<T>; 2]> {
+    let h_sq = T::one() - c * c;
+    if h_sq < T::zero() {
+        return None;
+    }
+    let along = v * c;
+    let across = v.perp() * h_sq.sqrt();
+    Some([along - across, along + across])
+}
+
+impl<T: Scalar> RayCast<T> for Circle<T> {
+    /// The circle is treated as a solid disk, so a ray starting inside hits at its
+    /// origin
+    fn cast_ray(&self, ray: &Ray<T>) -> Option<RayHit<T>> {
+        if self.contains(ray.origin) {
+            return Some(RayHit {
+                distance: T::zero(),
+                point: ray.origin,
+            });
+        }
+        let t = self.line_parameters(ray.origin, ray.direction)[0]?;
+        if t < T::zero() {
+            return None;
+        }
+        let point = ray.origin + ray.direction * t;
+        Some(RayHit {
+            distance: ray.origin.distance(&point),
+            point,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measurements() {
+        let c = Circle::new(Point { x: 1.0, y: 2.0 }, 2.0);
+        assert_eq!(4.0 * core::f64::consts::PI, c.area());
+        assert_eq!(4.0 * core::f64::consts::PI, c.circumference());
+        assert_eq!(
+            Rect::from_corners(Point { x: -1.0, y: 0.0 }, Point { x: 3.0, y: 4.0 }),
+            c.bounding_rect()
+        )
+    }
+
+    #[test]
+    fn test_from_three_points() {
+        let (a, b, c) = (
+            Point {
+                x: 1e6 + 3.0,
+                y: 1e6,
+            },
+            Point {
+                x: 1e6,
+                y: 1e6 + 3.0,
+            },
+            Point {
+                x: 1e6 - 3.0,
+                y: 1e6,
+            },
+        );
+        let circle = Circle::from_three_points(a, b, c).unwrap();
+        assert!(circle.center.isclose(Point { x: 1e6, y: 1e6 }));
+        assert!(crate::scalar_isclose(3.0, circle.radius));
+        // The order of the points does not matter
+        let reordered = Circle::from_three_points(c, a, b).unwrap();
+        assert!(reordered.center.isclose(circle.center))
+    }
+
+    #[test]
+    fn test_from_three_points_collinear() {
+        let a = Point { x: 0.1, y: 0.1 };
+        let b = Point { x: 0.2, y: 0.2 };
+        assert_eq!(
+            None,
+            Circle::from_three_points(a, b, Point { x: 0.7, y: 0.7 })
+        );
+        assert_eq!(None, Circle::from_three_points(a, a, b))
+    }
+
+    #[test]
+    fn test_contains_and_intersects() {
+        let c = Circle::new(Point { x: 0.0, y: 0.0 }, 5.0);
+        assert!(c.contains(Point { x: 3.0, y: 4.0 }));
+        assert!(!c.contains(Point { x: 3.0, y: 4.1 }));
+        let touching = Circle::new(Point { x: 8.0, y: 0.0 }, 3.0);
+        assert!(c.intersects(&touching));
+        let apart = Circle::new(Point { x: 8.1, y: 0.0 }, 3.0);
+        assert!(!c.intersects(&apart));
+        let inside = Circle::new(Point { x: 1.0, y: 0.0 }, 1.0);
+        assert!(c.intersects(&inside))
+    }
+
+    #[test]
+    fn test_intersect_segment_partial() {
+        let c = Circle::new(Point { x: 0.0, y: 0.0 }, 5.0);
+        // Starts inside and leaves through (3, 4)
+        let leaving: LineSegment = "(3, 0) -> (3, 10)".parse().unwrap();
+        assert_eq!(
+            [Some(Point { x: 3.0, y: 4.0 }), None],
+            c.intersect_segment(&leaving)
+        );
+        // Ends inside, and is reported in order from p1
+        assert_eq!(
+            [Some(Point { x: 3.0, y: 4.0 }), None],
+            c.intersect_segment(&leaving.reverse())
+        );
+        let tangent: LineSegment = "(-5, 5) -> (5, 5)".parse().unwrap();
+        assert_eq!(
+            [Some(Point { x: 0.0, y: 5.0 }), None],
+            c.intersect_segment(&tangent)
+        );
+        let inside: LineSegment = "(-1, 0) -> (1, 0)".parse().unwrap();
+        assert_eq!([None, None], c.intersect_segment(&inside))
+    }
+
+    #[test]
+    fn test_intersection_points_lie_on_both_circles() {
+        let a = Circle::new(Point { x: 1.0, y: -2.0 }, 3.0);
+        let b = Circle::new(Point { x: 3.5, y: 1.0 }, 2.0);
+        let [p, q] = a.intersection_points(&b);
+        let (p, q) = (p.unwrap(), q.unwrap());
+        for pt in &[p, q] {
+            assert!(crate::scalar_isclose(a.radius, a.center.distance(pt)));
+            assert!(crate::scalar_isclose(b.radius, b.center.distance(pt)));
+        }
+        // The first point is to the right of the line between the centers
+        assert!((b.center - a.center).cross(&(p - a.center)) < 0.0);
+        let [q_rev, p_rev] = b.intersection_points(&a);
+        assert!(p.isclose(p_rev.unwrap()));
+        assert!(q.isclose(q_rev.unwrap()))
+    }
+
+    #[test]
+    fn test_intersection_points_degenerate() {
+        let a = Circle::new(Point { x: 0.0, y: 0.0 }, 2.0);
+        let touching = Circle::new(Point { x: 3.0, y: 0.0 }, 1.0);
+        assert_eq!(
+            [Some(Point { x: 2.0, y: 0.0 }), None],
+            a.intersection_points(&touching)
+        );
+        let nested = Circle::new(Point { x: 0.5, y: 0.0 }, 1.0);
+        assert_eq!([None, None], a.intersection_points(&nested));
+        assert_eq!([None, None], a.intersection_points(&a));
+        let apart = Circle::new(Point { x: 3.5, y: 0.0 }, 1.0);
+        assert_eq!([None, None], a.intersection_points(&apart))
+    }
+
+    #[test]
+    fn test_overlap_area_limits() {
+        let a = Circle::new(Point { x: 0.0, y: 0.0 }, 2.0);
+        let apart = Circle::new(Point { x: 5.0, y: 0.0 }, 1.0);
+        assert_eq!(0.0, a.overlap_area(&apart));
+        let nested = Circle::new(Point { x: 0.5, y: 0.0 }, 1.0);
+        assert_eq!(nested.area(), a.overlap_area(&nested));
+        assert_eq!(a.area(), a.overlap_area(&a));
+        // The area shrinks smoothly to zero as the circles separate
+        let barely = Circle::new(Point { x: 2.999, y: 0.0 }, 1.0);
+        let area = a.overlap_area(&barely);
+        assert!(area > 0.0 && area < 1e-3);
+        assert!(crate::scalar_isclose(area, barely.overlap_area(&a)))
+    }
+
+    /// Whether `seg` lies on a line that touches `circle`
+    fn is_tangent(seg: &LineSegment, circle: &Circle) -> bool {
+        let line = crate::Line::from(*seg);
+        crate::scalar_isclose(circle.radius, line.distance_to_point(circle.center))
+    }
+
+    #[test]
+    fn test_tangents_from_point() {
+        let c = Circle::new(Point { x: 1.0, y: 1.0 }, 2.0);
+        let p = Point { x: 4.0, y: 5.0 };
+        let tangents = c.tangents_from_point(p).unwrap();
+        for t in &tangents {
+            assert_eq!(p, t.p1());
+            assert!(crate::scalar_isclose(c.radius, c.center.distance(&t.p2())));
+            assert!(is_tangent(t, &c));
+            // Both tangent lengths are sqrt(d^2 - r^2)
+            assert!(crate::scalar_isclose(21.0_f64.sqrt(), t.length()));
+        }
+        // The first touches on the right as seen from p
+        let towards = c.center - p;
+        assert!(towards.cross(&(tangents[0].p2() - p)) < 0.0);
+        assert_eq!(None, c.tangents_from_point(Point { x: 2.0, y: 1.0 }))
+    }
+
+    #[test]
+    fn test_external_tangents_different_radii() {
+        let a = Circle::new(Point { x: 0.0, y: 0.0 }, 3.0);
+        let b = Circle::new(Point { x: 10.0, y: 2.0 }, 1.0);
+        let straight_runs = a.external_tangents(&b).unwrap();
+        for t in &straight_runs {
+            assert!(is_tangent(t, &a));
+            assert!(is_tangent(t, &b));
+            assert!(crate::scalar_isclose(a.radius, a.center.distance(&t.p1())));
+            assert!(crate::scalar_isclose(b.radius, b.center.distance(&t.p2())));
+        }
+        let nested = Circle::new(Point { x: 1.0, y: 0.0 }, 1.0);
+        assert_eq!(None, a.external_tangents(&nested))
+    }
+
+    #[test]
+    fn test_internal_tangents_cross_between_centers() {
+        let a = Circle::new(Point { x: 0.0, y: 0.0 }, 1.0);
+        let b = Circle::new(Point { x: 6.0, y: 0.0 }, 2.0);
+        let crossed = a.internal_tangents(&b).unwrap();
+        let centers = LineSegment::new(a.center, b.center);
+        for t in &crossed {
+            assert!(is_tangent(t, &a));
+            assert!(is_tangent(t, &b));
+            assert_ne!(crate::SegmentIntersection::None, t.intersect(&centers));
+        }
+        assert!(crossed[0].p1().y < 0.0 && crossed[0].p2().y > 0.0);
+        let overlapping = Circle::new(Point { x: 2.5, y: 0.0 }, 2.0);
+        assert_eq!(None, a.internal_tangents(&overlapping))
+    }
+
+    #[test]
+    fn test_cast_ray() {
+        let c = Circle::new(Point { x: 10.0, y: 0.0 }, 2.0);
+        let ray = Ray::new(Point { x: 0.0, y: 0.0 }, Point { x: 3.0, y: 0.0 });
+        let hit = ray.cast(&c).unwrap();
+        assert_eq!(Point { x: 8.0, y: 0.0 }, hit.point);
+        assert_eq!(8.0, hit.distance);
+        let away = Ray::new(Point { x: 0.0, y: 0.0 }, Point { x: -1.0, y: 0.0 });
+        assert_eq!(None, away.cast(&c));
+        let inside = Ray::new(Point { x: 9.0, y: 1.0 }, Point { x: 0.0, y: 1.0 });
+        assert_eq!(0.0, inside.cast(&c).unwrap().distance)
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_min_enclosing() {
+        let mut rng = crate::Lcg::new(21);
+        let mut coord = || rng.below(1000) as f64 / 37.0;
+        let points: Vec<Point> = (0..300)
+            .map(|_| Point {
+                x: coord(),
+                y: coord(),
+            })
+            .collect();
+        let (circle, support) = Circle::min_enclosing(&points).unwrap();
+        let slack = circle.radius * 1e-12;
+        assert!(points
+            .iter()
+            .all(|p| circle.center.distance(p) <= circle.radius + slack));
+        assert!((2..=3).contains(&support.len()));
+        for s in &support {
+            assert!(points.contains(s));
+            assert!((circle.center.distance(s) - circle.radius).abs() <= slack);
+        }
+        // Nudging the center any way leaves some point outside
+        for i in 0..8 {
+            let step = Point::from_polar(1e-6, core::f64::consts::PI * f64::from(i) / 4.0);
+            let moved = circle.center + step;
+            assert!(points.iter().any(|p| moved.distance(p) > circle.radius));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_min_enclosing_degenerate() {
+        assert_eq!(None, Circle::<f64>::min_enclosing(&[]));
+        let dot = [Point { x: 1.0, y: 2.0 }; 3];
+        assert_eq!(
+            (Circle::new(dot[0], 0.0), vec![dot[0]]),
+            Circle::min_enclosing(&dot).unwrap()
+        );
+        let line: Vec<Point> = [3.0, 0.0, 1.0, 4.0, 2.0]
+            .iter()
+            .map(|&x| Point { x, y: x })
+            .collect();
+        let (circle, support) = Circle::min_enclosing(&line).unwrap();
+        assert!(circle.center.isclose(Point { x: 2.0, y: 2.0 }));
+        assert_eq!(2, support.len())
+    }
+}
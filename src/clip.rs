@@ -0,0 +1,350 @@
+use alloc::vec::Vec;
+
+use crate::polygon::ring_edges;
+use crate::predicates::{orient2d, Orientation};
+use crate::{
+    segment_intersections, LineSegment, Point, Polygon, Polyline, Scalar, SegmentIntersection,
+};
+
+/// The part of `subject` inside the convex polygon `window`, found by the
+/// Sutherland–Hodgman algorithm, which cuts each ring of the subject against the
+/// window's edges one at a time in O(nm) time. Which side of an edge a vertex is on is
+/// decided exactly, and the points where rings cross an edge are rounded. Rings that
+/// are cut away entirely are dropped, and if that includes the exterior the result has
+/// no vertices. The window may wind in either direction.
+///
+/// Where the subject is concave and leaves the window more than once, the pieces stay
+/// joined by edges running along the window's boundary rather than coming apart into
+/// separate polygons, so the area is right but the shape is not simple. Use
+/// `BooleanOps::intersection` to get the pieces. Panics if `window` is not convex.
+/// ```
+/// use rsgeo::{clip_polygon_convex, Point, Polygon, Rect};
+/// let viewport = Rect::from_corners(Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 4.0});
+/// let window = Polygon::new(viewport.corners().to_vec());
+/// let triangle = Polygon::new(vec![
+///     Point{x: 2.0, y: 1.0}, Point{x: 6.0, y: 1.0}, Point{x: 2.0, y: 5.0},
+/// ]);
+/// let clipped = clip_polygon_convex(&triangle, &window);
+/// assert_eq!(clipped.vertices().len(), 5);
+/// assert_eq!(clipped.area(), 5.5);
+/// ```
+pub fn clip_polygon_convex<T: Scalar>(subject: &Polygon<T>, window: &Polygon<T>) -> Polygon<T> {
+    assert!(window.is_convex(), "window must be convex");
+    let mut edges: Vec<(Point<T>, Point<T>)> = window.edges().map(|e| (e.p1(), e.p2())).collect();
+    if window.orientation() == Orientation::Clockwise {
+        edges = edges.into_iter().rev().map(|(a, b)| (b, a)).collect();
+    }
+    let mut rings = subject
+        .rings()
+        .map(|ring| clip_ring(ring, &edges))
+        .filter(|ring| !ring.is_empty());
+    match rings.next() {
+        Some(exterior) if !subject.vertices().is_empty() => {
+            Polygon::with_holes(exterior, rings.collect())
+        }
+        _ => Polygon::default(),
+    }
+}
+
+/// Cut `ring` down to the left of every one of `edges`
+pub(crate) fn clip_ring<T: Scalar>(
+    ring: &[Point<T>],
+    edges: &[(Point<T>, Point<T>)],
+) -> Vec<Point<T>> {
+    let mut ring = ring.to_vec();
+    for &(a, b) in edges {
+        let side = |p: Point<T>| orient2d(a, b, p);
+        let mut kept = Vec::with_capacity(ring.len() + 1);
+        for (i, &p) in ring.iter().enumerate() {
+            let q = ring[(i + 1) % ring.len()];
+            let (sp, sq) = (side(p), side(q));
+            if sp >= 0.0 {
+                kept.push(p);
+            }
+            if (sp > 0.0 && sq < 0.0) || (sp < 0.0 && sq > 0.0) {
+                // Where `pq` crosses the line through the edge, by similar triangles
+                let d = b - a;
+                let (cp, cq) = (d.cross(&(p - a)), d.cross(&(q - a)));
+                kept.push(p + (q - p) * (cp / (cp - cq)));
+            }
+        }
+        ring = kept;
+        if ring.is_empty() {
+            break;
+        }
+    }
+    ring
+}
+
+impl<T: Scalar> Polyline<T> {
+    /// The parts of the path inside `polygon` or along its boundary, in order along the
+    /// path. The path is split wherever it meets the boundary, found by a sweep over the
+    /// path's segments and the polygon's edges in O((n + m + k) log(n + m)) time, where
+    /// `k` is how many times they meet. Whether the path is inside can only change where
+    /// it meets the boundary, so the polygon is only consulted just after each of
+    /// those points. Parts that only touch the polygon at a point are left out.
+    /// ```
+    /// use rsgeo::{Point, Polygon, Polyline};
+    /// let square = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 4.0}, Point{x: 0.0, y: 4.0},
+    /// ]);
+    /// // In through the bottom, out through the right, and back in through the top
+    /// let track = Polyline::new(vec![
+    ///     Point{x: 1.0, y: -1.0}, Point{x: 1.0, y: 1.0}, Point{x: 6.0, y: 1.0},
+    ///     Point{x: 6.0, y: 6.0}, Point{x: 3.0, y: 3.0},
+    /// ]);
+    /// let inside = track.clip_to(&square);
+    /// assert_eq!(inside.len(), 2);
+    /// assert_eq!(inside[0].points(), &[
+    ///     Point{x: 1.0, y: 0.0}, Point{x: 1.0, y: 1.0}, Point{x: 4.0, y: 1.0},
+    /// ]);
+    /// assert_eq!(inside[1].points(), &[Point{x: 4.0, y: 4.0}, Point{x: 3.0, y: 3.0}]);
+    /// assert_eq!(track.clip_outside(&square).len(), 2);
+    /// ```
+    pub fn clip_to(&self, polygon: &Polygon<T>) -> Vec<Polyline<T>> {
+        clip_path(self, polygon, true)
+    }
+
+    /// The parts of the path strictly outside `polygon`, in order along the path. See
+    /// `clip_to`, which returns the rest of it.
+    pub fn clip_outside(&self, polygon: &Polygon<T>) -> Vec<Polyline<T>> {
+        clip_path(self, polygon, false)
+    }
+}
+
+/// A place along a segment of a path, as its parameter and point, and whether the path
+/// meets the boundary there
+type Stop<T> = (T, Point<T>, bool);
+
+fn clip_path<T: Scalar>(
+    path: &Polyline<T>,
+    polygon: &Polygon<T>,
+    inside: bool,
+) -> Vec<Polyline<T>> {
+    let steps: Vec<LineSegment<T>> = path.segments().collect();
+    let n = steps.len();
+    let mut segments = steps.clone();
+    segments.extend(polygon.rings().flat_map(ring_edges));
+    let param = |seg: &LineSegment<T>, q: Point<T>| {
+        let d = seg.direction();
+        let len2 = d.dot_product(&d);
+        if len2 == T::zero() {
+            T::zero()
+        } else {
+            ((q - seg.p1()).dot_product(&d) / len2)
+                .max(T::zero())
+                .min(T::one())
+        }
+    };
+    let mut stops: Vec<Vec<Stop<T>>> = steps
+        .iter()
+        .map(|s| alloc::vec![(T::zero(), s.p1(), false), (T::one(), s.p2(), false)])
+        .collect();
+    // The stretches of each step that run along the boundary
+    let mut along: Vec<Vec<(T, T)>> = alloc::vec![Vec::new(); n];
+    // The segments are all of the path's steps and then the polygon's edges
+    for (i, j, hit) in segment_intersections(&segments) {
+        if i >= n || j < n {
+            continue;
+        }
+        let step = &steps[i];
+        match hit {
+            SegmentIntersection::None => {}
+            SegmentIntersection::Point(q) => stops[i].push((param(step, q), q, true)),
+            SegmentIntersection::Segment(s) => {
+                let (a, b) = (param(step, s.p1()), param(step, s.p2()));
+                stops[i].push((a, s.p1(), true));
+                stops[i].push((b, s.p2(), true));
+                along[i].push((a.min(b), a.max(b)));
+            }
+        }
+    }
+
+    let mut pieces = Vec::new();
+    let mut current: Vec<Point<T>> = Vec::new();
+    let mut keep = false;
+    let mut relocate = true;
+    for (i, stops) in stops.iter_mut().enumerate() {
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        // Merge stops at the same place, keeping the ends of the step exactly
+        let mut merged: Vec<Stop<T>> = Vec::with_capacity(stops.len());
+        for &(t, p, meets) in stops.iter() {
+            match merged.last_mut() {
+                Some(last) if last.0 == t => last.2 |= meets,
+                _ => merged.push((t, p, meets)),
+            }
+        }
+        if let Some(last) = merged.last_mut() {
+            last.1 = steps[i].p2();
+        }
+        for pair in merged.windows(2) {
+            let ((t0, p0, meets), (t1, p1, _)) = (pair[0], pair[1]);
+            if relocate || meets {
+                keep = if along[i].iter().any(|&(lo, hi)| lo <= t0 && t1 <= hi) {
+                    inside
+                } else {
+                    polygon.contains(p0 + (p1 - p0) * T::from_f64(0.5)) == inside
+                };
+            }
+            relocate = false;
+            if keep {
+                if current.is_empty() {
+                    current.push(p0);
+                }
+                current.push(p1);
+            } else if !current.is_empty() {
+                pieces.push(Polyline::new(core::mem::take(&mut current)));
+            }
+        }
+        relocate |= merged.last().is_some_and(|last| last.2);
+    }
+    if !current.is_empty() {
+        pieces.push(Polyline::new(current));
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BooleanOps;
+
+    fn square(lo: f64, hi: f64) -> Vec<Point> {
+        vec![
+            Point { x: lo, y: lo },
+            Point { x: hi, y: lo },
+            Point { x: hi, y: hi },
+            Point { x: lo, y: hi },
+        ]
+    }
+
+    #[test]
+    fn test_inside_and_outside() {
+        let window = Polygon::new(square(0.0, 10.0));
+        let inner = Polygon::new(square(2.0, 3.0));
+        assert_eq!(inner, clip_polygon_convex(&inner, &window));
+        let outer = Polygon::new(square(20.0, 30.0));
+        assert!(clip_polygon_convex(&outer, &window).vertices().is_empty());
+        // The window wholly inside the subject comes back, whichever way it winds
+        let mut diamond = Polygon::new(vec![
+            Point { x: 5.0, y: 0.0 },
+            Point { x: 10.0, y: 5.0 },
+            Point { x: 5.0, y: 10.0 },
+            Point { x: 0.0, y: 5.0 },
+        ]);
+        diamond.reverse();
+        let big = Polygon::new(square(-5.0, 15.0));
+        assert_eq!(50.0, clip_polygon_convex(&big, &diamond).area());
+        assert!(clip_polygon_convex(&Polygon::default(), &window)
+            .vertices()
+            .is_empty())
+    }
+
+    #[test]
+    fn test_holes() {
+        let frame = Polygon::with_holes(square(0.0, 6.0), vec![square(2.0, 4.0)]);
+        // Keeps the hole whole
+        let clipped = clip_polygon_convex(&frame, &Polygon::new(square(1.0, 5.0)));
+        assert_eq!(1, clipped.holes().len());
+        assert_eq!(16.0 - 4.0, clipped.area());
+        // Cuts the hole away with the rest of the corner
+        let corner = clip_polygon_convex(&frame, &Polygon::new(square(-1.0, 1.5)));
+        assert!(corner.holes().is_empty());
+        assert_eq!(2.25, corner.area())
+    }
+
+    #[test]
+    fn test_agrees_with_intersection() {
+        // A comb whose teeth poke out of the window
+        let comb = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 5.0, y: 0.0 },
+            Point { x: 5.0, y: 4.0 },
+            Point { x: 4.0, y: 4.0 },
+            Point { x: 4.0, y: 1.0 },
+            Point { x: 3.0, y: 1.0 },
+            Point { x: 3.0, y: 4.0 },
+            Point { x: 2.0, y: 4.0 },
+            Point { x: 2.0, y: 1.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 1.0, y: 4.0 },
+            Point { x: 0.0, y: 4.0 },
+        ]);
+        // Leaving only the teeth, in three pieces
+        let window = Polygon::new(vec![
+            Point { x: -1.0, y: 2.0 },
+            Point { x: 6.0, y: 2.5 },
+            Point { x: 6.0, y: 5.0 },
+            Point { x: -1.0, y: 5.0 },
+        ]);
+        let clipped = clip_polygon_convex(&comb, &window);
+        let exact = comb.intersection(&window);
+        assert_eq!(3, exact.polygons().len());
+        assert!(crate::scalar_isclose(exact.area(), clipped.area()));
+        // The pieces stay joined along the window's edge
+        assert!(!clipped.is_simple())
+    }
+
+    #[test]
+    #[should_panic(expected = "window must be convex")]
+    fn test_window_must_be_convex() {
+        let subject = Polygon::new(square(0.0, 1.0));
+        let dented = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+        ]);
+        clip_polygon_convex(&subject, &dented);
+    }
+
+    fn path(points: &[(f64, f64)]) -> Polyline {
+        points.iter().map(|&(x, y)| Point { x, y }).collect()
+    }
+
+    #[test]
+    fn test_clip_path_with_hole() {
+        let frame = Polygon::with_holes(square(0.0, 6.0), vec![square(2.0, 4.0)]);
+        // Straight across, through the hole
+        let across = path(&[(-1.0, 3.0), (7.0, 3.0)]);
+        let inside = across.clip_to(&frame);
+        assert_eq!(
+            inside,
+            vec![
+                path(&[(0.0, 3.0), (2.0, 3.0)]),
+                path(&[(4.0, 3.0), (6.0, 3.0)])
+            ]
+        );
+        let outside = across.clip_outside(&frame);
+        assert_eq!(3, outside.len());
+        assert_eq!(path(&[(2.0, 3.0), (4.0, 3.0)]), outside[1]);
+        let total: f64 = inside.iter().chain(&outside).map(Polyline::length).sum();
+        assert_eq!(across.length(), total)
+    }
+
+    #[test]
+    fn test_clip_path_along_boundary() {
+        let poly = Polygon::new(square(0.0, 4.0));
+        // Along the bottom edge, then out, then touching the right edge at one point
+        let route = path(&[
+            (-2.0, 0.0),
+            (2.0, 0.0),
+            (2.0, -1.0),
+            (5.0, -1.0),
+            (4.0, 2.0),
+            (5.0, 3.0),
+        ]);
+        let inside = route.clip_to(&poly);
+        assert_eq!(vec![path(&[(0.0, 0.0), (2.0, 0.0)])], inside);
+        let outside = route.clip_outside(&poly);
+        assert_eq!(2, outside.len());
+        assert_eq!(path(&[(-2.0, 0.0), (0.0, 0.0)]), outside[0]);
+        assert_eq!(route.points()[1..].to_vec(), outside[1].points().to_vec());
+        // Entirely inside, and empty
+        let short = path(&[(1.0, 1.0), (2.0, 2.0), (3.0, 1.0)]);
+        assert_eq!(vec![short.clone()], short.clip_to(&poly));
+        assert!(short.clip_outside(&poly).is_empty());
+        assert!(Polyline::default().clip_to(&poly).is_empty())
+    }
+}
@@ -0,0 +1,113 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::{OrderedPoint, Point, Scalar};
+
+/// The two closest of `points` and the distance between them, found in O(n log n) time
+/// by sweeping a line across them from left to right. Only the points within the best
+/// distance so far behind the line are kept, ordered by y, so each new point need only
+/// be compared with the few of them that are near it in y. None if there are fewer
+/// than two points. If a point is repeated the distance is zero.
+/// ```
+/// use rsgeo::{closest_pair, Point};
+/// let points = [
+///     Point{x: 0.0, y: 0.0}, Point{x: 5.0, y: 1.0}, Point{x: 2.0, y: 3.0}, Point{x: 5.5, y: 1.5},
+/// ];
+/// let (a, b, d) = closest_pair(&points).unwrap();
+/// assert_eq!((a, b), (Point{x: 5.0, y: 1.0}, Point{x: 5.5, y: 1.5}));
+/// assert_eq!(d, 0.5_f64.sqrt());
+/// ```
+pub fn closest_pair<T: Scalar>(points: &[Point<T>]) -> Option<(Point<T>, Point<T>, T)> {
+    let mut sorted: Vec<OrderedPoint<T>> = points.iter().map(|&p| p.into()).collect();
+    sorted.sort_unstable();
+    let mut best = (
+        sorted.first()?.point(),
+        sorted.get(1)?.point(),
+        T::infinity(),
+    );
+    // The points behind the line, keyed by y and then x
+    let flip = |p: Point<T>| OrderedPoint::new(Point { x: p.y, y: p.x });
+    let mut active = BTreeSet::new();
+    let mut behind = 0;
+    for p in sorted.iter().map(OrderedPoint::point) {
+        let d = best.2;
+        while sorted[behind].point().x < p.x - d {
+            active.remove(&flip(sorted[behind].point()));
+            behind += 1;
+        }
+        let (lo, hi) = (
+            Point {
+                x: p.y - d,
+                y: T::neg_infinity(),
+            },
+            Point {
+                x: p.y + d,
+                y: T::infinity(),
+            },
+        );
+        let near = active.range(OrderedPoint::new(lo)..=OrderedPoint::new(hi));
+        for q in near.map(|q: &OrderedPoint<T>| flip(q.point()).point()) {
+            let dist = p.distance(&q);
+            if dist < best.2 {
+                best = (q, p, dist);
+            }
+        }
+        if best.2 == T::zero() {
+            break;
+        }
+        active.insert(flip(p));
+    }
+    Some(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lcg;
+
+    fn brute_force(points: &[Point]) -> f64 {
+        let mut best = f64::INFINITY;
+        for (i, p) in points.iter().enumerate() {
+            for q in &points[i + 1..] {
+                best = best.min(p.distance(q));
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = Lcg::new(17);
+        let mut coord = || rng.unit() * 100.0;
+        for n in [2, 3, 10, 500] {
+            let points: Vec<Point> = (0..n)
+                .map(|_| Point {
+                    x: coord(),
+                    y: coord(),
+                })
+                .collect();
+            let (a, b, d) = closest_pair(&points).unwrap();
+            assert_eq!(brute_force(&points), d);
+            assert_eq!(d, a.distance(&b));
+            assert!(points.contains(&a) && points.contains(&b));
+        }
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        assert_eq!(None, closest_pair::<f64>(&[]));
+        assert_eq!(None, closest_pair(&[Point { x: 1.0, y: 1.0 }]));
+        // Points on one vertical line, one of them repeated
+        let column: Vec<Point> = [4.0, 0.0, 9.0, 4.0, 1.5]
+            .iter()
+            .map(|&y| Point { x: 2.0, y })
+            .collect();
+        let (a, b, d) = closest_pair(&column).unwrap();
+        assert_eq!((a, b, d), (column[0], column[0], 0.0));
+        let spread: Vec<Point> = [0.0, 10.0, 3.0, 7.0]
+            .iter()
+            .map(|&y| Point { x: 2.0, y })
+            .collect();
+        assert_eq!(3.0, closest_pair(&spread).unwrap().2)
+    }
+}
@@ -0,0 +1,237 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::f64::consts::PI;
+
+use num_traits::Float;
+
+use crate::delaunay::delaunay_triangulation;
+use crate::{
+    convex_hull, LineSegment, MultiPolygon, Point, Polygon, Scalar, SegmentIntersection, Triangle,
+};
+
+/// A concave hull of `points`, found by the k-nearest neighbours method of Moreira and
+/// Santos. Starting from the lowest point, the hull repeatedly steps to whichever of
+/// the `k` nearest unvisited points turns furthest right without crossing the hull so
+/// far. If that gets stuck, or leaves some point outside, it starts again with a larger
+/// `k`, and falls back to the convex hull once `k` takes in every point. A smaller `k`
+/// follows the points more tightly. The result is a counter-clockwise polygon with
+/// every point inside or on it, and `k` is raised to at least 3.
+/// ```
+/// use rsgeo::{concave_hull, convex_hull, Point};
+/// // An L of points, with the inside corner at (1, 1)
+/// let mut points = vec![];
+/// for i in 0..=4 {
+///     points.push(Point{x: i as f64, y: 0.0});
+///     points.push(Point{x: 0.0, y: i as f64});
+///     points.push(Point{x: i as f64, y: 1.0});
+///     points.push(Point{x: 1.0, y: i as f64});
+/// }
+/// // The L itself covers 7, and the hull cuts across its inside corner
+/// assert_eq!(concave_hull(&points, 3).area(), 8.0);
+/// assert_eq!(convex_hull(&points, false).area(), 11.5);
+/// ```
+pub fn concave_hull<T: Scalar>(points: &[Point<T>], k: usize) -> Polygon<T> {
+    let mut distinct = points.to_vec();
+    distinct.sort_by(|a, b| {
+        (a.x, a.y)
+            .partial_cmp(&(b.x, b.y))
+            .unwrap_or(Ordering::Equal)
+    });
+    distinct.dedup();
+    for k in k.max(3)..distinct.len() {
+        if let Some(hull) = knn_hull(&distinct, k) {
+            return Polygon::new(hull);
+        }
+    }
+    convex_hull(&distinct, false)
+}
+
+/// The counter-clockwise angle from the direction `from` to the direction `to`, in
+/// radians in `(0, 2π]`, so that turning straight back comes last rather than first
+fn ccw_angle<T: Scalar>(from: Point<T>, to: Point<T>) -> f64 {
+    let angle = |d: Point<T>| Float::atan2(d.y.as_f64(), d.x.as_f64());
+    let turn = angle(to) - angle(from);
+    if turn <= 0.0 {
+        turn + 2.0 * PI
+    } else {
+        turn
+    }
+}
+
+fn knn_hull<T: Scalar>(points: &[Point<T>], k: usize) -> Option<Vec<Point<T>>> {
+    let n = points.len();
+    let lowest = |&i: &usize, &j: &usize| {
+        (points[i].y, points[i].x)
+            .partial_cmp(&(points[j].y, points[j].x))
+            .unwrap_or(Ordering::Equal)
+    };
+    let start = (0..n).min_by(lowest)?;
+    let mut used = alloc::vec![false; n];
+    used[start] = true;
+    let mut hull = alloc::vec![start];
+    // Pretend the hull arrived at the lowest point heading right
+    let mut back = Point {
+        x: -T::one(),
+        y: T::zero(),
+    };
+    let mut current = start;
+    loop {
+        let here = points[current];
+        let may_close = hull.len() >= 3;
+        let mut near: Vec<usize> = (0..n)
+            .filter(|&i| !used[i] || (i == start && may_close))
+            .collect();
+        let distance = |i: usize| here.distance_squared(&points[i]);
+        near.sort_by(|&i, &j| {
+            distance(i)
+                .partial_cmp(&distance(j))
+                .unwrap_or(Ordering::Equal)
+        });
+        near.truncate(k);
+        // Sorting is stable, so of the points in one direction the nearest comes first
+        near.sort_by(|&i, &j| {
+            let turn = |i: usize| ccw_angle(back, points[i] - here);
+            turn(i).partial_cmp(&turn(j)).unwrap_or(Ordering::Equal)
+        });
+        let next = near.into_iter().find(|&c| {
+            let step = LineSegment::new(here, points[c]);
+            let edges = hull.len() - 1;
+            (0..edges).all(|e| {
+                let edge = LineSegment::new(points[hull[e]], points[hull[e + 1]]);
+                // Edges that share an end with the step may only meet it there
+                let shares_end = e + 1 == edges || (c == start && e == 0);
+                match step.intersect(&edge) {
+                    SegmentIntersection::None => true,
+                    SegmentIntersection::Point(_) => shares_end,
+                    SegmentIntersection::Segment(_) => false,
+                }
+            })
+        })?;
+        if next == start {
+            break;
+        }
+        used[next] = true;
+        hull.push(next);
+        back = here - points[next];
+        current = next;
+    }
+    let ring: Vec<Point<T>> = hull.iter().map(|&i| points[i]).collect();
+    let polygon = Polygon::new(ring);
+    if points.iter().all(|&p| polygon.contains(p)) {
+        Some(polygon.vertices().to_vec())
+    } else {
+        None
+    }
+}
+
+/// The alpha shape of `points`: the union of the triangles of their Delaunay
+/// triangulation whose circumcircles have a radius of at most `alpha`. Smaller values
+/// follow the points more closely, and the shape can split into several polygons or
+/// gain holes where the points are sparse. The polygons wind counter-clockwise, and
+/// their holes clockwise. A pair of polygons can touch at a single corner. Panics if
+/// `alpha` is not positive.
+/// ```
+/// use rsgeo::{alpha_shape, Point};
+/// // Two unit squares of points, three apart
+/// let mut points = vec![];
+/// for x in [0.0, 1.0, 4.0, 5.0] {
+///     for y in [0.0, 1.0] {
+///         points.push(Point{x, y});
+///     }
+/// }
+/// assert_eq!(alpha_shape(&points, 1.0).polygons().len(), 2);
+/// assert_eq!(alpha_shape(&points, 10.0).polygons().len(), 1);
+/// ```
+pub fn alpha_shape<T: Scalar>(points: &[Point<T>], alpha: T) -> MultiPolygon<T> {
+    assert!(alpha > T::zero(), "alpha must be positive, got {}", alpha);
+    let mesh = delaunay_triangulation(points);
+    let kept: Vec<bool> = mesh
+        .triangles
+        .iter()
+        .map(|&[a, b, c]| {
+            Triangle::new(points[a], points[b], points[c])
+                .circumcircle()
+                .is_some_and(|circle| circle.radius <= alpha)
+        })
+        .collect();
+    // The edges with a kept triangle on their left and none on their right
+    let mut edges = Vec::new();
+    for (t, v) in mesh.triangles.iter().enumerate() {
+        if !kept[t] {
+            continue;
+        }
+        for i in 0..3 {
+            if !mesh.neighbors[t][i].is_some_and(|n| kept[n]) {
+                edges.push((points[v[(i + 1) % 3]], points[v[(i + 2) % 3]]));
+            }
+        }
+    }
+    MultiPolygon::from_boundary(&edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(size: i32, skip: impl Fn(i32, i32) -> bool) -> Vec<Point> {
+        let mut points = Vec::new();
+        for x in 0..=size {
+            for y in 0..=size {
+                if !skip(x, y) {
+                    points.push(Point {
+                        x: f64::from(x),
+                        y: f64::from(y),
+                    });
+                }
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn test_concave_hull_follows_notch() {
+        // A square of points with a notch cut into the top
+        let points = grid(6, |x, y| (2..=4).contains(&x) && y >= 2);
+        let hull = concave_hull(&points, 3);
+        assert!(points.iter().all(|&p| hull.contains(p)));
+        assert!(hull.is_simple());
+        // All but a few corners of the notch are cut away
+        assert!(hull.area() > 36.0 - 20.0 && hull.area() < 18.0);
+        // With every point as a neighbour, the hull is convex
+        assert_eq!(36.0, concave_hull(&points, points.len()).area())
+    }
+
+    #[test]
+    fn test_concave_hull_degenerate() {
+        assert!(concave_hull::<f64>(&[], 3).vertices().is_empty());
+        let line: Vec<Point> = (0..4)
+            .map(|i| Point {
+                x: f64::from(i),
+                y: 0.0,
+            })
+            .collect();
+        assert_eq!(2, concave_hull(&line, 3).vertices().len());
+        let triangle = grid(1, |x, y| x == 1 && y == 1);
+        assert_eq!(0.5, concave_hull(&triangle, 5).area())
+    }
+
+    #[test]
+    fn test_alpha_shape_hole() {
+        // A frame of points around an empty middle
+        let points = grid(6, |x, y| (2..=4).contains(&x) && (2..=4).contains(&y));
+        let shape = alpha_shape(&points, 1.0);
+        assert_eq!(1, shape.polygons().len());
+        let frame = &shape.polygons()[0];
+        assert_eq!(1, frame.holes().len());
+        // The corners of the middle still have the triangles of their missing cells
+        assert_eq!(36.0 - 16.0 + 4.0 * 0.5, shape.area());
+        assert!(!shape.contains(Point { x: 3.0, y: 3.0 }));
+        assert!(points.iter().all(|&p| shape.contains(p)))
+    }
+
+    #[test]
+    #[should_panic(expected = "alpha must be positive")]
+    fn test_alpha_must_be_positive() {
+        alpha_shape(&grid(2, |_, _| false), 0.0);
+    }
+}
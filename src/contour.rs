@@ -0,0 +1,272 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::{Grid, MultiPolygon, OrderedPoint, Point, Polyline, Scalar};
+
+impl<T: Scalar> Grid<T> {
+    /// The contour lines where the surface through the grid is at `level`, found by
+    /// marching squares: the surface is taken to run linearly between neighboring
+    /// points, and the lines to run straight across each square of the grid. Each
+    /// line has the values at or above `level` on its left, so that closed lines run
+    /// counter-clockwise around peaks and clockwise around pits, and those that are not
+    /// closed start and end on the edge of the grid. Closed ones end where they start.
+    ///
+    /// Where a square has its corners above and below the level by turns, the value at
+    /// its center decides which way the lines through it go, which keeps the values
+    /// above the level joined up if the center is too.
+    /// ```
+    /// use rsgeo::{Grid, Point};
+    /// // A cone, one unit high for each unit from its tip
+    /// let grid: Grid = Grid::from_fn(Point{x: -5.0, y: -5.0}, 0.5, 21, 21, |p| p.magnitude());
+    /// let lines = grid.contour_lines(3.0);
+    /// assert_eq!(lines.len(), 1);
+    /// let ring = lines[0].points();
+    /// assert_eq!(ring.first(), ring.last());
+    /// assert!(ring.iter().all(|p| (p.magnitude() - 3.0).abs() < 0.05));
+    /// ```
+    pub fn contour_lines(&self, level: T) -> Vec<Polyline<T>> {
+        let segments = self.contour_segments(level);
+        let mut next = alloc::vec![None; 2 * self.values().len()];
+        let mut entered = alloc::vec![false; next.len()];
+        for &(a, b) in &segments {
+            next[a] = Some(b);
+            entered[b] = true;
+        }
+        // The lines that are not closed start where no other line leads, and what is
+        // left after them is made of loops
+        let starts = segments.iter().map(|s| s.0);
+        let open = starts.clone().filter(|&a| !entered[a]);
+        let mut lines = Vec::new();
+        for start in open.collect::<Vec<_>>().into_iter().chain(starts) {
+            if next[start].is_none() {
+                continue;
+            }
+            let mut crossings = alloc::vec![start];
+            let mut at = start;
+            while let Some(b) = next[at].take() {
+                crossings.push(b);
+                at = b;
+            }
+            let mut points: Vec<Point<T>> = Vec::with_capacity(crossings.len());
+            for p in crossings.into_iter().map(|c| self.crossing(c, level)) {
+                if points.last() != Some(&p) {
+                    points.push(p);
+                }
+            }
+            if points.len() > 1 {
+                lines.push(Polyline::new(points));
+            }
+        }
+        lines
+    }
+
+    /// The region where the surface through the grid is at or above `level`, bounded
+    /// by its contour lines and the edge of the grid, with holes where it dips below.
+    /// The region between two levels is what is left of the one at the lower level
+    /// after taking away the one at the upper level.
+    /// ```
+    /// use rsgeo::{Grid, Point};
+    /// let grid = Grid::from_fn(Point{x: -5.0, y: -5.0}, 0.5, 21, 21, |p| p.magnitude());
+    /// // Everything but a disc of radius 3 around the tip
+    /// let region = grid.filled_contour(3.0);
+    /// assert_eq!(region.polygons()[0].holes().len(), 1);
+    /// assert!((region.area() - (100.0 - 9.0 * std::f64::consts::PI)).abs() < 0.2);
+    /// ```
+    pub fn filled_contour(&self, level: T) -> MultiPolygon<T> {
+        let (columns, rows) = (self.columns(), self.rows());
+        if columns < 2 || rows < 2 {
+            return MultiPolygon::new(Vec::new());
+        }
+        let mut edges: Vec<(Point<T>, Point<T>)> = self
+            .contour_segments(level)
+            .into_iter()
+            .map(|(a, b)| (self.crossing(a, level), self.crossing(b, level)))
+            .collect();
+        // The edge of the grid counter-clockwise, as far as it is at or above the
+        // level, with each side given as the points along it and the crossings
+        // between them
+        let bottom = (0..columns - 1).map(|c| ((c, 0), 2 * c));
+        let right = (0..rows - 1).map(|r| ((columns - 1, r), 2 * (r * columns + columns - 1) + 1));
+        let top = (1..columns)
+            .rev()
+            .map(|c| ((c, rows - 1), 2 * ((rows - 1) * columns + c - 1)));
+        let left = (1..rows)
+            .rev()
+            .map(|r| ((0, r), 2 * ((r - 1) * columns) + 1));
+        let around: Vec<_> = bottom.chain(right).chain(top).chain(left).collect();
+        for (i, &((column, row), crossing)) in around.iter().enumerate() {
+            let (to_column, to_row) = around[(i + 1) % around.len()].0;
+            let (a, b) = (self.point(column, row), self.point(to_column, to_row));
+            let high = |c: usize, r: usize| self.get(c, r) >= level;
+            match (high(column, row), high(to_column, to_row)) {
+                (true, true) => edges.push((a, b)),
+                (true, false) => edges.push((a, self.crossing(crossing, level))),
+                (false, true) => edges.push((self.crossing(crossing, level), b)),
+                (false, false) => {}
+            }
+        }
+        // Values on the level can leave slivers with no area, bounded by the same side
+        // of a square run both ways, which are dropped
+        let both: BTreeSet<(OrderedPoint<T>, OrderedPoint<T>)> =
+            edges.iter().map(|&(a, b)| (a.into(), b.into())).collect();
+        edges.retain(|&(a, b)| a != b && !both.contains(&(b.into(), a.into())));
+        MultiPolygon::from_boundary(&edges)
+    }
+
+    /// The pieces of contour line across each square of the grid, each running from
+    /// one crossing of the level to another with the values at or above it on its
+    /// left. Crossings are numbered by the side of a square they are on: twice the
+    /// index of the point a side starts from, plus one if it runs up rather than
+    /// right.
+    fn contour_segments(&self, level: T) -> Vec<(usize, usize)> {
+        let columns = self.columns();
+        let mut segments = Vec::new();
+        for row in 0..self.rows().saturating_sub(1) {
+            for column in 0..columns.saturating_sub(1) {
+                let at = row * columns + column;
+                // The corners counter-clockwise from the bottom left, and the sides
+                // leaving each
+                let corners = [
+                    (column, row),
+                    (column + 1, row),
+                    (column + 1, row + 1),
+                    (column, row + 1),
+                ];
+                let sides = [2 * at, 2 * (at + 1) + 1, 2 * (at + columns), 2 * at + 1];
+                let values = corners.map(|(c, r)| self.get(c, r));
+                // The crossings in order around the square, with whether each one
+                // leaves the values at or above the level
+                let crossings: Vec<(usize, bool)> = (0..4)
+                    .filter(|&i| (values[i] >= level) != (values[(i + 1) % 4] >= level))
+                    .map(|i| (sides[i], values[i] >= level))
+                    .collect();
+                match crossings.len() {
+                    2 if crossings[0].1 => segments.push((crossings[0].0, crossings[1].0)),
+                    2 => segments.push((crossings[1].0, crossings[0].0)),
+                    4 => {
+                        // A saddle. With the center high each low corner is cut off
+                        // on its own, and otherwise each high corner.
+                        let center =
+                            (values[0] + values[1] + values[2] + values[3]) / T::from_f64(4.0);
+                        let step = if center >= level { 1 } else { 3 };
+                        for i in (0..4).filter(|&i| crossings[i].1) {
+                            segments.push((crossings[i].0, crossings[(i + step) % 4].0));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        segments
+    }
+
+    /// Where the surface is at `level` along the side of a square numbered `crossing`
+    fn crossing(&self, crossing: usize, level: T) -> Point<T> {
+        let at = crossing / 2;
+        let (column, row) = (at % self.columns(), at / self.columns());
+        let (to_column, to_row) = match crossing % 2 {
+            0 => (column + 1, row),
+            _ => (column, row + 1),
+        };
+        let (a, b) = (self.get(column, row), self.get(to_column, to_row));
+        let t = (level - a) / (b - a);
+        let (p, q) = (self.point(column, row), self.point(to_column, to_row));
+        p + (q - p) * t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BooleanOps, Lcg};
+
+    fn saddle(level: f64) -> Vec<Polyline> {
+        Grid::new(Point::origin(), 1.0, 2, 2, vec![1.0, 0.0, 0.0, 1.0]).contour_lines(level)
+    }
+
+    #[test]
+    fn test_saddles() {
+        let line = |a: Point, b: Point| Polyline::new(vec![a, b]);
+        let at = |x: f64, y: f64| Point { x, y };
+        // With the center at 0.5, the low corners are cut off first
+        assert_eq!(
+            vec![
+                line(at(0.6, 0.0), at(1.0, 0.4)),
+                line(at(0.4, 1.0), at(0.0, 0.6)),
+            ],
+            saddle(0.4)
+        );
+        assert_eq!(
+            vec![
+                line(at(0.4, 0.0), at(0.0, 0.4)),
+                line(at(0.6, 1.0), at(1.0, 0.6)),
+            ],
+            saddle(0.6)
+        );
+        assert!(saddle(1.5).is_empty());
+    }
+
+    #[test]
+    fn test_lines_across_the_grid() {
+        let grid = Grid::from_fn(Point::origin(), 1.0, 5, 4, |p: Point| p.x + 0.5 * p.y);
+        let lines = grid.contour_lines(2.25);
+        assert_eq!(1, lines.len());
+        // Higher values on the left, so running down the grid
+        let ends = (lines[0].points()[0], *lines[0].points().last().unwrap());
+        assert_eq!((Point { x: 0.75, y: 3.0 }, Point { x: 2.25, y: 0.0 }), ends);
+        assert_eq!(6, lines[0].points().len());
+        let region = grid.filled_contour(2.25);
+        assert_eq!(1, region.polygons().len());
+        assert!((region.area() - 12.0 + 1.5 * 3.0).abs() < 1e-12);
+        assert_eq!(12.0, grid.filled_contour(-1.0).area());
+        assert_eq!(0.0, grid.filled_contour(10.0).area());
+    }
+
+    #[test]
+    fn test_rings_and_holes() {
+        // A ring of high ground at 3 from the center, around a pit
+        let grid = Grid::from_fn(Point { x: -6.0, y: -6.0 }, 0.25, 49, 49, |p: Point| {
+            -(p.magnitude() - 3.0).powi(2)
+        });
+        let lines = grid.contour_lines(-1.0);
+        assert_eq!(2, lines.len());
+        let mut areas: Vec<f64> = lines
+            .iter()
+            .map(|l| crate::Polygon::new(l.points()[1..].to_vec()).signed_area())
+            .collect();
+        areas.sort_by(f64::total_cmp);
+        // The inner line runs clockwise with the ring on its left
+        assert!((areas[0] + 4.0 * std::f64::consts::PI).abs() < 0.1);
+        assert!((areas[1] - 16.0 * std::f64::consts::PI).abs() < 0.2);
+        let region = grid.filled_contour(-1.0);
+        assert_eq!(1, region.polygons().len());
+        assert_eq!(1, region.polygons()[0].holes().len());
+        assert!((region.area() - 12.0 * std::f64::consts::PI).abs() < 0.2);
+        assert!(region.contains(Point { x: 0.0, y: 3.0 }));
+        assert!(!region.contains(Point::origin()));
+    }
+
+    #[test]
+    fn test_bands_from_random_grid() {
+        let mut rng = Lcg::new(21);
+        let values: Vec<f64> = (0..30 * 20)
+            .map(|_| (rng.unit() * 4.0).floor() / 2.0)
+            .collect();
+        let grid = Grid::new(Point::origin(), 1.0, 30, 20, values);
+        let levels = [-1.0, 0.5, 1.0, 1.5, 10.0];
+        let filled: Vec<MultiPolygon> = levels.iter().map(|&l| grid.filled_contour(l)).collect();
+        assert_eq!(29.0 * 19.0, filled[0].area());
+        // Each region holds the ones at higher levels, and the bands between them
+        // cover the grid, even with values on the levels and plenty of saddles
+        let mut total = 0.0;
+        for pair in filled.windows(2) {
+            let band = pair[0].difference(&pair[1]);
+            assert!((band.area() - (pair[0].area() - pair[1].area())).abs() < 1e-9);
+            total += band.area();
+        }
+        assert!((total - 29.0 * 19.0).abs() < 1e-9);
+        for region in &filled {
+            assert!(region.polygons().iter().all(|p| p.signed_area() > 0.0));
+        }
+    }
+}
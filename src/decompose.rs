@@ -0,0 +1,234 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::predicates::orient2d;
+use crate::{OrderedPoint, Point, Polygon, Scalar};
+
+impl<T: Scalar> Polygon<T> {
+    /// Cut the polygon into convex pieces by the method of Hertel and Mehlhorn. It is
+    /// triangulated as by [`Polygon::triangulate_monotone`], and then each diagonal is
+    /// taken out in turn unless that would leave a reflex corner at either end. Every
+    /// diagonal left is then needed by a reflex vertex, so there are at most twice as
+    /// many pieces as reflex vertices, plus one, and never more than four times as many
+    /// as the fewest possible. The pieces are counter-clockwise and may keep straight
+    /// vertices where diagonals were taken out, and the whole takes O(n log n) time.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let l = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 1.0},
+    ///     Point{x: 1.0, y: 1.0}, Point{x: 1.0, y: 2.0}, Point{x: 0.0, y: 2.0},
+    /// ]);
+    /// let pieces = l.convex_decomposition();
+    /// assert_eq!(pieces.len(), 2);
+    /// assert!(pieces.iter().all(|p| p.is_convex()));
+    /// assert_eq!(pieces.iter().map(|p| p.area()).sum::<f64>(), 3.0);
+    /// ```
+    pub fn convex_decomposition(&self) -> Vec<Polygon<T>> {
+        if self.is_convex() {
+            let mut piece = self.clone();
+            piece.make_ccw();
+            return alloc::vec![piece];
+        }
+        let mut mesh = Mesh {
+            origin: Vec::new(),
+            next: Vec::new(),
+            prev: Vec::new(),
+            twin: Vec::new(),
+            alive: Vec::new(),
+        };
+        let mut edges: BTreeMap<(OrderedPoint<T>, OrderedPoint<T>), Option<usize>> =
+            BTreeMap::new();
+        for triangle in self.triangulate_monotone() {
+            let first = mesh.origin.len();
+            for (k, &p) in [triangle.a, triangle.b, triangle.c].iter().enumerate() {
+                mesh.origin.push(p);
+                mesh.next.push(first + (k + 1) % 3);
+                mesh.prev.push(first + (k + 2) % 3);
+                mesh.twin.push(None);
+                mesh.alive.push(true);
+            }
+            for k in 0..3 {
+                let h = first + k;
+                let key = (mesh.origin[h].into(), mesh.origin[mesh.next[h]].into());
+                // Two edges between the same points can only come from rings that
+                // touch, and are best left alone
+                edges
+                    .entry(key)
+                    .and_modify(|e| *e = None)
+                    .or_insert(Some(h));
+            }
+        }
+        for (&(a, b), &h) in &edges {
+            if let (Some(h), Some(&Some(t))) = (h, edges.get(&(b, a))) {
+                mesh.twin[h] = Some(t);
+            }
+        }
+        for h in 0..mesh.origin.len() {
+            if let Some(t) = mesh.twin[h] {
+                if h < t && mesh.alive[h] && mesh.can_remove(h, t) {
+                    mesh.remove(h, t);
+                }
+            }
+        }
+        mesh.pieces()
+    }
+}
+
+/// Half edges around the triangles and, as diagonals are taken out, the pieces
+/// they merge into
+struct Mesh<T> {
+    origin: Vec<Point<T>>,
+    next: Vec<usize>,
+    prev: Vec<usize>,
+    /// The half edge running the other way along the same diagonal
+    twin: Vec<Option<usize>>,
+    alive: Vec<bool>,
+}
+
+impl<T: Scalar> Mesh<T> {
+    fn target(&self, h: usize) -> Point<T> {
+        self.origin[self.next[h]]
+    }
+
+    /// Whether taking out the diagonal of `h` and its twin `t` leaves both of its ends
+    /// convex
+    fn can_remove(&self, h: usize, t: usize) -> bool {
+        let convex = |into: usize, out: usize| {
+            orient2d(self.origin[into], self.origin[out], self.target(out)) >= 0.0
+        };
+        // After the diagonal goes, the edge into each end is followed by the one that
+        // left it round the other side
+        convex(self.prev[h], self.next[t]) && convex(self.prev[t], self.next[h])
+    }
+
+    fn remove(&mut self, h: usize, t: usize) {
+        let (hp, hn, tp, tn) = (self.prev[h], self.next[h], self.prev[t], self.next[t]);
+        self.next[hp] = tn;
+        self.prev[tn] = hp;
+        self.next[tp] = hn;
+        self.prev[hn] = tp;
+        self.alive[h] = false;
+        self.alive[t] = false;
+    }
+
+    fn pieces(&self) -> Vec<Polygon<T>> {
+        let mut seen = alloc::vec![false; self.origin.len()];
+        let mut pieces = Vec::new();
+        for start in 0..self.origin.len() {
+            if seen[start] || !self.alive[start] {
+                continue;
+            }
+            let mut ring = Vec::new();
+            let mut h = start;
+            while !seen[h] {
+                seen[h] = true;
+                ring.push(self.origin[h]);
+                h = self.next[h];
+            }
+            pieces.push(Polygon::new(ring));
+        }
+        pieces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::predicates::Orientation;
+    use crate::Lcg;
+
+    fn check(polygon: &Polygon) -> Vec<Polygon> {
+        let pieces = polygon.convex_decomposition();
+        let total: f64 = pieces.iter().map(|p| p.area()).sum();
+        assert!((total - polygon.area()).abs() <= 1e-9 * polygon.area());
+        for piece in &pieces {
+            assert!(piece.is_convex(), "{:?}", piece);
+            assert_eq!(Orientation::CounterClockwise, piece.orientation());
+        }
+        pieces
+    }
+
+    fn reflex_count(polygon: &Polygon) -> usize {
+        let v = polygon.vertices();
+        let n = v.len();
+        (0..n)
+            .filter(|&i| orient2d(v[(i + n - 1) % n], v[i], v[(i + 1) % n]) < 0.0)
+            .count()
+    }
+
+    #[test]
+    fn test_convex_input() {
+        let mut hexagon: Vec<Point> = (0..6)
+            .map(|i| {
+                let angle = core::f64::consts::TAU * i as f64 / 6.0;
+                Point {
+                    x: angle.cos(),
+                    y: angle.sin(),
+                }
+            })
+            .collect();
+        hexagon.reverse();
+        let pieces = check(&Polygon::new(hexagon));
+        assert_eq!(1, pieces.len());
+        assert_eq!(6, pieces[0].vertices().len());
+    }
+
+    #[test]
+    fn test_comb_and_frame() {
+        // Each gap between the teeth of a comb is one reflex corner on either side
+        let mut comb = vec![Point { x: 0.0, y: 0.0 }, Point { x: 9.0, y: 0.0 }];
+        for i in (0..5).rev() {
+            let x = 2.0 * i as f64;
+            comb.push(Point { x: x + 1.0, y: 3.0 });
+            comb.push(Point { x, y: 3.0 });
+            if i > 0 {
+                comb.push(Point { x, y: 1.0 });
+                comb.push(Point { x: x - 1.0, y: 1.0 });
+            }
+        }
+        let comb = Polygon::new(comb);
+        let pieces = check(&comb);
+        assert!(pieces.len() <= 2 * reflex_count(&comb) + 1);
+        let frame = Polygon::with_holes(
+            vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 4.0, y: 0.0 },
+                Point { x: 4.0, y: 4.0 },
+                Point { x: 0.0, y: 4.0 },
+            ],
+            vec![vec![
+                Point { x: 1.0, y: 1.0 },
+                Point { x: 3.0, y: 1.0 },
+                Point { x: 3.0, y: 3.0 },
+                Point { x: 1.0, y: 3.0 },
+            ]],
+        );
+        // At least four pieces are needed, and each corner of the hole needs two
+        let pieces = check(&frame);
+        assert!(pieces.len() >= 4 && pieces.len() <= 8);
+    }
+
+    #[test]
+    fn test_random_stars() {
+        let mut rng = Lcg::new(23);
+        let mut next = || rng.unit();
+        for _ in 0..200 {
+            let n = 3 + (next() * 50.0) as usize;
+            let star = Polygon::new(
+                (0..n)
+                    .map(|i| {
+                        let angle = core::f64::consts::TAU * i as f64 / n as f64;
+                        let r = 1.0 + 9.0 * next();
+                        Point {
+                            x: r * angle.cos(),
+                            y: r * angle.sin(),
+                        }
+                    })
+                    .collect(),
+            );
+            let pieces = check(&star);
+            assert!(pieces.len() <= 2 * reflex_count(&star) + 1);
+            assert!(pieces.len() < star.triangulate_monotone().len() || n == 3);
+        }
+    }
+}
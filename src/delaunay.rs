@@ -0,0 +1,746 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::predicates::{incircle, orient2d};
+use crate::{OrderedPoint, Point, Polygon, Scalar, Triangle};
+
+/// Stands in for the vertex at infinity that every ghost triangle shares
+const GHOST: usize = usize::MAX;
+
+/// A Delaunay triangulation of a set of points, with each triangle given as the
+/// indices of its corners in counter-clockwise order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delaunay {
+    pub triangles: Vec<[usize; 3]>,
+    /// For each triangle, the triangle across the edge opposite each of its corners,
+    /// or None along the convex hull
+    pub neighbors: Vec<[Option<usize>; 3]>,
+}
+
+impl Delaunay {
+    /// Each edge of the triangulation once, as the indices of its ends
+    pub fn edges(&self) -> impl Iterator<Item = [usize; 2]> + '_ {
+        self.triangles
+            .iter()
+            .zip(&self.neighbors)
+            .enumerate()
+            .flat_map(|(t, (v, n))| {
+                // An edge inside the hull is taken from the lower numbered triangle
+                (0..3)
+                    .filter(move |&i| n[i].is_none_or(|u| t < u))
+                    .map(move |i| [v[(i + 1) % 3], v[(i + 2) % 3]])
+            })
+    }
+
+    /// The triangle `t` with its corners taken from `points`, which should be the
+    /// points that were triangulated
+    pub fn triangle<T: Scalar>(&self, points: &[Point<T>], t: usize) -> Triangle<T> {
+        let [a, b, c] = self.triangles[t];
+        Triangle::new(points[a], points[b], points[c])
+    }
+
+    /// For each of `n` points, the triangles around it counter-clockwise with the
+    /// corner of each that it is at. For a point on the hull they start just after the
+    /// hull edge that leaves it clockwise, and points left out of the triangulation
+    /// have none.
+    pub(crate) fn fans(&self, n: usize) -> Vec<Vec<(usize, usize)>> {
+        let mut corner = alloc::vec![None; n];
+        for (t, v) in self.triangles.iter().enumerate() {
+            for (i, &c) in v.iter().enumerate() {
+                corner[c] = Some((t, i));
+            }
+        }
+        let at = |t: usize, c: usize| (t, self.triangles[t].iter().position(|&x| x == c).unwrap());
+        (0..n)
+            .map(|c| {
+                let mut fan = Vec::new();
+                let start = match corner[c] {
+                    Some(start) => start,
+                    None => return fan,
+                };
+                // Back clockwise to the hull, if it is reached
+                let mut first = start;
+                while let Some(n) = self.neighbors[first.0][(first.1 + 2) % 3] {
+                    if n == start.0 {
+                        break;
+                    }
+                    first = at(n, c);
+                }
+                let mut t = first;
+                loop {
+                    fan.push(t);
+                    match self.neighbors[t.0][(t.1 + 1) % 3] {
+                        Some(n) if n != first.0 => t = at(n, c),
+                        _ => return fan,
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// The Delaunay triangulation of `points`, found by Bowyer-Watson insertion. Beyond
+/// each edge of the convex hull there is a ghost triangle with a corner at infinity, so
+/// points outside the hull need no special handling, and every decision is made with
+/// the exact orientation and incircle predicates. Repeated points are ignored, and if
+/// all the points lie on one line there are no triangles.
+/// ```
+/// use rsgeo::{delaunay_triangulation, Point};
+/// let points = [
+///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 3.0},
+///     Point{x: 0.0, y: 3.0}, Point{x: 2.0, y: 1.0},
+/// ];
+/// let mesh = delaunay_triangulation(&points);
+/// assert_eq!(mesh.triangles.len(), 4);
+/// assert_eq!(mesh.edges().count(), 8);
+/// // Each triangle has one edge on the hull and two shared with its neighbors
+/// assert!(mesh.neighbors.iter().all(|n| n.iter().flatten().count() == 2));
+/// ```
+pub fn delaunay_triangulation<T: Scalar>(points: &[Point<T>]) -> Delaunay {
+    let mut mesh = Mesh {
+        points,
+        triangles: Vec::new(),
+        adjacent: Vec::new(),
+        alive: Vec::new(),
+    };
+    let first = match mesh.first_triangle() {
+        Some(first) => first,
+        None => {
+            return Delaunay {
+                triangles: Vec::new(),
+                neighbors: Vec::new(),
+            }
+        }
+    };
+    let mut last = 0;
+    for i in (0..points.len()).filter(|i| !first.contains(i)) {
+        if let Some(t) = mesh.insert(i, last) {
+            last = t;
+        }
+    }
+    mesh.finish()
+}
+
+/// The constrained Delaunay triangulation of `points`, in which each of the
+/// `constraints`, given as pairs of indices into the points, is made up of edges of
+/// the triangulation, and every other edge is Delaunay as far as the constraints
+/// allow. A constraint that runs through other points is split at them. Each one is
+/// put in by taking out the triangles it crosses and filling the two sides with the
+/// Delaunay triangulations of the pieces left, so it costs time in proportion to the
+/// square of the number of triangles crossed. Constraints should only meet at their
+/// ends; where two of them cross, the later one is kept.
+/// ```
+/// use rsgeo::{constrained_delaunay, delaunay_triangulation, Point};
+/// let points = [
+///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 1.0}, Point{x: 8.0, y: 0.0},
+///     Point{x: 4.0, y: -1.0},
+/// ];
+/// // The short diagonal is the Delaunay one, but the long one can be asked for
+/// let long = |[a, b]: [usize; 2]| a.min(b) == 0 && a.max(b) == 2;
+/// assert!(!delaunay_triangulation(&points).edges().any(long));
+/// assert!(constrained_delaunay(&points, &[[0, 2]]).edges().any(long));
+/// ```
+pub fn constrained_delaunay<T: Scalar>(
+    points: &[Point<T>],
+    constraints: &[[usize; 2]],
+) -> Delaunay {
+    let mesh = delaunay_triangulation(points);
+    // Repeated points are triangulated at their first appearance
+    let mut first = BTreeMap::new();
+    let canonical: Vec<usize> = points
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| *first.entry(OrderedPoint::from(p)).or_insert(i))
+        .collect();
+    let mut corner = alloc::vec![GHOST; points.len()];
+    for (t, v) in mesh.triangles.iter().enumerate() {
+        for &c in v {
+            corner[c] = t;
+        }
+    }
+    let mut cdt = Constrained {
+        points,
+        mesh,
+        corner,
+    };
+    for &[a, b] in constraints {
+        let (mut a, b) = (canonical[a], canonical[b]);
+        // With every point on one line there are no triangles to hold the constraints
+        while a != b && cdt.corner[a] != GHOST {
+            a = cdt.insert(a, b);
+        }
+    }
+    cdt.mesh
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// Split the polygon into triangles by the constrained Delaunay triangulation of
+    /// its vertices with its edges as the constraints, keeping the triangles inside.
+    /// Of all the ways to triangulate the polygon without adding vertices, this one
+    /// makes the smallest angle as large as it can be, which suits meshing far better
+    /// than the slivers of [`Polygon::triangulate`].
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let l: Polygon = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 1.0},
+    ///     Point{x: 1.0, y: 1.0}, Point{x: 1.0, y: 2.0}, Point{x: 0.0, y: 2.0},
+    /// ]);
+    /// let triangles = l.triangulate_delaunay();
+    /// assert_eq!(triangles.len(), 4);
+    /// assert_eq!(triangles.iter().map(|t| t.area()).sum::<f64>(), 3.0);
+    /// ```
+    pub fn triangulate_delaunay(&self) -> Vec<Triangle<T>> {
+        let (points, mesh) = self.constrained_delaunay();
+        (0..mesh.triangles.len())
+            .map(|t| mesh.triangle(&points, t))
+            .filter(|t| self.contains(t.centroid()))
+            .collect()
+    }
+
+    /// The vertices of every ring, one after another, and their constrained Delaunay
+    /// triangulation with the edges of the rings as the constraints. This covers the
+    /// convex hull, so triangles outside the polygon are included.
+    pub(crate) fn constrained_delaunay(&self) -> (Vec<Point<T>>, Delaunay) {
+        let mut points = Vec::new();
+        let mut constraints = Vec::new();
+        for ring in self.rings() {
+            let start = points.len();
+            for i in 0..ring.len() {
+                constraints.push([start + i, start + (i + 1) % ring.len()]);
+            }
+            points.extend_from_slice(ring);
+        }
+        let mesh = constrained_delaunay(&points, &constraints);
+        (points, mesh)
+    }
+}
+
+/// A triangulation that constraints are being put into
+struct Constrained<'a, T> {
+    points: &'a [Point<T>],
+    mesh: Delaunay,
+    /// A triangle at each point, or GHOST for points left out of the triangulation
+    corner: Vec<usize>,
+}
+
+impl<'a, T: Scalar> Constrained<'a, T> {
+    /// The triangles around point `a`, with the corner of each that it is at
+    fn fan(&self, a: usize) -> Vec<(usize, usize)> {
+        let at = |t: usize| -> (usize, usize) {
+            (
+                t,
+                self.mesh.triangles[t].iter().position(|&c| c == a).unwrap(),
+            )
+        };
+        let start = self.corner[a];
+        let mut fan = alloc::vec![at(start)];
+        // Counter-clockwise round the point, and if that reaches the hull, clockwise
+        for turn in [1, 2] {
+            let mut t = start;
+            while let Some(n) = self.mesh.neighbors[t][(at(t).1 + turn) % 3] {
+                if n == start {
+                    return fan;
+                }
+                fan.push(at(n));
+                t = n;
+            }
+        }
+        fan
+    }
+
+    /// Make the constraint from `a` towards `b` part of the triangulation as far as the
+    /// first point on the way, which is returned
+    fn insert(&mut self, a: usize, b: usize) -> usize {
+        let p = self.points;
+        let (pa, pb) = (p[a], p[b]);
+        let side = |x: usize| orient2d(pa, pb, p[x]);
+        let fan = self.fan(a);
+        for &(t, i) in &fan {
+            let v = self.mesh.triangles[t];
+            for x in [v[(i + 1) % 3], v[(i + 2) % 3]] {
+                // Already joined to `a`, or a point along the way to b
+                if x == b || side(x) == 0.0 && (p[x] - pa).dot_product(&(pb - pa)) > T::zero() {
+                    return x;
+                }
+            }
+        }
+        // The triangle at `a` that the constraint leaves through the far edge of
+        let (mut t, mut i) = *fan
+            .iter()
+            .find(|&&(t, i)| {
+                let v = self.mesh.triangles[t];
+                side(v[(i + 1) % 3]) < 0.0 && side(v[(i + 2) % 3]) > 0.0
+            })
+            .unwrap();
+        let v = self.mesh.triangles[t];
+        let (mut right, mut left) = (alloc::vec![v[(i + 1) % 3]], alloc::vec![v[(i + 2) % 3]]);
+        let mut crossed = alloc::vec![t];
+        let end = loop {
+            let (r, l) = (*right.last().unwrap(), *left.last().unwrap());
+            let n = self.mesh.neighbors[t][i].unwrap();
+            crossed.push(n);
+            let w = self.mesh.triangles[n];
+            let x = *w.iter().find(|&&c| c != r && c != l).unwrap();
+            let s = side(x);
+            if x == b || s == 0.0 {
+                break x;
+            }
+            let passed = if s > 0.0 {
+                left.push(x);
+                l
+            } else {
+                right.push(x);
+                r
+            };
+            t = n;
+            i = w.iter().position(|&c| c == passed).unwrap();
+        };
+        self.refill(&crossed, a, end, &left, &right);
+        end
+    }
+
+    /// Replace the `crossed` triangles with the constrained Delaunay triangulations of
+    /// the points to the `left` and `right` of the new edge from `a` to `end`, each
+    /// listed in order from `a`
+    fn refill(&mut self, crossed: &[usize], a: usize, end: usize, left: &[usize], right: &[usize]) {
+        // The triangles just outside the cavity, by the edges they share with it
+        let mut rim = BTreeMap::new();
+        for &t in crossed {
+            let v = self.mesh.triangles[t];
+            for k in 0..3 {
+                let outside = self.mesh.neighbors[t][k].filter(|n| !crossed.contains(n));
+                if outside.is_some() || self.mesh.neighbors[t][k].is_none() {
+                    rim.insert((v[(k + 1) % 3], v[(k + 2) % 3]), outside);
+                }
+            }
+        }
+        let mut filled = Vec::with_capacity(crossed.len());
+        let reversed: Vec<usize> = right.iter().rev().copied().collect();
+        self.fill(a, end, left, &mut filled);
+        self.fill(end, a, &reversed, &mut filled);
+        // The cavity has as many triangles as before, so they take the same places
+        let mut inside = BTreeMap::new();
+        for (&t, &v) in crossed.iter().zip(&filled) {
+            self.mesh.triangles[t] = v;
+            for k in 0..3 {
+                inside.insert((v[(k + 1) % 3], v[(k + 2) % 3]), t);
+                self.corner[v[k]] = t;
+            }
+        }
+        for (&t, &v) in crossed.iter().zip(&filled) {
+            for k in 0..3 {
+                let (x, y) = (v[(k + 1) % 3], v[(k + 2) % 3]);
+                self.mesh.neighbors[t][k] = match rim.get(&(x, y)) {
+                    Some(&outside) => {
+                        if let Some(o) = outside {
+                            let w = self.mesh.triangles[o];
+                            let j = (0..3).find(|&j| w[(j + 1) % 3] == y && w[(j + 2) % 3] == x);
+                            self.mesh.neighbors[o][j.unwrap()] = Some(t);
+                        }
+                        outside
+                    }
+                    None => Some(inside[&(y, x)]),
+                };
+            }
+        }
+    }
+
+    /// Triangulate the polygon of the edge from `a` to `b` and the `chain` of points to
+    /// its left, listed from the end at `a`
+    fn fill(&self, a: usize, b: usize, chain: &[usize], triangles: &mut Vec<[usize; 3]>) {
+        if chain.is_empty() {
+            return;
+        }
+        let p = self.points;
+        // The point whose circle through a and b holds none of the others
+        let mut best = 0;
+        for k in 1..chain.len() {
+            if incircle(p[a], p[b], p[chain[best]], p[chain[k]]) > 0.0 {
+                best = k;
+            }
+        }
+        let c = chain[best];
+        triangles.push([a, b, c]);
+        self.fill(a, c, &chain[..best], triangles);
+        self.fill(c, b, &chain[best + 1..], triangles);
+    }
+}
+
+struct Mesh<'a, T> {
+    points: &'a [Point<T>],
+    triangles: Vec<[usize; 3]>,
+    /// The triangle across the edge opposite each corner
+    adjacent: Vec<[usize; 3]>,
+    alive: Vec<bool>,
+}
+
+impl<'a, T: Scalar> Mesh<'a, T> {
+    /// Start from the first three points that are not on one line, with a ghost
+    /// triangle beyond each edge
+    fn first_triangle(&mut self) -> Option<[usize; 3]> {
+        let p = self.points;
+        let a = 0;
+        let b = (1..p.len()).find(|&i| p[i] != p[a])?;
+        let c = (b + 1..p.len()).find(|&i| orient2d(p[a], p[b], p[i]) != 0.0)?;
+        let (b, c) = if orient2d(p[a], p[b], p[c]) > 0.0 {
+            (b, c)
+        } else {
+            (c, b)
+        };
+        let created = [
+            self.push([a, b, c]),
+            self.push([b, a, GHOST]),
+            self.push([c, b, GHOST]),
+            self.push([a, c, GHOST]),
+        ];
+        self.link(&created);
+        Some([a, b, c])
+    }
+
+    fn push(&mut self, corners: [usize; 3]) -> usize {
+        self.triangles.push(corners);
+        self.adjacent.push([GHOST; 3]);
+        self.alive.push(true);
+        self.triangles.len() - 1
+    }
+
+    /// Join up the triangles in `created` that share edges
+    fn link(&mut self, created: &[usize]) {
+        let mut edges = BTreeMap::new();
+        for &t in created {
+            let v = self.triangles[t];
+            for i in 0..3 {
+                edges.insert((v[(i + 1) % 3], v[(i + 2) % 3]), (t, i));
+            }
+        }
+        for (&(a, b), &(t, i)) in &edges {
+            if let Some(&(u, _)) = edges.get(&(b, a)) {
+                self.adjacent[t][i] = u;
+            }
+        }
+    }
+
+    /// Whether `p` is inside the circumcircle of triangle `t`. For a ghost triangle
+    /// that is the open half plane beyond its hull edge, along with the inside of the
+    /// edge itself.
+    fn conflicts(&self, t: usize, p: Point<T>) -> bool {
+        let [a, b, c] = self.triangles[t];
+        let (a, b) = (self.points[a], self.points[b]);
+        if c != GHOST {
+            return incircle(a, b, self.points[c], p) > 0.0;
+        }
+        let turn = orient2d(a, b, p);
+        turn > 0.0
+            || turn == 0.0
+                && (p - a).dot_product(&(b - a)) > T::zero()
+                && (p - b).dot_product(&(a - b)) > T::zero()
+    }
+
+    /// A triangle whose circumcircle contains `p`, walking towards it from the live
+    /// triangle `start`. None if `p` repeats a corner of the triangulation.
+    fn locate(&self, p: Point<T>, start: usize) -> Option<usize> {
+        let mut t = start;
+        'walk: loop {
+            let v = self.triangles[t];
+            if v[2] == GHOST {
+                return Some(t);
+            }
+            for i in 0..3 {
+                let (a, b) = (self.points[v[(i + 1) % 3]], self.points[v[(i + 2) % 3]]);
+                if orient2d(a, b, p) < 0.0 {
+                    t = self.adjacent[t][i];
+                    continue 'walk;
+                }
+            }
+            if v.iter().any(|&c| self.points[c] == p) {
+                return None;
+            }
+            return Some(t);
+        }
+    }
+
+    /// Insert point `i`, replacing the triangles whose circumcircles contain it with a
+    /// fan of triangles around it. Returns one of the new triangles that is not a
+    /// ghost, or None if the point was a repeat.
+    fn insert(&mut self, i: usize, start: usize) -> Option<usize> {
+        let p = self.points[i];
+        let seed = self.locate(p, start)?;
+        self.alive[seed] = false;
+        let mut stack = alloc::vec![seed];
+        // Edges between the cavity and the rest, with the triangles either side
+        let mut rim = Vec::new();
+        while let Some(t) = stack.pop() {
+            for j in 0..3 {
+                let n = self.adjacent[t][j];
+                if !self.alive[n] {
+                    continue;
+                }
+                if self.conflicts(n, p) {
+                    self.alive[n] = false;
+                    stack.push(n);
+                } else {
+                    let v = self.triangles[t];
+                    rim.push((v[(j + 1) % 3], v[(j + 2) % 3], t, n));
+                }
+            }
+        }
+        let mut created = Vec::with_capacity(rim.len());
+        let mut real = None;
+        for (a, b, old, outside) in rim {
+            // Ghost triangles keep the vertex at infinity last
+            let corners = match (a, b) {
+                (GHOST, _) => [b, i, GHOST],
+                (_, GHOST) => [i, a, GHOST],
+                _ => [a, b, i],
+            };
+            let t = self.push(corners);
+            if corners[2] != GHOST {
+                real = Some(t);
+            }
+            let facing = corners.iter().position(|&c| c != a && c != b).unwrap();
+            self.adjacent[t][facing] = outside;
+            for k in 0..3 {
+                if self.adjacent[outside][k] == old {
+                    self.adjacent[outside][k] = t;
+                }
+            }
+            created.push(t);
+        }
+        self.link(&created);
+        real
+    }
+
+    /// Drop the ghost and replaced triangles and renumber the rest
+    fn finish(self) -> Delaunay {
+        let keep = |t: usize| self.alive[t] && self.triangles[t][2] != GHOST;
+        let mut index = alloc::vec![None; self.triangles.len()];
+        let mut triangles = Vec::new();
+        for t in (0..self.triangles.len()).filter(|&t| keep(t)) {
+            index[t] = Some(triangles.len());
+            triangles.push(self.triangles[t]);
+        }
+        let neighbors = (0..self.triangles.len())
+            .filter(|&t| keep(t))
+            .map(|t| {
+                let [a, b, c] = self.adjacent[t];
+                [index[a], index[b], index[c]]
+            })
+            .collect();
+        Delaunay {
+            triangles,
+            neighbors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convex_hull, scalar_isclose, Lcg};
+
+    /// Points on a grid a quarter apart, so that many are collinear or cocircular, and
+    /// some repeat
+    fn on_grid(n: usize, seed: u64) -> Vec<Point> {
+        let mut rng = Lcg::new(seed);
+        (0..n)
+            .map(|_| Point {
+                x: rng.below(40) as f64 / 4.0,
+                y: rng.below(40) as f64 / 4.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_circumcircles() {
+        let points = on_grid(150, 3);
+        let mesh = delaunay_triangulation(&points);
+        let mut distinct = points.clone();
+        distinct.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+        distinct.dedup();
+        // Euler's formula fixes the count, counting every hull point as a corner
+        let hull = convex_hull(&points, true).vertices().len();
+        assert_eq!(2 * distinct.len() - 2 - hull, mesh.triangles.len());
+        for &[a, b, c] in &mesh.triangles {
+            assert!(orient2d(points[a], points[b], points[c]) > 0.0);
+            for &p in &points {
+                assert!(incircle(points[a], points[b], points[c], p) <= 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_neighbors_share_edges() {
+        let points = on_grid(60, 8);
+        let mesh = delaunay_triangulation(&points);
+        let mut hull_edges = 0;
+        for (t, v) in mesh.triangles.iter().enumerate() {
+            for i in 0..3 {
+                let (a, b) = (v[(i + 1) % 3], v[(i + 2) % 3]);
+                match mesh.neighbors[t][i] {
+                    Some(n) => {
+                        let w = mesh.triangles[n];
+                        let j = mesh.neighbors[n]
+                            .iter()
+                            .position(|&m| m == Some(t))
+                            .unwrap();
+                        assert_eq!((b, a), (w[(j + 1) % 3], w[(j + 2) % 3]));
+                    }
+                    None => hull_edges += 1,
+                }
+            }
+        }
+        let hull = convex_hull(&points, true).vertices().len();
+        assert_eq!(hull, hull_edges);
+        let mut edges: Vec<[usize; 2]> = mesh.edges().map(|[a, b]| [a.min(b), a.max(b)]).collect();
+        edges.sort();
+        edges.dedup();
+        assert_eq!(edges.len(), mesh.edges().count());
+        assert_eq!((3 * mesh.triangles.len() + hull) / 2, edges.len());
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        let line: Vec<Point> = (0..5)
+            .map(|i| Point {
+                x: i as f64,
+                y: 2.0 * i as f64,
+            })
+            .collect();
+        assert!(delaunay_triangulation(&line).triangles.is_empty());
+        let mut square = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 0.0, y: 1.0 },
+        ];
+        square.extend(square.clone());
+        // The four corners are cocircular, so either diagonal will do
+        assert_eq!(2, delaunay_triangulation(&square).triangles.len())
+    }
+
+    /// Whether every edge that is not along a constraint has the Delaunay property
+    /// with respect to the triangles either side of it
+    fn locally_delaunay(points: &[Point], mesh: &Delaunay, constraints: &[[usize; 2]]) -> bool {
+        let on_constraint = |a: Point, b: Point| {
+            constraints.iter().any(|&[c, d]| {
+                let (c, d) = (points[c], points[d]);
+                orient2d(c, d, a) == 0.0
+                    && orient2d(c, d, b) == 0.0
+                    && (a - c).dot_product(&(a - d)) <= 0.0
+                    && (b - c).dot_product(&(b - d)) <= 0.0
+            })
+        };
+        mesh.triangles.iter().enumerate().all(|(t, v)| {
+            (0..3).all(|i| match mesh.neighbors[t][i] {
+                Some(n) => {
+                    let (a, b) = (points[v[(i + 1) % 3]], points[v[(i + 2) % 3]]);
+                    let far = *mesh.triangles[n]
+                        .iter()
+                        .find(|&&c| !v.contains(&c))
+                        .unwrap();
+                    on_constraint(a, b)
+                        || incircle(points[v[0]], points[v[1]], points[v[2]], points[far]) <= 0.0
+                }
+                None => true,
+            })
+        })
+    }
+
+    #[test]
+    fn test_constraints_become_edges() {
+        let mut points = on_grid(120, 5);
+        // A star shaped ring of constraints, which cross many Delaunay edges
+        let ring = points.len();
+        for i in 0..16 {
+            let angle = core::f64::consts::TAU * i as f64 / 16.0;
+            let r = if i % 2 == 0 { 4.9 } else { 1.3 };
+            points.push(Point {
+                x: 5.0 + r * angle.cos(),
+                y: 5.0 + r * angle.sin(),
+            });
+        }
+        let constraints: Vec<[usize; 2]> =
+            (0..16).map(|i| [ring + i, ring + (i + 1) % 16]).collect();
+        let mesh = constrained_delaunay(&points, &constraints);
+        let delaunay = delaunay_triangulation(&points);
+        assert_eq!(delaunay.triangles.len(), mesh.triangles.len());
+        let edges: Vec<[usize; 2]> = mesh.edges().collect();
+        for &[a, b] in &constraints {
+            assert!(edges
+                .iter()
+                .any(|&[c, d]| (c, d) == (a, b) || (c, d) == (b, a)));
+        }
+        for &[a, b, c] in &mesh.triangles {
+            assert!(orient2d(points[a], points[b], points[c]) > 0.0);
+        }
+        assert!(locally_delaunay(&points, &mesh, &constraints));
+        assert!(!locally_delaunay(&points, &mesh, &[]));
+    }
+
+    #[test]
+    fn test_constraint_through_points() {
+        let points: Vec<Point> = (0..36)
+            .map(|i| Point {
+                x: (i % 6) as f64 + 0.25 * (i / 6) as f64,
+                y: (i / 6) as f64,
+            })
+            .collect();
+        // The long diagonal runs through four other points, and the other one cuts
+        // across the grid
+        let constraints = [[0, 35], [5, 22]];
+        let mesh = constrained_delaunay(&points, &constraints);
+        assert!(locally_delaunay(&points, &mesh, &constraints));
+        let edges: Vec<[usize; 2]> = mesh.edges().map(|[a, b]| [a.min(b), a.max(b)]).collect();
+        for i in 0..5 {
+            assert!(edges.contains(&[7 * i, 7 * i + 7]));
+        }
+        assert!(edges.contains(&[5, 22]));
+        // Repeating Delaunay edges as constraints changes nothing
+        let delaunay = delaunay_triangulation(&points);
+        let existing: Vec<[usize; 2]> = delaunay.edges().collect();
+        assert_eq!(delaunay, constrained_delaunay(&points, &existing));
+    }
+
+    #[test]
+    fn test_polygon_triangulation() {
+        let mut comb = vec![Point { x: 0.0, y: 0.0 }, Point { x: 9.0, y: 0.0 }];
+        for i in (0..5).rev() {
+            let x = 2.0 * i as f64;
+            comb.push(Point { x: x + 1.0, y: 3.0 });
+            comb.push(Point { x, y: 3.0 });
+            if i > 0 {
+                comb.push(Point { x, y: 1.1 });
+                comb.push(Point { x: x - 1.0, y: 1.0 });
+            }
+        }
+        let hole = vec![
+            Point { x: 4.0, y: 0.5 },
+            Point { x: 3.0, y: 0.6 },
+            Point { x: 3.5, y: 0.3 },
+        ];
+        let n = comb.len() + hole.len();
+        let polygon = Polygon::with_holes(comb, vec![hole]);
+        let triangles = polygon.triangulate_delaunay();
+        assert_eq!(n, triangles.len());
+        let area: f64 = triangles.iter().map(|t| t.area()).sum();
+        assert!(scalar_isclose(area, polygon.area()));
+        let mut rng = Lcg::new(17);
+        for _ in 0..100 {
+            let star: Vec<Point> = (0..40)
+                .map(|i| {
+                    let r = 1.0 + 9.0 * rng.unit();
+                    let angle = core::f64::consts::TAU * i as f64 / 40.0;
+                    Point {
+                        x: r * angle.cos(),
+                        y: r * angle.sin(),
+                    }
+                })
+                .collect();
+            let star = Polygon::new(star);
+            let triangles = star.triangulate_delaunay();
+            assert_eq!(38, triangles.len());
+            let area: f64 = triangles.iter().map(|t| t.area()).sum();
+            assert!(scalar_isclose(area, star.area()));
+        }
+    }
+}
@@ -0,0 +1,384 @@
+//! The minimum distance between any two of `Point`, `LineSegment`, `Polyline`,
+//! `Polygon`, `Circle` and `Rect`, with the points on each shape that are that far
+//! apart.
+//!
+//! Shapes that cover an area are solid, so a point inside a polygon is a distance of
+//! zero from it, and a point in one of its holes is measured to the edge of the hole.
+//! Pairs drawn from points, segments, circles and rectangles don't allocate and are
+//! available without the `alloc` feature.
+
+#[cfg(feature = "alloc")]
+use crate::polygon::ring_edges;
+use crate::{Circle, LineSegment, Point, Rect, Scalar};
+#[cfg(feature = "alloc")]
+use crate::{Polygon, Polyline};
+
+pub trait Distance<T: Scalar, Rhs = Self> {
+    /// The minimum distance between the shapes, with the closest point on `self` and
+    /// then the closest point on `other`. Shapes that meet are a distance of zero apart,
+    /// and both points are one they share. Returns None if either shape is empty.
+    /// ```
+    /// use rsgeo::{Circle, Distance, Point, Rect};
+    /// let disk = Circle::new(Point{x: 5.0, y: 1.0}, 1.0);
+    /// let rect = Rect::from_corners(Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 2.0});
+    /// assert_eq!(
+    ///     disk.closest_points(&rect),
+    ///     Some((2.0, Point{x: 4.0, y: 1.0}, Point{x: 2.0, y: 1.0}))
+    /// );
+    /// ```
+    fn closest_points(&self, other: &Rhs) -> Option<(T, Point<T>, Point<T>)>;
+
+    /// The minimum distance between the shapes, or None if either is empty
+    fn min_distance(&self, other: &Rhs) -> Option<T> {
+        self.closest_points(other).map(|(d, _, _)| d)
+    }
+}
+
+/// One of the simple pieces that make up the outline of a shape
+#[derive(Clone, Copy)]
+enum Piece<T> {
+    Point(Point<T>),
+    Segment(LineSegment<T>),
+    Circle(Circle<T>),
+}
+
+impl<T: Scalar> Piece<T> {
+    /// A point on the piece
+    fn anchor(&self) -> Point<T> {
+        match self {
+            Piece::Point(p) => *p,
+            Piece::Segment(s) => s.p1(),
+            Piece::Circle(c) => {
+                c.center
+                    + Point {
+                        x: c.radius,
+                        y: T::zero(),
+                    }
+            }
+        }
+    }
+}
+
+/// A shape seen as its outline and, for shapes that cover an area, what lies inside it
+#[derive(Clone, Copy)]
+enum Parts<'a, T> {
+    Point(Point<T>),
+    Segment(LineSegment<T>),
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    Chain(&'a [Point<T>]),
+    Disk(Circle<T>),
+    Box(Rect<T>),
+    #[cfg(feature = "alloc")]
+    Region(&'a Polygon<T>),
+}
+
+impl<'a, T: Scalar> Parts<'a, T> {
+    fn is_empty(&self) -> bool {
+        match self {
+            Parts::Chain(points) => points.is_empty(),
+            #[cfg(feature = "alloc")]
+            Parts::Region(r) => r.vertices().is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Whether `p` is in the area the shape covers
+    fn covers(&self, p: Point<T>) -> bool {
+        match self {
+            Parts::Disk(c) => c.contains(p),
+            Parts::Box(r) => r.contains(p),
+            #[cfg(feature = "alloc")]
+            Parts::Region(r) => r.contains(p),
+            _ => false,
+        }
+    }
+
+    fn for_each_piece(&self, f: &mut dyn FnMut(Piece<T>)) {
+        match *self {
+            Parts::Point(p) => f(Piece::Point(p)),
+            Parts::Segment(s) => f(Piece::Segment(s)),
+            Parts::Chain(&[p]) => f(Piece::Point(p)),
+            Parts::Chain(points) => points
+                .windows(2)
+                .for_each(|w| f(Piece::Segment(LineSegment::new(w[0], w[1])))),
+            Parts::Disk(c) => f(Piece::Circle(c)),
+            Parts::Box(r) => r.edges().for_each(|e| f(Piece::Segment(e))),
+            #[cfg(feature = "alloc")]
+            Parts::Region(r) => r
+                .rings()
+                .flat_map(ring_edges)
+                .for_each(|e| f(Piece::Segment(e))),
+        }
+    }
+}
+
+trait AsParts<T> {
+    fn parts(&self) -> Parts<'_, T>;
+}
+
+impl<T: Scalar> AsParts<T> for Point<T> {
+    fn parts(&self) -> Parts<'_, T> {
+        Parts::Point(*self)
+    }
+}
+
+impl<T: Scalar> AsParts<T> for LineSegment<T> {
+    fn parts(&self) -> Parts<'_, T> {
+        Parts::Segment(*self)
+    }
+}
+
+impl<T: Scalar> AsParts<T> for Circle<T> {
+    fn parts(&self) -> Parts<'_, T> {
+        Parts::Disk(*self)
+    }
+}
+
+impl<T: Scalar> AsParts<T> for Rect<T> {
+    fn parts(&self) -> Parts<'_, T> {
+        Parts::Box(*self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> AsParts<T> for Polyline<T> {
+    fn parts(&self) -> Parts<'_, T> {
+        Parts::Chain(self.points())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> AsParts<T> for Polygon<T> {
+    fn parts(&self) -> Parts<'_, T> {
+        Parts::Region(self)
+    }
+}
+
+/// The point of the circle's boundary nearest to `p`
+fn nearest_on_circle<T: Scalar>(c: &Circle<T>, p: Point<T>) -> Point<T> {
+    let offset = p - c.center;
+    let length = offset.magnitude();
+    if length == T::zero() {
+        // Every point of the boundary is equally near to the center
+        c.center
+            + Point {
+                x: c.radius,
+                y: T::zero(),
+            }
+    } else {
+        c.center + offset * (c.radius / length)
+    }
+}
+
+fn segment_to_circle<T: Scalar>(s: &LineSegment<T>, c: &Circle<T>) -> (T, Point<T>, Point<T>) {
+    if let [Some(p), _] = c.intersect_segment(s) {
+        return (T::zero(), p, p);
+    }
+    let near = s.closest_point(c.center);
+    let d = near.distance(&c.center);
+    if d >= c.radius {
+        return (d - c.radius, near, nearest_on_circle(c, near));
+    }
+    // The segment lies wholly inside, so its far end is nearest to the boundary
+    let far = if s.p1().distance_squared(&c.center) >= s.p2().distance_squared(&c.center) {
+        s.p1()
+    } else {
+        s.p2()
+    };
+    let on_circle = nearest_on_circle(c, far);
+    (far.distance(&on_circle), far, on_circle)
+}
+
+fn circle_to_circle<T: Scalar>(a: &Circle<T>, b: &Circle<T>) -> (T, Point<T>, Point<T>) {
+    let d = a.center.distance(&b.center);
+    if d >= a.radius + b.radius {
+        return (
+            d - a.radius - b.radius,
+            nearest_on_circle(a, b.center),
+            nearest_on_circle(b, a.center),
+        );
+    }
+    if let [Some(p), _] = a.intersection_points(b) {
+        return (T::zero(), p, p);
+    }
+    // One circle is nested in the other, and they are nearest where a ray from the outer
+    // center through the inner one leaves both
+    let (outer, inner, swapped) = if a.radius >= b.radius {
+        (a, b, false)
+    } else {
+        (b, a, true)
+    };
+    let toward = if d == T::zero() {
+        inner.center
+            + Point {
+                x: T::one(),
+                y: T::zero(),
+            }
+    } else {
+        inner.center + (inner.center - outer.center)
+    };
+    let (p, q) = (
+        nearest_on_circle(outer, toward),
+        nearest_on_circle(inner, toward),
+    );
+    let gap = p.distance(&q);
+    if swapped {
+        (gap, q, p)
+    } else {
+        (gap, p, q)
+    }
+}
+
+fn piece_distance<T: Scalar>(a: Piece<T>, b: Piece<T>) -> (T, Point<T>, Point<T>) {
+    match (a, b) {
+        (Piece::Point(p), Piece::Point(q)) => (p.distance(&q), p, q),
+        (Piece::Point(p), Piece::Segment(s)) => {
+            let q = s.closest_point(p);
+            (p.distance(&q), p, q)
+        }
+        (Piece::Point(p), Piece::Circle(c)) => {
+            let q = nearest_on_circle(&c, p);
+            (p.distance(&q), p, q)
+        }
+        (Piece::Segment(s), Piece::Segment(t)) => s.distance_to(&t),
+        (Piece::Segment(s), Piece::Circle(c)) => segment_to_circle(&s, &c),
+        (Piece::Circle(c), Piece::Circle(d)) => circle_to_circle(&c, &d),
+        _ => {
+            let (d, q, p) = piece_distance(b, a);
+            (d, p, q)
+        }
+    }
+}
+
+fn parts_distance<T: Scalar>(a: &Parts<T>, b: &Parts<T>) -> Option<(T, Point<T>, Point<T>)> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    // A shape with a point inside the other is as near as can be. Otherwise no piece of
+    // either lies inside the other, and the nearest pair of pieces decides.
+    let mut shared = None;
+    a.for_each_piece(&mut |piece| {
+        if shared.is_none() && b.covers(piece.anchor()) {
+            shared = Some(piece.anchor());
+        }
+    });
+    b.for_each_piece(&mut |piece| {
+        if shared.is_none() && a.covers(piece.anchor()) {
+            shared = Some(piece.anchor());
+        }
+    });
+    if let Some(p) = shared {
+        return Some((T::zero(), p, p));
+    }
+
+    let mut best: Option<(T, Point<T>, Point<T>)> = None;
+    a.for_each_piece(&mut |pa| {
+        b.for_each_piece(&mut |pb| {
+            let found = piece_distance(pa, pb);
+            if best.is_none_or(|(d, _, _)| found.0 < d) {
+                best = Some(found);
+            }
+        })
+    });
+    best
+}
+
+macro_rules! impl_distance {
+    ($a:ident, $b:ident) => {
+        impl<T: Scalar> Distance<T, $b<T>> for $a<T> {
+            fn closest_points(&self, other: &$b<T>) -> Option<(T, Point<T>, Point<T>)> {
+                parts_distance(&self.parts(), &other.parts())
+            }
+        }
+    };
+}
+
+for_pairs!(impl_distance;
+    [Point, LineSegment, Circle, Rect] x [Point, LineSegment, Circle, Rect]);
+#[cfg(feature = "alloc")]
+for_pairs!(impl_distance;
+    [Polyline, Polygon] x [Point, LineSegment, Polyline, Polygon, Circle, Rect]);
+#[cfg(feature = "alloc")]
+for_pairs!(impl_distance; [Point, LineSegment, Circle, Rect] x [Polyline, Polygon]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(lo: f64, hi: f64) -> Vec<Point> {
+        vec![
+            Point { x: lo, y: lo },
+            Point { x: hi, y: lo },
+            Point { x: hi, y: hi },
+            Point { x: lo, y: hi },
+        ]
+    }
+
+    #[test]
+    fn test_polygons_are_solid() {
+        let frame = Polygon::with_holes(square(0.0, 6.0), vec![square(2.0, 4.0)]);
+        let inside = Point { x: 1.0, y: 3.0 };
+        assert_eq!(Some((0.0, inside, inside)), inside.closest_points(&frame));
+        let in_hole = Point { x: 3.5, y: 3.0 };
+        assert_eq!(
+            Some((0.5, in_hole, Point { x: 4.0, y: 3.0 })),
+            in_hole.closest_points(&frame)
+        );
+        let far = Point { x: 9.0, y: 10.0 };
+        assert_eq!(Some(5.0), far.min_distance(&frame));
+        // A circle in the hole is measured to the hole's edge
+        let c = Circle::new(Point { x: 3.0, y: 3.0 }, 0.5);
+        let (d, on_c, on_frame) = c.closest_points(&frame).unwrap();
+        assert!(crate::scalar_isclose(0.5, d));
+        assert!(crate::scalar_isclose(0.5, on_c.distance(&on_frame)));
+        assert_eq!(Some(0.0), Circle::new(c.center, 1.5).min_distance(&frame))
+    }
+
+    #[test]
+    fn test_witnesses_follow_argument_order() {
+        let a = Polygon::new(square(0.0, 1.0));
+        let line: Polyline = vec![Point { x: 3.0, y: 0.5 }, Point { x: 2.0, y: 3.0 }]
+            .into_iter()
+            .collect();
+        let (d, on_a, on_line) = a.closest_points(&line).unwrap();
+        assert_eq!(Some((d, on_line, on_a)), line.closest_points(&a));
+        assert_eq!(Point { x: 1.0, y: 1.0 }, on_a);
+        assert!(crate::scalar_isclose(d, on_a.distance(&on_line)));
+        // Crossing shapes are zero apart at a point they share
+        let seg: LineSegment = "(0.5, 0.5) -> (3, 3)".parse().unwrap();
+        let (d, p, q) = seg.closest_points(&line).unwrap();
+        assert_eq!((0.0, p), (d, q));
+        assert!(seg.contains_point(p, 1e-9) && line.segments().any(|s| s.contains_point(p, 1e-9)))
+    }
+
+    #[test]
+    fn test_circles() {
+        let a = Circle::new(Point::origin(), 1.0);
+        let b = Circle::new(Point { x: 4.0, y: 0.0 }, 2.0);
+        assert_eq!(
+            Some((1.0, Point { x: 1.0, y: 0.0 }, Point { x: 2.0, y: 0.0 })),
+            a.closest_points(&b)
+        );
+        // Disks are solid, so a circle inside another is no distance from it
+        let big = Circle::new(Point::origin(), 5.0);
+        assert_eq!(Some(0.0), big.min_distance(&b));
+        let seg: LineSegment = "(0, -3) -> (4, -3)".parse().unwrap();
+        assert_eq!(
+            Some((2.0, Point { x: 0.0, y: -1.0 }, Point { x: 0.0, y: -3.0 })),
+            a.closest_points(&seg)
+        );
+        let r = Rect::from_corners(Point { x: 3.0, y: 3.0 }, Point { x: 5.0, y: 5.0 });
+        let (d, _, on_r) = a.closest_points(&r).unwrap();
+        assert!(crate::scalar_isclose(d, 18.0_f64.sqrt() - 1.0));
+        assert_eq!(Point { x: 3.0, y: 3.0 }, on_r)
+    }
+
+    #[test]
+    fn test_empty() {
+        let empty = Polyline::<f64>::default();
+        assert_eq!(None, empty.closest_points(&Point::origin()));
+        assert_eq!(None, Polygon::new(square(0.0, 1.0)).min_distance(&empty));
+        let single: Polyline = vec![Point { x: 3.0, y: 4.0 }].into_iter().collect();
+        assert_eq!(Some(5.0), Point::origin().min_distance(&single))
+    }
+}
@@ -0,0 +1,639 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::polygon::ring_orientation;
+use crate::predicates::{orient2d, Orientation};
+use crate::{Point, Polygon, Scalar, Triangle};
+
+impl<T: Scalar> Polygon<T> {
+    /// Cut the polygon into triangles by ear clipping, after the manner of Mapbox's
+    /// earcut. Each hole is first joined to the exterior by a bridge to a vertex it can
+    /// see, and then corners whose triangle holds no other vertex are clipped off one
+    /// at a time. Repeated and collinear vertices are dropped along the way; if that
+    /// leaves no ear, crossings left by slivers are cured, and failing that the
+    /// remainder is split along a diagonal and each part done separately. Every
+    /// triangle is counter-clockwise, and for a valid polygon they cover it exactly,
+    /// which takes O(n^2) time in the worst case. Self-intersecting polygons give some
+    /// triangulation, but not necessarily a covering one.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let framed = Polygon::with_holes(
+    ///     vec![Point{x: 0.0, y: 0.0}, Point{x: 3.0, y: 0.0}, Point{x: 3.0, y: 3.0}, Point{x: 0.0, y: 3.0}],
+    ///     vec![vec![Point{x: 1.0, y: 1.0}, Point{x: 2.0, y: 1.0}, Point{x: 2.0, y: 2.0}, Point{x: 1.0, y: 2.0}]],
+    /// );
+    /// let triangles = framed.triangulate();
+    /// // Eight vertices, and one hole to bridge
+    /// assert_eq!(triangles.len(), 8);
+    /// assert_eq!(triangles.iter().map(|t| t.area()).sum::<f64>(), 8.0);
+    /// ```
+    pub fn triangulate(&self) -> Vec<Triangle<T>> {
+        let points: Vec<Point<T>> = self.rings().flatten().copied().collect();
+        self.triangulate_indices()
+            .iter()
+            .map(|&[a, b, c]| Triangle::new(points[a], points[b], points[c]))
+            .collect()
+    }
+
+    /// The same triangles as [`Polygon::triangulate`], as indices into the exterior
+    /// ring followed by each of the holes in turn, which suits a vertex buffer.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let square = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.0}, Point{x: 1.0, y: 1.0}, Point{x: 0.0, y: 1.0},
+    /// ]);
+    /// assert_eq!(square.triangulate_indices(), [[2, 3, 0], [0, 1, 2]]);
+    /// ```
+    pub fn triangulate_indices(&self) -> Vec<[usize; 3]> {
+        let mut list = Ears {
+            nodes: Vec::new(),
+            triangles: Vec::new(),
+        };
+        let mut first = 0;
+        let mut outer = None;
+        let mut holes = Vec::new();
+        for (i, ring) in self.rings().enumerate() {
+            let orientation = ring_orientation(ring);
+            // Rings that enclose nothing leave nothing to fill or cut out
+            if orientation != Orientation::Collinear {
+                let start = list.ring(ring, first, orientation, i == 0);
+                if i == 0 {
+                    outer = Some(start);
+                } else {
+                    holes.push(start);
+                }
+            }
+            first += ring.len();
+        }
+        let mut outer = match outer {
+            Some(outer) => outer,
+            None => return Vec::new(),
+        };
+        outer = list.eliminate_holes(holes, outer);
+        if list.nodes[outer].next != list.nodes[outer].prev {
+            list.clip(outer, Pass::Filtered);
+        }
+        list.triangles
+    }
+}
+
+struct Node<T> {
+    /// Where the vertex is among all the rings of the polygon
+    i: usize,
+    p: Point<T>,
+    prev: usize,
+    next: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Pass {
+    /// Clipping ears straight away
+    Filtered,
+    /// Again, with repeated and collinear vertices dropped
+    Refiltered,
+    /// Again, with crossings between neighbouring edges cured, and then splitting
+    /// what is left in two
+    Cured,
+}
+
+/// Rings of vertices linked both ways, each node standing in for an index into the
+/// polygon's vertices. A vertex can have more than one node once a bridge or
+/// diagonal runs from it.
+struct Ears<T> {
+    nodes: Vec<Node<T>>,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl<T: Scalar> Ears<T> {
+    fn node(&self, n: usize) -> Point<T> {
+        self.nodes[n].p
+    }
+
+    fn next(&self, n: usize) -> usize {
+        self.nodes[n].next
+    }
+
+    fn prev(&self, n: usize) -> usize {
+        self.nodes[n].prev
+    }
+
+    /// Whether the corner at `b` turns left on the way from `a` to `c`
+    fn turn(&self, a: usize, b: usize, c: usize) -> f64 {
+        orient2d(self.node(a), self.node(b), self.node(c))
+    }
+
+    /// Link up a ring, counter-clockwise for the exterior and clockwise for a hole,
+    /// returning its last node
+    fn ring(
+        &mut self,
+        ring: &[Point<T>],
+        first: usize,
+        orientation: Orientation,
+        exterior: bool,
+    ) -> usize {
+        let mut order: Vec<usize> = (0..ring.len()).collect();
+        if (orientation == Orientation::CounterClockwise) != exterior {
+            order.reverse();
+        }
+        let last = self.nodes.len() + ring.len() - 1;
+        let mut prev = last;
+        for j in order {
+            let n = self.nodes.len();
+            self.nodes.push(Node {
+                i: first + j,
+                p: ring[j],
+                prev,
+                next: if n == last {
+                    last + 1 - ring.len()
+                } else {
+                    n + 1
+                },
+            });
+            prev = n;
+        }
+        if self.node(last) == self.node(self.next(last)) {
+            let next = self.next(last);
+            self.remove(last);
+            return next;
+        }
+        last
+    }
+
+    fn remove(&mut self, n: usize) {
+        let (prev, next) = (self.prev(n), self.next(n));
+        self.nodes[next].prev = prev;
+        self.nodes[prev].next = next;
+    }
+
+    /// Drop repeated vertices, and those in line with their neighbours, between `start`
+    /// and `end`
+    fn filter(&mut self, start: usize, end: Option<usize>) -> usize {
+        let mut end = end.unwrap_or(start);
+        let mut p = start;
+        loop {
+            let next = self.next(p);
+            let mut again = false;
+            if self.node(p) == self.node(next) || self.turn(self.prev(p), p, next) == 0.0 {
+                self.remove(p);
+                p = self.prev(p);
+                end = p;
+                if p == self.next(p) {
+                    break;
+                }
+                again = true;
+            } else {
+                p = next;
+            }
+            if !again && p == end {
+                break;
+            }
+        }
+        end
+    }
+
+    /// Clip ears off the ring through `ear` until only a triangle is left, trying a
+    /// harder pass each time it goes all the way round without finding one
+    fn clip(&mut self, mut ear: usize, pass: Pass) {
+        let mut stop = ear;
+        while self.prev(ear) != self.next(ear) {
+            let (prev, next) = (self.prev(ear), self.next(ear));
+            if self.is_ear(ear) {
+                let triangle = [self.nodes[prev].i, self.nodes[ear].i, self.nodes[next].i];
+                self.triangles.push(triangle);
+                self.remove(ear);
+                ear = self.next(next);
+                stop = ear;
+                continue;
+            }
+            ear = next;
+            if ear == stop {
+                match pass {
+                    Pass::Filtered => {
+                        let ear = self.filter(ear, None);
+                        self.clip(ear, Pass::Refiltered);
+                    }
+                    Pass::Refiltered => {
+                        let ear = self.filter(ear, None);
+                        let ear = self.cure_crossings(ear);
+                        self.clip(ear, Pass::Cured);
+                    }
+                    Pass::Cured => self.split(ear),
+                }
+                break;
+            }
+        }
+    }
+
+    /// Whether the triangle at `ear` is convex and holds no reflex vertex of the ring
+    fn is_ear(&self, ear: usize) -> bool {
+        let (a, c) = (self.prev(ear), self.next(ear));
+        if self.turn(a, ear, c) <= 0.0 {
+            return false;
+        }
+        let mut p = self.next(c);
+        while p != a {
+            if self.node(p) != self.node(a)
+                && in_triangle(self.node(a), self.node(ear), self.node(c), self.node(p))
+                && self.turn(self.prev(p), p, self.next(p)) <= 0.0
+            {
+                return false;
+            }
+            p = self.next(p);
+        }
+        true
+    }
+
+    /// Clip off the corner between each pair of neighbouring edges that cross, which
+    /// slivers can leave behind
+    fn cure_crossings(&mut self, start: usize) -> usize {
+        let (mut start, mut p) = (start, start);
+        loop {
+            let (a, next) = (self.prev(p), self.next(p));
+            let b = self.next(next);
+            if self.node(a) != self.node(b)
+                && self.crosses(a, p, next, b)
+                && self.locally_inside(a, b)
+                && self.locally_inside(b, a)
+            {
+                let triangle = [self.nodes[a].i, self.nodes[p].i, self.nodes[b].i];
+                self.triangles.push(triangle);
+                self.remove(p);
+                self.remove(next);
+                p = b;
+                start = b;
+            }
+            p = self.next(p);
+            if p == start {
+                break;
+            }
+        }
+        self.filter(p, None)
+    }
+
+    /// Split the ring along a diagonal, and clip each part on its own
+    fn split(&mut self, start: usize) {
+        let mut a = start;
+        loop {
+            let mut b = self.next(self.next(a));
+            while b != self.prev(a) {
+                if self.nodes[a].i != self.nodes[b].i && self.is_valid_diagonal(a, b) {
+                    let c = self.split_ring(a, b);
+                    let a = self.filter(a, Some(self.next(a)));
+                    let c = self.filter(c, Some(self.next(c)));
+                    self.clip(a, Pass::Filtered);
+                    self.clip(c, Pass::Filtered);
+                    return;
+                }
+                b = self.next(b);
+            }
+            a = self.next(a);
+            if a == start {
+                return;
+            }
+        }
+    }
+
+    /// Join each hole to the exterior, the leftmost first
+    fn eliminate_holes(&mut self, holes: Vec<usize>, mut outer: usize) -> usize {
+        let mut leftmost: Vec<usize> = holes.into_iter().map(|h| self.leftmost(h)).collect();
+        leftmost.sort_by(|&a, &b| {
+            let (a, b) = (self.node(a), self.node(b));
+            (a.x, a.y)
+                .partial_cmp(&(b.x, b.y))
+                .unwrap_or(Ordering::Equal)
+        });
+        for hole in leftmost {
+            outer = self.eliminate_hole(hole, outer);
+        }
+        outer
+    }
+
+    fn leftmost(&self, start: usize) -> usize {
+        let (mut p, mut best) = (start, start);
+        loop {
+            let (q, b) = (self.node(p), self.node(best));
+            if q.x < b.x || q.x == b.x && q.y < b.y {
+                best = p;
+            }
+            p = self.next(p);
+            if p == start {
+                return best;
+            }
+        }
+    }
+
+    fn eliminate_hole(&mut self, hole: usize, outer: usize) -> usize {
+        let bridge = match self.hole_bridge(hole, outer) {
+            Some(bridge) => bridge,
+            None => return outer,
+        };
+        let back = self.split_ring(bridge, hole);
+        self.filter(back, Some(self.next(back)));
+        self.filter(bridge, Some(self.next(bridge)))
+    }
+
+    /// A vertex of the exterior that the leftmost vertex of a hole can see, found by
+    /// casting a ray to the left and then looking for reflex vertices that block the
+    /// way to where it lands
+    fn hole_bridge(&self, hole: usize, outer: usize) -> Option<usize> {
+        let h = self.node(hole);
+        let mut p = outer;
+        let mut nearest: Option<(T, usize)> = None;
+        loop {
+            let (a, b) = (self.node(p), self.node(self.next(p)));
+            if h.y <= a.y && h.y >= b.y && b.y != a.y {
+                let x = a.x + (h.y - a.y) * (b.x - a.x) / (b.y - a.y);
+                if x <= h.x && nearest.is_none_or(|(q, _)| x > q) {
+                    let m = if a.x < b.x { p } else { self.next(p) };
+                    if x == h.x {
+                        // The hole touches the edge
+                        return Some(m);
+                    }
+                    nearest = Some((x, m));
+                }
+            }
+            p = self.next(p);
+            if p == outer {
+                break;
+            }
+        }
+        let (qx, mut m) = nearest?;
+        let stop = m;
+        let corner = self.node(m);
+        let hit = Point { x: qx, y: h.y };
+        let (left, right) = if h.y < corner.y { (h, hit) } else { (hit, h) };
+        let mut least: Option<T> = None;
+        let mut p = m;
+        loop {
+            let q = self.node(p);
+            if h.x >= q.x && q.x >= corner.x && h.x != q.x && in_triangle(left, corner, right, q) {
+                let tan = (h.y - q.y).abs() / (h.x - q.x);
+                let best = self.node(m);
+                if self.locally_inside(p, hole)
+                    && (least.is_none_or(|least| tan < least)
+                        || least == Some(tan)
+                            && (q.x > best.x || q.x == best.x && self.sector_holds(m, p)))
+                {
+                    m = p;
+                    least = Some(tan);
+                }
+            }
+            p = self.next(p);
+            if p == stop {
+                return Some(m);
+            }
+        }
+    }
+
+    /// Whether the sector of the ring at `m` contains the sector at `p`
+    fn sector_holds(&self, m: usize, p: usize) -> bool {
+        self.turn(self.prev(m), m, self.prev(p)) > 0.0
+            && self.turn(self.next(p), m, self.next(m)) > 0.0
+    }
+
+    /// Whether a diagonal from `a` to `b` lies inside the ring and crosses none of it
+    fn is_valid_diagonal(&self, a: usize, b: usize) -> bool {
+        let (ia, ib) = (self.nodes[a].i, self.nodes[b].i);
+        if self.nodes[self.next(a)].i == ib || self.nodes[self.prev(a)].i == ib {
+            return false;
+        }
+        if self.crosses_ring(a, b) {
+            return false;
+        }
+        let visible = self.locally_inside(a, b)
+            && self.locally_inside(b, a)
+            && self.middle_inside(a, b)
+            && (self.turn(self.prev(a), a, self.prev(b)) != 0.0
+                || self.turn(a, self.prev(b), b) != 0.0);
+        // Two nodes of one vertex at reflex corners, where a bridge left it
+        let pinched = ia != ib
+            && self.node(a) == self.node(b)
+            && self.turn(self.prev(a), a, self.next(a)) < 0.0
+            && self.turn(self.prev(b), b, self.next(b)) < 0.0;
+        visible || pinched
+    }
+
+    /// Whether segment `p1 q1` meets segment `p2 q2`, even if only by touching
+    fn crosses(&self, p1: usize, q1: usize, p2: usize, q2: usize) -> bool {
+        let [p1, q1, p2, q2] = [p1, q1, p2, q2].map(|n| self.node(n));
+        let sign = |x: f64| x.partial_cmp(&0.0).unwrap_or(Ordering::Equal);
+        let o1 = sign(orient2d(p1, q1, p2));
+        let o2 = sign(orient2d(p1, q1, q2));
+        let o3 = sign(orient2d(p2, q2, p1));
+        let o4 = sign(orient2d(p2, q2, q1));
+        let between = |p: Point<T>, q: Point<T>, r: Point<T>| {
+            q.x <= p.x.max(r.x) && q.x >= p.x.min(r.x) && q.y <= p.y.max(r.y) && q.y >= p.y.min(r.y)
+        };
+        o1 != o2 && o3 != o4
+            || o1 == Ordering::Equal && between(p1, p2, q1)
+            || o2 == Ordering::Equal && between(p1, q2, q1)
+            || o3 == Ordering::Equal && between(p2, p1, q2)
+            || o4 == Ordering::Equal && between(p2, q1, q2)
+    }
+
+    /// Whether the segment from `a` to `b` meets an edge of the ring away from them
+    fn crosses_ring(&self, a: usize, b: usize) -> bool {
+        let (ia, ib) = (self.nodes[a].i, self.nodes[b].i);
+        let mut p = a;
+        loop {
+            let next = self.next(p);
+            let (ip, inext) = (self.nodes[p].i, self.nodes[next].i);
+            if ip != ia && inext != ia && ip != ib && inext != ib && self.crosses(p, next, a, b) {
+                return true;
+            }
+            p = next;
+            if p == a {
+                return false;
+            }
+        }
+    }
+
+    /// Whether the segment from `a` to `b` starts off into the inside of the ring
+    fn locally_inside(&self, a: usize, b: usize) -> bool {
+        let (prev, next) = (self.prev(a), self.next(a));
+        if self.turn(prev, a, next) > 0.0 {
+            self.turn(a, b, next) <= 0.0 && self.turn(a, prev, b) <= 0.0
+        } else {
+            self.turn(a, b, prev) > 0.0 || self.turn(a, next, b) > 0.0
+        }
+    }
+
+    /// Whether the midpoint of `a` and `b` is inside the ring
+    fn middle_inside(&self, a: usize, b: usize) -> bool {
+        let mid = (self.node(a) + self.node(b)) / T::from_f64(2.0);
+        let mut inside = false;
+        let mut p = a;
+        loop {
+            let (q, r) = (self.node(p), self.node(self.next(p)));
+            if (q.y > mid.y) != (r.y > mid.y)
+                && r.y != q.y
+                && mid.x < (r.x - q.x) * (mid.y - q.y) / (r.y - q.y) + q.x
+            {
+                inside = !inside;
+            }
+            p = self.next(p);
+            if p == a {
+                return inside;
+            }
+        }
+    }
+
+    /// Join `a` to `b` within one ring, cutting it in two, or between two rings,
+    /// making them one. The nodes of `a` and `b` go round one side and new nodes for
+    /// them round the other; the new node for `b` is returned.
+    fn split_ring(&mut self, a: usize, b: usize) -> usize {
+        let (a2, b2) = (self.nodes.len(), self.nodes.len() + 1);
+        let (an, bp) = (self.next(a), self.prev(b));
+        let copy = |n: &Node<T>| Node {
+            i: n.i,
+            p: n.p,
+            prev: 0,
+            next: 0,
+        };
+        let (na, nb) = (copy(&self.nodes[a]), copy(&self.nodes[b]));
+        self.nodes.push(na);
+        self.nodes.push(nb);
+        self.nodes[a].next = b;
+        self.nodes[b].prev = a;
+        self.nodes[a2].next = an;
+        self.nodes[an].prev = a2;
+        self.nodes[b2].next = a2;
+        self.nodes[a2].prev = b2;
+        self.nodes[bp].next = b2;
+        self.nodes[b2].prev = bp;
+        b2
+    }
+}
+
+/// Whether `p` is inside or on the counter-clockwise triangle `a b c`
+fn in_triangle<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>, p: Point<T>) -> bool {
+    orient2d(c, a, p) >= 0.0 && orient2d(a, b, p) >= 0.0 && orient2d(b, c, p) >= 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lcg;
+
+    fn assert_covers(polygon: &Polygon, triangles: &[Triangle]) {
+        let total: f64 = triangles.iter().map(|t| t.area()).sum();
+        assert!(
+            (total - polygon.area()).abs() <= 1e-9 * polygon.area(),
+            "{} {}",
+            total,
+            polygon.area()
+        );
+        for t in triangles {
+            assert!(t.signed_area() >= 0.0);
+        }
+    }
+
+    #[test]
+    fn test_concave_outlines() {
+        // A comb, whose teeth leave many reflex corners
+        let mut comb = vec![Point { x: 0.0, y: 0.0 }, Point { x: 10.0, y: 0.0 }];
+        for i in (0..5).rev() {
+            let x = 2.0 * i as f64;
+            comb.extend([
+                Point { x: x + 2.0, y: 5.0 },
+                Point { x: x + 1.5, y: 5.0 },
+                Point { x: x + 1.0, y: 1.0 },
+                Point { x: x + 0.5, y: 5.0 },
+            ]);
+        }
+        comb.push(Point { x: 0.0, y: 5.0 });
+        let comb = Polygon::new(comb);
+        let triangles = comb.triangulate();
+        assert_eq!(comb.vertices().len() - 2, triangles.len());
+        assert_covers(&comb, &triangles);
+        // Clockwise input gives the same counter-clockwise triangles
+        let mut reversed = comb.clone();
+        reversed.reverse();
+        assert_covers(&comb, &reversed.triangulate());
+    }
+
+    #[test]
+    fn test_many_holes() {
+        let mut holes = Vec::new();
+        for i in 0..4 {
+            for j in 0..4 {
+                let (x, y) = (1.0 + 2.0 * i as f64, 1.0 + 2.0 * j as f64);
+                holes.push(vec![
+                    Point { x, y },
+                    Point { x, y: y + 1.0 },
+                    Point {
+                        x: x + 1.0,
+                        y: y + 0.5,
+                    },
+                ]);
+            }
+        }
+        let sheet = Polygon::with_holes(
+            vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 9.0, y: 0.0 },
+                Point { x: 9.0, y: 9.0 },
+                Point { x: 0.0, y: 9.0 },
+            ],
+            holes,
+        );
+        let triangles = sheet.triangulate();
+        // n + 2h - 2 triangles for n vertices and h holes
+        assert_eq!(4 + 16 * 3 + 2 * 16 - 2, triangles.len());
+        assert_covers(&sheet, &triangles);
+        let indices = sheet.triangulate_indices();
+        assert!(indices.iter().flatten().all(|&i| i < 4 + 16 * 3));
+    }
+
+    #[test]
+    fn test_degenerate_vertices() {
+        // Repeated vertices, collinear runs and a hole touching the exterior
+        let poly = Polygon::with_holes(
+            vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 2.0, y: 0.0 },
+                Point { x: 2.0, y: 0.0 },
+                Point { x: 4.0, y: 0.0 },
+                Point { x: 4.0, y: 4.0 },
+                Point { x: 2.0, y: 4.0 },
+                Point { x: 0.0, y: 4.0 },
+                Point { x: 0.0, y: 0.0 },
+            ],
+            vec![vec![
+                Point { x: 1.0, y: 1.0 },
+                Point { x: 1.0, y: 3.0 },
+                Point { x: 4.0, y: 2.0 },
+            ]],
+        );
+        assert_covers(&poly, &poly.triangulate());
+        let sliver = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+        ]);
+        assert!(sliver.triangulate().is_empty());
+        assert!(Polygon::<f64>::new(vec![]).triangulate().is_empty());
+    }
+
+    #[test]
+    fn test_random_stars() {
+        let mut rng = Lcg::new(5);
+        let mut next = || rng.unit();
+        for _ in 0..200 {
+            let n = 3 + (next() * 40.0) as usize;
+            let star: Vec<Point> = (0..n)
+                .map(|i| {
+                    let angle = core::f64::consts::TAU * i as f64 / n as f64;
+                    let r = 1.0 + 9.0 * next();
+                    Point {
+                        x: r * angle.cos(),
+                        y: r * angle.sin(),
+                    }
+                })
+                .collect();
+            let star = Polygon::new(star);
+            let triangles = star.triangulate();
+            assert_eq!(n - 2, triangles.len());
+            assert_covers(&star, &triangles);
+        }
+    }
+}
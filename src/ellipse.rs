@@ -0,0 +1,181 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{Angle, Point, Rect, Scalar};
+
+/// An ellipse, or the region it encloses, centered on `center`. Before rotation the
+/// major axis runs along x and the minor axis along y, and `rotation` turns the
+/// ellipse counter-clockwise about its center. The boundary counts as part of the
+/// ellipse.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ellipse<T = f64> {
+    pub center: Point<T>,
+    pub semi_major: T,
+    pub semi_minor: T,
+    pub rotation: Angle<T>,
+}
+
+impl<T: Scalar> Ellipse<T> {
+    pub fn new(
+        center: Point<T>,
+        semi_major: T,
+        semi_minor: T,
+        rotation: impl Into<Angle<T>>,
+    ) -> Ellipse<T> {
+        Ellipse {
+            center,
+            semi_major,
+            semi_minor,
+            rotation: rotation.into(),
+        }
+    }
+
+    pub fn area(&self) -> T {
+        T::PI() * self.semi_major * self.semi_minor
+    }
+
+    /// The perimeter, using Ramanujan's second approximation. The error is negligible
+    /// except for very eccentric ellipses, and at most 0.04% as they flatten to a line.
+    /// ```
+    /// use rsgeo::{Ellipse, Point};
+    /// let circle = Ellipse::new(Point{x: 0.0, y: 0.0}, 2.0, 2.0, 0.0);
+    /// assert!((circle.perimeter() - 4.0 * std::f64::consts::PI).abs() < 1e-12);
+    /// ```
+    pub fn perimeter(&self) -> T {
+        let (a, b) = (self.semi_major, self.semi_minor);
+        let h = ((a - b) / (a + b)).powi(2);
+        let three = T::from_f64(3.0);
+        T::PI()
+            * (a + b)
+            * (T::one() + three * h / (T::from_f64(10.0) + (T::from_f64(4.0) - three * h).sqrt()))
+    }
+
+    /// The point on the ellipse at parameter `t`, i.e. the image of the point at angle
+    /// `t` on a unit circle. Parameter zero is the end of the major axis.
+    pub fn point_at(&self, t: impl Into<Angle<T>>) -> Point<T> {
+        let (s, c) = t.into().sin_cos();
+        let local = Point {
+            x: self.semi_major * c,
+            y: self.semi_minor * s,
+        };
+        local.rotate(self.rotation) + self.center
+    }
+
+    /// Whether `p` is inside the ellipse or on its boundary
+    /// ```
+    /// use rsgeo::{Angle, Ellipse, Point};
+    /// let e = Ellipse::new(Point{x: 0.0, y: 0.0}, 2.0, 1.0, Angle::from_degrees(90.0));
+    /// assert!(e.contains(Point{x: 0.0, y: 1.9}));
+    /// assert!(!e.contains(Point{x: 1.9, y: 0.0}));
+    /// ```
+    pub fn contains(&self, p: Point<T>) -> bool {
+        let local = (p - self.center).rotate(-self.rotation);
+        let u = local.x / self.semi_major;
+        let v = local.y / self.semi_minor;
+        u * u + v * v <= T::one()
+    }
+
+    /// The smallest axis-aligned rectangle containing the ellipse
+    pub fn bounding_rect(&self) -> Rect<T> {
+        let (s, c) = self.rotation.sin_cos();
+        let (a, b) = (self.semi_major, self.semi_minor);
+        let half = Point {
+            x: (a * a * c * c + b * b * s * s).sqrt(),
+            y: (a * a * s * s + b * b * c * c).sqrt(),
+        };
+        Rect::from_corners(self.center - half, self.center + half)
+    }
+
+    /// Approximate the ellipse by a polygon whose edges stray at most `tolerance` from
+    /// the true curve. The vertices lie on the ellipse, in counter-clockwise order
+    /// starting from `point_at(0)`, and the first vertex is not repeated at the end.
+    /// Panics if `tolerance` is not positive.
+    /// ```
+    /// use rsgeo::{Ellipse, Point};
+    /// let e = Ellipse::new(Point{x: 0.0, y: 0.0}, 10.0, 4.0, 0.0);
+    /// let coarse = e.approximate(0.5);
+    /// let fine = e.approximate(0.01);
+    /// assert!(coarse.len() < fine.len());
+    /// assert_eq!(coarse[0], Point{x: 10.0, y: 0.0});
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn approximate(&self, tolerance: T) -> Vec<Point<T>> {
+        assert!(
+            tolerance > T::zero(),
+            "tolerance must be positive, got {}",
+            tolerance
+        );
+        // The ellipse is the unit circle stretched by at most `semi_major`, so its
+        // chords stray no further than those of a circle of that radius
+        let radius = self.semi_major.max(self.semi_minor);
+        let cos_half_step = (T::one() - tolerance / radius).max(T::zero());
+        let segments = (T::PI() / cos_half_step.acos())
+            .ceil()
+            .max(T::from_f64(4.0));
+        let n = segments.as_f64() as usize;
+        (0..n)
+            .map(|i| self.point_at(T::TAU() * T::from_f64(i as f64) / segments))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_perimeter_matches_known_values() {
+        // An ellipse with a = 5, b = 3 has a perimeter of 25.526998863398...
+        let e: Ellipse = Ellipse::new(Point { x: 0.0, y: 0.0 }, 5.0, 3.0, 0.0);
+        assert!((e.perimeter() - 25.526_998_863_398).abs() < 1e-9);
+        assert!(crate::scalar_isclose(
+            15.0 * core::f64::consts::PI,
+            e.area()
+        ))
+    }
+
+    #[test]
+    fn test_point_at_lies_on_boundary() {
+        let e: Ellipse = Ellipse::new(Point { x: 1.0, y: -2.0 }, 3.0, 1.5, 0.7);
+        for i in 0..16 {
+            let p = e.point_at(f64::from(i) * 0.4);
+            let nudge = (p - e.center) * 1e-6;
+            assert!(e.contains(p - nudge));
+            assert!(!e.contains(p + nudge));
+        }
+    }
+
+    #[test]
+    fn test_bounding_rect_is_tight() {
+        let e: Ellipse = Ellipse::new(
+            Point { x: 0.0, y: 0.0 },
+            4.0,
+            1.0,
+            Angle::from_degrees(30.0),
+        );
+        let rect = e.bounding_rect();
+        let samples: Vec<Point> = (0..10_000)
+            .map(|i| e.point_at(f64::from(i) * core::f64::consts::TAU / 10_000.0))
+            .collect();
+        let sampled = Rect::from_points(samples.iter().copied()).unwrap();
+        assert!(rect.expand(1e-12).contains(sampled.min()));
+        assert!(rect.expand(1e-12).contains(sampled.max()));
+        assert!(sampled.min().isclose_with(rect.min(), 1e-6, 0.0));
+        assert!(sampled.max().isclose_with(rect.max(), 1e-6, 0.0))
+    }
+
+    #[test]
+    fn test_approximate_respects_tolerance() {
+        let e: Ellipse = Ellipse::new(Point { x: 5.0, y: 5.0 }, 8.0, 2.0, 1.2);
+        let tolerance = 0.05;
+        let ring = e.approximate(tolerance);
+        for i in 0..ring.len() {
+            let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+            // The curve bulges furthest from the chord halfway along in parameter
+            let t = core::f64::consts::TAU * (i as f64 + 0.5) / ring.len() as f64;
+            let seg = crate::LineSegment::new(a, b);
+            assert!(seg.distance_to_point(e.point_at(t)) <= tolerance);
+        }
+    }
+}
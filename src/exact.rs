@@ -0,0 +1,248 @@
+//! An exact arithmetic fallback, where coordinates are arbitrary precision rationals.
+//! Every `f64` converts to a rational exactly, so orientation tests and intersection
+//! points computed here are never affected by rounding. This is much slower than the
+//! float code, so it is meant for resolving degenerate or near-degenerate cases.
+//!
+//! Enabled by the `rational` feature.
+
+use std::cmp::Ordering;
+use std::ops::{Add, Sub};
+
+use num_rational::BigRational;
+use num_traits::{ToPrimitive, Zero};
+
+use crate::{Point, Scalar};
+
+/// A point with exact rational coordinates
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RationalPoint {
+    pub x: BigRational,
+    pub y: BigRational,
+}
+
+impl RationalPoint {
+    pub fn new(x: BigRational, y: BigRational) -> RationalPoint {
+        RationalPoint { x, y }
+    }
+
+    /// Convert a float point exactly. Returns None if either coordinate is NaN or
+    /// infinite.
+    /// ```
+    /// use rsgeo::{exact::RationalPoint, Point};
+    /// let p = Point{x: 0.1, y: -2.5};
+    /// let r = RationalPoint::from_point(p).unwrap();
+    /// assert_eq!(r.to_point::<f64>(), p);
+    /// ```
+    pub fn from_point<T: Scalar>(p: Point<T>) -> Option<RationalPoint> {
+        Some(RationalPoint {
+            x: BigRational::from_float(p.x.as_f64())?,
+            y: BigRational::from_float(p.y.as_f64())?,
+        })
+    }
+
+    /// Round the point to the nearest float coordinates
+    pub fn to_point<T: Scalar>(&self) -> Point<T> {
+        let round = |v: &BigRational| T::from_f64(v.to_f64().unwrap_or(f64::NAN));
+        Point {
+            x: round(&self.x),
+            y: round(&self.y),
+        }
+    }
+
+    /// The exact 2D cross product of two vectors
+    pub fn cross(&self, other: &RationalPoint) -> BigRational {
+        &self.x * &other.y - &self.y * &other.x
+    }
+
+    /// The exact dot product of two vectors
+    pub fn dot_product(&self, other: &RationalPoint) -> BigRational {
+        &self.x * &other.x + &self.y * &other.y
+    }
+}
+
+impl Add for &RationalPoint {
+    type Output = RationalPoint;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        RationalPoint::new(&self.x + &rhs.x, &self.y + &rhs.y)
+    }
+}
+
+impl Sub for &RationalPoint {
+    type Output = RationalPoint;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        RationalPoint::new(&self.x - &rhs.x, &self.y - &rhs.y)
+    }
+}
+
+/// The exact orientation of `c` relative to the directed line from `a` to `b`.
+/// `Greater` means `a`, `b`, `c` turn counter-clockwise, `Less` clockwise, and `Equal`
+/// means the three points are exactly collinear.
+pub fn orient(a: &RationalPoint, b: &RationalPoint, c: &RationalPoint) -> Ordering {
+    (b - a).cross(&(c - a)).cmp(&BigRational::zero())
+}
+
+/// Like `orient`, but for float points, which are converted exactly. Panics if any
+/// coordinate is NaN or infinite.
+/// ```
+/// use std::cmp::Ordering;
+/// use rsgeo::Point;
+/// // Nearly collinear, where a naive float determinant can get the sign wrong
+/// let a = Point{x: 0.5, y: 0.5};
+/// let b = Point{x: 12.0, y: 12.0};
+/// let c = Point{x: 24.0, y: 24.0};
+/// assert_eq!(rsgeo::exact::orient_points(a, b, c), Ordering::Equal);
+/// ```
+pub fn orient_points<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>) -> Ordering {
+    let conv = |p| RationalPoint::from_point(p).expect("coordinates must be finite");
+    orient(&conv(a), &conv(b), &conv(c))
+}
+
+/// The exact intersection point of the infinite line through `a1` and `a2` with the
+/// infinite line through `b1` and `b2`. Returns None if the lines are parallel or
+/// coincident, or if either pair of points is identical.
+pub fn line_intersection(
+    a1: &RationalPoint,
+    a2: &RationalPoint,
+    b1: &RationalPoint,
+    b2: &RationalPoint,
+) -> Option<RationalPoint> {
+    let r = a2 - a1;
+    let s = b2 - b1;
+    let denom = r.cross(&s);
+    if denom.is_zero() {
+        return None;
+    }
+    let t = (b1 - a1).cross(&s) / denom;
+    Some(RationalPoint::new(&a1.x + &r.x * &t, &a1.y + &r.y * &t))
+}
+
+/// Whether the closed segments `a1`-`a2` and `b1`-`b2` share at least one point,
+/// including touching at an endpoint and collinear overlaps
+pub fn segments_intersect(
+    a1: &RationalPoint,
+    a2: &RationalPoint,
+    b1: &RationalPoint,
+    b2: &RationalPoint,
+) -> bool {
+    let d1 = orient(b1, b2, a1);
+    let d2 = orient(b1, b2, a2);
+    let d3 = orient(a1, a2, b1);
+    let d4 = orient(a1, a2, b2);
+
+    let straddles = |u: Ordering, v: Ordering| u != Ordering::Equal && u == v.reverse();
+    if straddles(d1, d2) && straddles(d3, d4) {
+        return true;
+    }
+    (d1 == Ordering::Equal && on_segment(b1, b2, a1))
+        || (d2 == Ordering::Equal && on_segment(b1, b2, a2))
+        || (d3 == Ordering::Equal && on_segment(a1, a2, b1))
+        || (d4 == Ordering::Equal && on_segment(a1, a2, b2))
+}
+
+/// The exact point where the closed segments `a1`-`a2` and `b1`-`b2` meet. Returns None
+/// when they do not intersect, and also when they are collinear and overlap, since then
+/// there is no single intersection point.
+pub fn segment_intersection(
+    a1: &RationalPoint,
+    a2: &RationalPoint,
+    b1: &RationalPoint,
+    b2: &RationalPoint,
+) -> Option<RationalPoint> {
+    if !segments_intersect(a1, a2, b1, b2) {
+        return None;
+    }
+    if let Some(p) = line_intersection(a1, a2, b1, b2) {
+        return Some(p);
+    }
+    // The segments are collinear (or degenerate), so they meet in a single point only
+    // if every endpoint lying on the other segment is the same point
+    let shared: Vec<&RationalPoint> = [a1, a2]
+        .iter()
+        .filter(|p| on_segment(b1, b2, p))
+        .chain([b1, b2].iter().filter(|p| on_segment(a1, a2, p)))
+        .copied()
+        .collect();
+    let first = shared.first()?;
+    if shared.iter().all(|p| p == first) {
+        Some((*first).clone())
+    } else {
+        None
+    }
+}
+
+/// Whether `p`, known to be collinear with `a` and `b`, lies within the bounding box of
+/// the segment `a`-`b`
+fn on_segment(a: &RationalPoint, b: &RationalPoint, p: &RationalPoint) -> bool {
+    let within = |lo: &BigRational, hi: &BigRational, v: &BigRational| {
+        let (lo, hi) = if lo <= hi { (lo, hi) } else { (hi, lo) };
+        lo <= v && v <= hi
+    };
+    within(&a.x, &b.x, &p.x) && within(&a.y, &b.y, &p.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rp(x: f64, y: f64) -> RationalPoint {
+        RationalPoint::from_point(Point { x, y }).unwrap()
+    }
+
+    #[test]
+    fn test_orient_nearly_collinear() {
+        // Classic example where the float determinant gives an inconsistent sign
+        let a = rp(0.5, 0.5);
+        let b = rp(12.0, 12.0);
+        let c = rp(0.5 + f64::EPSILON, 0.5);
+        assert_eq!(Ordering::Less, orient(&a, &b, &c));
+        assert_eq!(Ordering::Greater, orient(&b, &a, &c))
+    }
+
+    #[test]
+    fn test_line_intersection_is_exact() {
+        let p = line_intersection(&rp(0.0, 0.0), &rp(3.0, 1.0), &rp(0.0, 1.0), &rp(3.0, 0.0));
+        let p = p.unwrap();
+        assert_eq!(BigRational::new(3.into(), 2.into()), p.x);
+        assert_eq!(BigRational::new(1.into(), 2.into()), p.y)
+    }
+
+    #[test]
+    fn test_parallel_lines_do_not_intersect() {
+        let p = line_intersection(&rp(0.0, 0.0), &rp(1.0, 1.0), &rp(0.0, 1.0), &rp(1.0, 2.0));
+        assert_eq!(None, p)
+    }
+
+    #[test]
+    fn test_segments_touching_at_endpoint() {
+        let (a1, a2) = (rp(0.0, 0.0), rp(1.0, 0.0));
+        let (b1, b2) = (rp(1.0, 0.0), rp(2.0, 5.0));
+        assert!(segments_intersect(&a1, &a2, &b1, &b2));
+        assert_eq!(Some(rp(1.0, 0.0)), segment_intersection(&a1, &a2, &b1, &b2))
+    }
+
+    #[test]
+    fn test_collinear_segments() {
+        let (a1, a2) = (rp(0.0, 0.0), rp(2.0, 0.0));
+        // Overlapping: intersect, but there is no single point
+        let (b1, b2) = (rp(1.0, 0.0), rp(3.0, 0.0));
+        assert!(segments_intersect(&a1, &a2, &b1, &b2));
+        assert_eq!(None, segment_intersection(&a1, &a2, &b1, &b2));
+        // End to end: a single shared point
+        let (c1, c2) = (rp(2.0, 0.0), rp(3.0, 0.0));
+        assert_eq!(Some(rp(2.0, 0.0)), segment_intersection(&a1, &a2, &c1, &c2));
+        // Disjoint
+        let (d1, d2) = (rp(2.5, 0.0), rp(3.0, 0.0));
+        assert!(!segments_intersect(&a1, &a2, &d1, &d2))
+    }
+
+    #[test]
+    fn test_crossing_and_disjoint_segments() {
+        let (a1, a2) = (rp(0.0, 0.0), rp(2.0, 2.0));
+        let (b1, b2) = (rp(0.0, 2.0), rp(2.0, 0.0));
+        assert_eq!(Some(rp(1.0, 1.0)), segment_intersection(&a1, &a2, &b1, &b2));
+        let (c1, c2) = (rp(3.0, 0.0), rp(3.0, 5.0));
+        assert!(!segments_intersect(&a1, &a2, &c1, &c2))
+    }
+}
@@ -0,0 +1,170 @@
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+use crate::{Circle, LineSegment, MultiPoint, MultiPolygon, Point, Polygon, Polyline, Rect};
+
+/// Any one of the crate's geometry types, so that shapes of different kinds can be
+/// stored together and matched on
+/// ```
+/// use rsgeo::{Circle, Geometry, Point, Rect};
+/// let shapes: Vec<Geometry> = vec![
+///     Point{x: 1.0, y: 2.0}.into(),
+///     Circle::new(Point{x: 0.0, y: 0.0}, 1.0).into(),
+///     Rect::from_corners(Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 1.0}).into(),
+/// ];
+/// let circles = shapes.iter().filter(|g| matches!(g, Geometry::Circle(_))).count();
+/// assert_eq!(circles, 1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Geometry<T = f64> {
+    Point(Point<T>),
+    LineSegment(LineSegment<T>),
+    Polyline(Polyline<T>),
+    Polygon(Polygon<T>),
+    MultiPolygon(MultiPolygon<T>),
+    Circle(Circle<T>),
+    Rect(Rect<T>),
+    MultiPoint(MultiPoint<T>),
+    GeometryCollection(GeometryCollection<T>),
+}
+
+/// A heterogeneous collection of geometries, which may itself contain collections
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeometryCollection<T = f64> {
+    geometries: Vec<Geometry<T>>,
+}
+
+impl<T> GeometryCollection<T> {
+    pub fn new(geometries: Vec<Geometry<T>>) -> GeometryCollection<T> {
+        GeometryCollection { geometries }
+    }
+
+    pub fn geometries(&self) -> &[Geometry<T>] {
+        &self.geometries
+    }
+
+    pub fn push(&mut self, geometry: impl Into<Geometry<T>>) {
+        self.geometries.push(geometry.into());
+    }
+
+    pub fn len(&self) -> usize {
+        self.geometries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.geometries.is_empty()
+    }
+
+    /// Iterate over the geometries that are not collections, descending into nested
+    /// collections depth first
+    /// ```
+    /// use rsgeo::{Geometry, GeometryCollection, Point};
+    /// let mut inner = GeometryCollection::default();
+    /// inner.push(Point{x: 1.0, y: 1.0});
+    /// let mut outer = GeometryCollection::default();
+    /// outer.push(Point{x: 0.0, y: 0.0});
+    /// outer.push(inner);
+    /// assert_eq!(outer.len(), 2);
+    /// assert_eq!(outer.flatten().count(), 2);
+    /// assert!(outer.flatten().all(|g| matches!(g, Geometry::Point(_))));
+    /// ```
+    pub fn flatten(&self) -> impl Iterator<Item = &Geometry<T>> + '_ {
+        let mut stack: Vec<core::slice::Iter<'_, Geometry<T>>> =
+            alloc::vec![self.geometries.iter()];
+        core::iter::from_fn(move || loop {
+            let next = stack.last_mut()?.next();
+            match next {
+                Some(Geometry::GeometryCollection(c)) => stack.push(c.geometries.iter()),
+                Some(g) => return Some(g),
+                None => {
+                    stack.pop();
+                }
+            }
+        })
+    }
+}
+
+impl<T> FromIterator<Geometry<T>> for GeometryCollection<T> {
+    fn from_iter<I: IntoIterator<Item = Geometry<T>>>(iter: I) -> Self {
+        GeometryCollection::new(iter.into_iter().collect())
+    }
+}
+
+macro_rules! impl_into_geometry {
+    ($($variant:ident),*) => {
+        $(
+            impl<T> From<$variant<T>> for Geometry<T> {
+                fn from(g: $variant<T>) -> Self {
+                    Geometry::$variant(g)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_geometry!(
+    Point,
+    LineSegment,
+    Polyline,
+    Polygon,
+    MultiPolygon,
+    Circle,
+    Rect,
+    MultiPoint,
+    GeometryCollection
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_and_match() {
+        let seg: LineSegment = "(0, 0) -> (1, 1)".parse().unwrap();
+        let line: Polyline = vec![Point { x: 0.0, y: 0.0 }, Point { x: 2.0, y: 0.0 }]
+            .into_iter()
+            .collect();
+        let pts: MultiPoint = vec![Point { x: 5.0, y: 5.0 }; 3].into_iter().collect();
+        let all: GeometryCollection = vec![seg.into(), line.clone().into(), pts.into()]
+            .into_iter()
+            .collect();
+        assert_eq!(3, all.len());
+        let lengths: Vec<usize> = all
+            .geometries()
+            .iter()
+            .map(|g| match g {
+                Geometry::Polyline(p) => p.points().len(),
+                Geometry::MultiPoint(m) => m.points().len(),
+                _ => 0,
+            })
+            .collect();
+        assert_eq!(vec![0, 2, 3], lengths);
+        assert_eq!(Geometry::Polyline(line), all.geometries()[1])
+    }
+
+    #[test]
+    fn test_flatten_nested_collections() {
+        let mut empty = GeometryCollection::<f64>::default();
+        assert_eq!(0, empty.flatten().count());
+        empty.push(GeometryCollection::default());
+        assert_eq!(0, empty.flatten().count());
+        let mut deep = GeometryCollection::default();
+        deep.push(Point { x: 2.0, y: 0.0 });
+        let mut middle = GeometryCollection::default();
+        middle.push(deep);
+        middle.push(Point { x: 3.0, y: 0.0 });
+        let mut top = GeometryCollection::default();
+        top.push(Point { x: 1.0, y: 0.0 });
+        top.push(middle);
+        let xs: Vec<f64> = top
+            .flatten()
+            .map(|g| match g {
+                Geometry::Point(p) => p.x,
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(vec![1.0, 2.0, 3.0], xs)
+    }
+}
@@ -0,0 +1,216 @@
+use alloc::vec::Vec;
+
+use crate::{Point, Rect, Scalar};
+
+/// Values sampled at the points of a square grid, such as a raster of heights. The
+/// values are stored a row at a time, each row running in x from its first point, and
+/// the rows running up in y from the one through `origin`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T = f64> {
+    origin: Point<T>,
+    spacing: T,
+    columns: usize,
+    rows: usize,
+    values: Vec<T>,
+}
+
+/// Where the points of a grid are, without any values at them: `columns` by `rows`
+/// points `spacing` apart, in rows running in x from the one through `origin`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridSpec<T = f64> {
+    pub origin: Point<T>,
+    pub spacing: T,
+    pub columns: usize,
+    pub rows: usize,
+}
+
+impl<T: Scalar> GridSpec<T> {
+    /// The fewest points `spacing` apart from the bottom left corner of `bounds` that
+    /// reach all of it, running past the top and right sides if they are not a whole
+    /// number of steps away. Panics if `spacing` is not positive.
+    /// ```
+    /// use rsgeo::{GridSpec, Point, Rect};
+    /// let bounds = Rect::from_corners(Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 1.2});
+    /// let spec = GridSpec::covering(bounds, 0.5);
+    /// assert_eq!((spec.columns, spec.rows), (5, 4));
+    /// ```
+    pub fn covering(bounds: Rect<T>, spacing: T) -> GridSpec<T> {
+        assert!(
+            spacing > T::zero(),
+            "spacing must be positive, got {}",
+            spacing
+        );
+        let steps = |length: T| (length / spacing).ceil().as_f64() as usize + 1;
+        GridSpec {
+            origin: bounds.min(),
+            spacing,
+            columns: steps(bounds.width()),
+            rows: steps(bounds.height()),
+        }
+    }
+}
+
+impl<T: Scalar> Grid<T> {
+    /// The grid of `columns` by `rows` points `spacing` apart, taking `values` a row
+    /// at a time from the one through `origin`. Panics if `spacing` is not positive or
+    /// there is not one value for each point.
+    pub fn new(
+        origin: Point<T>,
+        spacing: T,
+        columns: usize,
+        rows: usize,
+        values: Vec<T>,
+    ) -> Grid<T> {
+        assert!(
+            spacing > T::zero(),
+            "spacing must be positive, got {}",
+            spacing
+        );
+        assert!(
+            values.len() == columns * rows,
+            "values must have one entry per point, got {} for {} points",
+            values.len(),
+            columns * rows
+        );
+        Grid {
+            origin,
+            spacing,
+            columns,
+            rows,
+            values,
+        }
+    }
+
+    /// The grid of `columns` by `rows` points `spacing` apart from `origin`, taking
+    /// the value of `f` at each. Panics if `spacing` is not positive.
+    /// ```
+    /// use rsgeo::{Grid, Point};
+    /// let grid = Grid::from_fn(Point{x: 1.0, y: 0.0}, 0.5, 3, 2, |p| p.x + 10.0 * p.y);
+    /// assert_eq!(grid.values(), &[1.0, 1.5, 2.0, 6.0, 6.5, 7.0]);
+    /// assert_eq!(grid.point(2, 1), Point{x: 2.0, y: 0.5});
+    /// ```
+    pub fn from_fn(
+        origin: Point<T>,
+        spacing: T,
+        columns: usize,
+        rows: usize,
+        mut f: impl FnMut(Point<T>) -> T,
+    ) -> Grid<T> {
+        let mut grid = Grid::new(origin, spacing, 0, 0, Vec::new());
+        grid.columns = columns;
+        grid.rows = rows;
+        grid.values = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| (column, row)))
+            .map(|(column, row)| f(grid.point(column, row)))
+            .collect();
+        grid
+    }
+
+    /// Where the points of the grid are
+    pub fn spec(&self) -> GridSpec<T> {
+        GridSpec {
+            origin: self.origin,
+            spacing: self.spacing,
+            columns: self.columns,
+            rows: self.rows,
+        }
+    }
+
+    /// The first point of the first row
+    pub fn origin(&self) -> Point<T> {
+        self.origin
+    }
+
+    /// The distance between neighboring points
+    pub fn spacing(&self) -> T {
+        self.spacing
+    }
+
+    /// The number of points in each row
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// The number of rows
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The value at each point, a row at a time
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The value at the point in `column` of `row`. Panics if there is no such point.
+    pub fn get(&self, column: usize, row: usize) -> T {
+        assert!(
+            column < self.columns && row < self.rows,
+            "no point at column {} of row {} in a grid of {} by {}",
+            column,
+            row,
+            self.columns,
+            self.rows
+        );
+        self.values[row * self.columns + column]
+    }
+
+    /// Where the point in `column` of `row` is, which need not be on the grid
+    pub fn point(&self, column: usize, row: usize) -> Point<T> {
+        let step = |i: usize| T::from_f64(i as f64) * self.spacing;
+        Point {
+            x: self.origin.x + step(column),
+            y: self.origin.y + step(row),
+        }
+    }
+
+    /// The smallest rectangle holding every point, or None if there are none
+    pub fn bounds(&self) -> Option<Rect<T>> {
+        match self.columns * self.rows {
+            0 => None,
+            _ => Some(Rect::from_corners(
+                self.origin,
+                self.point(self.columns - 1, self.rows - 1),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout() {
+        let grid = Grid::new(
+            Point { x: -1.0, y: 2.0 },
+            2.0,
+            2,
+            3,
+            vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0],
+        );
+        assert_eq!(3.0, grid.get(1, 1));
+        assert_eq!(4.0, grid.get(0, 2));
+        assert_eq!(Point { x: 1.0, y: 6.0 }, grid.point(1, 2));
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(
+            (Point { x: -1.0, y: 2.0 }, Point { x: 1.0, y: 6.0 }),
+            (bounds.min(), bounds.max())
+        );
+        assert_eq!(
+            None,
+            Grid::<f64>::new(Point::origin(), 1.0, 4, 0, vec![]).bounds()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_values_must_match() {
+        Grid::new(Point::origin(), 1.0, 2, 2, vec![0.0; 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_out_of_range() {
+        Grid::from_fn(Point::origin(), 1.0, 2, 2, |p| p.x).get(2, 0);
+    }
+}
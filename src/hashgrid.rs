@@ -0,0 +1,350 @@
+use alloc::vec::Vec;
+
+use crate::{BoundingRect, Intersects, LineSegment, Point, Polygon, Rect, Scalar};
+
+/// The column and row of a cell, counted in cells from the origin
+type Cell = (i64, i64);
+
+/// A spatial hash: the plane cut into square cells of a fixed size, with each point
+/// or segment listed under every cell it meets, in a hash table of cells that only
+/// has room for those in use. Adding an item takes time in proportion to the number
+/// of cells it meets, and `clear` keeps the table's memory for the next use, so a
+/// scene that moves can be rebuilt from scratch each step more cheaply than a tree.
+/// Queries look only at the cells they cover, which works best with cells about as
+/// big as the items and the distances asked about.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HashGrid<T = f64, D = usize> {
+    cell_size: T,
+    /// Each item as a segment, with points as segments of no length
+    items: Vec<(LineSegment<T>, D)>,
+    /// The cells in use and the items listed under them, in as many buckets as a
+    /// power of two
+    buckets: Vec<Vec<(Cell, usize)>>,
+    listed: usize,
+}
+
+impl<T: Scalar, D> HashGrid<T, D> {
+    /// An empty grid of cells `cell_size` across. Panics if `cell_size` is not
+    /// positive.
+    /// ```
+    /// use rsgeo::{HashGrid, LineSegment, Point, Rect};
+    /// let mut grid = HashGrid::new(1.0);
+    /// grid.insert_point(Point{x: 0.5, y: 0.5}, "a");
+    /// grid.insert_point(Point{x: 3.5, y: 0.5}, "b");
+    /// grid.insert_segment(LineSegment::new(Point{x: 0.5, y: 2.5}, Point{x: 4.5, y: 2.5}), "c");
+    /// assert_eq!(grid.cell_of(Point{x: 3.5, y: -0.1}), (3, -1));
+    /// assert_eq!(grid.query_cell((2, 2)).collect::<Vec<_>>(), vec![&"c"]);
+    /// let rect = Rect::from_corners(Point{x: 3.0, y: 0.0}, Point{x: 4.0, y: 3.0});
+    /// assert_eq!(grid.query_rect(&rect), vec![&"b", &"c"]);
+    /// ```
+    pub fn new(cell_size: T) -> HashGrid<T, D> {
+        assert!(
+            cell_size > T::zero(),
+            "cell size must be positive, got {}",
+            cell_size
+        );
+        HashGrid {
+            cell_size,
+            items: Vec::new(),
+            buckets: (0..64).map(|_| Vec::new()).collect(),
+            listed: 0,
+        }
+    }
+
+    /// The size of each cell
+    pub fn cell_size(&self) -> T {
+        self.cell_size
+    }
+
+    /// The number of items in the grid
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether the grid has no items
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Take out every item, keeping the memory the grid has taken up so far
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.buckets.iter_mut().for_each(Vec::clear);
+        self.listed = 0;
+    }
+
+    /// The column and row of the cell holding `p`, where points on the line between
+    /// two cells belong to the one above or to the right
+    pub fn cell_of(&self, p: Point<T>) -> Cell {
+        let index = |v: T| (v / self.cell_size).floor().as_f64() as i64;
+        (index(p.x), index(p.y))
+    }
+
+    /// The square that `cell` covers
+    pub fn cell_rect(&self, cell: Cell) -> Rect<T> {
+        let corner = |c: i64, r: i64| Point {
+            x: T::from_f64(c as f64) * self.cell_size,
+            y: T::from_f64(r as f64) * self.cell_size,
+        };
+        Rect::from_corners(corner(cell.0, cell.1), corner(cell.0 + 1, cell.1 + 1))
+    }
+
+    /// Add `p` to the grid with its payload
+    pub fn insert_point(&mut self, p: Point<T>, payload: D) {
+        let item = self.items.len();
+        self.items.push((LineSegment::new(p, p), payload));
+        self.list(self.cell_of(p), item);
+    }
+
+    /// Add `segment` to the grid with its payload, listing it under every cell it
+    /// passes through
+    pub fn insert_segment(&mut self, segment: LineSegment<T>, payload: D) {
+        let item = self.items.len();
+        self.items.push((segment, payload));
+        let (lo, hi) = (self.cell_of(segment.p1()), self.cell_of(segment.p2()));
+        for row in lo.1.min(hi.1)..=lo.1.max(hi.1) {
+            for column in lo.0.min(hi.0)..=lo.0.max(hi.0) {
+                let cell = (column, row);
+                if self.cell_rect(cell).clip_segment(&segment).is_some() {
+                    self.list(cell, item);
+                }
+            }
+        }
+    }
+
+    /// The payloads of the items listed under `cell`, in the order they were added
+    pub fn query_cell(&self, cell: Cell) -> impl Iterator<Item = &D> + '_ {
+        self.buckets[self.bucket(cell)]
+            .iter()
+            .filter(move |entry| entry.0 == cell)
+            .map(move |entry| &self.items[entry.1].1)
+    }
+
+    /// The payloads of the items listed under `cell` and the eight cells around it.
+    /// A segment passing through several of them is given once for each.
+    pub fn query_neighbors(&self, cell: Cell) -> impl Iterator<Item = &D> + '_ {
+        (-1..=1)
+            .flat_map(move |dy| (-1..=1).map(move |dx| (cell.0 + dx, cell.1 + dy)))
+            .flat_map(move |c| self.query_cell(c))
+    }
+
+    /// The payloads of the items that meet `rect`, including those that only touch
+    /// it, each once and in the order they were added
+    pub fn query_rect(&self, rect: &Rect<T>) -> Vec<&D> {
+        self.candidates(rect)
+            .into_iter()
+            .filter(|&i| rect.clip_segment(&self.items[i].0).is_some())
+            .map(|i| &self.items[i].1)
+            .collect()
+    }
+
+    /// The payloads of the items that meet `rect`, including those that only touch
+    /// it, each once and in the order they were added. This is `query_rect` under the
+    /// name it shares with the other spatial indexes.
+    pub fn range_query(&self, rect: &Rect<T>) -> Vec<&D> {
+        self.query_rect(rect)
+    }
+
+    /// The payloads of the items that meet `polygon`, including those that only touch
+    /// it, each once and in the order they were added. Only the items listed under the
+    /// cells the bounding rectangle of the polygon covers are tested against the
+    /// polygon itself.
+    /// ```
+    /// use rsgeo::{HashGrid, LineSegment, Point, Polygon};
+    /// let mut grid = HashGrid::new(1.0);
+    /// grid.insert_point(Point{x: 0.5, y: 0.5}, "a");
+    /// grid.insert_point(Point{x: 3.5, y: 3.5}, "b");
+    /// grid.insert_segment(LineSegment::new(Point{x: 3.0, y: 3.0}, Point{x: 3.0, y: 0.5}), "c");
+    /// let triangle = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 0.0, y: 4.0},
+    /// ]);
+    /// assert_eq!(grid.range_query_polygon(&triangle), vec![&"a", &"c"]);
+    /// ```
+    pub fn range_query_polygon(&self, polygon: &Polygon<T>) -> Vec<&D> {
+        let bounds = match polygon.bounding_rect() {
+            Some(rect) => rect,
+            None => return Vec::new(),
+        };
+        self.candidates(&bounds)
+            .into_iter()
+            .filter(|&i| self.items[i].0.intersects(polygon))
+            .map(|i| &self.items[i].1)
+            .collect()
+    }
+
+    /// The items listed under the cells `rect` covers, each once and in the order they
+    /// were added
+    fn candidates(&self, rect: &Rect<T>) -> Vec<usize> {
+        let (lo, hi) = (self.cell_of(rect.min()), self.cell_of(rect.max()));
+        let cells = (hi.0 - lo.0 + 1).saturating_mul(hi.1 - lo.1 + 1);
+        // Over more cells than are in use it is quicker to check every item
+        if (cells as usize) >= self.listed {
+            return (0..self.items.len()).collect();
+        }
+        let mut found = Vec::new();
+        for row in lo.1..=hi.1 {
+            for column in lo.0..=hi.0 {
+                let cell = (column, row);
+                let bucket = &self.buckets[self.bucket(cell)];
+                found.extend(bucket.iter().filter(|e| e.0 == cell).map(|e| e.1));
+            }
+        }
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    /// List `item` under `cell`, making the table bigger once it is well used
+    fn list(&mut self, cell: Cell, item: usize) {
+        if self.listed >= self.buckets.len() {
+            let mut buckets: Vec<Vec<(Cell, usize)>> =
+                (0..2 * self.buckets.len()).map(|_| Vec::new()).collect();
+            let old = core::mem::take(&mut self.buckets);
+            let mask = buckets.len() - 1;
+            for entry in old.into_iter().flatten() {
+                buckets[hash(entry.0) & mask].push(entry);
+            }
+            self.buckets = buckets;
+        }
+        let bucket = self.bucket(cell);
+        self.buckets[bucket].push((cell, item));
+        self.listed += 1;
+    }
+
+    fn bucket(&self, cell: Cell) -> usize {
+        hash(cell) & (self.buckets.len() - 1)
+    }
+}
+
+/// A hash of a cell that spreads neighboring cells across the table
+fn hash(cell: Cell) -> usize {
+    let h = (cell.0 as u64).wrapping_mul(0x9e3779b97f4a7c15)
+        ^ (cell.1 as u64).wrapping_mul(0xc2b2ae3d27d4eb4f);
+    (h ^ (h >> 29)) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Scattered points moved to lie around the origin, so that some are in cells with
+    /// negative indices
+    fn centered(n: usize, seed: u64) -> Vec<Point> {
+        crate::scattered(n, seed)
+            .into_iter()
+            .map(|p| p * 2.0 - Point { x: 10.0, y: 10.0 })
+            .collect()
+    }
+
+    #[test]
+    fn test_rect_queries_match_brute_force() {
+        let points = centered(2000, 3);
+        let ends = centered(400, 8);
+        let segments: Vec<LineSegment> = ends
+            .chunks(2)
+            .map(|pair| LineSegment::new(pair[0], pair[0] + (pair[1] - pair[0]) * 0.2))
+            .collect();
+        let mut grid = HashGrid::new(0.75);
+        for (i, &p) in points.iter().enumerate() {
+            grid.insert_point(p, i);
+        }
+        for (i, &s) in segments.iter().enumerate() {
+            grid.insert_segment(s, points.len() + i);
+        }
+        assert_eq!(2200, grid.len());
+        let corners = centered(60, 4);
+        for pair in corners.chunks(2) {
+            let rect = Rect::from_corners(pair[0], pair[1]);
+            let found: Vec<usize> = grid.query_rect(&rect).into_iter().copied().collect();
+            let expected: Vec<usize> = (0..points.len())
+                .filter(|&i| rect.contains(points[i]))
+                .chain(
+                    (0..segments.len())
+                        .filter(|&i| rect.clip_segment(&segments[i]).is_some())
+                        .map(|i| points.len() + i),
+                )
+                .collect();
+            assert_eq!(expected, found);
+        }
+    }
+
+    #[test]
+    fn test_polygon_queries_match_brute_force() {
+        let points = centered(1500, 13);
+        let ends = centered(200, 14);
+        let segments: Vec<LineSegment> = ends
+            .chunks(2)
+            .map(|pair| LineSegment::new(pair[0], pair[0] + (pair[1] - pair[0]) * 0.2))
+            .collect();
+        let mut grid = HashGrid::new(0.5);
+        for (i, &p) in points.iter().enumerate() {
+            grid.insert_point(p, i);
+        }
+        for (i, &s) in segments.iter().enumerate() {
+            grid.insert_segment(s, points.len() + i);
+        }
+        let corners = centered(30, 15);
+        for tri in corners.chunks(3) {
+            let triangle = Polygon::new(tri.to_vec());
+            let found: Vec<usize> = grid
+                .range_query_polygon(&triangle)
+                .into_iter()
+                .copied()
+                .collect();
+            let expected: Vec<usize> = (0..points.len())
+                .filter(|&i| triangle.contains(points[i]))
+                .chain(
+                    (0..segments.len())
+                        .filter(|&i| segments[i].intersects(&triangle))
+                        .map(|i| points.len() + i),
+                )
+                .collect();
+            assert_eq!(expected, found);
+        }
+        let bounds = Rect::from_corners(corners[0], corners[1]);
+        assert_eq!(grid.query_rect(&bounds), grid.range_query(&bounds));
+        assert!(grid.range_query_polygon(&Polygon::new(vec![])).is_empty());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let points = centered(500, 6);
+        let mut grid = HashGrid::new(1.0);
+        for (i, &p) in points.iter().enumerate() {
+            grid.insert_point(p, i);
+        }
+        // Everything within a cell's width of a point is in the cells around its own
+        for (i, &p) in points.iter().enumerate() {
+            let near: Vec<usize> = grid.query_neighbors(grid.cell_of(p)).copied().collect();
+            assert!(near.contains(&i));
+            for (j, &q) in points.iter().enumerate() {
+                if p.distance(&q) < 1.0 {
+                    assert!(near.contains(&j));
+                }
+            }
+            let cell = grid.cell_of(p);
+            assert!(grid
+                .query_cell(cell)
+                .all(|&j| grid.cell_of(points[j]) == cell));
+        }
+    }
+
+    #[test]
+    fn test_rebuild() {
+        let mut grid = HashGrid::new(0.5);
+        for step in 0..3 {
+            grid.clear();
+            for (i, &p) in centered(300, step).iter().enumerate() {
+                grid.insert_point(p, i);
+            }
+            assert_eq!(300, grid.len());
+            let all = Rect::from_corners(Point { x: -10.0, y: -10.0 }, Point { x: 10.0, y: 10.0 });
+            assert_eq!(300, grid.query_rect(&all).len());
+        }
+        let buckets = grid.buckets.len();
+        grid.clear();
+        assert!(grid.is_empty());
+        assert_eq!(buckets, grid.buckets.len());
+        assert_eq!(None, grid.query_cell((0, 0)).next());
+    }
+}
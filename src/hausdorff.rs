@@ -0,0 +1,409 @@
+use alloc::vec::Vec;
+
+use num_traits::Float;
+
+use crate::bezier::roots_between;
+use crate::polygon::ring_edges;
+use crate::{LineSegment, MultiPoint, Point, Polygon, Polyline, Rect, Scalar};
+
+/// The Hausdorff distance between point sets, polylines and polygons, which is how far
+/// a point on one shape can be from the nearest point on the other. Polylines are
+/// compared along their whole length rather than only at their vertices, and polygons
+/// by their outlines, including the outlines of any holes.
+pub trait Hausdorff<T: Scalar, Rhs = Self> {
+    /// The largest distance from a point of `self` to the nearest point of `other`,
+    /// found exactly. Returns None if either shape is empty.
+    /// ```
+    /// use rsgeo::{Hausdorff, Point, Polyline};
+    /// let a = Polyline::new(vec![Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}]);
+    /// // The far end of `a` is farthest from `b`, which lies along its other end
+    /// let b = Polyline::new(vec![Point{x: 0.0, y: 1.0}, Point{x: 0.0, y: 0.0}]);
+    /// assert_eq!(a.directed_hausdorff(&b), Some(4.0));
+    /// assert_eq!(b.directed_hausdorff(&a), Some(1.0));
+    /// ```
+    fn directed_hausdorff(&self, other: &Rhs) -> Option<T>;
+
+    /// The larger of the directed distances each way, found exactly. Returns None if
+    /// either shape is empty.
+    fn hausdorff(&self, other: &Rhs) -> Option<T>;
+
+    /// An approximation of `hausdorff` that measures only from points no more than
+    /// `spacing` apart along each shape, and finds the nearest piece of the other shape
+    /// with a grid. The result is never more than the exact distance, and is less by at
+    /// most half of `spacing`. Panics if `spacing` is not positive.
+    fn hausdorff_approx(&self, other: &Rhs, spacing: T) -> Option<T>;
+}
+
+/// The pieces a shape is measured along, with each point as a segment of zero length
+trait AsPieces<T: Scalar> {
+    fn pieces(&self) -> Vec<LineSegment<T>>;
+}
+
+impl<T: Scalar> AsPieces<T> for MultiPoint<T> {
+    fn pieces(&self) -> Vec<LineSegment<T>> {
+        self.points()
+            .iter()
+            .map(|&p| LineSegment::new(p, p))
+            .collect()
+    }
+}
+
+impl<T: Scalar> AsPieces<T> for Polyline<T> {
+    fn pieces(&self) -> Vec<LineSegment<T>> {
+        match self.points() {
+            &[p] => alloc::vec![LineSegment::new(p, p)],
+            _ => self.segments().collect(),
+        }
+    }
+}
+
+impl<T: Scalar> AsPieces<T> for Polygon<T> {
+    fn pieces(&self) -> Vec<LineSegment<T>> {
+        self.rings().flat_map(ring_edges).collect()
+    }
+}
+
+/// A quadratic `a t² + b t + c`, as `[a, b, c]`, that holds for `t` in a range
+type Quadratic<T> = (T, T, [T; 3]);
+
+/// The squared distance from `s.p1() + t * s.direction()` to `e`, as up to three
+/// quadratics in `t`: near `e.p1()`, alongside `e`, and near `e.p2()`
+fn squared_distance_pieces<T: Scalar>(
+    s: &LineSegment<T>,
+    e: &LineSegment<T>,
+) -> [Option<Quadratic<T>>; 3] {
+    let (p, d) = (s.p1(), s.direction());
+    let to_point = |q: Point<T>| {
+        let f = p - q;
+        [
+            d.dot_product(&d),
+            T::from_f64(2.0) * f.dot_product(&d),
+            f.dot_product(&f),
+        ]
+    };
+    let len2 = e.length_squared();
+    let everywhere = (T::neg_infinity(), T::infinity());
+    if len2 == T::zero() {
+        return [
+            Some((everywhere.0, everywhere.1, to_point(e.p1()))),
+            None,
+            None,
+        ];
+    }
+    let dir = e.direction();
+    let f = p - e.p1();
+    let (c0, c1) = (dir.cross(&f), dir.cross(&d));
+    let alongside = [
+        c1 * c1 / len2,
+        T::from_f64(2.0) * c0 * c1 / len2,
+        c0 * c0 / len2,
+    ];
+    // The position of the nearest point along `e`, before clamping to its ends
+    let (u0, u1) = (f.dot_product(&dir) / len2, d.dot_product(&dir) / len2);
+    if u1 == T::zero() {
+        let piece = if u0 <= T::zero() {
+            to_point(e.p1())
+        } else if u0 >= T::one() {
+            to_point(e.p2())
+        } else {
+            alongside
+        };
+        return [Some((everywhere.0, everywhere.1, piece)), None, None];
+    }
+    let (t_start, t_end) = (-u0 / u1, (T::one() - u0) / u1);
+    let (lo, hi, before, after) = if u1 > T::zero() {
+        (t_start, t_end, e.p1(), e.p2())
+    } else {
+        (t_end, t_start, e.p2(), e.p1())
+    };
+    [
+        Some((everywhere.0, lo, to_point(before))),
+        Some((lo, hi, alongside)),
+        Some((hi, everywhere.1, to_point(after))),
+    ]
+}
+
+fn nearest_squared<'a, T: Scalar, I>(pieces: I, p: Point<T>) -> T
+where
+    I: IntoIterator<Item = &'a LineSegment<T>>,
+{
+    pieces
+        .into_iter()
+        .map(|e| e.distance_squared_to_point(p))
+        .fold(T::infinity(), T::min)
+}
+
+/// The exact directed Hausdorff distance from the pieces of `a` to those of `b`. Along
+/// a segment of `a`, the distance to each piece of `b` is a convex function, and the
+/// distance to `b` is the least of them. Between the places where two of those
+/// functions cross, the least is a single convex function, so it is largest at one of
+/// the crossings or at an end of the segment.
+fn directed<T: Scalar>(a: &[LineSegment<T>], b: &[LineSegment<T>]) -> Option<T> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    let mut best = T::zero();
+    for s in a {
+        let ends = [s.p1(), s.p2()];
+        for &p in &ends {
+            best = best.max(nearest_squared(b, p));
+        }
+        if s.p1() == s.p2() {
+            continue;
+        }
+        // Each function is convex, so never more along the segment than at its ends
+        let bound = b
+            .iter()
+            .map(|e| {
+                e.distance_squared_to_point(ends[0])
+                    .max(e.distance_squared_to_point(ends[1]))
+            })
+            .fold(T::infinity(), T::min);
+        if bound <= best {
+            continue;
+        }
+        let near: Vec<_> = b
+            .iter()
+            .filter(|e| {
+                let d = s.distance_to(e).0;
+                d * d <= bound
+            })
+            .collect();
+        let functions: Vec<_> = near.iter().map(|e| squared_distance_pieces(s, e)).collect();
+        for (i, f) in functions.iter().enumerate() {
+            for g in &functions[i + 1..] {
+                for &(f_lo, f_hi, fq) in f.iter().flatten() {
+                    for &(g_lo, g_hi, gq) in g.iter().flatten() {
+                        let lo = f_lo.max(g_lo).max(T::zero());
+                        let hi = f_hi.min(g_hi).min(T::one());
+                        if lo > hi {
+                            continue;
+                        }
+                        let diff = [fq[0] - gq[0], fq[1] - gq[1], fq[2] - gq[2]];
+                        for &t in roots_between(diff, lo, hi).iter().flatten() {
+                            let p = s.p1() + s.direction() * t;
+                            best = best.max(nearest_squared(near.iter().copied(), p));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Some(best.sqrt())
+}
+
+/// A uniform grid of cells, each listing the segments whose bounding boxes overlap it,
+/// for finding the nearest segment to a point without testing every one
+struct SegmentGrid<'a, T> {
+    segments: &'a [LineSegment<T>],
+    origin: Point<T>,
+    cell: T,
+    cols: usize,
+    rows: usize,
+    cells: Vec<Vec<usize>>,
+}
+
+impl<'a, T: Scalar> SegmentGrid<'a, T> {
+    fn new(segments: &'a [LineSegment<T>]) -> Option<SegmentGrid<'a, T>> {
+        let bounds = Rect::from_points(segments.iter().flat_map(|s| [s.p1(), s.p2()]))?;
+        // Aim for about one segment per cell
+        let across = Float::ceil(Float::sqrt(segments.len() as f64)).max(1.0);
+        let mut cell = bounds.width().max(bounds.height()) / T::from_f64(across);
+        if cell == T::zero() {
+            cell = T::one();
+        }
+        let count = |extent: T| (extent / cell).floor().as_f64() as usize + 1;
+        let (cols, rows) = (count(bounds.width()), count(bounds.height()));
+        let mut grid = SegmentGrid {
+            segments,
+            origin: bounds.min(),
+            cell,
+            cols,
+            rows,
+            cells: alloc::vec![Vec::new(); cols * rows],
+        };
+        for (k, s) in segments.iter().enumerate() {
+            let (lo, hi) = (grid.cell_of(s.p1()), grid.cell_of(s.p2()));
+            for i in lo.0.min(hi.0)..=lo.0.max(hi.0) {
+                for j in lo.1.min(hi.1)..=lo.1.max(hi.1) {
+                    grid.cells[j * cols + i].push(k);
+                }
+            }
+        }
+        Some(grid)
+    }
+
+    /// The cell containing `p`, or the nearest cell if `p` is outside the grid
+    fn cell_of(&self, p: Point<T>) -> (usize, usize) {
+        let index = |v: T, start: T, n: usize| {
+            let i = ((v - start) / self.cell).floor().max(T::zero()).as_f64() as usize;
+            i.min(n - 1)
+        };
+        (
+            index(p.x, self.origin.x, self.cols),
+            index(p.y, self.origin.y, self.rows),
+        )
+    }
+
+    /// The squared distance from `p` to the nearest segment. The cells are searched in
+    /// square rings around the one nearest `p`, and every cell beyond ring `r` is at
+    /// least `r` cells away, so the search stops once the best so far is that near.
+    fn nearest_squared(&self, p: Point<T>) -> T {
+        let (ci, cj) = self.cell_of(p);
+        let (ci, cj) = (ci as isize, cj as isize);
+        let mut best = T::infinity();
+        for r in 0..=self.cols.max(self.rows) as isize {
+            for dj in -r..=r {
+                let step = if dj.abs() == r { 1 } else { 2 * r };
+                let mut di = -r;
+                while di <= r {
+                    let (i, j) = (ci + di, cj + dj);
+                    if i >= 0 && j >= 0 && (i as usize) < self.cols && (j as usize) < self.rows {
+                        let cell = &self.cells[j as usize * self.cols + i as usize];
+                        best =
+                            best.min(nearest_squared(cell.iter().map(|&k| &self.segments[k]), p));
+                    }
+                    di += step;
+                }
+            }
+            let reach = self.cell * T::from_f64(r as f64);
+            if best <= reach * reach {
+                break;
+            }
+        }
+        best
+    }
+}
+
+fn directed_approx<T: Scalar>(a: &[LineSegment<T>], b: &[LineSegment<T>], spacing: T) -> Option<T> {
+    assert!(
+        spacing > T::zero(),
+        "spacing must be positive, got {}",
+        spacing
+    );
+    if a.is_empty() {
+        return None;
+    }
+    let grid = SegmentGrid::new(b)?;
+    let mut best = T::zero();
+    for s in a {
+        let pieces = (s.length() / spacing).ceil().max(T::one());
+        for i in 0..=pieces.as_f64() as usize {
+            let p = s.p1().lerp(&s.p2(), T::from_f64(i as f64) / pieces);
+            best = best.max(grid.nearest_squared(p));
+        }
+    }
+    Some(best.sqrt())
+}
+
+macro_rules! impl_hausdorff {
+    ($a:ident, $b:ident) => {
+        impl<T: Scalar> Hausdorff<T, $b<T>> for $a<T> {
+            fn directed_hausdorff(&self, other: &$b<T>) -> Option<T> {
+                directed(&self.pieces(), &other.pieces())
+            }
+
+            fn hausdorff(&self, other: &$b<T>) -> Option<T> {
+                let (a, b) = (self.pieces(), other.pieces());
+                Some(directed(&a, &b)?.max(directed(&b, &a)?))
+            }
+
+            fn hausdorff_approx(&self, other: &$b<T>, spacing: T) -> Option<T> {
+                let (a, b) = (self.pieces(), other.pieces());
+                Some(directed_approx(&a, &b, spacing)?.max(directed_approx(&b, &a, spacing)?))
+            }
+        }
+    };
+}
+
+for_pairs!(impl_hausdorff;
+    [MultiPoint, Polyline, Polygon] x [MultiPoint, Polyline, Polygon]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lcg;
+
+    fn line(points: &[(f64, f64)]) -> Polyline {
+        points.iter().map(|&(x, y)| Point { x, y }).collect()
+    }
+
+    /// The directed distance measured only at points `step` apart along `a`
+    fn sampled(a: &Polyline, b: &Polyline, step: f64) -> f64 {
+        let pieces = b.pieces();
+        let dense = a.densify(step);
+        dense
+            .points()
+            .iter()
+            .map(|&p| nearest_squared(&pieces, p).sqrt())
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_maximum_between_vertices() {
+        // The ends of `a` are 3 from the ends of `b`, but its middle is farther from
+        // both arms of `b`
+        let a = line(&[(-4.0, 0.0), (4.0, 0.0)]);
+        let b = line(&[(-4.0, 3.0), (0.0, 6.0), (4.0, 3.0)]);
+        assert!(crate::scalar_isclose(
+            4.8,
+            a.directed_hausdorff(&b).unwrap()
+        ));
+        let bent = line(&[(-4.0, 3.0), (-1.0, 3.0), (0.0, 5.0), (1.0, 3.0), (4.0, 3.0)]);
+        let exact = a.directed_hausdorff(&bent).unwrap();
+        assert!((exact - sampled(&a, &bent, 1e-3)).abs() < 1e-3);
+        assert!(exact > 3.0)
+    }
+
+    #[test]
+    fn test_matches_dense_sampling() {
+        let mut rng = Lcg::new(7);
+        let mut coord = || rng.unit() * 10.0;
+        for _ in 0..20 {
+            let a: Polyline = (0..5)
+                .map(|_| Point {
+                    x: coord(),
+                    y: coord(),
+                })
+                .collect();
+            let b: Polyline = (0..6)
+                .map(|_| Point {
+                    x: coord(),
+                    y: coord(),
+                })
+                .collect();
+            let exact = a.directed_hausdorff(&b).unwrap();
+            let dense = sampled(&a, &b, 1e-3);
+            assert!(dense <= exact + 1e-9 && exact - dense < 1e-3);
+            let approx = a.hausdorff_approx(&b, 0.1).unwrap();
+            let both = a.hausdorff(&b).unwrap();
+            assert!(approx <= both + 1e-9 && both - approx <= 0.05 + 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_mixed_shapes() {
+        let square = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+        ]);
+        let center: MultiPoint = vec![Point { x: 1.0, y: 1.0 }].into_iter().collect();
+        // Polygons are measured by their outlines, so the center is a unit from them
+        assert_eq!(Some(1.0), center.directed_hausdorff(&square));
+        assert!(crate::scalar_isclose(
+            2.0_f64.sqrt(),
+            square.hausdorff(&center).unwrap()
+        ));
+        assert_eq!(Some(0.0), square.hausdorff(&square));
+        assert_eq!(None, square.hausdorff(&MultiPoint::default()))
+    }
+
+    #[test]
+    #[should_panic(expected = "spacing must be positive")]
+    fn test_approx_spacing() {
+        let a = line(&[(0.0, 0.0), (1.0, 0.0)]);
+        a.hausdorff_approx(&a, 0.0);
+    }
+}
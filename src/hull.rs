@@ -0,0 +1,216 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::predicates::{orientation, Orientation};
+use crate::{Point, Polygon, Scalar};
+
+/// The convex hull of `points`, as a counter-clockwise polygon starting from the
+/// lowest of the leftmost points, found with Andrew's monotone chain. Every turn is
+/// decided with the exact orientation predicate, and repeated points are ignored.
+///
+/// Points lying along an edge of the hull become vertices of it if `keep_collinear` is
+/// set, and are dropped otherwise. If all the points lie on one line the hull is just
+/// the two ends of that line, or the single point if they are all the same.
+/// ```
+/// use rsgeo::{convex_hull, Point};
+/// let points = [
+///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.0}, Point{x: 2.0, y: 0.0},
+///     Point{x: 1.0, y: 1.0}, Point{x: 1.0, y: 3.0}, Point{x: 2.0, y: 0.0},
+/// ];
+/// let hull = convex_hull(&points, false);
+/// assert_eq!(hull.vertices(), &[
+///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 1.0, y: 3.0},
+/// ]);
+/// assert_eq!(convex_hull(&points, true).vertices().len(), 4);
+/// ```
+pub fn convex_hull<T: Scalar>(points: &[Point<T>], keep_collinear: bool) -> Polygon<T> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .unwrap_or(Ordering::Equal)
+            .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+    });
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return Polygon::new(sorted);
+    }
+    let (first, last) = (sorted[0], sorted[sorted.len() - 1]);
+    if sorted
+        .iter()
+        .all(|&p| orientation(first, last, p) == Orientation::Collinear)
+    {
+        return Polygon::new(alloc::vec![first, last]);
+    }
+    // A point is popped when the chain would not turn left at it, or with
+    // `keep_collinear` only when the chain would turn right
+    let pops = |turn: Orientation| match turn {
+        Orientation::Clockwise => true,
+        Orientation::Collinear => !keep_collinear,
+        Orientation::CounterClockwise => false,
+    };
+    let mut hull: Vec<Point<T>> = Vec::with_capacity(sorted.len() + 1);
+    let visit = |hull: &mut Vec<Point<T>>, floor: usize, p: Point<T>| {
+        while hull.len() >= floor + 2
+            && pops(orientation(hull[hull.len() - 2], hull[hull.len() - 1], p))
+        {
+            hull.pop();
+        }
+        hull.push(p);
+    };
+    // The lower chain from left to right, then the upper chain back from the last
+    // point, which must not pop any of the lower chain
+    for &p in &sorted {
+        visit(&mut hull, 0, p);
+    }
+    let floor = hull.len() - 1;
+    for &p in sorted.iter().rev().skip(1) {
+        visit(&mut hull, floor, p);
+    }
+    // Both chains end where the other starts
+    hull.pop();
+    Polygon::new(hull)
+}
+
+/// The convex layers of `points`, from the outside in: the convex hull, then the hull
+/// of the points left inside it, and so on until none are left. Points along the edges
+/// of a hull belong to that layer. When the points left all lie on one line they form
+/// the last layer together, as a polygon of them in order along the line.
+/// ```
+/// use rsgeo::{convex_layers, Point};
+/// let mut points = vec![Point{x: 2.0, y: 2.0}];
+/// for &(x, y) in &[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)] {
+///     points.push(Point{x, y});
+///     points.push(Point{x: (x + 2.0) / 2.0, y: (y + 2.0) / 2.0});
+/// }
+/// let layers = convex_layers(&points);
+/// let sizes: Vec<_> = layers.iter().map(|layer| layer.vertices().len()).collect();
+/// assert_eq!(sizes, [4, 4, 1]);
+/// ```
+pub fn convex_layers<T: Scalar>(points: &[Point<T>]) -> Vec<Polygon<T>> {
+    let mut left = points.to_vec();
+    let mut layers = Vec::new();
+    while !left.is_empty() {
+        let hull = convex_hull(&left, true);
+        if hull.vertices().len() < 3 {
+            left.sort_by(|a, b| {
+                (a.x, a.y)
+                    .partial_cmp(&(b.x, b.y))
+                    .unwrap_or(Ordering::Equal)
+            });
+            left.dedup();
+            layers.push(Polygon::new(left));
+            break;
+        }
+        left.retain(|p| !hull.vertices().contains(p));
+        layers.push(hull);
+    }
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lcg;
+
+    fn pts(coords: &[(f64, f64)]) -> Vec<Point> {
+        coords.iter().map(|&(x, y)| Point { x, y }).collect()
+    }
+
+    #[test]
+    fn test_collinear_flag() {
+        let points = pts(&[
+            (1.0, 1.0),
+            (0.0, 2.0),
+            (2.0, 0.0),
+            (0.0, 0.0),
+            (2.0, 2.0),
+            (0.0, 1.0),
+            (2.0, 1.0),
+            (1.0, 0.0),
+        ]);
+        let dropped = convex_hull(&points, false);
+        let corners = pts(&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]);
+        assert_eq!(corners, dropped.vertices());
+        let kept = convex_hull(&points, true);
+        let edges = pts(&[
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+            (2.0, 2.0),
+            (0.0, 2.0),
+            (0.0, 1.0),
+        ]);
+        assert_eq!(edges, kept.vertices());
+        assert_eq!(4.0, kept.area())
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        assert!(convex_hull::<f64>(&[], false).vertices().is_empty());
+        let dot = pts(&[(1.0, 1.0), (1.0, 1.0)]);
+        assert_eq!(&dot[..1], convex_hull(&dot, true).vertices());
+        let line = pts(&[(2.0, 2.0), (0.0, 0.0), (1.0, 1.0), (3.0, 3.0), (1.0, 1.0)]);
+        for keep in [false, true] {
+            assert_eq!(
+                pts(&[(0.0, 0.0), (3.0, 3.0)]),
+                convex_hull(&line, keep).vertices()
+            );
+        }
+    }
+
+    #[test]
+    fn test_hull_encloses_points() {
+        let mut rng = Lcg::new(11);
+        let mut coord = || rng.below(50) as f64 / 5.0;
+        let points: Vec<Point> = (0..200)
+            .map(|_| Point {
+                x: coord(),
+                y: coord(),
+            })
+            .collect();
+        for keep in [false, true] {
+            let hull = convex_hull(&points, keep);
+            assert!(hull.is_convex());
+            assert_eq!(Orientation::CounterClockwise, hull.orientation());
+            for &p in &points {
+                assert!(
+                    hull.winding_number(p) == 1
+                        || hull.edges().any(|e| e.distance_to_point(p) == 0.0)
+                );
+            }
+            for v in hull.vertices() {
+                assert!(points.contains(v));
+            }
+        }
+    }
+
+    #[test]
+    fn test_convex_layers_peel() {
+        // Nested squares, each with a point along every edge, around a line of points
+        let mut points = pts(&[(3.5, 4.0), (4.0, 4.0), (4.5, 4.0)]);
+        for size in [1.0, 2.0, 3.0] {
+            for &(x, y) in &[(-1.0, -1.0), (0.0, -1.0)] {
+                for &(x, y) in &[(x, y), (-y, x), (-x, -y), (y, -x)] {
+                    points.push(Point {
+                        x: 4.0 + size * x,
+                        y: 4.0 + size * y,
+                    });
+                }
+            }
+        }
+        let layers = convex_layers(&points);
+        assert_eq!(4, layers.len());
+        for (layer, size) in layers.iter().zip([3.0, 2.0, 1.0]) {
+            assert_eq!(8, layer.vertices().len());
+            assert_eq!(4.0 * size * size, layer.area());
+        }
+        assert_eq!(
+            pts(&[(3.5, 4.0), (4.0, 4.0), (4.5, 4.0)]),
+            layers[3].vertices()
+        );
+        let total: usize = layers.iter().map(|layer| layer.vertices().len()).sum();
+        assert_eq!(points.len(), total);
+        assert!(convex_layers::<f64>(&[]).is_empty())
+    }
+}
@@ -0,0 +1,329 @@
+use alloc::vec::Vec;
+
+use num_traits::Float;
+
+use crate::{Point, Rect, Scalar};
+
+/// Which samples an inverse distance weighted estimate is made from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IdwSearch<T = f64> {
+    /// Every sample
+    All,
+    /// The samples no further than this from the query point
+    Radius(T),
+    /// The nearest this many samples to the query point
+    Nearest(usize),
+}
+
+/// Inverse distance weighted interpolation of values at scattered points, which
+/// estimates the value at a query point as the average of the values of nearby
+/// samples, each weighted by its distance to the power of `-power`. The samples are
+/// sorted into a grid of buckets, about one to a bucket, so that only the buckets
+/// near a query point need to be searched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Idw<T = f64> {
+    points: Vec<Point<T>>,
+    values: Vec<T>,
+    power: T,
+    search: IdwSearch<T>,
+    bounds: Option<Rect<T>>,
+    columns: usize,
+    rows: usize,
+    /// Where each bucket's samples start in `members`, with one more at the end
+    starts: Vec<usize>,
+    members: Vec<usize>,
+}
+
+impl<T: Scalar> Idw<T> {
+    /// The interpolant taking `values[i]` at `points[i]`, which takes O(n) time to set
+    /// up. A power of 2 is the usual choice, and higher powers favour the nearest
+    /// samples more. Panics if there is not one value for each point, if `power` is
+    /// negative, or if the search radius is not positive or asks for no neighbors.
+    /// ```
+    /// use rsgeo::{Idw, IdwSearch, Point};
+    /// let points = [Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 9.0, y: 0.0}];
+    /// let idw = Idw::new(&points, &[1.0, 3.0, 100.0], 2.0, IdwSearch::Nearest(2));
+    /// // Halfway between the first two, and too far from the last to use it
+    /// assert_eq!(idw.interpolate_at(Point{x: 2.0, y: 0.0}), Some(2.0));
+    /// assert_eq!(idw.interpolate_at(Point{x: 4.0, y: 0.0}), Some(3.0));
+    /// let near = Idw::new(&points, &[1.0, 3.0, 100.0], 2.0, IdwSearch::Radius(1.0));
+    /// assert_eq!(near.interpolate_at(Point{x: 6.5, y: 0.0}), None);
+    /// ```
+    pub fn new(points: &[Point<T>], values: &[T], power: T, search: IdwSearch<T>) -> Idw<T> {
+        assert!(
+            values.len() == points.len(),
+            "values must have one entry per point, got {} for {} points",
+            values.len(),
+            points.len()
+        );
+        assert!(
+            power >= T::zero(),
+            "power must not be negative, got {}",
+            power
+        );
+        match search {
+            IdwSearch::Radius(r) => assert!(r > T::zero(), "radius must be positive, got {}", r),
+            IdwSearch::Nearest(k) => assert!(k > 0, "must search for at least one neighbor"),
+            IdwSearch::All => {}
+        }
+        let bounds = Rect::from_points(points.iter().copied());
+        let n = points.len().max(1);
+        let (columns, rows) = match bounds {
+            Some(b) if b.width() > T::zero() && b.height() > T::zero() => {
+                let aspect = (b.width() / b.height()).as_f64();
+                let columns = (Float::ceil(Float::sqrt(n as f64 * aspect)) as usize).clamp(1, n);
+                (columns, n.div_ceil(columns))
+            }
+            Some(b) if b.width() > T::zero() => (n, 1),
+            Some(b) if b.height() > T::zero() => (1, n),
+            _ => (1, 1),
+        };
+        let mut idw = Idw {
+            points: points.to_vec(),
+            values: values.to_vec(),
+            power,
+            search,
+            bounds,
+            columns,
+            rows,
+            starts: Vec::new(),
+            members: Vec::new(),
+        };
+        let mut buckets = alloc::vec![Vec::new(); columns * rows];
+        for (i, &p) in points.iter().enumerate() {
+            let (column, row) = idw.bucket(p);
+            buckets[row * columns + column].push(i);
+        }
+        for bucket in buckets {
+            idw.starts.push(idw.members.len());
+            idw.members.extend(bucket);
+        }
+        idw.starts.push(idw.members.len());
+        idw
+    }
+
+    /// The estimated value at `p`. A sample at `p` itself gives its own value, or the
+    /// average of theirs if there are several. None if the search finds no samples.
+    pub fn interpolate_at(&self, p: Point<T>) -> Option<T> {
+        let near = match self.search {
+            IdwSearch::All => (0..self.points.len())
+                .map(|i| (self.points[i].distance(&p), i))
+                .collect(),
+            IdwSearch::Radius(r) => self.within(p, r),
+            IdwSearch::Nearest(k) => self.nearest(p, k),
+        };
+        if near.is_empty() {
+            return None;
+        }
+        let (mut total, mut weights, mut here) = (T::zero(), T::zero(), 0);
+        if near.iter().any(|&(d, _)| d == T::zero()) {
+            for &(_, i) in near.iter().filter(|&&(d, _)| d == T::zero()) {
+                total += self.values[i];
+                here += 1;
+            }
+            return Some(total / T::from_f64(here as f64));
+        }
+        for &(d, i) in &near {
+            let w = d.powf(-self.power);
+            total += w * self.values[i];
+            weights += w;
+        }
+        Some(total / weights)
+    }
+
+    /// The samples within `radius` of `p`, with their distances
+    fn within(&self, p: Point<T>, radius: T) -> Vec<(T, usize)> {
+        let reach = Point {
+            x: radius,
+            y: radius,
+        };
+        let (lo, hi) = (self.bucket(p - reach), self.bucket(p + reach));
+        let mut near = Vec::new();
+        for row in lo.1..=hi.1 {
+            for column in lo.0..=hi.0 {
+                for &i in self.members_of(column, row) {
+                    let d = self.points[i].distance(&p);
+                    if d <= radius {
+                        near.push((d, i));
+                    }
+                }
+            }
+        }
+        near
+    }
+
+    /// The `k` samples nearest `p`, with their distances, found by searching rings of
+    /// buckets further and further out until no bucket left could hold a nearer one
+    fn nearest(&self, p: Point<T>, k: usize) -> Vec<(T, usize)> {
+        let b = match self.bounds {
+            Some(b) => b,
+            None => return Vec::new(),
+        };
+        let (width, height) = (
+            b.width() / T::from_f64(self.columns as f64),
+            b.height() / T::from_f64(self.rows as f64),
+        );
+        let (column, row) = self.bucket(p);
+        let mut best: Vec<(T, usize)> = Vec::with_capacity(k + 1);
+        let furthest = self.columns.max(self.rows);
+        for ring in 0..=furthest {
+            // Every bucket on this ring is outside the block of those on the rings
+            // within it, so no nearer than the edge of that block
+            if best.len() == k && ring > 0 {
+                let inner = T::from_f64((ring - 1) as f64);
+                let lo = Point {
+                    x: b.min().x + (T::from_f64(column as f64) - inner) * width,
+                    y: b.min().y + (T::from_f64(row as f64) - inner) * height,
+                };
+                let hi = Point {
+                    x: b.min().x + (T::from_f64(column as f64) + inner + T::one()) * width,
+                    y: b.min().y + (T::from_f64(row as f64) + inner + T::one()) * height,
+                };
+                // A grid one bucket across is only searched along its length
+                let gap = |size: T, lo: T, hi: T, at: T| match size > T::zero() {
+                    true => (at - lo).min(hi - at),
+                    false => T::infinity(),
+                };
+                let gap = gap(width, lo.x, hi.x, p.x).min(gap(height, lo.y, hi.y, p.y));
+                if best[k - 1].0 <= gap {
+                    break;
+                }
+            }
+            for (c, r) in ring_cells(column, row, ring) {
+                if c >= self.columns || r >= self.rows {
+                    continue;
+                }
+                for &i in self.members_of(c, r) {
+                    let d = self.points[i].distance(&p);
+                    if best.len() == k && d >= best[k - 1].0 {
+                        continue;
+                    }
+                    let at = best.partition_point(|&(e, _)| e <= d);
+                    best.insert(at, (d, i));
+                    best.truncate(k);
+                }
+            }
+        }
+        best
+    }
+
+    fn members_of(&self, column: usize, row: usize) -> &[usize] {
+        let bucket = row * self.columns + column;
+        &self.members[self.starts[bucket]..self.starts[bucket + 1]]
+    }
+
+    /// The column and row of the bucket holding `p`, which is clamped to the grid
+    fn bucket(&self, p: Point<T>) -> (usize, usize) {
+        let b = match self.bounds {
+            Some(b) => b,
+            None => return (0, 0),
+        };
+        let index = |offset: T, size: T, count: usize| {
+            if size <= T::zero() {
+                return 0;
+            }
+            let i = (offset / size * T::from_f64(count as f64)).floor();
+            (i.max(T::zero()).as_f64() as usize).min(count - 1)
+        };
+        (
+            index(p.x - b.min().x, b.width(), self.columns),
+            index(p.y - b.min().y, b.height(), self.rows),
+        )
+    }
+}
+
+/// The cells whose larger offset in either direction from `(column, row)` is `ring`,
+/// leaving out those that would be at negative positions
+fn ring_cells(column: usize, row: usize, ring: usize) -> impl Iterator<Item = (usize, usize)> {
+    let (c, r, n) = (column as isize, row as isize, ring as isize);
+    (-n..=n)
+        .flat_map(move |dy| (-n..=n).map(move |dx| (dx, dy)))
+        .filter(move |&(dx, dy)| dx.abs() == n || dy.abs() == n)
+        .map(move |(dx, dy)| (c + dx, r + dy))
+        .filter(|&(x, y)| x >= 0 && y >= 0)
+        .map(|(x, y)| (x as usize, y as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scattered;
+
+    /// The estimate from the given samples, found by checking every one
+    fn brute_force(points: &[Point], values: &[f64], near: &[usize], q: Point) -> f64 {
+        let (mut total, mut weights) = (0.0, 0.0);
+        for &i in near {
+            let w = points[i].distance(&q).powi(-2);
+            total += w * values[i];
+            weights += w;
+        }
+        total / weights
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let points = scattered(500, 4);
+        let values: Vec<f64> = points.iter().map(|p| p.x * p.x - p.y).collect();
+        let by_radius = Idw::new(&points, &values, 2.0, IdwSearch::Radius(0.8));
+        let by_count = Idw::new(&points, &values, 2.0, IdwSearch::Nearest(7));
+        let by_all = Idw::new(&points, &values, 2.0, IdwSearch::All);
+        // Some queries are well outside the samples
+        for q in scattered(500, 8)
+            .into_iter()
+            .map(|q| q * 1.4 - Point { x: 2.0, y: 2.0 })
+        {
+            let mut order: Vec<usize> = (0..points.len()).collect();
+            order.sort_by(|&a, &b| points[a].distance(&q).total_cmp(&points[b].distance(&q)));
+            let within: Vec<usize> = order
+                .iter()
+                .copied()
+                .take_while(|&i| points[i].distance(&q) <= 0.8)
+                .collect();
+            match by_radius.interpolate_at(q) {
+                Some(z) => assert!((z - brute_force(&points, &values, &within, q)).abs() < 1e-9),
+                None => assert!(within.is_empty()),
+            }
+            let z = by_count.interpolate_at(q).unwrap();
+            assert!((z - brute_force(&points, &values, &order[..7], q)).abs() < 1e-9);
+            let z = by_all.interpolate_at(q).unwrap();
+            assert!((z - brute_force(&points, &values, &order, q)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_exact_at_samples() {
+        let mut points = scattered(50, 3);
+        points.push(points[10]);
+        let mut values: Vec<f64> = (0..50).map(|i| i as f64).collect();
+        values.push(20.0);
+        let idw = Idw::new(&points, &values, 3.0, IdwSearch::Nearest(4));
+        assert_eq!(Some(3.0), idw.interpolate_at(points[3]));
+        assert_eq!(Some(15.0), idw.interpolate_at(points[10]));
+        // With no power every sample found counts the same
+        let flat = Idw::new(&points[..4], &values[..4], 0.0, IdwSearch::All);
+        assert_eq!(Some(1.5), flat.interpolate_at(Point { x: 20.0, y: 0.0 }));
+    }
+
+    #[test]
+    fn test_degenerate_samples() {
+        let line: Vec<Point> = (0..100)
+            .map(|i| Point {
+                x: i as f64,
+                y: 0.0,
+            })
+            .collect();
+        let values: Vec<f64> = (0..100).map(|i| i as f64).collect();
+        let idw = Idw::new(&line, &values, 2.0, IdwSearch::Nearest(2));
+        assert_eq!(Some(40.5), idw.interpolate_at(Point { x: 40.5, y: 3.0 }));
+        let one = Idw::new(&line[..1], &values[..1], 2.0, IdwSearch::Nearest(3));
+        assert_eq!(Some(0.0), one.interpolate_at(Point { x: 5.0, y: 5.0 }));
+        let none = Idw::<f64>::new(&[], &[], 2.0, IdwSearch::Nearest(3));
+        assert_eq!(None, none.interpolate_at(Point::origin()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_radius_must_be_positive() {
+        Idw::new(&scattered(5, 1), &[0.0; 5], 2.0, IdwSearch::Radius(0.0));
+    }
+}
@@ -0,0 +1,252 @@
+use alloc::vec::Vec;
+
+use crate::{LineSegment, Point, Polygon, Rect, Scalar};
+
+/// The bottom and top of the part of a convex ring above `x`, if any
+fn vertical_span<T: Scalar>(ring: &[Point<T>], x: T) -> Option<(T, T)> {
+    let mut span: Option<(T, T)> = None;
+    let mut include = |y: T| {
+        span = Some(match span {
+            Some((lo, hi)) => (lo.min(y), hi.max(y)),
+            None => (y, y),
+        })
+    };
+    for (i, &a) in ring.iter().enumerate() {
+        let b = ring[(i + 1) % ring.len()];
+        if a.x == x {
+            include(a.y);
+        }
+        if (a.x < x && x < b.x) || (b.x < x && x < a.x) {
+            include(a.y + (b.y - a.y) * ((x - a.x) / (b.x - a.x)));
+        }
+    }
+    span
+}
+
+/// Whether some of `seg` is strictly inside `rect`, rather than only touching its
+/// boundary
+fn enters<T: Scalar>(rect: &Rect<T>, seg: &LineSegment<T>) -> bool {
+    let (p, d) = (seg.p1(), seg.direction());
+    let (mut lo, mut hi) = (T::neg_infinity(), T::infinity());
+    for (start, step, min, max) in [
+        (p.x, d.x, rect.min().x, rect.max().x),
+        (p.y, d.y, rect.min().y, rect.max().y),
+    ] {
+        if step == T::zero() {
+            if start <= min || start >= max {
+                return false;
+            }
+        } else {
+            let (a, b) = ((min - start) / step, (max - start) / step);
+            lo = lo.max(a.min(b));
+            hi = hi.min(a.max(b));
+        }
+    }
+    lo < hi && lo < T::one() && hi > T::zero()
+}
+
+/// The largest `f` over `[lo, hi]`, for an `f` that rises to its largest value and
+/// then falls, found by ternary search. Where `f` is level the search moves left.
+fn ternary_max<T: Scalar>(mut lo: T, mut hi: T, f: impl Fn(T) -> T) -> T {
+    let three = T::from_f64(3.0);
+    for _ in 0..100 {
+        let (m1, m2) = (lo + (hi - lo) / three, hi - (hi - lo) / three);
+        if f(m1) >= f(m2) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+    (lo + hi) / T::from_f64(2.0)
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// The largest axis-aligned rectangle inside a convex polygon. A rectangle is inside
+    /// exactly when its four corners are, so its area is a function of where its left
+    /// and right sides are, and for a convex polygon the logarithm of that function is
+    /// concave. Nested ternary searches over the two sides therefore converge on the
+    /// best rectangle, to within rounding. None if the polygon is not convex, which
+    /// includes having holes, or has no area. See `largest_inscribed_rect_approx` for
+    /// other polygons.
+    /// ```
+    /// use rsgeo::{Point, Polygon, Rect};
+    /// let tri = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 0.0, y: 2.0},
+    /// ]);
+    /// let rect: Rect = tri.largest_inscribed_rect().unwrap();
+    /// assert!((rect.area() - 2.0).abs() < 1e-9);
+    /// assert!(rect.min().isclose(Point{x: 0.0, y: 0.0}));
+    /// ```
+    pub fn largest_inscribed_rect(&self) -> Option<Rect<T>> {
+        if !self.is_convex() || self.area() == T::zero() {
+            return None;
+        }
+        let ring = self.vertices();
+        let bounds = Rect::from_points(ring.iter().copied())?;
+        let (left, right) = (bounds.min().x, bounds.max().x);
+        // The bottom and top of the tallest rectangle between `a` and `b`
+        let between = |a: T, b: T| {
+            let ((la, ua), (lb, ub)) = (vertical_span(ring, a)?, vertical_span(ring, b)?);
+            Some((la.max(lb), ua.min(ub)))
+        };
+        let area = |a: T, b: T| match between(a, b) {
+            Some((lo, hi)) if hi > lo => (b - a) * (hi - lo),
+            _ => T::zero(),
+        };
+        let best_right = |a: T| ternary_max(a, right, |b| area(a, b));
+        let a = ternary_max(left, right, |a| area(a, best_right(a)));
+        let b = best_right(a);
+        let (lo, hi) = between(a, b)?;
+        Some(Rect::from_corners(
+            Point { x: a, y: lo },
+            Point { x: b, y: hi },
+        ))
+    }
+
+    /// An approximation of the largest axis-aligned rectangle inside any polygon,
+    /// holes included. The polygon's bounding rectangle is divided into square cells,
+    /// `cells` of them along its longer side, and the result is the largest rectangle
+    /// made of cells that are wholly inside, so it is always inside the polygon. It can
+    /// fall short of the true largest rectangle by up to a cell on every side. None if
+    /// no cell is wholly inside. Panics if `cells` is zero.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// // An L whose arms are each 4 long and 1 wide
+    /// let l = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 1.0},
+    ///     Point{x: 1.0, y: 1.0}, Point{x: 1.0, y: 4.0}, Point{x: 0.0, y: 4.0},
+    /// ]);
+    /// let rect = l.largest_inscribed_rect_approx(40).unwrap();
+    /// assert!(rect.area() >= 3.5 && rect.area() <= 4.0);
+    /// ```
+    pub fn largest_inscribed_rect_approx(&self, cells: usize) -> Option<Rect<T>> {
+        assert!(cells > 0, "cells must be positive, got {}", cells);
+        let bounds = Rect::from_points(self.vertices().iter().copied())?;
+        let size = bounds.width().max(bounds.height()) / T::from_f64(cells as f64);
+        if size == T::zero() {
+            return None;
+        }
+        let count = |extent: T| ((extent / size).ceil().as_f64() as usize).max(1);
+        let (cols, rows) = (count(bounds.width()), count(bounds.height()));
+        let origin = bounds.min();
+        let cell = |c: usize, r: usize| {
+            let min = origin
+                + Point {
+                    x: size * T::from_f64(c as f64),
+                    y: size * T::from_f64(r as f64),
+                };
+            Rect::from_corners(min, min + Point { x: size, y: size })
+        };
+        let edges: Vec<_> = self.rings().flat_map(crate::polygon::ring_edges).collect();
+        // A cell is free when it is inside and no edge passes through it. The largest
+        // rectangle of them is found a row at a time, from the heights of the columns
+        // of free cells ending at that row
+        let mut heights = alloc::vec![0usize; cols];
+        let mut best: Option<(usize, usize, usize, usize)> = None;
+        let mut best_area = 0;
+        for r in 0..rows {
+            for (c, height) in heights.iter_mut().enumerate() {
+                let square = cell(c, r);
+                let free =
+                    self.contains(square.center()) && !edges.iter().any(|e| enters(&square, e));
+                *height = if free { *height + 1 } else { 0 };
+            }
+            // Each column's bar extends left and right until a shorter one
+            let mut stack: Vec<usize> = Vec::new();
+            for c in 0..=cols {
+                let h = if c < cols { heights[c] } else { 0 };
+                while let Some(&top) = stack.last() {
+                    if heights[top] < h {
+                        break;
+                    }
+                    stack.pop();
+                    let start = stack.last().map_or(0, |&s| s + 1);
+                    let area = heights[top] * (c - start);
+                    if area > best_area {
+                        best_area = area;
+                        best = Some((start, c, r + 1 - heights[top], r + 1));
+                    }
+                }
+                stack.push(c);
+            }
+        }
+        let (c0, c1, r0, r1) = best?;
+        Some(Rect::from_corners(
+            cell(c0, r0).min(),
+            cell(c1 - 1, r1 - 1).max(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(coords: &[(f64, f64)]) -> Polygon {
+        Polygon::new(coords.iter().map(|&(x, y)| Point { x, y }).collect())
+    }
+
+    #[test]
+    fn test_convex_shapes() {
+        let diamond = polygon(&[(1.0, 0.0), (0.0, 1.0), (-1.0, 0.0), (0.0, -1.0)]);
+        let rect = diamond.largest_inscribed_rect().unwrap();
+        assert!((rect.area() - 1.0).abs() < 1e-9);
+        assert!(rect.center().isclose(Point::origin()));
+        let square = polygon(&[(2.0, 2.0), (5.0, 2.0), (5.0, 5.0), (2.0, 5.0)]);
+        let rect = square.largest_inscribed_rect().unwrap();
+        assert!(rect.min().isclose(Point { x: 2.0, y: 2.0 }));
+        assert!(rect.max().isclose(Point { x: 5.0, y: 5.0 }));
+        // A wide, lopsided hexagon
+        let hexagon = polygon(&[
+            (0.0, 1.0),
+            (2.0, 0.0),
+            (9.0, 0.5),
+            (10.0, 2.0),
+            (7.0, 3.0),
+            (1.0, 2.5),
+        ]);
+        let rect = hexagon.largest_inscribed_rect().unwrap();
+        for corner in rect.corners() {
+            assert!(hexagon
+                .edges()
+                .all(|e| e.direction().cross(&(corner - e.p1())) > -1e-9));
+        }
+        let approx = hexagon.largest_inscribed_rect_approx(200).unwrap();
+        assert!(approx.area() <= rect.area() + 1e-9);
+        assert!(approx.area() > rect.area() * 0.95)
+    }
+
+    #[test]
+    fn test_approx_respects_holes() {
+        let frame = Polygon::with_holes(
+            vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 10.0, y: 0.0 },
+                Point { x: 10.0, y: 10.0 },
+                Point { x: 0.0, y: 10.0 },
+            ],
+            vec![vec![
+                Point { x: 2.0, y: 2.0 },
+                Point { x: 2.0, y: 8.0 },
+                Point { x: 8.0, y: 8.0 },
+                Point { x: 8.0, y: 2.0 },
+            ]],
+        );
+        assert_eq!(None, frame.largest_inscribed_rect());
+        let rect = frame.largest_inscribed_rect_approx(50).unwrap();
+        // The widest strip along one side of the hole
+        assert!(crate::scalar_isclose(20.0, rect.area()));
+        for corner in rect.corners() {
+            assert!(frame.contains(corner));
+        }
+        assert!(!rect.contains(Point { x: 5.0, y: 5.0 }))
+    }
+
+    #[test]
+    fn test_degenerate_polygons() {
+        let flat = polygon(&[(0.0, 0.0), (1.0, 0.0), (2.0, 0.0)]);
+        assert_eq!(None, flat.largest_inscribed_rect());
+        assert_eq!(None, flat.largest_inscribed_rect_approx(10));
+        assert_eq!(None, polygon(&[]).largest_inscribed_rect_approx(10))
+    }
+}
@@ -0,0 +1,320 @@
+//! Geometry over `i64` coordinates. Integer coordinates make every orientation and area
+//! computation exact (wide intermediate results are computed in `i128`), which is how
+//! Clipper avoids the slivers and inconsistencies that floating point overlays produce.
+//! Float geometry is moved onto the integer grid and back with a `Scaling`.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::ops::{Add, Neg, Sub};
+
+use num_traits::Float;
+
+use crate::{Point, Scalar};
+
+/// The largest coordinate magnitude a `Scaling` will produce. Staying below 2^62 leaves
+/// headroom so that sums and differences of two coordinates cannot overflow an `i64`.
+pub const MAX_COORD: i64 = (1 << 62) - 1;
+
+/// A point on the integer grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IPoint {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl IPoint {
+    pub fn new(x: i64, y: i64) -> IPoint {
+        IPoint { x, y }
+    }
+
+    /// The exact 2D cross product of two integer vectors
+    /// ```
+    /// use rsgeo::IPoint;
+    /// assert_eq!(IPoint::new(1, 0).cross(&IPoint::new(0, 1)), 1);
+    /// ```
+    pub fn cross(&self, other: &IPoint) -> i128 {
+        i128::from(self.x) * i128::from(other.y) - i128::from(self.y) * i128::from(other.x)
+    }
+
+    /// The exact dot product of two integer vectors
+    pub fn dot_product(&self, other: &IPoint) -> i128 {
+        i128::from(self.x) * i128::from(other.x) + i128::from(self.y) * i128::from(other.y)
+    }
+
+    /// The exact squared distance between two points
+    pub fn distance_squared(&self, other: &IPoint) -> i128 {
+        let dx = i128::from(other.x) - i128::from(self.x);
+        let dy = i128::from(other.y) - i128::from(self.y);
+        dx * dx + dy * dy
+    }
+
+    /// Twice the signed area of the triangle `a`, `b`, `c`. This is positive when the
+    /// points turn counter-clockwise, negative when clockwise, and zero when collinear.
+    pub fn orient(a: &IPoint, b: &IPoint, c: &IPoint) -> i128 {
+        let ab = (
+            i128::from(b.x) - i128::from(a.x),
+            i128::from(b.y) - i128::from(a.y),
+        );
+        let ac = (
+            i128::from(c.x) - i128::from(a.x),
+            i128::from(c.y) - i128::from(a.y),
+        );
+        ab.0 * ac.1 - ab.1 * ac.0
+    }
+}
+
+impl Add for IPoint {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        IPoint::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for IPoint {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        IPoint::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for IPoint {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        IPoint::new(-self.x, -self.y)
+    }
+}
+
+impl From<(i64, i64)> for IPoint {
+    fn from((x, y): (i64, i64)) -> Self {
+        IPoint { x, y }
+    }
+}
+
+/// A closed ring of integer points. The last vertex is implicitly joined back to the
+/// first, so it should not be repeated.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IPolygon {
+    vertices: Vec<IPoint>,
+}
+
+#[cfg(feature = "alloc")]
+impl IPolygon {
+    pub fn new(vertices: Vec<IPoint>) -> IPolygon {
+        IPolygon { vertices }
+    }
+
+    pub fn vertices(&self) -> &[IPoint] {
+        &self.vertices
+    }
+
+    /// Iterate over the edges of the ring as `(start, end)` pairs, including the closing
+    /// edge from the last vertex back to the first
+    pub fn edges(&self) -> impl Iterator<Item = (IPoint, IPoint)> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |i| (self.vertices[i], self.vertices[(i + 1) % n]))
+    }
+
+    /// Twice the signed area of the ring, computed exactly. Positive for
+    /// counter-clockwise rings.
+    /// ```
+    /// use rsgeo::{IPoint, IPolygon};
+    /// let square = IPolygon::new(vec![
+    ///     IPoint::new(0, 0), IPoint::new(2, 0), IPoint::new(2, 2), IPoint::new(0, 2),
+    /// ]);
+    /// assert_eq!(square.signed_area2(), 8);
+    /// ```
+    pub fn signed_area2(&self) -> i128 {
+        self.edges().map(|(a, b)| a.cross(&b)).sum()
+    }
+
+    /// The unsigned area of the ring
+    pub fn area(&self) -> f64 {
+        self.signed_area2().abs() as f64 / 2.0
+    }
+
+    /// Whether the ring winds counter-clockwise. Degenerate rings with zero area are not.
+    pub fn is_ccw(&self) -> bool {
+        self.signed_area2() > 0
+    }
+
+    /// Reverse the winding direction of the ring in place
+    pub fn reverse(&mut self) {
+        self.vertices.reverse();
+    }
+}
+
+/// Converts between float coordinates and the integer grid by multiplying by `scale`
+/// and rounding to the nearest integer. A scale of 1000 keeps three decimal places.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scaling {
+    scale: f64,
+}
+
+impl Scaling {
+    /// Use a fixed scale factor. Panics if `scale` is not finite and positive.
+    pub fn new(scale: f64) -> Scaling {
+        assert!(
+            scale.is_finite() && scale > 0.0,
+            "scale must be finite and positive, got {}",
+            scale
+        );
+        Scaling { scale }
+    }
+
+    /// Choose the largest power of ten scale that keeps every coordinate up to
+    /// `max_abs` in magnitude within `MAX_COORD`. This keeps as much precision as
+    /// possible for data with a known extent.
+    /// ```
+    /// let s = rsgeo::Scaling::for_extent(1000.0);
+    /// assert_eq!(s.scale(), 1e15);
+    /// ```
+    pub fn for_extent(max_abs: f64) -> Scaling {
+        let max_abs = max_abs.abs().max(f64::MIN_POSITIVE);
+        let exp = Float::floor(Float::log10(MAX_COORD as f64 / max_abs));
+        Scaling::new(Float::powi(10f64, exp as i32))
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Snap a float point onto the integer grid. Returns None if either coordinate is
+    /// not finite, or would be larger than `MAX_COORD` in magnitude after scaling.
+    pub fn to_int<T: Scalar>(&self, p: Point<T>) -> Option<IPoint> {
+        Some(IPoint {
+            x: self.scale_coord(p.x.as_f64())?,
+            y: self.scale_coord(p.y.as_f64())?,
+        })
+    }
+
+    /// Map an integer point back to float coordinates
+    pub fn to_float<T: Scalar>(&self, p: IPoint) -> Point<T> {
+        Point {
+            x: T::from_f64(p.x as f64 / self.scale),
+            y: T::from_f64(p.y as f64 / self.scale),
+        }
+    }
+
+    /// Snap a ring of float points onto the integer grid. Returns None if any of the
+    /// points could not be converted, see `Scaling::to_int`.
+    #[cfg(feature = "alloc")]
+    pub fn polygon_to_int<T: Scalar>(&self, ring: &[Point<T>]) -> Option<IPolygon> {
+        ring.iter()
+            .map(|&p| self.to_int(p))
+            .collect::<Option<Vec<_>>>()
+            .map(IPolygon::new)
+    }
+
+    /// Map an integer ring back to float coordinates
+    #[cfg(feature = "alloc")]
+    pub fn polygon_to_float<T: Scalar>(&self, poly: &IPolygon) -> Vec<Point<T>> {
+        poly.vertices.iter().map(|&p| self.to_float(p)).collect()
+    }
+
+    fn scale_coord(&self, v: f64) -> Option<i64> {
+        let s = Float::round(v * self.scale);
+        // `MAX_COORD` rounds up to 2^62 as a float, which is itself too big
+        if s.is_finite() && s.abs() < MAX_COORD as f64 {
+            Some(s as i64)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for Scaling {
+    /// A scale of one, i.e. plain rounding to the nearest integer
+    fn default() -> Self {
+        Scaling::new(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orient_is_exact_for_large_coordinates() {
+        // Three collinear points far from the origin, where f64 would lose precision
+        let a = IPoint::new(MAX_COORD - 3, MAX_COORD - 3);
+        let b = IPoint::new(MAX_COORD - 2, MAX_COORD - 2);
+        let c = IPoint::new(MAX_COORD - 1, MAX_COORD - 1);
+        assert_eq!(0, IPoint::orient(&a, &b, &c));
+        let d = IPoint::new(MAX_COORD - 1, MAX_COORD);
+        assert!(IPoint::orient(&a, &b, &d) > 0)
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_polygon_orientation_and_reverse() {
+        let mut tri = IPolygon::new(vec![
+            IPoint::new(0, 0),
+            IPoint::new(0, 4),
+            IPoint::new(3, 0),
+        ]);
+        assert!(!tri.is_ccw());
+        assert_eq!(6.0, tri.area());
+        tri.reverse();
+        assert!(tri.is_ccw());
+        assert_eq!(12, tri.signed_area2())
+    }
+
+    #[test]
+    fn test_scaling_round_trip() {
+        let s = Scaling::new(1000.0);
+        let p = Point {
+            x: 1.2345,
+            y: -0.0004,
+        };
+        let ip = s.to_int(p).unwrap();
+        assert_eq!(IPoint::new(1235, 0), ip);
+        let back: Point = s.to_float(ip);
+        assert!(back.isclose_with(p, 1e-3, 0.0))
+    }
+
+    #[test]
+    fn test_scaling_rejects_out_of_range() {
+        let s = Scaling::for_extent(10.0);
+        assert!(s.to_int(Point { x: 10.0, y: -10.0 }).is_some());
+        assert!(s.to_int(Point { x: 1000.0, y: 0.0 }).is_none());
+        assert!(s
+            .to_int(Point {
+                x: f64::NAN,
+                y: 0.0
+            })
+            .is_none());
+        // The largest float below 2^62 still fits, but 2^62 itself does not
+        let s = Scaling::new(4.0);
+        let limit = (1u64 << 62) as f64 / 4.0;
+        let near = limit - 128.0;
+        assert_eq!(
+            Some(IPoint::new((1 << 62) - 512, 0)),
+            s.to_int(Point { x: near, y: 0.0 })
+        );
+        assert_eq!(None, s.to_int(Point { x: limit, y: 0.0 }));
+        assert_eq!(None, s.to_int(Point { x: 0.0, y: -limit }))
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_polygon_to_int() {
+        let s = Scaling::default();
+        let ring = vec![
+            Point { x: 0.4, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 2.0, y: 1.6 },
+        ];
+        let poly = s.polygon_to_int(&ring).unwrap();
+        assert_eq!(
+            &[IPoint::new(0, 0), IPoint::new(2, 0), IPoint::new(2, 2)],
+            poly.vertices()
+        );
+        assert_eq!(3, s.polygon_to_float::<f64>(&poly).len())
+    }
+}
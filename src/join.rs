@@ -0,0 +1,135 @@
+use alloc::vec::Vec;
+
+use crate::{Point, Polygon, PreparedPolygon, RTree, Rect, Scalar};
+
+/// For each of `points`, the index of the first of `polygons` that holds it, inside or
+/// on its boundary, or None if none do. The polygons are put in an R-tree by their
+/// bounding rectangles, so each point is only tested against the few polygons whose
+/// rectangles hold it, and each polygon is prepared so that a test looks at only a few
+/// of its edges. Joining n points to m polygons then takes about O((n + m) log m) time
+/// for polygons that don't overlap much, however many edges they have.
+/// ```
+/// use rsgeo::{spatial_join, Point, Polygon};
+/// let square = |x: f64| Polygon::new(vec![
+///     Point{x, y: 0.0},
+///     Point{x: x + 1.0, y: 0.0},
+///     Point{x: x + 1.0, y: 1.0},
+///     Point{x, y: 1.0},
+/// ]);
+/// let zones = [square(0.0), square(2.0)];
+/// let points = [Point{x: 2.5, y: 0.5}, Point{x: 1.5, y: 0.5}, Point{x: 0.5, y: 0.5}];
+/// assert_eq!(spatial_join(&points, &zones), vec![Some(1), None, Some(0)]);
+/// ```
+pub fn spatial_join<T: Scalar>(points: &[Point<T>], polygons: &[Polygon<T>]) -> Vec<Option<usize>> {
+    let (tree, prepared) = index(polygons);
+    let mut candidates = Vec::new();
+    points
+        .iter()
+        .map(|&p| {
+            gather(&tree, p, &mut candidates);
+            candidates
+                .iter()
+                .copied()
+                .find(|&i| prepared[i].contains(p))
+        })
+        .collect()
+}
+
+/// Every pair of the index of one of `points` and the index of one of `polygons` that
+/// holds it, inside or on its boundary, in order of the points and then the polygons.
+/// Points in no polygon are left out, and points where polygons overlap are given once
+/// for each. See `spatial_join` for how the polygons are found.
+pub fn spatial_join_all<T: Scalar>(
+    points: &[Point<T>],
+    polygons: &[Polygon<T>],
+) -> Vec<(usize, usize)> {
+    let (tree, prepared) = index(polygons);
+    let mut candidates = Vec::new();
+    let mut found = Vec::new();
+    for (i, &p) in points.iter().enumerate() {
+        gather(&tree, p, &mut candidates);
+        found.extend(
+            candidates
+                .iter()
+                .filter(|&&j| prepared[j].contains(p))
+                .map(|&j| (i, j)),
+        );
+    }
+    found
+}
+
+/// The R-tree over the bounding rectangles of `polygons`, and the polygons prepared
+fn index<T: Scalar>(polygons: &[Polygon<T>]) -> (RTree<T, usize>, Vec<PreparedPolygon<T>>) {
+    let prepared = polygons.iter().map(PreparedPolygon::new).collect();
+    (RTree::from_shapes(polygons), prepared)
+}
+
+/// Replace `candidates` with the indices of the polygons whose rectangles hold `p`, in
+/// order
+fn gather<T: Scalar>(tree: &RTree<T, usize>, p: Point<T>, candidates: &mut Vec<usize>) {
+    candidates.clear();
+    tree.visit_rect(&Rect::from_corners(p, p), |_, &i| candidates.push(i));
+    candidates.sort_unstable();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scattered;
+
+    /// Triangles around scattered centers, some of them overlapping
+    fn zones() -> Vec<Polygon> {
+        scattered(150, 5)
+            .into_iter()
+            .map(|c| {
+                let corner = |dx: f64, dy: f64| c + Point { x: dx, y: dy };
+                Polygon::new(vec![
+                    corner(-0.5, -0.4),
+                    corner(0.6, -0.3),
+                    corner(0.0, 0.7),
+                ])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let (points, polygons) = (scattered(3000, 2), zones());
+        let first = spatial_join(&points, &polygons);
+        let all = spatial_join_all(&points, &polygons);
+        let mut expected = Vec::new();
+        for (i, &p) in points.iter().enumerate() {
+            let holding: Vec<usize> = (0..polygons.len())
+                .filter(|&j| polygons[j].contains(p))
+                .collect();
+            assert_eq!(holding.first().copied(), first[i]);
+            expected.extend(holding.into_iter().map(|j| (i, j)));
+        }
+        assert!(expected.windows(2).any(|w| w[0].0 == w[1].0));
+        assert_eq!(expected, all);
+    }
+
+    #[test]
+    fn test_boundaries_and_empty_input() {
+        let unit = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 0.0, y: 1.0 },
+        ]);
+        let next = Polygon::new(
+            unit.vertices()
+                .iter()
+                .map(|&p| p + Point { x: 1.0, y: 0.0 })
+                .collect(),
+        );
+        let points = [Point { x: 1.0, y: 0.5 }, Point { x: 2.0, y: 1.0 }];
+        // A point on an edge shared by two polygons is in both
+        assert_eq!(
+            vec![(0, 0), (0, 1), (1, 1)],
+            spatial_join_all(&points, &[unit.clone(), next.clone()])
+        );
+        assert_eq!(vec![None, None], spatial_join(&points, &[]));
+        assert!(spatial_join(&[], &[unit, next]).is_empty());
+    }
+}
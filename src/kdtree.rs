@@ -0,0 +1,443 @@
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{BoundingRect, Point, Polygon, Rect, Scalar};
+
+/// A 2-d tree over points, each carrying a payload, for finding the points nearest a
+/// query point or within some distance of it in time that grows with the logarithm
+/// of the number of points rather than in proportion to it. The tree is balanced and
+/// kept in a single array: the middle point of each range splits the rest of it in
+/// two at its x or y coordinate, by turns at each level down.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KdTree<T = f64, D = usize> {
+    items: Vec<(Point<T>, D)>,
+}
+
+/// The points found by a query, with their distances and payloads, nearest first
+type Found<'a, T, D> = Vec<(T, Point<T>, &'a D)>;
+
+impl<T: Scalar> KdTree<T, usize> {
+    /// The tree over `points`, in O(n log n) time, with the index of each point as its
+    /// payload
+    /// ```
+    /// use rsgeo::{KdTree, Point};
+    /// let points = [Point{x: 0.0, y: 0.0}, Point{x: 3.0, y: 1.0}, Point{x: 1.0, y: 1.0}];
+    /// let tree = KdTree::new(&points);
+    /// assert_eq!(tree.nearest(Point{x: 2.5, y: 0.0}), Some((1.118033988749895, points[1], &1)));
+    /// let near: Vec<usize> = tree
+    ///     .within_radius(Point{x: 0.0, y: 0.5}, 1.2)
+    ///     .into_iter()
+    ///     .map(|(_, _, &i)| i)
+    ///     .collect();
+    /// assert_eq!(near, vec![0, 2]);
+    /// ```
+    pub fn new(points: &[Point<T>]) -> KdTree<T, usize> {
+        KdTree::with_payloads(points.iter().copied().zip(0..).collect())
+    }
+}
+
+impl<T: Scalar, D> KdTree<T, D> {
+    /// The tree over the points in `items`, each with its payload, in O(n log n) time
+    pub fn with_payloads(mut items: Vec<(Point<T>, D)>) -> KdTree<T, D> {
+        split(&mut items, 0);
+        KdTree { items }
+    }
+
+    /// The number of points in the tree
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether the tree has no points
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Every point in the tree with its payload, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point<T>, &D)> + '_ {
+        self.items.iter().map(|(p, d)| (*p, d))
+    }
+
+    /// The point nearest `p`, with its distance and payload, or None if the tree is
+    /// empty. Of points equally near, any one may be given.
+    pub fn nearest(&self, p: Point<T>) -> Option<(T, Point<T>, &D)> {
+        self.k_nearest(p, 1).pop()
+    }
+
+    /// The `k` points nearest `p`, or all of them if there are fewer, nearest first
+    pub fn k_nearest(&self, p: Point<T>, k: usize) -> Found<'_, T, D> {
+        let mut best = Vec::with_capacity(k.min(self.len()) + 1);
+        if k > 0 {
+            let query = Query {
+                p,
+                k,
+                reach: T::infinity(),
+            };
+            self.search(0, self.len(), 0, &query, &mut best);
+        }
+        self.found(best)
+    }
+
+    /// The points no further than `radius` from `p`, nearest first
+    pub fn within_radius(&self, p: Point<T>, radius: T) -> Found<'_, T, D> {
+        let mut best = Vec::new();
+        if radius >= T::zero() {
+            let query = Query {
+                p,
+                k: usize::MAX,
+                reach: radius * radius,
+            };
+            self.search(0, self.len(), 0, &query, &mut best);
+        }
+        self.found(best)
+    }
+
+    /// The points inside `rect` or on its boundary, with their payloads, in no
+    /// particular order
+    pub fn range_query(&self, rect: &Rect<T>) -> Vec<(Point<T>, &D)> {
+        let mut found = Vec::new();
+        self.gather(0, self.len(), 0, rect, &mut found);
+        found
+            .into_iter()
+            .map(|i| (self.items[i].0, &self.items[i].1))
+            .collect()
+    }
+
+    /// The points inside `polygon` or on its boundary, with their payloads, in no
+    /// particular order. Only the points in the bounding rectangle of the polygon are
+    /// tested against the polygon itself.
+    /// ```
+    /// use rsgeo::{KdTree, Point, Polygon};
+    /// let points: Vec<Point> = (0..25).map(|i| Point{x: (i % 5) as f64, y: (i / 5) as f64}).collect();
+    /// let tree = KdTree::new(&points);
+    /// let triangle = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0},
+    ///     Point{x: 4.0, y: 0.0},
+    ///     Point{x: 0.0, y: 4.0},
+    /// ]);
+    /// let mut found: Vec<usize> = tree.range_query_polygon(&triangle).iter().map(|f| *f.1).collect();
+    /// found.sort_unstable();
+    /// assert_eq!(found, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 10, 11, 12, 15, 16, 20]);
+    /// ```
+    pub fn range_query_polygon(&self, polygon: &Polygon<T>) -> Vec<(Point<T>, &D)> {
+        let mut found = match polygon.bounding_rect() {
+            Some(rect) => self.range_query(&rect),
+            None => Vec::new(),
+        };
+        found.retain(|f| polygon.contains(f.0));
+        found
+    }
+
+    /// Every point in the tree with its distance and payload, nearest `p` first, found
+    /// one at a time as they are asked for. Getting the first `k` takes about as long
+    /// as `k_nearest`, so this is the way to search when how many are wanted depends
+    /// on what is found. Points equally near may come in any order.
+    /// ```
+    /// use rsgeo::{KdTree, Point};
+    /// let points: Vec<Point> = (0..10).map(|i| Point{x: i as f64, y: 0.0}).collect();
+    /// let tree = KdTree::new(&points);
+    /// // The points nearest the origin until their x coordinates add up to 10
+    /// let mut total = 0.0;
+    /// let found: Vec<usize> = tree
+    ///     .nearest_iter(Point{x: -1.0, y: 0.0})
+    ///     .take_while(|(_, p, _)| {
+    ///         total += p.x;
+    ///         total <= 10.0
+    ///     })
+    ///     .map(|(_, _, &i)| i)
+    ///     .collect();
+    /// assert_eq!(found, vec![0, 1, 2, 3, 4]);
+    /// ```
+    pub fn nearest_iter(&self, p: Point<T>) -> impl Iterator<Item = (T, Point<T>, &D)> + '_ {
+        let mut heap = BinaryHeap::new();
+        if !self.is_empty() {
+            heap.push(Pending {
+                d: T::zero(),
+                waiting: Waiting::Range(0, self.len(), 0),
+            });
+        }
+        core::iter::from_fn(move || loop {
+            let Pending { d, waiting } = heap.pop()?;
+            let (lo, hi, depth) = match waiting {
+                Waiting::Point(i) => {
+                    return Some((d.sqrt(), self.items[i].0, &self.items[i].1));
+                }
+                Waiting::Range(lo, hi, depth) => (lo, hi, depth),
+            };
+            let mid = lo + (hi - lo) / 2;
+            let q = self.items[mid].0;
+            heap.push(Pending {
+                d: (q - p).magnitude_squared(),
+                waiting: Waiting::Point(mid),
+            });
+            // Everything on the far side of the split line is at least as far as the line
+            let offset = axis(p, depth) - axis(q, depth);
+            let beyond = d.max(offset * offset);
+            let (below, above) = match offset < T::zero() {
+                true => (d, beyond),
+                false => (beyond, d),
+            };
+            for (lo, hi, d) in [(lo, mid, below), (mid + 1, hi, above)] {
+                if lo < hi {
+                    heap.push(Pending {
+                        d,
+                        waiting: Waiting::Range(lo, hi, depth + 1),
+                    });
+                }
+            }
+        })
+    }
+
+    /// Gather into `best` the points in the range from `lo` to `hi` that `query` asks
+    /// for, keeping them in order of distance
+    fn search(
+        &self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        query: &Query<T>,
+        best: &mut Vec<(T, usize)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let Query { p, k, reach } = *query;
+        let mid = lo + (hi - lo) / 2;
+        let q = self.items[mid].0;
+        let bound = |best: &Vec<(T, usize)>| match best.len() == k {
+            true => best[k - 1].0,
+            false => reach,
+        };
+        let d = (q - p).magnitude_squared();
+        if d <= bound(best) {
+            let at = best.partition_point(|&(e, _)| e <= d);
+            best.insert(at, (d, mid));
+            best.truncate(k);
+        }
+        // The side of the split holding `p` first, and then the other side if the
+        // split line is near enough to hold anything nearer
+        let offset = axis(p, depth) - axis(q, depth);
+        let (near, far) = match offset < T::zero() {
+            true => ((lo, mid), (mid + 1, hi)),
+            false => ((mid + 1, hi), (lo, mid)),
+        };
+        self.search(near.0, near.1, depth + 1, query, best);
+        if offset * offset <= bound(best) {
+            self.search(far.0, far.1, depth + 1, query, best);
+        }
+    }
+
+    /// Gather into `found` the indices of the points in the range from `lo` to `hi`
+    /// that are in `rect`
+    fn gather(&self, lo: usize, hi: usize, depth: usize, rect: &Rect<T>, found: &mut Vec<usize>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let q = self.items[mid].0;
+        if rect.contains(q) {
+            found.push(mid);
+        }
+        let at = axis(q, depth);
+        if axis(rect.min(), depth) <= at {
+            self.gather(lo, mid, depth + 1, rect, found);
+        }
+        if axis(rect.max(), depth) >= at {
+            self.gather(mid + 1, hi, depth + 1, rect, found);
+        }
+    }
+
+    fn found(&self, best: Vec<(T, usize)>) -> Found<'_, T, D> {
+        best.into_iter()
+            .map(|(d, i)| (d.sqrt(), self.items[i].0, &self.items[i].1))
+            .collect()
+    }
+}
+
+/// What a search is for: up to `k` of the points nearest `p`, with squared distances
+/// up to `reach`
+#[derive(Clone, Copy)]
+struct Query<T> {
+    p: Point<T>,
+    k: usize,
+    reach: T,
+}
+
+/// A part of the tree waiting to be looked at by `nearest_iter`, with the squared
+/// distance to it, or for a range of points a bound that none of them are nearer than
+struct Pending<T> {
+    d: T,
+    waiting: Waiting,
+}
+
+#[derive(Clone, Copy)]
+enum Waiting {
+    /// The point at an index, to be given next if nothing is nearer
+    Point(usize),
+    /// The points in a range, and their depth in the tree, still to be split
+    Range(usize, usize, usize),
+}
+
+impl<T: Scalar> PartialEq for Pending<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Scalar> Eq for Pending<T> {}
+
+impl<T: Scalar> PartialOrd for Pending<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Scalar> Ord for Pending<T> {
+    /// Nearer is greater, so that the nearest comes off the heap first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.d.total_cmp(&self.d)
+    }
+}
+
+/// The coordinate that the points at `depth` in the tree are split at
+fn axis<T: Scalar>(p: Point<T>, depth: usize) -> T {
+    match depth % 2 {
+        0 => p.x,
+        _ => p.y,
+    }
+}
+
+/// Arrange `items` so that each range has its middle point split the rest in two
+fn split<T: Scalar, D>(items: &mut [(Point<T>, D)], depth: usize) {
+    if items.len() < 2 {
+        return;
+    }
+    let mid = items.len() / 2;
+    items.select_nth_unstable_by(mid, |a, b| {
+        axis(a.0, depth)
+            .partial_cmp(&axis(b.0, depth))
+            .unwrap_or(Ordering::Equal)
+    });
+    let (left, right) = items.split_at_mut(mid);
+    split(left, depth + 1);
+    split(&mut right[1..], depth + 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scattered;
+
+    #[test]
+    fn test_matches_brute_force() {
+        let points = scattered(1000, 3);
+        let tree = KdTree::new(&points);
+        for q in scattered(200, 7) {
+            let mut order: Vec<usize> = (0..points.len()).collect();
+            order.sort_by(|&a, &b| points[a].distance(&q).total_cmp(&points[b].distance(&q)));
+            let (d, p, &i) = tree.nearest(q).unwrap();
+            assert_eq!((points[order[0]], order[0]), (p, i));
+            assert_eq!(points[i].distance(&q), d);
+            let near: Vec<usize> = tree.k_nearest(q, 10).iter().map(|f| *f.2).collect();
+            assert_eq!(&order[..10], &near[..]);
+            let within: Vec<usize> = tree.within_radius(q, 0.7).iter().map(|f| *f.2).collect();
+            let expected: Vec<usize> = order
+                .iter()
+                .copied()
+                .take_while(|&i| points[i].distance(&q) <= 0.7)
+                .collect();
+            assert_eq!(expected, within);
+        }
+    }
+
+    #[test]
+    fn test_nearest_iter() {
+        let points = scattered(1000, 5);
+        let tree = KdTree::new(&points);
+        for q in scattered(50, 8) {
+            let all: Vec<(f64, Point, &usize)> = tree.nearest_iter(q).collect();
+            assert_eq!(points.len(), all.len());
+            assert!(all.windows(2).all(|w| w[0].0 <= w[1].0));
+            assert!(all
+                .iter()
+                .all(|&(d, p, &i)| p == points[i] && d == p.distance(&q)));
+            let distances: Vec<f64> = tree.k_nearest(q, 25).iter().map(|f| f.0).collect();
+            let first: Vec<f64> = all[..25].iter().map(|f| f.0).collect();
+            assert_eq!(distances, first);
+        }
+        assert_eq!(
+            None,
+            KdTree::<f64>::new(&[]).nearest_iter(Point::origin()).next()
+        );
+    }
+
+    #[test]
+    fn test_range_queries() {
+        let points = scattered(1000, 6);
+        let tree = KdTree::new(&points);
+        let corners = scattered(40, 2);
+        for pair in corners.chunks(2) {
+            let rect = Rect::from_corners(pair[0], pair[1]);
+            let mut found: Vec<usize> = tree.range_query(&rect).iter().map(|f| *f.1).collect();
+            found.sort_unstable();
+            let expected: Vec<usize> = (0..points.len())
+                .filter(|&i| rect.contains(points[i]))
+                .collect();
+            assert_eq!(expected, found);
+        }
+        // A polygon with a hole, and with points exactly on the boundary
+        let grid: Vec<Point> = (0..121)
+            .map(|i| Point {
+                x: (i % 11) as f64,
+                y: (i / 11) as f64,
+            })
+            .collect();
+        let tree = KdTree::new(&grid);
+        let square = |lo: f64, hi: f64| {
+            vec![
+                Point { x: lo, y: lo },
+                Point { x: hi, y: lo },
+                Point { x: hi, y: hi },
+                Point { x: lo, y: hi },
+            ]
+        };
+        let frame = Polygon::with_holes(square(2.0, 8.0), vec![square(4.0, 6.0)]);
+        let found = tree.range_query_polygon(&frame);
+        assert_eq!(49 - 1, found.len());
+        assert!(found.iter().all(|f| frame.contains(f.0)));
+        assert!(tree.range_query_polygon(&Polygon::new(vec![])).is_empty());
+    }
+
+    #[test]
+    fn test_payloads() {
+        let items = vec![
+            (Point { x: 0.0, y: 0.0 }, "origin"),
+            (Point { x: 5.0, y: 5.0 }, "far"),
+            (Point { x: 1.0, y: 0.0 }, "east"),
+        ];
+        let tree = KdTree::with_payloads(items);
+        assert_eq!(3, tree.len());
+        let names: Vec<&str> = tree
+            .k_nearest(Point { x: 0.9, y: 0.1 }, 2)
+            .iter()
+            .map(|f| *f.2)
+            .collect();
+        assert_eq!(vec!["east", "origin"], names);
+        assert_eq!(3, tree.iter().count());
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let empty = KdTree::<f64>::new(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(None, empty.nearest(Point::origin()));
+        // Repeated points are all found, and asking for more than there are gives all
+        let same = vec![Point { x: 1.0, y: 1.0 }; 20];
+        let tree = KdTree::new(&same);
+        assert_eq!(20, tree.k_nearest(Point::origin(), 50).len());
+        assert_eq!(20, tree.within_radius(Point { x: 1.0, y: 1.0 }, 0.0).len());
+        assert!(tree.k_nearest(Point::origin(), 0).is_empty());
+        assert!(tree.within_radius(Point::origin(), 1.0).is_empty());
+    }
+}
@@ -1,29 +1,276 @@
-#![feature(test)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(test, feature(test))]
 
-use std::ops::{Add, Sub};
+use core::fmt;
+use core::num::ParseFloatError;
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+};
+use core::str::FromStr;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
 
-const ATOL: f64 = 1e-8;
-const RTOL: f64 = 1e-5;
+/// Apply `$m!(A, B)` to every pair of types drawn from the two lists
+macro_rules! for_pairs {
+    ($m:ident; [$($a:ident),*] x $bs:tt) => {
+        $( for_pairs!(@row $m; $a $bs); )*
+    };
+    (@row $m:ident; $a:ident [$($b:ident),*]) => {
+        $( $m!($a, $b); )*
+    };
+}
+
+mod angle;
+mod arc;
+mod bezier;
+#[cfg(feature = "alloc")]
+mod boolean;
+#[cfg(feature = "alloc")]
+mod bvh;
+#[cfg(feature = "alloc")]
+mod calipers;
+mod circle;
+#[cfg(feature = "alloc")]
+mod clip;
+#[cfg(feature = "alloc")]
+mod closest;
+#[cfg(feature = "alloc")]
+mod concave;
+#[cfg(feature = "alloc")]
+mod contour;
+#[cfg(feature = "alloc")]
+mod decompose;
+#[cfg(feature = "alloc")]
+mod delaunay;
+mod distance;
+#[cfg(feature = "alloc")]
+mod earcut;
+mod ellipse;
+#[cfg(feature = "rational")]
+pub mod exact;
+#[cfg(feature = "alloc")]
+mod geometry;
+#[cfg(feature = "alloc")]
+mod grid;
+#[cfg(feature = "alloc")]
+mod hashgrid;
+#[cfg(feature = "alloc")]
+mod hausdorff;
+#[cfg(feature = "alloc")]
+mod hull;
+#[cfg(feature = "alloc")]
+mod idw;
+#[cfg(feature = "alloc")]
+mod inscribed;
+mod integer;
+#[cfg(feature = "alloc")]
+mod join;
+#[cfg(feature = "alloc")]
+mod kdtree;
+mod line;
+mod measure;
+#[cfg(feature = "alloc")]
+mod medial;
+#[cfg(feature = "alloc")]
+mod minkowski;
+#[cfg(feature = "alloc")]
+mod monotone;
+#[cfg(feature = "alloc")]
+mod multipoint;
+#[cfg(feature = "alloc")]
+mod multipolygon;
+#[cfg(feature = "alloc")]
+mod natural;
+mod obb;
+#[cfg(feature = "alloc")]
+mod offset;
+mod ordered;
+#[cfg(feature = "alloc")]
+mod polygon;
+#[cfg(feature = "alloc")]
+mod polyline;
+pub mod predicates;
+#[cfg(feature = "alloc")]
+mod prepared;
+#[cfg(feature = "alloc")]
+mod quadtree;
+mod ray;
+mod rect;
+#[cfg(feature = "alloc")]
+mod refine;
+#[cfg(feature = "alloc")]
+mod relate;
+#[cfg(feature = "alloc")]
+mod rtree;
+mod scalar;
+#[cfg(feature = "alloc")]
+mod sdf;
+mod segment;
+#[cfg(feature = "serde")]
+pub mod serde_compact;
+#[cfg(feature = "alloc")]
+mod simplify;
+#[cfg(feature = "alloc")]
+mod skeleton;
+#[cfg(feature = "alloc")]
+mod smooth;
+mod spacefill;
+#[cfg(feature = "alloc")]
+mod split;
+#[cfg(feature = "alloc")]
+mod sweep;
+#[cfg(feature = "alloc")]
+mod tin;
+mod tolerance;
+mod topology;
+mod triangle;
+#[cfg(feature = "alloc")]
+mod voronoi;
+
+pub use angle::Angle;
+pub use arc::{Arc, ArcDirection};
+pub use bezier::{CubicBezier, QuadBezier};
+#[cfg(feature = "alloc")]
+pub use boolean::{BooleanOp, BooleanOps};
+#[cfg(feature = "alloc")]
+pub use bvh::Bvh;
+#[cfg(feature = "alloc")]
+pub use calipers::{min_area_rect, min_perimeter_rect};
+pub use circle::Circle;
+#[cfg(feature = "alloc")]
+pub use clip::clip_polygon_convex;
+#[cfg(feature = "alloc")]
+pub use closest::closest_pair;
+#[cfg(feature = "alloc")]
+pub use concave::{alpha_shape, concave_hull};
+#[cfg(feature = "alloc")]
+pub use delaunay::{constrained_delaunay, delaunay_triangulation, Delaunay};
+pub use distance::Distance;
+pub use ellipse::Ellipse;
+#[cfg(feature = "alloc")]
+pub use geometry::{Geometry, GeometryCollection};
+#[cfg(feature = "alloc")]
+pub use grid::{Grid, GridSpec};
+#[cfg(feature = "alloc")]
+pub use hashgrid::HashGrid;
+#[cfg(feature = "alloc")]
+pub use hausdorff::Hausdorff;
+#[cfg(feature = "alloc")]
+pub use hull::{convex_hull, convex_layers};
+#[cfg(feature = "alloc")]
+pub use idw::{Idw, IdwSearch};
+#[cfg(feature = "alloc")]
+pub use integer::IPolygon;
+pub use integer::{IPoint, Scaling, MAX_COORD};
+#[cfg(feature = "alloc")]
+pub use join::{spatial_join, spatial_join_all};
+#[cfg(feature = "alloc")]
+pub use kdtree::KdTree;
+pub use line::Line;
+pub use measure::{Area, BoundingRect, Centroid, Length};
+#[cfg(feature = "alloc")]
+pub use medial::MedialAxis;
+#[cfg(feature = "alloc")]
+pub use minkowski::minkowski_sum_convex;
+#[cfg(feature = "alloc")]
+pub use multipoint::MultiPoint;
+#[cfg(feature = "alloc")]
+pub use multipolygon::MultiPolygon;
+#[cfg(feature = "alloc")]
+pub use natural::NaturalNeighbor;
+pub use obb::Obb;
+#[cfg(feature = "alloc")]
+pub use offset::{Cap, Join};
+pub use ordered::OrderedPoint;
+#[cfg(feature = "alloc")]
+pub use polygon::{FillRule, Polygon};
+#[cfg(feature = "alloc")]
+pub use polyline::Polyline;
+#[cfg(feature = "alloc")]
+pub use prepared::{PreparedPolygon, PreparedPolyline};
+#[cfg(feature = "alloc")]
+pub use quadtree::Quadtree;
+pub use ray::{Ray, RayCast, RayHit};
+pub use rect::Rect;
+#[cfg(feature = "alloc")]
+pub use refine::TriangleMesh;
+#[cfg(feature = "alloc")]
+pub use relate::{relate, Dimension, IntersectionMatrix, Part, Relate};
+#[cfg(feature = "alloc")]
+pub use rtree::RTree;
+pub use scalar::Scalar;
+#[cfg(feature = "alloc")]
+pub use sdf::{sdf, SignedDistance};
+pub use segment::{LineSegment, SegmentIntersection};
+#[cfg(feature = "alloc")]
+pub use simplify::simplify_coverage;
+#[cfg(feature = "alloc")]
+pub use skeleton::StraightSkeleton;
+pub use spacefill::{hilbert_index, morton_index, sort_by_space_filling_curve, SpaceFillingCurve};
+#[cfg(feature = "alloc")]
+pub use sweep::segment_intersections;
+#[cfg(feature = "alloc")]
+pub use tin::Tin;
+pub use tolerance::{ulps_distance, Tolerance};
+pub use topology::{Contains, Intersects, Touches, Within};
+pub use triangle::Triangle;
+#[cfg(feature = "alloc")]
+pub use voronoi::{voronoi_diagram, Voronoi, VoronoiCell, VoronoiEdge};
+
+/// The default absolute tolerance used by the `isclose` methods for `f64` coordinates.
+/// See `Scalar::DEFAULT_ATOL` for other precisions.
+pub const ATOL: f64 = 1e-8;
+/// The default relative tolerance used by the `isclose` methods for `f64` coordinates.
+/// See `Scalar::DEFAULT_RTOL` for other precisions.
+pub const RTOL: f64 = 1e-5;
 
 /// A point in 2D space. Can also be thought of as a 2D vector
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Point {
-    pub x: f64,
-    pub y: f64,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point<T = f64> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Point {
-    /// What is the angle (in radians) from the positive x-axis to the point. I.e. the
-    /// angle if this point were converted to polar coordinates
+impl<T: Scalar> Point<T> {
+    /// Create a point from polar coordinates: a distance `r` from the origin, at an angle
+    /// `theta` counter-clockwise from the positive x-axis. A bare scalar angle is in
+    /// radians.
+    /// ```
+    /// let p = rsgeo::Point::from_polar(2.0, std::f64::consts::PI / 2.0);
+    /// assert!(p.isclose(rsgeo::Point{x: 0.0, y: 2.0}));
+    /// let q = rsgeo::Point::from_polar(2.0, rsgeo::Angle::from_degrees(90.0));
+    /// assert!(q.isclose(p));
+    /// ```
+    pub fn from_polar(r: T, theta: impl Into<Angle<T>>) -> Point<T> {
+        let (s, c) = theta.into().sin_cos();
+        Point { x: r * c, y: r * s }
+    }
+
+    /// Convert the point to polar coordinates, returned as `(magnitude, angle)`. This is
+    /// the inverse of `Point::from_polar`.
+    /// ```
+    /// let p = rsgeo::Point{x: 0.0, y: 2.0};
+    /// let (r, theta) = p.to_polar();
+    /// assert_eq!(r, 2.0);
+    /// assert_eq!(theta, std::f64::consts::PI / 2.0);
+    /// ```
+    pub fn to_polar(&self) -> (T, Angle<T>) {
+        (self.magnitude(), self.angle())
+    }
+
+    /// What is the angle from the positive x-axis to the point. I.e. the angle if this
+    /// point were converted to polar coordinates. It lies in the range (-pi, pi].
     /// ```
     /// let p = rsgeo::Point{x: 1.0, y: 1.0};
     /// let a = p.angle();
     /// assert_eq!(a, std::f64::consts::PI / 4.0)
     /// ```
     #[must_use]
-    pub fn angle(&self) -> f64 {
-        self.y.atan2(self.x)
+    pub fn angle(&self) -> Angle<T> {
+        Angle::from_radians(self.y.atan2(self.x))
     }
 
     /// Multiply a point by a scalar
@@ -32,7 +279,7 @@ impl Point {
     /// let result = p.mul(3.0);
     /// assert_eq!(result, rsgeo::Point{x: 3.0, y: 3.0})
     ///```
-    pub fn mul(&self, x: f64) -> Point {
+    pub fn mul(&self, x: T) -> Point<T> {
         Point {
             x: self.x * x,
             y: self.y * x,
@@ -45,62 +292,261 @@ impl Point {
     /// let result = p.div(3.0);
     /// assert_eq!(result, rsgeo::Point{x: 1.0, y: 1.0})
     ///```
-    pub fn div(&self, x: f64) -> Point {
+    pub fn div(&self, x: T) -> Point<T> {
         Point {
             x: self.x / x,
             y: self.y / x,
         }
     }
 
-    /// rotate will rotate the point about the origin.
+    /// rotate will rotate the point counter-clockwise about the origin. A bare scalar
+    /// angle is in radians.
     /// ```
     /// let p = rsgeo::Point{x: 1.0, y: 1.0};
     /// // Rotate by 45 degrees (pi/4 rad)
     /// let result = p.rotate(std::f64::consts::PI / 4.0);
     /// assert!(result.isclose(rsgeo::Point{x: 0.0, y: 2.0_f64.sqrt()}))
     ///```
-    pub fn rotate(&self, angle: f64) -> Point {
-        let s = angle.sin();
-        let c = angle.cos();
+    pub fn rotate(&self, angle: impl Into<Angle<T>>) -> Point<T> {
+        self.rotate_about(Point::origin(), angle)
+    }
+
+    /// rotate_about will rotate the point counter-clockwise about `pivot`
+    /// ```
+    /// let p = rsgeo::Point{x: 2.0, y: 1.0};
+    /// let pivot = rsgeo::Point{x: 1.0, y: 1.0};
+    /// // Rotate by 90 degrees (pi/2 rad)
+    /// let result = p.rotate_about(pivot, std::f64::consts::PI / 2.0);
+    /// assert!(result.isclose(rsgeo::Point{x: 1.0, y: 2.0}))
+    ///```
+    pub fn rotate_about(&self, pivot: Point<T>, angle: impl Into<Angle<T>>) -> Point<T> {
+        let (s, c) = angle.into().sin_cos();
+        let dx = self.x - pivot.x;
+        let dy = self.y - pivot.y;
         Point {
-            x: (self.x * c) - (self.y * s),
-            y: (self.x * s) + (self.y * c),
+            x: pivot.x + (dx * c) - (dy * s),
+            y: pivot.y + (dx * s) + (dy * c),
         }
     }
 
     /// Check if two points are close to eachother
-    pub fn isclose(&self, other: Point) -> bool {
-        f64_isclose(self.x, other.x) && f64_isclose(self.y, other.y)
+    pub fn isclose(&self, other: Point<T>) -> bool {
+        scalar_isclose(self.x, other.x) && scalar_isclose(self.y, other.y)
+    }
+
+    /// Check if two points are close to eachother, using the given absolute and relative
+    /// tolerances instead of the defaults `Scalar::DEFAULT_ATOL` and `DEFAULT_RTOL`. Each component must
+    /// satisfy `|a - b| <= atol + rtol * |b|`.
+    /// ```
+    /// let p = rsgeo::Point{x: 1000.0, y: 1000.0};
+    /// let q = rsgeo::Point{x: 1000.4, y: 1000.0};
+    /// assert!(!p.isclose(q));
+    /// assert!(p.isclose_with(q, 0.5, 0.0));
+    ///```
+    pub fn isclose_with(&self, other: Point<T>, atol: T, rtol: T) -> bool {
+        scalar_isclose_with(self.x, other.x, atol, rtol)
+            && scalar_isclose_with(self.y, other.y, atol, rtol)
     }
 
     /// xintercept will calculate the x-intercept of an infinite line, as defined by the
-    /// two points `self` and `other`. If the line is horizontal, returns Inf.
-    pub fn xintercept(&self, other: &Point) -> f64 {
+    /// two points `self` and `other`. If the line is horizontal, returns Inf. See `Line`
+    /// for working with infinite lines more generally.
+    pub fn xintercept(&self, other: &Point<T>) -> T {
         let i = self.x - (self.y * (other.x - self.x) / (other.y - self.y));
-        if f64::is_infinite(i) {
-            f64::INFINITY
+        if i.is_infinite() {
+            T::infinity()
         } else {
             i
         }
     }
 
     /// magnitude computes the magnitude of the vector
-    pub fn magnitude(&self) -> f64 {
+    pub fn magnitude(&self) -> T {
         (self.x.powi(2) + self.y.powi(2)).sqrt()
     }
 
+    /// magnitude_squared computes the squared magnitude of the vector, avoiding the sqrt
+    pub fn magnitude_squared(&self) -> T {
+        self.x * self.x + self.y * self.y
+    }
+
+    /// distance computes the euclidean distance between two points
+    /// ```
+    /// let p = rsgeo::Point{x: 1.0, y: 1.0};
+    /// let q = rsgeo::Point{x: 4.0, y: 5.0};
+    /// assert_eq!(p.distance(&q), 5.0)
+    ///```
+    pub fn distance(&self, other: &Point<T>) -> T {
+        (*other - *self).magnitude()
+    }
+
+    /// distance_squared computes the squared euclidean distance between two points. It
+    /// orders the same as `distance`, but is cheaper since it skips the sqrt.
+    pub fn distance_squared(&self, other: &Point<T>) -> T {
+        (*other - *self).magnitude_squared()
+    }
+
     /// normalize will normalize a point to unit magnitude
-    pub fn normalize(&self) -> Point {
+    pub fn normalize(&self) -> Point<T> {
         self.div(self.magnitude())
     }
 
     /// Compute the dot product of two Points
-    pub fn dot_product(&self, other: &Point) -> f64 {
+    pub fn dot_product(&self, other: &Point<T>) -> T {
         self.x * other.x + self.y * other.y
     }
+
+    /// Compute the 2D cross product of two Points, i.e. the z-component of the 3D
+    /// cross product. Positive when `other` is counter-clockwise from `self`.
+    /// ```
+    /// let p = rsgeo::Point{x: 1.0, y: 0.0};
+    /// let q = rsgeo::Point{x: 0.0, y: 1.0};
+    /// assert_eq!(p.cross(&q), 1.0);
+    /// assert_eq!(q.cross(&p), -1.0);
+    ///```
+    pub fn cross(&self, other: &Point<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// perp returns the vector rotated 90 degrees counter-clockwise
+    /// ```
+    /// let p = rsgeo::Point{x: 2.0, y: 1.0};
+    /// assert_eq!(p.perp(), rsgeo::Point{x: -1.0, y: 2.0})
+    ///```
+    pub fn perp(&self) -> Point<T> {
+        Point {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    /// perp_dot is the dot product of `self.perp()` with `other`. This is numerically
+    /// the same as `cross`, and is provided under the name used in most references.
+    pub fn perp_dot(&self, other: &Point<T>) -> T {
+        self.perp().dot_product(other)
+    }
+
+    /// signed_area is the signed area of the triangle spanned by the two vectors. It is
+    /// positive when `other` is counter-clockwise from `self`, and half the cross product.
+    pub fn signed_area(&self, other: &Point<T>) -> T {
+        self.cross(other) / T::from_f64(2.0)
+    }
+
+    /// angle_between is the unsigned angle (in radians, in [0, pi]) between the two
+    /// vectors, the arccosine of their normalized dot product. Vectors pointing the same
+    /// way give exactly 0, and opposite ones exactly pi. If either vector has zero
+    /// length the angle is undefined, and 0.0 is returned.
+    /// ```
+    /// let p = rsgeo::Point{x: 1.0, y: 0.0};
+    /// let q = rsgeo::Point{x: 0.0, y: -3.0};
+    /// assert_eq!(p.angle_between(&q), std::f64::consts::PI / 2.0)
+    ///```
+    pub fn angle_between(&self, other: &Point<T>) -> T {
+        let denom = self.magnitude() * other.magnitude();
+        if denom == T::zero() {
+            return T::zero();
+        }
+        // The cosine of vectors along the same line can round to just short of 1 or -1
+        if self.cross(other) == T::zero() {
+            return if self.dot_product(other) > T::zero() {
+                T::zero()
+            } else {
+                T::PI()
+            };
+        }
+        // Rounding can push the cosine slightly outside of [-1, 1]
+        (self.dot_product(other) / denom)
+            .max(-T::one())
+            .min(T::one())
+            .acos()
+    }
+
+    /// signed_angle_between is the angle (in radians, in (-pi, pi]) needed to rotate
+    /// `self` onto the direction of `other`. Positive angles are counter-clockwise. If
+    /// either vector has zero length, 0.0 is returned.
+    /// ```
+    /// let p = rsgeo::Point{x: 1.0, y: 0.0};
+    /// let q = rsgeo::Point{x: 0.0, y: -3.0};
+    /// assert_eq!(p.signed_angle_between(&q), -std::f64::consts::PI / 2.0)
+    ///```
+    pub fn signed_angle_between(&self, other: &Point<T>) -> T {
+        self.cross(other).atan2(self.dot_product(other))
+    }
+
+    /// project_onto returns the component of `self` that is parallel to `other`. If
+    /// `other` has zero length there is no direction to project onto, and the zero
+    /// vector is returned.
+    /// ```
+    /// let p = rsgeo::Point{x: 2.0, y: 3.0};
+    /// let axis = rsgeo::Point{x: 5.0, y: 0.0};
+    /// assert_eq!(p.project_onto(&axis), rsgeo::Point{x: 2.0, y: 0.0})
+    ///```
+    pub fn project_onto(&self, other: &Point<T>) -> Point<T> {
+        let denom = other.magnitude_squared();
+        if denom == T::zero() {
+            return Point::origin();
+        }
+        other.mul(self.dot_product(other) / denom)
+    }
+
+    /// reject_from returns the component of `self` that is perpendicular to `other`,
+    /// such that `self.project_onto(other) + self.reject_from(other) == self`
+    /// ```
+    /// let p = rsgeo::Point{x: 2.0, y: 3.0};
+    /// let axis = rsgeo::Point{x: 5.0, y: 0.0};
+    /// assert_eq!(p.reject_from(&axis), rsgeo::Point{x: 0.0, y: 3.0})
+    ///```
+    pub fn reject_from(&self, other: &Point<T>) -> Point<T> {
+        *self - self.project_onto(other)
+    }
+
+    /// reflect mirrors the vector across the direction `axis`, as if `axis` were a mirror
+    /// line through the origin. If `axis` has zero length, the result is `-self`.
+    /// ```
+    /// let p = rsgeo::Point{x: 2.0, y: 3.0};
+    /// let axis = rsgeo::Point{x: 1.0, y: 0.0};
+    /// assert_eq!(p.reflect(axis), rsgeo::Point{x: 2.0, y: -3.0})
+    ///```
+    pub fn reflect(&self, axis: Point<T>) -> Point<T> {
+        self.project_onto(&axis).mul(T::from_f64(2.0)) - *self
+    }
+
+    /// lerp linearly interpolates between `self` (at `t = 0`) and `other` (at `t = 1`).
+    /// Values of `t` outside of [0, 1] extrapolate along the same line.
+    /// ```
+    /// let p = rsgeo::Point{x: 0.0, y: 0.0};
+    /// let q = rsgeo::Point{x: 4.0, y: 2.0};
+    /// assert_eq!(p.lerp(&q, 0.25), rsgeo::Point{x: 1.0, y: 0.5});
+    /// assert_eq!(p.lerp(&q, 2.0), rsgeo::Point{x: 8.0, y: 4.0});
+    ///```
+    pub fn lerp(&self, other: &Point<T>, t: T) -> Point<T> {
+        Point {
+            x: self.x + (other.x - self.x) * t,
+            y: self.y + (other.y - self.y) * t,
+        }
+    }
+
+    /// lerp_clamped is like `lerp`, but `t` is first clamped to [0, 1], so the result
+    /// always lies between the two points.
+    pub fn lerp_clamped(&self, other: &Point<T>, t: T) -> Point<T> {
+        self.lerp(other, t.max(T::zero()).min(T::one()))
+    }
+
+    /// midpoint returns the point halfway between `self` and `other`
+    pub fn midpoint(&self, other: &Point<T>) -> Point<T> {
+        self.lerp(other, T::from_f64(0.5))
+    }
+
+    /// The point at the origin, `(0, 0)`
+    pub fn origin() -> Point<T> {
+        Point {
+            x: T::zero(),
+            y: T::zero(),
+        }
+    }
 }
 
-impl Add for Point {
+impl<T: Scalar> Add for Point<T> {
     type Output = Self;
 
     /// Add the x and y components of two points
@@ -118,7 +564,7 @@ impl Add for Point {
     }
 }
 
-impl Sub for Point {
+impl<T: Scalar> Sub for Point<T> {
     type Output = Self;
 
     /// Subtract the x and y components of two points
@@ -136,20 +582,312 @@ impl Sub for Point {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct LineSegment {
-    p1: Point,
-    p2: Point,
+impl<T: Scalar> Neg for Point<T> {
+    type Output = Self;
+
+    /// Negate both components of a point
+    /// ```
+    /// let p = rsgeo::Point{x: 3.0, y: -1.0};
+    /// assert_eq!(-p, rsgeo::Point{x: -3.0, y: 1.0})
+    ///```
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: Scalar> Mul<T> for Point<T> {
+    type Output = Self;
+
+    /// Multiply a point by a scalar
+    /// ```
+    /// let p = rsgeo::Point{x: 3.0, y: -1.0};
+    /// assert_eq!(p * 2.0, rsgeo::Point{x: 6.0, y: -2.0})
+    ///```
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+// This is only implemented for the default f64, since a second impl for f32 would make
+// the type of a float literal like the `2.0` in `2.0 * p` ambiguous
+impl Mul<Point<f64>> for f64 {
+    type Output = Point<f64>;
+
+    /// Multiply a scalar by a point
+    /// ```
+    /// let p = rsgeo::Point{x: 3.0, y: -1.0};
+    /// assert_eq!(2.0 * p, rsgeo::Point{x: 6.0, y: -2.0})
+    ///```
+    fn mul(self, rhs: Point<f64>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl<T: Scalar> Div<T> for Point<T> {
+    type Output = Self;
+
+    /// Divide a point by a scalar
+    /// ```
+    /// let p = rsgeo::Point{x: 3.0, y: -1.0};
+    /// assert_eq!(p / 2.0, rsgeo::Point{x: 1.5, y: -0.5})
+    ///```
+    fn div(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+        }
+    }
+}
+
+impl<T: Scalar> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: Scalar> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Scalar> MulAssign<T> for Point<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+impl<T: Scalar> DivAssign<T> for Point<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.x /= rhs;
+        self.y /= rhs;
+    }
+}
+
+impl<T: Scalar> From<(T, T)> for Point<T> {
+    /// Create a point from an `(x, y)` tuple
+    /// ```
+    /// let p = rsgeo::Point::from((1.0, 2.0));
+    /// assert_eq!(p, rsgeo::Point{x: 1.0, y: 2.0})
+    ///```
+    fn from((x, y): (T, T)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T: Scalar> From<[T; 2]> for Point<T> {
+    /// Create a point from an `[x, y]` array
+    /// ```
+    /// let p: rsgeo::Point = [1.0, 2.0].into();
+    /// assert_eq!(p, rsgeo::Point{x: 1.0, y: 2.0})
+    ///```
+    fn from([x, y]: [T; 2]) -> Self {
+        Point { x, y }
+    }
 }
 
-impl LineSegment {
-    pub fn isclose(&self, other: &LineSegment) -> bool {
-        self.p1.isclose(other.p1) && self.p2.isclose(other.p2)
+impl<T: Scalar> From<Point<T>> for (T, T) {
+    fn from(p: Point<T>) -> Self {
+        (p.x, p.y)
     }
 }
 
-fn f64_isclose(a: f64, b: f64) -> bool {
-    (a - b).abs() <= (ATOL + (RTOL * b.abs()))
+impl<T: Scalar> From<Point<T>> for [T; 2] {
+    fn from(p: Point<T>) -> Self {
+        [p.x, p.y]
+    }
+}
+
+impl<T: Scalar> Index<usize> for Point<T> {
+    type Output = T;
+
+    /// Access the components of a point by index, where 0 is x and 1 is y. Panics for
+    /// any other index.
+    /// ```
+    /// let p = rsgeo::Point{x: 1.0, y: 2.0};
+    /// assert_eq!(p[0], 1.0);
+    /// assert_eq!(p[1], 2.0);
+    ///```
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Point index out of bounds: {} (must be 0 or 1)", index),
+        }
+    }
+}
+
+impl<T: Scalar> IndexMut<usize> for Point<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Point index out of bounds: {} (must be 0 or 1)", index),
+        }
+    }
+}
+
+impl<T: Scalar> fmt::Display for Point<T> {
+    /// Format a point as `(x, y)`. A precision, if given, is applied to both components.
+    /// ```
+    /// let p = rsgeo::Point{x: 1.5, y: -2.0};
+    /// assert_eq!(p.to_string(), "(1.5, -2.0)");
+    /// assert_eq!(format!("{:.2}", p), "(1.50, -2.00)");
+    ///```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match f.precision() {
+            Some(prec) => write!(f, "({:.*}, {:.*})", prec, self.x, prec, self.y),
+            None => write!(f, "({:?}, {:?})", self.x, self.y),
+        }
+    }
+}
+
+impl<T: Scalar> FromStr for Point<T> {
+    type Err = ParseError;
+
+    /// Parse a point from any of the forms `"x y"`, `"x,y"`, or `"(x, y)"`. Surrounding
+    /// whitespace is ignored.
+    /// ```
+    /// use rsgeo::Point;
+    /// let expected = Point{x: 1.5, y: -2.0};
+    /// assert_eq!("1.5 -2".parse::<Point>().unwrap(), expected);
+    /// assert_eq!("1.5,-2".parse::<Point>().unwrap(), expected);
+    /// assert_eq!(" (1.5, -2.0) ".parse::<Point>().unwrap(), expected);
+    ///```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let inner = match (s.strip_prefix('('), s.ends_with(')')) {
+            (Some(rest), true) => &rest[..rest.len() - 1],
+            (None, false) => s,
+            _ => return Err(ParseError::UnbalancedParentheses),
+        };
+
+        let (x, y) = if inner.contains(',') {
+            two_coords(inner.split(',').map(str::trim))?
+        } else {
+            two_coords(inner.split_whitespace())?
+        };
+        Ok(Point {
+            x: x.parse()?,
+            y: y.parse()?,
+        })
+    }
+}
+
+/// Take exactly two coordinates from `parts`, without allocating
+fn two_coords<'a>(
+    mut parts: impl Iterator<Item = &'a str>,
+) -> Result<(&'a str, &'a str), ParseError> {
+    match (parts.next(), parts.next()) {
+        (Some(x), Some(y)) => match parts.count() {
+            0 => Ok((x, y)),
+            extra => Err(ParseError::WrongCoordinateCount(2 + extra)),
+        },
+        (first, _) => Err(ParseError::WrongCoordinateCount(usize::from(
+            first.is_some(),
+        ))),
+    }
+}
+
+/// The error returned when parsing a geometry from a string fails
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// The point did not have exactly two coordinates. Holds the number found.
+    WrongCoordinateCount(usize),
+    /// One of the coordinates was not a valid number
+    InvalidCoordinate(ParseFloatError),
+    /// An opening parenthesis was not matched by a closing one, or vice versa
+    UnbalancedParentheses,
+    /// A line segment did not contain the `->` separating its two points
+    MissingSeparator,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::WrongCoordinateCount(n) => {
+                write!(f, "expected 2 coordinates, found {}", n)
+            }
+            ParseError::InvalidCoordinate(e) => write!(f, "invalid coordinate: {}", e),
+            ParseError::UnbalancedParentheses => write!(f, "unbalanced parentheses"),
+            ParseError::MissingSeparator => write!(f, "expected `->` between two points"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::InvalidCoordinate(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseFloatError> for ParseError {
+    fn from(e: ParseFloatError) -> Self {
+        ParseError::InvalidCoordinate(e)
+    }
+}
+
+fn scalar_isclose<T: Scalar>(a: T, b: T) -> bool {
+    scalar_isclose_with(a, b, T::DEFAULT_ATOL, T::DEFAULT_RTOL)
+}
+
+fn scalar_isclose_with<T: Scalar>(a: T, b: T, atol: T, rtol: T) -> bool {
+    (a - b).abs() <= (atol + (rtol * b.abs()))
+}
+
+/// A linear congruential generator, for test data that is the same on every run
+#[cfg(test)]
+pub(crate) struct Lcg(u64);
+
+#[cfg(test)]
+impl Lcg {
+    pub(crate) fn new(seed: u64) -> Lcg {
+        Lcg(seed)
+    }
+
+    /// The next state, whose high bits are the most random
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.0
+    }
+
+    /// A number in [0, 1)
+    pub(crate) fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A whole number below `m`
+    pub(crate) fn below(&mut self, m: u64) -> u64 {
+        (self.next_u64() >> 33) % m
+    }
+}
+
+/// `n` points spread over the square from the origin to (10, 10), the same for each seed
+#[cfg(all(test, feature = "alloc"))]
+pub(crate) fn scattered(n: usize, seed: u64) -> alloc::vec::Vec<Point> {
+    let mut rng = Lcg::new(seed);
+    (0..n)
+        .map(|_| Point {
+            x: rng.unit() * 10.0,
+            y: rng.unit() * 10.0,
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -163,6 +901,20 @@ mod tests {
         assert_eq!(p.angle(), 1.0_f64.atan2(1.0))
     }
 
+    #[test]
+    fn test_polar_round_trip() {
+        let p = Point { x: -3.0, y: 4.0 };
+        let (r, theta) = p.to_polar();
+        assert_eq!(5.0, r);
+        assert!(Point::from_polar(r, theta).isclose(p))
+    }
+
+    #[test]
+    fn test_from_polar_negative_radius_points_backwards() {
+        let p = Point::from_polar(-1.0, 0.0);
+        assert!(p.isclose(Point { x: -1.0, y: 0.0 }))
+    }
+
     #[test]
     fn test_add() {
         let p1 = Point { x: 1.0, y: 1.0 };
@@ -170,6 +922,84 @@ mod tests {
         assert_eq!(Point { x: 4.0, y: 11.0 }, p1 + p2)
     }
 
+    #[test]
+    fn test_operator_expression() {
+        let a = Point { x: 4.0, y: 1.0 };
+        let b = Point { x: 1.0, y: 7.0 };
+        let got = 2.0 * (a - b) / 3.0;
+        assert!(got.isclose(Point { x: 2.0, y: -4.0 }));
+        assert_eq!(-(a * 2.0), (-a) * 2.0)
+    }
+
+    #[test]
+    fn test_assign_operators() {
+        let mut p = Point { x: 1.0, y: 2.0 };
+        p += Point { x: 1.0, y: 1.0 };
+        assert_eq!(Point { x: 2.0, y: 3.0 }, p);
+        p -= Point { x: 4.0, y: 0.0 };
+        assert_eq!(Point { x: -2.0, y: 3.0 }, p);
+        p *= 2.0;
+        assert_eq!(Point { x: -4.0, y: 6.0 }, p);
+        p /= 4.0;
+        assert_eq!(Point { x: -1.0, y: 1.5 }, p)
+    }
+
+    #[test]
+    fn test_tuple_and_array_round_trip() {
+        let p = Point { x: 1.5, y: -2.0 };
+        let t: (f64, f64) = p.into();
+        let a: [f64; 2] = p.into();
+        assert_eq!((1.5, -2.0), t);
+        assert_eq!([1.5, -2.0], a);
+        assert_eq!(p, Point::from(t));
+        assert_eq!(p, Point::from(a))
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut p = Point { x: 1.5, y: -2.0 };
+        p[0] = 3.0;
+        p[1] += 1.0;
+        assert_eq!(Point { x: 3.0, y: -1.0 }, p)
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let p = Point { x: 1.5, y: -2.0 };
+        let _ = p[2];
+    }
+
+    #[test]
+    fn test_display_round_trips_through_from_str() {
+        let p = Point { x: -0.125, y: 1e10 };
+        assert_eq!(p, p.to_string().parse().unwrap())
+    }
+
+    #[test]
+    fn test_from_str_errors() {
+        assert_eq!(
+            Err(ParseError::WrongCoordinateCount(3)),
+            "1 2 3".parse::<Point>()
+        );
+        assert_eq!(
+            Err(ParseError::WrongCoordinateCount(1)),
+            "(4)".parse::<Point>()
+        );
+        assert_eq!(
+            Err(ParseError::UnbalancedParentheses),
+            "(1, 2".parse::<Point>()
+        );
+        assert!(matches!(
+            "1, y".parse::<Point>(),
+            Err(ParseError::InvalidCoordinate(_))
+        ));
+        assert!(matches!(
+            "1,,2".parse::<Point>(),
+            Err(ParseError::WrongCoordinateCount(3))
+        ))
+    }
+
     #[bench]
     fn bench_add_two(b: &mut Bencher) {
         let p = Point { x: 1.0, y: 1.0 };
@@ -195,6 +1025,22 @@ mod tests {
         b.iter(|| p.rotate(angle));
     }
 
+    #[test]
+    fn test_rotate_by_degrees() {
+        let p = Point { x: 1.0, y: 0.0 };
+        let got = p.rotate(Angle::from_degrees(90.0));
+        assert!(got.isclose(Point { x: 0.0, y: 1.0 }))
+    }
+
+    #[test]
+    fn test_f32_point() {
+        let p = Point { x: 1.0_f32, y: 0.0 };
+        let got = p.rotate(std::f32::consts::PI / 2.0);
+        assert!(got.isclose(Point { x: 0.0, y: 1.0 }));
+        assert_eq!(5.0_f32, Point { x: 3.0_f32, y: 4.0 }.magnitude());
+        assert_eq!(Ok(Point { x: 0.5_f32, y: 2.0 }), "0.5, 2".parse())
+    }
+
     #[test]
     fn test_rotate_by90deg() {
         let p = Point { x: 1.0, y: 0.0 };
@@ -204,6 +1050,27 @@ mod tests {
         assert!(expected.isclose(got))
     }
 
+    #[test]
+    fn test_rotate_about_pivot_is_fixed() {
+        let pivot = Point { x: -2.0, y: 3.0 };
+        assert_eq!(pivot, pivot.rotate_about(pivot, 1.234))
+    }
+
+    #[test]
+    fn test_rotate_about_by180deg() {
+        let p = Point { x: 3.0, y: 3.0 };
+        let pivot = Point { x: 1.0, y: 2.0 };
+        let got = p.rotate_about(pivot, std::f64::consts::PI);
+        assert!(got.isclose(Point { x: -1.0, y: 1.0 }))
+    }
+
+    #[test]
+    fn test_rotate_about_origin_matches_rotate() {
+        let p = Point { x: 3.0, y: -4.0 };
+        let origin = Point { x: 0.0, y: 0.0 };
+        assert_eq!(p.rotate(0.7), p.rotate_about(origin, 0.7))
+    }
+
     #[test]
     fn test_xintercept_two_pts_stacked_vertically() {
         let p = Point { x: 1.0, y: -1.0 };
@@ -235,4 +1102,152 @@ mod tests {
         let q = Point { x: 0.0, y: 1.0 };
         b.iter(|| p.xintercept(&q));
     }
+
+    #[test]
+    fn test_cross_parallel_is_zero() {
+        let p = Point { x: 2.0, y: 4.0 };
+        let q = Point { x: 1.0, y: 2.0 };
+        assert_eq!(0.0, p.cross(&q))
+    }
+
+    #[test]
+    fn test_perp_is_orthogonal() {
+        let p = Point { x: 3.0, y: -7.0 };
+        assert_eq!(0.0, p.dot_product(&p.perp()));
+        assert!(p.perp().perp().isclose(p.mul(-1.0)))
+    }
+
+    #[test]
+    fn test_perp_dot_matches_cross() {
+        let p = Point { x: 3.0, y: -7.0 };
+        let q = Point { x: -2.0, y: 5.0 };
+        assert_eq!(p.cross(&q), p.perp_dot(&q))
+    }
+
+    #[test]
+    fn test_signed_area_unit_triangle() {
+        let p = Point { x: 1.0, y: 0.0 };
+        let q = Point { x: 0.0, y: 1.0 };
+        assert_eq!(0.5, p.signed_area(&q));
+        assert_eq!(-0.5, q.signed_area(&p))
+    }
+
+    #[test]
+    fn test_angle_between_parallel_and_opposite() {
+        let p = Point { x: 1.0, y: 1.0 };
+        assert_eq!(0.0, p.angle_between(&p.mul(2.0)));
+        assert!(scalar_isclose(
+            std::f64::consts::PI,
+            p.angle_between(&p.mul(-1.0))
+        ))
+    }
+
+    #[test]
+    fn test_angle_between_zero_length() {
+        let p = Point { x: 1.0, y: 1.0 };
+        let zero = Point { x: 0.0, y: 0.0 };
+        assert_eq!(0.0, p.angle_between(&zero));
+        assert_eq!(0.0, zero.angle_between(&p));
+        assert_eq!(0.0, p.signed_angle_between(&zero));
+        assert_eq!(0.0, zero.signed_angle_between(&zero))
+    }
+
+    #[test]
+    fn test_signed_angle_between_is_antisymmetric() {
+        let p = Point { x: 1.0, y: 0.0 };
+        let q = Point { x: 1.0, y: 1.0 };
+        let angle = std::f64::consts::PI / 4.0;
+        assert!(scalar_isclose(angle, p.signed_angle_between(&q)));
+        assert!(scalar_isclose(-angle, q.signed_angle_between(&p)));
+        assert!(scalar_isclose(angle, p.angle_between(&q)))
+    }
+
+    #[test]
+    fn test_project_and_reject_recompose() {
+        let p = Point { x: 3.0, y: -1.5 };
+        let axis = Point { x: 1.0, y: 2.0 };
+        let par = p.project_onto(&axis);
+        let perp = p.reject_from(&axis);
+        assert!(scalar_isclose(0.0, par.cross(&axis)));
+        assert!(scalar_isclose(0.0, perp.dot_product(&axis)));
+        assert!((par + perp).isclose(p))
+    }
+
+    #[test]
+    fn test_project_onto_zero_length() {
+        let p = Point { x: 3.0, y: -1.5 };
+        let zero = Point { x: 0.0, y: 0.0 };
+        assert_eq!(zero, p.project_onto(&zero));
+        assert_eq!(p, p.reject_from(&zero))
+    }
+
+    #[test]
+    fn test_reflect_is_involution() {
+        let p = Point { x: 3.0, y: -1.5 };
+        let axis = Point { x: 1.0, y: 2.0 };
+        assert!(p.reflect(axis).reflect(axis).isclose(p));
+        assert!(scalar_isclose(p.magnitude(), p.reflect(axis).magnitude()))
+    }
+
+    #[test]
+    fn test_reflect_across_diagonal_swaps_coordinates() {
+        let p = Point { x: 3.0, y: -1.5 };
+        let axis = Point { x: 1.0, y: 1.0 };
+        assert!(p.reflect(axis).isclose(Point { x: -1.5, y: 3.0 }))
+    }
+
+    #[test]
+    fn test_lerp_endpoints() {
+        let p = Point { x: 1.0, y: -2.0 };
+        let q = Point { x: 5.0, y: 6.0 };
+        assert_eq!(p, p.lerp(&q, 0.0));
+        assert_eq!(q, p.lerp(&q, 1.0))
+    }
+
+    #[test]
+    fn test_lerp_outside_unit_interval() {
+        let p = Point { x: 1.0, y: -2.0 };
+        let q = Point { x: 5.0, y: 6.0 };
+        assert!(p.lerp(&q, -0.5).isclose(Point { x: -1.0, y: -6.0 }));
+        assert!(p.lerp(&q, 1.5).isclose(Point { x: 7.0, y: 10.0 }))
+    }
+
+    #[test]
+    fn test_lerp_clamped_outside_unit_interval() {
+        let p = Point { x: 1.0, y: -2.0 };
+        let q = Point { x: 5.0, y: 6.0 };
+        assert_eq!(p, p.lerp_clamped(&q, -0.5));
+        assert_eq!(q, p.lerp_clamped(&q, 1.5));
+        assert_eq!(p.lerp(&q, 0.3), p.lerp_clamped(&q, 0.3))
+    }
+
+    #[test]
+    fn test_midpoint() {
+        let p = Point { x: 1.0, y: -2.0 };
+        let q = Point { x: 5.0, y: 6.0 };
+        assert_eq!(Point { x: 3.0, y: 2.0 }, p.midpoint(&q))
+    }
+
+    #[test]
+    fn test_distance_is_symmetric() {
+        let p = Point { x: -1.0, y: 2.0 };
+        let q = Point { x: 2.0, y: -2.0 };
+        assert_eq!(5.0, p.distance(&q));
+        assert_eq!(5.0, q.distance(&p))
+    }
+
+    #[test]
+    fn test_distance_squared() {
+        let p = Point { x: -1.0, y: 2.0 };
+        let q = Point { x: 2.0, y: -2.0 };
+        assert_eq!(25.0, p.distance_squared(&q));
+        assert_eq!(0.0, p.distance_squared(&p))
+    }
+
+    #[bench]
+    fn bench_distance_squared(b: &mut Bencher) {
+        let p = Point { x: -1.0, y: 2.0 };
+        let q = Point { x: 2.0, y: -2.0 };
+        b.iter(|| p.distance_squared(&q));
+    }
 }
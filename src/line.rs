@@ -0,0 +1,187 @@
+use crate::predicates::{orient2d, orientation, Orientation};
+use crate::{LineSegment, Point, Scalar, SegmentIntersection};
+
+/// An infinite line, stored as a point on the line and a direction vector along it.
+/// The direction does not need to be normalized, but must not be zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line<T = f64> {
+    point: Point<T>,
+    direction: Point<T>,
+}
+
+impl<T: Scalar> Line<T> {
+    /// The line through `point`, running along `direction`
+    pub fn new(point: Point<T>, direction: Point<T>) -> Line<T> {
+        Line { point, direction }
+    }
+
+    /// The line through `a` and `b`, running from `a` towards `b`
+    pub fn from_points(a: Point<T>, b: Point<T>) -> Line<T> {
+        Line::new(a, b - a)
+    }
+
+    /// The line `a*x + b*y + c = 0`. Returns None if both `a` and `b` are zero, since
+    /// that does not describe a line.
+    /// ```
+    /// use rsgeo::{Line, Point};
+    /// // y = 2x + 1
+    /// let line = Line::from_coefficients(2.0, -1.0, 1.0).unwrap();
+    /// assert_eq!(line.distance_to_point(Point{x: 0.0, y: 1.0}), 0.0);
+    /// assert_eq!(line.distance_to_point(Point{x: 1.0, y: 3.0}), 0.0);
+    /// ```
+    pub fn from_coefficients(a: T, b: T, c: T) -> Option<Line<T>> {
+        let norm2 = a * a + b * b;
+        if norm2 == T::zero() {
+            return None;
+        }
+        // The point on the line closest to the origin
+        let point = Point {
+            x: -a * c / norm2,
+            y: -b * c / norm2,
+        };
+        Some(Line::new(point, Point { x: b, y: -a }))
+    }
+
+    /// The coefficients `(a, b, c)` of the equation `a*x + b*y + c = 0` describing the
+    /// line. They are not normalized.
+    pub fn coefficients(&self) -> (T, T, T) {
+        let a = -self.direction.y;
+        let b = self.direction.x;
+        let c = -(a * self.point.x + b * self.point.y);
+        (a, b, c)
+    }
+
+    pub fn point(&self) -> Point<T> {
+        self.point
+    }
+
+    pub fn direction(&self) -> Point<T> {
+        self.direction
+    }
+
+    /// A second point on the line, one direction vector along from `point`
+    fn second_point(&self) -> Point<T> {
+        self.point + self.direction
+    }
+
+    /// Whether the two lines run in the same or opposite directions. The test is exact,
+    /// so directions that differ only by rounding error are not parallel.
+    pub fn parallel_to(&self, other: &Line<T>) -> bool {
+        orient2d(Point::origin(), self.direction, other.direction) == 0.0
+    }
+
+    /// The perpendicular distance from `p` to the line
+    pub fn distance_to_point(&self, p: Point<T>) -> T {
+        (p - self.point).cross(&self.direction).abs() / self.direction.magnitude()
+    }
+
+    /// The point where two lines cross, or None if they are parallel
+    /// ```
+    /// use rsgeo::{Line, Point};
+    /// let a = Line::from_points(Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 1.0});
+    /// let b = Line::new(Point{x: 4.0, y: 0.0}, Point{x: 0.0, y: 1.0});
+    /// assert_eq!(a.intersect(&b), Some(Point{x: 4.0, y: 4.0}));
+    /// ```
+    pub fn intersect(&self, other: &Line<T>) -> Option<Point<T>> {
+        if self.parallel_to(other) {
+            return None;
+        }
+        let t = (other.point - self.point).cross(&other.direction)
+            / self.direction.cross(&other.direction);
+        Some(self.point + self.direction * t)
+    }
+
+    /// Find where the line meets a segment. As with `LineSegment::intersect`, the
+    /// classification uses exact orientation predicates, and a segment endpoint lying
+    /// on the line is returned exactly. A segment lying along the line is returned
+    /// whole.
+    pub fn intersect_segment(&self, seg: &LineSegment<T>) -> SegmentIntersection<T> {
+        let q = self.second_point();
+        let o1 = orientation(self.point, q, seg.p1());
+        let o2 = orientation(self.point, q, seg.p2());
+        match (o1, o2) {
+            (Orientation::Collinear, Orientation::Collinear) => {
+                if seg.p1() == seg.p2() {
+                    SegmentIntersection::Point(seg.p1())
+                } else {
+                    SegmentIntersection::Segment(*seg)
+                }
+            }
+            (Orientation::Collinear, _) => SegmentIntersection::Point(seg.p1()),
+            (_, Orientation::Collinear) => SegmentIntersection::Point(seg.p2()),
+            _ if o1 == o2 => SegmentIntersection::None,
+            _ => match self.intersect(&Line::from(*seg)) {
+                Some(p) => SegmentIntersection::Point(p),
+                None => SegmentIntersection::None,
+            },
+        }
+    }
+}
+
+impl<T: Scalar> From<LineSegment<T>> for Line<T> {
+    /// The infinite line through both ends of the segment
+    fn from(seg: LineSegment<T>) -> Self {
+        Line::from_points(seg.p1(), seg.p2())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coefficients_round_trip() {
+        let line = Line::from_points(Point { x: 1.0, y: 2.0 }, Point { x: 3.0, y: 3.0 });
+        let (a, b, c) = line.coefficients();
+        let back = Line::from_coefficients(a, b, c).unwrap();
+        assert!(back.parallel_to(&line));
+        assert!(line.distance_to_point(back.point()) < 1e-12);
+        assert_eq!(None, Line::from_coefficients(0.0, 0.0, 1.0))
+    }
+
+    #[test]
+    fn test_distance_to_point() {
+        let line = Line::new(Point { x: 0.0, y: 1.0 }, Point { x: 2.0, y: 0.0 });
+        assert_eq!(3.0, line.distance_to_point(Point { x: -7.0, y: 4.0 }));
+        assert_eq!(3.0, line.distance_to_point(Point { x: 100.0, y: -2.0 }))
+    }
+
+    #[test]
+    fn test_parallel_lines_do_not_intersect() {
+        let a = Line::new(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 2.0 });
+        let b = Line::new(Point { x: 5.0, y: 0.0 }, Point { x: -2.0, y: -4.0 });
+        assert!(a.parallel_to(&b));
+        assert_eq!(None, a.intersect(&b))
+    }
+
+    #[test]
+    fn test_intersect_segment() {
+        let line = Line::new(Point { x: 0.0, y: 1.0 }, Point { x: 1.0, y: 0.0 });
+        let crossing: LineSegment = "(2, 0) -> (4, 4)".parse().unwrap();
+        assert_eq!(
+            SegmentIntersection::Point(Point { x: 2.5, y: 1.0 }),
+            line.intersect_segment(&crossing)
+        );
+        let touching: LineSegment = "(2, 0) -> (-3, 1)".parse().unwrap();
+        assert_eq!(
+            SegmentIntersection::Point(Point { x: -3.0, y: 1.0 }),
+            line.intersect_segment(&touching)
+        );
+        let below: LineSegment = "(2, 0) -> (-3, 0.5)".parse().unwrap();
+        assert_eq!(SegmentIntersection::None, line.intersect_segment(&below));
+        let along: LineSegment = "(7, 1) -> (-3, 1)".parse().unwrap();
+        assert_eq!(
+            SegmentIntersection::Segment(along),
+            line.intersect_segment(&along)
+        )
+    }
+
+    #[test]
+    fn test_from_segment() {
+        let seg: LineSegment = "(1, 1) -> (2, 3)".parse().unwrap();
+        let line = Line::from(seg);
+        assert_eq!(seg.p1(), line.point());
+        assert_eq!(seg.direction(), line.direction())
+    }
+}
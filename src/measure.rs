@@ -0,0 +1,593 @@
+//! Measurement traits implemented by every shape, so that generic code can measure a
+//! shape without knowing its concrete type. Most shapes also have inherent methods of
+//! the same names, which return a bare value where the shape can never be empty.
+
+#[cfg(feature = "alloc")]
+use crate::{
+    polygon::ring_edges, Geometry, GeometryCollection, MultiPoint, MultiPolygon, Polygon, Polyline,
+};
+use crate::{
+    Arc, Circle, CubicBezier, Ellipse, LineSegment, Obb, Point, QuadBezier, Rect, Scalar, Triangle,
+};
+
+pub trait Area<T: Scalar> {
+    /// The area covered by the shape, less any holes. Points and curves have zero area.
+    fn area(&self) -> T;
+}
+
+pub trait Length<T: Scalar> {
+    /// The length of a curve, or the perimeter of a shape that covers an area,
+    /// including the boundaries of any holes. Points have zero length.
+    fn length(&self) -> T;
+}
+
+pub trait Centroid<T: Scalar> {
+    /// The center of mass, or None if the shape is empty. Shapes that cover an area are
+    /// weighted by area, curves by length and points equally.
+    fn centroid(&self) -> Option<Point<T>>;
+}
+
+pub trait BoundingRect<T: Scalar> {
+    /// The smallest axis-aligned rectangle containing the shape, or None if it is empty
+    fn bounding_rect(&self) -> Option<Rect<T>>;
+}
+
+impl<T: Scalar> Area<T> for Point<T> {
+    fn area(&self) -> T {
+        T::zero()
+    }
+}
+
+impl<T: Scalar> Length<T> for Point<T> {
+    fn length(&self) -> T {
+        T::zero()
+    }
+}
+
+impl<T: Scalar> Centroid<T> for Point<T> {
+    fn centroid(&self) -> Option<Point<T>> {
+        Some(*self)
+    }
+}
+
+impl<T: Scalar> BoundingRect<T> for Point<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        Some(Rect::from_corners(*self, *self))
+    }
+}
+
+impl<T: Scalar> Area<T> for LineSegment<T> {
+    fn area(&self) -> T {
+        T::zero()
+    }
+}
+
+impl<T: Scalar> Length<T> for LineSegment<T> {
+    fn length(&self) -> T {
+        LineSegment::length(self)
+    }
+}
+
+impl<T: Scalar> Centroid<T> for LineSegment<T> {
+    fn centroid(&self) -> Option<Point<T>> {
+        Some(self.midpoint())
+    }
+}
+
+impl<T: Scalar> BoundingRect<T> for LineSegment<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        Some(Rect::from_corners(self.p1(), self.p2()))
+    }
+}
+
+impl<T: Scalar> Area<T> for Arc<T> {
+    fn area(&self) -> T {
+        T::zero()
+    }
+}
+
+impl<T: Scalar> Length<T> for Arc<T> {
+    fn length(&self) -> T {
+        Arc::length(self)
+    }
+}
+
+impl<T: Scalar> Centroid<T> for Arc<T> {
+    /// The centroid of the curve itself, which lies on the bisector of the arc at
+    /// `radius * sin(a) / a` from the center, for half the sweep `a`
+    fn centroid(&self) -> Option<Point<T>> {
+        let half = self.sweep() / T::from_f64(2.0);
+        let a = half.radians().abs();
+        Some(self.center + Point::from_polar(self.radius * a.sin() / a, self.start_angle + half))
+    }
+}
+
+impl<T: Scalar> BoundingRect<T> for Arc<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        Some(Arc::bounding_rect(self))
+    }
+}
+
+impl<T: Scalar> BoundingRect<T> for QuadBezier<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        Some(QuadBezier::bounding_rect(self))
+    }
+}
+
+impl<T: Scalar> BoundingRect<T> for CubicBezier<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        Some(CubicBezier::bounding_rect(self))
+    }
+}
+
+impl<T: Scalar> Area<T> for Circle<T> {
+    fn area(&self) -> T {
+        Circle::area(self)
+    }
+}
+
+impl<T: Scalar> Length<T> for Circle<T> {
+    fn length(&self) -> T {
+        self.circumference()
+    }
+}
+
+impl<T: Scalar> Centroid<T> for Circle<T> {
+    fn centroid(&self) -> Option<Point<T>> {
+        Some(self.center)
+    }
+}
+
+impl<T: Scalar> BoundingRect<T> for Circle<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        Some(Circle::bounding_rect(self))
+    }
+}
+
+impl<T: Scalar> Area<T> for Ellipse<T> {
+    fn area(&self) -> T {
+        Ellipse::area(self)
+    }
+}
+
+impl<T: Scalar> Length<T> for Ellipse<T> {
+    fn length(&self) -> T {
+        self.perimeter()
+    }
+}
+
+impl<T: Scalar> Centroid<T> for Ellipse<T> {
+    fn centroid(&self) -> Option<Point<T>> {
+        Some(self.center)
+    }
+}
+
+impl<T: Scalar> BoundingRect<T> for Ellipse<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        Some(Ellipse::bounding_rect(self))
+    }
+}
+
+impl<T: Scalar> Area<T> for Rect<T> {
+    fn area(&self) -> T {
+        Rect::area(self)
+    }
+}
+
+impl<T: Scalar> Length<T> for Rect<T> {
+    fn length(&self) -> T {
+        T::from_f64(2.0) * (self.width() + self.height())
+    }
+}
+
+impl<T: Scalar> Centroid<T> for Rect<T> {
+    fn centroid(&self) -> Option<Point<T>> {
+        Some(self.center())
+    }
+}
+
+impl<T: Scalar> BoundingRect<T> for Rect<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        Some(*self)
+    }
+}
+
+impl<T: Scalar> Area<T> for Obb<T> {
+    fn area(&self) -> T {
+        Obb::area(self)
+    }
+}
+
+impl<T: Scalar> Length<T> for Obb<T> {
+    fn length(&self) -> T {
+        T::from_f64(4.0) * (self.half_extents.x + self.half_extents.y)
+    }
+}
+
+impl<T: Scalar> Centroid<T> for Obb<T> {
+    fn centroid(&self) -> Option<Point<T>> {
+        Some(self.center)
+    }
+}
+
+impl<T: Scalar> BoundingRect<T> for Obb<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        Some(Obb::bounding_rect(self))
+    }
+}
+
+impl<T: Scalar> Area<T> for Triangle<T> {
+    fn area(&self) -> T {
+        Triangle::area(self)
+    }
+}
+
+impl<T: Scalar> Length<T> for Triangle<T> {
+    fn length(&self) -> T {
+        self.perimeter()
+    }
+}
+
+impl<T: Scalar> Centroid<T> for Triangle<T> {
+    fn centroid(&self) -> Option<Point<T>> {
+        Some(Triangle::centroid(self))
+    }
+}
+
+impl<T: Scalar> BoundingRect<T> for Triangle<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        Rect::from_points([self.a, self.b, self.c].iter().copied())
+    }
+}
+
+/// The mean of the points, or None if there are none
+#[cfg(feature = "alloc")]
+fn mean<T: Scalar>(points: &[Point<T>]) -> Option<Point<T>> {
+    if points.is_empty() {
+        return None;
+    }
+    let sum = points.iter().fold(Point::origin(), |acc, &p| acc + p);
+    Some(sum / T::from_f64(points.len() as f64))
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Area<T> for Polyline<T> {
+    fn area(&self) -> T {
+        T::zero()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Length<T> for Polyline<T> {
+    fn length(&self) -> T {
+        Polyline::length(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Centroid<T> for Polyline<T> {
+    /// The length-weighted mean of the segment midpoints. A polyline of zero length
+    /// falls back to the mean of its points.
+    fn centroid(&self) -> Option<Point<T>> {
+        let (sum, total) = self
+            .segments()
+            .fold((Point::origin(), T::zero()), |(sum, total), s| {
+                let len = s.length();
+                (sum + s.midpoint() * len, total + len)
+            });
+        if total > T::zero() {
+            Some(sum / total)
+        } else {
+            mean(self.points())
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> BoundingRect<T> for Polyline<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        Rect::from_points(self.points().iter().copied())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Area<T> for Polygon<T> {
+    fn area(&self) -> T {
+        Polygon::area(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Length<T> for Polygon<T> {
+    fn length(&self) -> T {
+        self.rings()
+            .flat_map(ring_edges)
+            .fold(T::zero(), |acc, e| acc + e.length())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Centroid<T> for Polygon<T> {
+    fn centroid(&self) -> Option<Point<T>> {
+        Polygon::centroid(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> BoundingRect<T> for Polygon<T> {
+    /// The bounds of the exterior ring, since the holes lie inside it
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        Rect::from_points(self.vertices().iter().copied())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Area<T> for MultiPolygon<T> {
+    fn area(&self) -> T {
+        MultiPolygon::area(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Length<T> for MultiPolygon<T> {
+    fn length(&self) -> T {
+        self.polygons()
+            .iter()
+            .fold(T::zero(), |acc, p| acc + Length::length(p))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Centroid<T> for MultiPolygon<T> {
+    fn centroid(&self) -> Option<Point<T>> {
+        MultiPolygon::centroid(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> BoundingRect<T> for MultiPolygon<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        union_all(self.polygons().iter().map(BoundingRect::bounding_rect))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Area<T> for MultiPoint<T> {
+    fn area(&self) -> T {
+        T::zero()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Length<T> for MultiPoint<T> {
+    fn length(&self) -> T {
+        T::zero()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Centroid<T> for MultiPoint<T> {
+    fn centroid(&self) -> Option<Point<T>> {
+        mean(self.points())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> BoundingRect<T> for MultiPoint<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        Rect::from_points(self.points().iter().copied())
+    }
+}
+
+/// The union of the rectangles that are present, or None if none are
+#[cfg(feature = "alloc")]
+fn union_all<T: Scalar>(rects: impl Iterator<Item = Option<Rect<T>>>) -> Option<Rect<T>> {
+    rects.flatten().reduce(|a, b| a.union(&b))
+}
+
+/// Apply `$body` to whichever shape a `Geometry` holds
+#[cfg(feature = "alloc")]
+macro_rules! dispatch {
+    ($geometry:expr, $g:ident => $body:expr) => {
+        match $geometry {
+            Geometry::Point($g) => $body,
+            Geometry::LineSegment($g) => $body,
+            Geometry::Polyline($g) => $body,
+            Geometry::Polygon($g) => $body,
+            Geometry::MultiPolygon($g) => $body,
+            Geometry::Circle($g) => $body,
+            Geometry::Rect($g) => $body,
+            Geometry::MultiPoint($g) => $body,
+            Geometry::GeometryCollection($g) => $body,
+        }
+    };
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Area<T> for Geometry<T> {
+    fn area(&self) -> T {
+        dispatch!(self, g => Area::area(g))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Length<T> for Geometry<T> {
+    fn length(&self) -> T {
+        dispatch!(self, g => Length::length(g))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Centroid<T> for Geometry<T> {
+    fn centroid(&self) -> Option<Point<T>> {
+        dispatch!(self, g => Centroid::centroid(g))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> BoundingRect<T> for Geometry<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        dispatch!(self, g => BoundingRect::bounding_rect(g))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Area<T> for GeometryCollection<T> {
+    fn area(&self) -> T {
+        self.flatten().fold(T::zero(), |acc, g| acc + g.area())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Length<T> for GeometryCollection<T> {
+    fn length(&self) -> T {
+        self.flatten().fold(T::zero(), |acc, g| acc + g.length())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> Centroid<T> for GeometryCollection<T> {
+    /// The centroid of the members of the highest dimension, so that the points and
+    /// curves in a collection do not move the centroid of the areas in it. Members of
+    /// the same dimension are weighted by their area, length or number of points.
+    /// ```
+    /// use rsgeo::{Centroid, GeometryCollection, Point, Rect};
+    /// let mut shapes = GeometryCollection::default();
+    /// shapes.push(Rect::from_corners(Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 2.0}));
+    /// shapes.push(Point{x: 100.0, y: 100.0});
+    /// assert_eq!(shapes.centroid(), Some(Point{x: 1.0, y: 1.0}));
+    /// ```
+    fn centroid(&self) -> Option<Point<T>> {
+        // The weighted sum of centroids and total weight for each dimension
+        let mut moments = [(Point::origin(), T::zero()); 3];
+        for g in self.flatten() {
+            let c = match g.centroid() {
+                Some(c) => c,
+                None => continue,
+            };
+            let (dim, weight) = match g {
+                Geometry::MultiPoint(m) => (0, T::from_f64(m.points().len() as f64)),
+                _ if g.area() > T::zero() => (2, g.area()),
+                _ if g.length() > T::zero() => (1, g.length()),
+                _ => (0, T::one()),
+            };
+            let (sum, total) = moments[dim];
+            moments[dim] = (sum + c * weight, total + weight);
+        }
+        moments
+            .iter()
+            .rev()
+            .find(|(_, total)| *total > T::zero())
+            .map(|&(sum, total)| sum / total)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> BoundingRect<T> for GeometryCollection<T> {
+    fn bounding_rect(&self) -> Option<Rect<T>> {
+        union_all(self.flatten().map(BoundingRect::bounding_rect))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Generic code that only sees the traits
+    fn summary<G: Area<f64> + Length<f64> + Centroid<f64> + BoundingRect<f64>>(
+        g: &G,
+    ) -> (f64, f64, Option<Point>, Option<Rect>) {
+        (g.area(), g.length(), g.centroid(), g.bounding_rect())
+    }
+
+    #[test]
+    fn test_shapes_agree_with_inherent_methods() {
+        let rect = Rect::from_corners(Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 2.0 });
+        assert_eq!(
+            (8.0, 12.0, Some(Point { x: 2.0, y: 1.0 }), Some(rect)),
+            summary(&rect)
+        );
+        let obb = Obb::from(rect);
+        let (area, length, centroid, bounds) = summary(&obb);
+        assert_eq!((8.0, 12.0, Some(rect.center())), (area, length, centroid));
+        assert_eq!(Some(rect), bounds);
+        let seg: LineSegment = "(0, 0) -> (3, 4)".parse().unwrap();
+        assert_eq!(
+            (0.0, 5.0, Some(Point { x: 1.5, y: 2.0 })),
+            (seg.area(), Length::length(&seg), seg.centroid())
+        );
+        let p = Point { x: 1.0, y: 2.0 };
+        assert_eq!(Some(p), summary(&p).2)
+    }
+
+    #[test]
+    fn test_arc_centroid() {
+        // The centroid of a semicircular arc is 2r/pi from the center
+        let arc = Arc::new(
+            Point { x: 0.0, y: 0.0 },
+            1.0,
+            0.0,
+            core::f64::consts::PI,
+            crate::ArcDirection::CounterClockwise,
+        );
+        let c = arc.centroid().unwrap();
+        assert!(c.isclose(Point {
+            x: 0.0,
+            y: 2.0 / core::f64::consts::PI
+        }));
+        let full = Arc {
+            end_angle: arc.start_angle,
+            ..arc
+        };
+        assert!(full.centroid().unwrap().isclose(Point::origin()))
+    }
+
+    #[test]
+    fn test_polygon_length_includes_holes() {
+        let square = |lo: f64, hi: f64| {
+            vec![
+                Point { x: lo, y: lo },
+                Point { x: hi, y: lo },
+                Point { x: hi, y: hi },
+                Point { x: lo, y: hi },
+            ]
+        };
+        let frame = Polygon::with_holes(square(0.0, 3.0), vec![square(1.0, 2.0)]);
+        assert_eq!(16.0, Length::length(&frame));
+        let g = Geometry::from(frame);
+        assert_eq!(8.0, g.area());
+        assert_eq!(
+            Some(Rect::from_corners(
+                Point::origin(),
+                Point { x: 3.0, y: 3.0 }
+            )),
+            g.bounding_rect()
+        )
+    }
+
+    #[test]
+    fn test_collection_centroid_uses_highest_dimension() {
+        let mut shapes = GeometryCollection::default();
+        assert_eq!(None, shapes.centroid());
+        assert_eq!(None, shapes.bounding_rect());
+        shapes.push(MultiPoint::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 0.0, y: 3.0 },
+        ]));
+        shapes.push(Point { x: 3.0, y: 0.0 });
+        assert_eq!(Some(Point { x: 1.0, y: 1.0 }), shapes.centroid());
+        let line: Polyline = vec![Point { x: 10.0, y: 0.0 }, Point { x: 10.0, y: 4.0 }]
+            .into_iter()
+            .collect();
+        shapes.push(line);
+        assert_eq!(Some(Point { x: 10.0, y: 2.0 }), shapes.centroid());
+        assert_eq!(4.0, shapes.length());
+        assert_eq!(
+            Some(Rect::from_corners(
+                Point::origin(),
+                Point { x: 10.0, y: 4.0 }
+            )),
+            shapes.bounding_rect()
+        )
+    }
+}
@@ -0,0 +1,268 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+use crate::delaunay::delaunay_triangulation;
+use crate::{Circle, LineSegment, OrderedPoint, Point, Polygon, Scalar};
+
+/// An approximate medial axis of a polygon: the points inside it that are closest to
+/// two or more places on its boundary, joined up into a graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct MedialAxis<T = f64> {
+    /// The junctions, dead ends and bends of the axis
+    pub vertices: Vec<Point<T>>,
+    /// The distance from each vertex to the boundary, which is half the width of the
+    /// polygon there
+    pub radii: Vec<T>,
+    /// Pairs of indices into the vertices
+    pub edges: Vec<[usize; 2]>,
+}
+
+impl<T: Scalar> MedialAxis<T> {
+    /// The edges of the axis as segments
+    pub fn segments(&self) -> impl Iterator<Item = LineSegment<T>> + '_ {
+        self.edges
+            .iter()
+            .map(move |&[a, b]| LineSegment::new(self.vertices[a], self.vertices[b]))
+    }
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// The medial axis of the polygon, approximated by the Voronoi diagram of points
+    /// placed along its boundary no more than `spacing` apart. The Voronoi vertices
+    /// inside the polygon, which are the circumcenters of the Delaunay triangles of
+    /// those points, are joined across the edges the triangles share. Then branches
+    /// shorter than `prune` that run out from a junction to a dead end are cut off, as
+    /// many times as that leaves new ones, which clears away the spikes that every
+    /// convex corner and every wiggle of the boundary would otherwise add. A junction
+    /// whose branches are all short keeps the longest of them. The result is only as
+    /// accurate as the spacing, and costs O(n log n) in the number of points placed.
+    /// Panics if `spacing` is not positive.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let strip: Polygon = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 10.0, y: 0.0}, Point{x: 10.0, y: 2.0}, Point{x: 0.0, y: 2.0},
+    /// ]);
+    /// // Pruning takes off the branches out to the corners, leaving the centerline
+    /// let axis = strip.medial_axis(0.25, 2.0);
+    /// assert!(axis.vertices.iter().all(|v| (v.y - 1.0).abs() < 1e-9));
+    /// let length: f64 = axis.segments().map(|s| s.length()).sum();
+    /// assert!((length - 8.0).abs() < 0.25);
+    /// ```
+    pub fn medial_axis(&self, spacing: T, prune: T) -> MedialAxis<T> {
+        assert!(
+            spacing > T::zero(),
+            "spacing must be positive, got {}",
+            spacing
+        );
+        let mut samples = Vec::new();
+        for ring in self.rings() {
+            for (i, &a) in ring.iter().enumerate() {
+                let b = ring[(i + 1) % ring.len()];
+                let pieces = (a.distance(&b) / spacing).ceil().max(T::one());
+                let n = pieces.as_f64() as usize;
+                samples.extend((0..n).map(|j| a.lerp(&b, T::from_f64(j as f64) / pieces)));
+            }
+        }
+        let mesh = delaunay_triangulation(&samples);
+        let mut vertices = Vec::new();
+        let mut radii = Vec::new();
+        let mut merged: BTreeMap<OrderedPoint<T>, usize> = BTreeMap::new();
+        let vertex: Vec<Option<usize>> = mesh
+            .triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                let circle = Circle::from_three_points(samples[a], samples[b], samples[c])?;
+                if !self.contains(circle.center) {
+                    return None;
+                }
+                // Cocircular samples give several triangles with the same circumcenter
+                let next = vertices.len();
+                let i = *merged.entry(circle.center.into()).or_insert(next);
+                if i == next {
+                    vertices.push(circle.center);
+                    radii.push(circle.radius);
+                }
+                Some(i)
+            })
+            .collect();
+        let mut edges = BTreeSet::new();
+        for (t, neighbors) in mesh.neighbors.iter().enumerate() {
+            for &u in neighbors.iter().flatten() {
+                if let (Some(a), Some(b)) = (vertex[t], vertex[u]) {
+                    if a != b {
+                        edges.insert((a.min(b), a.max(b)));
+                    }
+                }
+            }
+        }
+        prune_branches(&vertices, &mut edges, prune);
+        // Renumber the vertices that are still joined to something
+        let mut index = alloc::vec![None; vertices.len()];
+        let mut axis = MedialAxis {
+            vertices: Vec::new(),
+            radii: Vec::new(),
+            edges: Vec::new(),
+        };
+        for &(a, b) in &edges {
+            let mut renumber = |v: usize| {
+                *index[v].get_or_insert_with(|| {
+                    axis.vertices.push(vertices[v]);
+                    axis.radii.push(radii[v]);
+                    axis.vertices.len() - 1
+                })
+            };
+            let edge = [renumber(a), renumber(b)];
+            axis.edges.push(edge);
+        }
+        axis
+    }
+}
+
+/// The length of a branch and its edges
+type Branch<T> = (T, Vec<(usize, usize)>);
+
+/// Repeatedly cut off the branches shorter than `prune` that lead from a junction to
+/// a dead end
+fn prune_branches<T: Scalar>(points: &[Point<T>], edges: &mut BTreeSet<(usize, usize)>, prune: T) {
+    loop {
+        let mut around = alloc::vec![Vec::new(); points.len()];
+        for &(a, b) in edges.iter() {
+            around[a].push(b);
+            around[b].push(a);
+        }
+        // The short branches at each junction, with their lengths and edges
+        let mut branches: BTreeMap<usize, Vec<Branch<T>>> = BTreeMap::new();
+        for leaf in (0..points.len()).filter(|&v| around[v].len() == 1) {
+            let (mut from, mut at) = (leaf, around[leaf][0]);
+            let mut length = points[from].distance(&points[at]);
+            let mut path = alloc::vec![(from.min(at), from.max(at))];
+            while around[at].len() == 2 {
+                let next = if around[at][0] == from {
+                    around[at][1]
+                } else {
+                    around[at][0]
+                };
+                length += points[at].distance(&points[next]);
+                path.push((at.min(next), at.max(next)));
+                from = at;
+                at = next;
+            }
+            if around[at].len() > 2 && length < prune {
+                branches.entry(at).or_default().push((length, path));
+            }
+        }
+        let mut cut = false;
+        for (junction, mut short) in branches {
+            if short.len() == around[junction].len() {
+                let longest = (0..short.len())
+                    .max_by(|&i, &j| short[i].0.total_cmp(&short[j].0))
+                    .unwrap();
+                short.swap_remove(longest);
+            }
+            for (_, path) in short {
+                for edge in path {
+                    cut |= edges.remove(&edge);
+                }
+            }
+        }
+        if !cut {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn degrees(axis: &MedialAxis) -> Vec<usize> {
+        let mut degree = vec![0; axis.vertices.len()];
+        for &[a, b] in &axis.edges {
+            degree[a] += 1;
+            degree[b] += 1;
+        }
+        degree
+    }
+
+    #[test]
+    fn test_strip_centerline() {
+        let strip = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 20.0, y: 0.0 },
+            Point { x: 20.0, y: 4.0 },
+            Point { x: 0.0, y: 4.0 },
+        ]);
+        let unpruned = strip.medial_axis(0.5, 0.0);
+        // Without pruning there is a branch out to each corner
+        assert_eq!(4, degrees(&unpruned).iter().filter(|&&d| d == 1).count());
+        let axis = strip.medial_axis(0.5, 4.0);
+        assert_eq!(2, degrees(&axis).iter().filter(|&&d| d == 1).count());
+        assert!(degrees(&axis).iter().all(|&d| d <= 2));
+        for (v, &r) in axis.vertices.iter().zip(&axis.radii) {
+            assert!((v.y - 2.0).abs() < 1e-9);
+            assert!((r - 2.0).abs() < 0.1);
+        }
+    }
+
+    #[test]
+    fn test_ring_around_hole() {
+        let frame = Polygon::with_holes(
+            vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 10.0, y: 0.0 },
+                Point { x: 10.0, y: 10.0 },
+                Point { x: 0.0, y: 10.0 },
+            ],
+            vec![vec![
+                Point { x: 3.0, y: 3.0 },
+                Point { x: 7.0, y: 3.0 },
+                Point { x: 7.0, y: 7.0 },
+                Point { x: 3.0, y: 7.0 },
+            ]],
+        );
+        let axis = frame.medial_axis(0.25, 3.0);
+        // A loop around the hole, with no dead ends left to prune
+        assert!(!axis.edges.is_empty());
+        assert!(degrees(&axis).iter().all(|&d| d == 2));
+        for v in &axis.vertices {
+            assert!(
+                hole_distance(*v) > 1.0 && hole_distance(*v) < 2.2,
+                "{:?}",
+                v
+            );
+        }
+    }
+
+    fn hole_distance(p: Point) -> f64 {
+        let dx = (3.0 - p.x).max(p.x - 7.0).max(0.0);
+        let dy = (3.0 - p.y).max(p.y - 7.0).max(0.0);
+        dx.hypot(dy)
+    }
+
+    #[test]
+    fn test_short_branches_keep_longest() {
+        // An equilateral triangle's axis is three equal branches from its center
+        let triangle = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 6.0, y: 0.0 },
+            Point {
+                x: 3.0,
+                y: 27.0f64.sqrt(),
+            },
+        ]);
+        let axis = triangle.medial_axis(0.2, 100.0);
+        assert!(!axis.edges.is_empty());
+        assert_eq!(2, degrees(&axis).iter().filter(|&&d| d == 1).count());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_spacing() {
+        Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 0.0, y: 1.0 },
+        ])
+        .medial_axis(0.0, 1.0);
+    }
+}
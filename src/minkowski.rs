@@ -0,0 +1,302 @@
+use alloc::vec::Vec;
+
+use crate::predicates::{orient2d, Orientation};
+use crate::{BooleanOps, MultiPolygon, OrderedPoint, Point, Polygon, Polyline, Rect, Scalar};
+
+/// The Minkowski sum of two convex polygons, the shape covered by `a` when its origin
+/// is moved over every point of `b`. It is convex, and its edges are those of `a` and
+/// `b` in order of direction, so the two rings are merged in O(n + m) time. Either
+/// polygon may wind in either direction, and the result winds counter-clockwise.
+/// Panics if either polygon is not convex. See `Polygon::minkowski_sum` for other
+/// polygons.
+/// ```
+/// use rsgeo::{minkowski_sum_convex, Point, Polygon};
+/// let square = Polygon::new(vec![
+///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 2.0}, Point{x: 0.0, y: 2.0},
+/// ]);
+/// let diamond = Polygon::new(vec![
+///     Point{x: 1.0, y: 0.0}, Point{x: 0.0, y: 1.0}, Point{x: -1.0, y: 0.0}, Point{x: 0.0, y: -1.0},
+/// ]);
+/// // The square with its corners cut off
+/// let sum = minkowski_sum_convex(&square, &diamond);
+/// assert_eq!(sum.vertices().len(), 8);
+/// assert_eq!(sum.area(), 4.0 + 8.0 + 2.0);
+/// ```
+pub fn minkowski_sum_convex<T: Scalar>(a: &Polygon<T>, b: &Polygon<T>) -> Polygon<T> {
+    assert!(a.is_convex() && b.is_convex(), "polygons must be convex");
+    Polygon::new(convex_sum(&ccw_ring(a), &ccw_ring(b)))
+}
+
+/// The vertices of a polygon counter-clockwise, starting from the lowest
+fn ccw_ring<T: Scalar>(polygon: &Polygon<T>) -> Vec<Point<T>> {
+    let mut ring = polygon.vertices().to_vec();
+    if polygon.orientation() == Orientation::Clockwise {
+        ring.reverse();
+    }
+    let lowest = |&i: &usize| {
+        let p = ring[i];
+        OrderedPoint::new(Point { x: p.y, y: p.x })
+    };
+    let first = (0..ring.len()).min_by_key(lowest).unwrap_or(0);
+    ring.rotate_left(first);
+    ring
+}
+
+/// Merge two counter-clockwise convex rings that start from their lowest vertices.
+/// Edges heading the same way are taken together.
+fn convex_sum<T: Scalar>(p: &[Point<T>], q: &[Point<T>]) -> Vec<Point<T>> {
+    let (n, m) = (p.len(), q.len());
+    if n == 0 || m == 0 {
+        return Vec::new();
+    }
+    let edge = |ring: &[Point<T>], i: usize| ring[(i + 1) % ring.len()] - ring[i % ring.len()];
+    let mut sum = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        sum.push(p[i % n] + q[j % m]);
+        let turn = edge(p, i).cross(&edge(q, j));
+        if j == m || (i < n && turn > T::zero()) {
+            i += 1;
+        } else if i == n || turn < T::zero() {
+            j += 1;
+        } else {
+            i += 1;
+            j += 1;
+        }
+    }
+    sum
+}
+
+/// Convex polygons covering `polygon`, found by cutting it from each reflex corner
+/// along the corner's bisector, which leaves both sides of the corner convex. The
+/// corners of holes are reflex too, and are cut through in both directions, since a
+/// cut from a hole to the outside would not divide the polygon. Each cut removes at
+/// least one reflex corner or hole.
+fn convex_pieces<T: Scalar>(polygon: &Polygon<T>) -> Vec<Polygon<T>> {
+    let bounds = match Rect::from_points(polygon.vertices().iter().copied()) {
+        Some(bounds) => bounds,
+        None => return Vec::new(),
+    };
+    let reach = (bounds.max() - bounds.min()).magnitude() * T::from_f64(2.0);
+    let cut_at = |ring: &[Point<T>], through: bool| {
+        let n = ring.len();
+        (0..n).find_map(|i| {
+            let (u, v, w) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+            if orient2d(u, v, w) >= 0.0 {
+                return None;
+            }
+            let inward = -((u - v).normalize() + (w - v).normalize()).normalize();
+            let mut path = alloc::vec![v, v + inward * reach];
+            if through {
+                path.insert(0, v - inward * reach);
+            }
+            Some(Polyline::new(path))
+        })
+    };
+    // Splitting without a cut tidies the polygon into simple counter-clockwise pieces
+    let mut todo = polygon.split_by_polyline(&Polyline::new(Vec::new()));
+    let mut pieces = Vec::new();
+    while let Some(piece) = todo.pop() {
+        let cut = match piece.holes().first() {
+            Some(hole) => cut_at(hole, true),
+            None => cut_at(piece.vertices(), false),
+        };
+        match cut {
+            Some(cut) => {
+                let parts = piece.split_by_polyline(&cut);
+                // A cut that rounds away to nothing leaves the piece as it is
+                if parts.len() > 1 {
+                    todo.extend(parts);
+                } else {
+                    pieces.push(piece);
+                }
+            }
+            None => pieces.push(piece),
+        }
+    }
+    pieces
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// The Minkowski sum of two polygons of any shape, the region covered by `self`
+    /// when its origin is moved over every point of `other`. Both are cut into convex
+    /// pieces, every pair of pieces is summed with `minkowski_sum_convex`, and the sums
+    /// are merged into one region with `BooleanOps::union`. Holes that are too small to
+    /// survive the sum close up. Inflating an obstacle by a robot's footprint, taken
+    /// about the robot's reference point, is `minkowski_difference`.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// // An L whose arms are 2 long and 1 wide, thickened by a unit square
+    /// let l = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 1.0},
+    ///     Point{x: 1.0, y: 1.0}, Point{x: 1.0, y: 2.0}, Point{x: 0.0, y: 2.0},
+    /// ]);
+    /// let square = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.0}, Point{x: 1.0, y: 1.0}, Point{x: 0.0, y: 1.0},
+    /// ]);
+    /// let sum = l.minkowski_sum(&square);
+    /// assert_eq!(sum.polygons().len(), 1);
+    /// assert_eq!(sum.polygons()[0].vertices().len(), 6);
+    /// assert_eq!(sum.area(), 8.0);
+    /// ```
+    pub fn minkowski_sum(&self, other: &Polygon<T>) -> MultiPolygon<T> {
+        let (a, b) = (convex_pieces(self), convex_pieces(other));
+        let (a, b): (Vec<_>, Vec<_>) = (
+            a.iter().map(ccw_ring).collect(),
+            b.iter().map(ccw_ring).collect(),
+        );
+        // The sums overlap a great deal, so they are merged a pair at a time, which
+        // keeps the boundaries being merged short
+        let mut merged: Vec<MultiPolygon<T>> = a
+            .iter()
+            .flat_map(|p| b.iter().map(move |q| Polygon::new(convex_sum(p, q)).into()))
+            .collect();
+        while merged.len() > 1 {
+            merged = merged
+                .chunks(2)
+                .map(|pair| match pair {
+                    [x, y] => x.union(y),
+                    _ => pair[0].clone(),
+                })
+                .collect();
+        }
+        merged.pop().unwrap_or_default()
+    }
+
+    /// The Minkowski difference of two polygons, the sum of `self` and `other` turned
+    /// about the origin, which is every `p - q` for `p` in `self` and `q` in `other`.
+    /// It is the set of moves of `other` that make it overlap `self`, so with `self` an
+    /// obstacle and `other` a robot's footprint around its reference point it is the
+    /// obstacle in the robot's configuration space.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let obstacle = Polygon::new(vec![
+    ///     Point{x: 2.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 2.0}, Point{x: 2.0, y: 2.0},
+    /// ]);
+    /// // A robot reaching a unit to the right of its reference point
+    /// let robot = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.0}, Point{x: 0.0, y: 1.0},
+    /// ]);
+    /// let space = obstacle.minkowski_difference(&robot);
+    /// assert!(space.contains(Point{x: 1.5, y: 1.0}));
+    /// assert!(!space.contains(Point{x: 0.5, y: 1.0}));
+    /// assert_eq!(space.area(), 4.0 + 4.0 + 0.5);
+    /// ```
+    pub fn minkowski_difference(&self, other: &Polygon<T>) -> MultiPolygon<T> {
+        let turned = Polygon::with_holes(
+            other.vertices().iter().map(|&p| -p).collect(),
+            other
+                .holes()
+                .iter()
+                .map(|h| h.iter().map(|&p| -p).collect())
+                .collect(),
+        );
+        self.minkowski_sum(&turned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(lo: f64, hi: f64) -> Vec<Point> {
+        vec![
+            Point { x: lo, y: lo },
+            Point { x: hi, y: lo },
+            Point { x: hi, y: hi },
+            Point { x: lo, y: hi },
+        ]
+    }
+
+    #[test]
+    fn test_convex_sum() {
+        let a = Polygon::new(square(0.0, 1.0));
+        let mut b = square(0.0, 1.0);
+        b.reverse();
+        let sum = minkowski_sum_convex(&a, &Polygon::new(b));
+        assert_eq!(square(0.0, 2.0), sum.vertices());
+        // A triangle and its reflection make a hexagon
+        let tri = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 0.0, y: 1.0 },
+        ]);
+        let turned = Polygon::new(tri.vertices().iter().map(|&p| -p).collect());
+        let hexagon = minkowski_sum_convex(&tri, &turned);
+        assert_eq!(6, hexagon.vertices().len());
+        assert_eq!(3.0, hexagon.area());
+        assert!(hexagon.is_convex())
+    }
+
+    #[test]
+    #[should_panic(expected = "polygons must be convex")]
+    fn test_convex_sum_rejects_concave() {
+        let dented = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+        ]);
+        minkowski_sum_convex(&dented, &Polygon::new(square(0.0, 1.0)));
+    }
+
+    #[test]
+    fn test_sum_with_holes() {
+        let mut hole = square(2.0, 4.0);
+        hole[1..].reverse();
+        let frame = Polygon::with_holes(square(0.0, 6.0), vec![hole]);
+        // The hole shrinks, and then closes up
+        let sum = frame.minkowski_sum(&Polygon::new(square(0.0, 1.0)));
+        assert_eq!(1, sum.polygons().len());
+        let thick = &sum.polygons()[0];
+        assert_eq!(square(0.0, 7.0), thick.vertices());
+        assert_eq!(1, thick.holes().len());
+        assert_eq!(49.0 - 1.0, sum.area());
+        let sum = frame.minkowski_sum(&Polygon::new(square(0.0, 2.0)));
+        assert_eq!(vec![Polygon::new(square(0.0, 8.0))], sum.polygons());
+        // The frame's own pieces
+        let pieces = convex_pieces(&frame);
+        assert!(pieces.iter().all(Polygon::is_convex));
+        assert_eq!(32.0, pieces.iter().map(Polygon::area).sum::<f64>())
+    }
+
+    #[test]
+    fn test_difference_is_overlapping_moves() {
+        // A comb of three teeth, and a step-shaped robot
+        let comb = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 7.0, y: 0.0 },
+            Point { x: 7.0, y: 4.0 },
+            Point { x: 6.0, y: 4.0 },
+            Point { x: 6.0, y: 1.0 },
+            Point { x: 4.0, y: 1.0 },
+            Point { x: 4.0, y: 4.0 },
+            Point { x: 3.0, y: 4.0 },
+            Point { x: 3.0, y: 1.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 1.0, y: 4.0 },
+            Point { x: 0.0, y: 4.0 },
+        ]);
+        let robot = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.5, y: 0.0 },
+            Point { x: 1.5, y: 0.5 },
+            Point { x: 0.5, y: 0.5 },
+            Point { x: 0.5, y: 1.5 },
+            Point { x: 0.0, y: 1.5 },
+        ]);
+        let space = comb.minkowski_difference(&robot);
+        for i in 0..40 {
+            for j in 0..30 {
+                let t = Point {
+                    x: -2.05 + i as f64 * 0.25,
+                    y: -2.05 + j as f64 * 0.25,
+                };
+                let moved = Polygon::new(robot.vertices().iter().map(|&p| p + t).collect());
+                let overlap = comb.intersection(&moved).area() > 0.0;
+                assert_eq!(overlap, space.contains(t), "{:?}", t);
+            }
+        }
+    }
+}
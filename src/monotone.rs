@@ -0,0 +1,463 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::polygon::ring_orientation;
+use crate::predicates::{orient2d, Orientation};
+use crate::{Point, Polygon, Scalar, Triangle};
+
+impl<T: Scalar> Polygon<T> {
+    /// Cut the polygon into y-monotone pieces, each of which any horizontal line
+    /// crosses at most once, by the sweep of Lee and Preparata. Sweeping down from the
+    /// top, every vertex where the boundary turns back upwards or downwards on the
+    /// inside gets a diagonal to the vertex last seen on the same stretch of the
+    /// sweep line, which also joins each hole to the rest. Vertices at the same height
+    /// are taken from left to right, as though the polygon were turned a little
+    /// clockwise. The pieces are counter-clockwise and without holes, and finding them
+    /// takes O(n log n) time. The polygon should be simple, with simple holes.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// // A W, whose two inner points escape the sweep as it moves down
+    /// let w = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 3.0},
+    ///     Point{x: 3.0, y: 1.0}, Point{x: 2.0, y: 3.0}, Point{x: 1.0, y: 1.0}, Point{x: 0.0, y: 3.0},
+    /// ]);
+    /// let pieces = w.monotone_pieces();
+    /// assert_eq!(pieces.len(), 3);
+    /// assert_eq!(pieces.iter().map(|p| p.area()).sum::<f64>(), w.area());
+    /// ```
+    pub fn monotone_pieces(&self) -> Vec<Polygon<T>> {
+        let sweep = Sweep::new(self);
+        sweep
+            .pieces()
+            .iter()
+            .map(|piece| Polygon::new(piece.iter().map(|&i| sweep.points[i]).collect()))
+            .collect()
+    }
+
+    /// Cut the polygon into triangles in O(n log n) time, by cutting it into monotone
+    /// pieces as [`Polygon::monotone_pieces`] does, and then each of those along a
+    /// single sweep down its two sides. This suits large polygons, where
+    /// [`Polygon::triangulate`] slows down. Every triangle is counter-clockwise, and
+    /// there are n + 2h - 2 of them for n vertices and h holes, less any that would be
+    /// flat.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let framed = Polygon::with_holes(
+    ///     vec![Point{x: 0.0, y: 0.0}, Point{x: 3.0, y: 0.0}, Point{x: 3.0, y: 3.0}, Point{x: 0.0, y: 3.0}],
+    ///     vec![vec![Point{x: 1.0, y: 1.0}, Point{x: 2.0, y: 1.0}, Point{x: 2.0, y: 2.0}, Point{x: 1.0, y: 2.0}]],
+    /// );
+    /// let triangles = framed.triangulate_monotone();
+    /// assert_eq!(triangles.len(), 8);
+    /// assert_eq!(triangles.iter().map(|t| t.area()).sum::<f64>(), 8.0);
+    /// ```
+    pub fn triangulate_monotone(&self) -> Vec<Triangle<T>> {
+        let sweep = Sweep::new(self);
+        let mut triangles = Vec::new();
+        for piece in sweep.pieces() {
+            sweep.triangulate_piece(&piece, &mut triangles);
+        }
+        triangles
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    /// Both neighbours below, and the inside between them
+    Start,
+    /// Both neighbours below, and the inside all around
+    Split,
+    /// Both neighbours above, and the inside between them
+    End,
+    /// Both neighbours above, and the inside all around
+    Merge,
+    /// One neighbour above and one below
+    Regular,
+}
+
+/// The rings of a polygon linked into one list, with the exterior counter-clockwise
+/// and the holes clockwise. Edge `i` runs from vertex `i` to the next.
+struct Sweep<T> {
+    points: Vec<Point<T>>,
+    next: Vec<usize>,
+    prev: Vec<usize>,
+}
+
+impl<T: Scalar> Sweep<T> {
+    fn new(polygon: &Polygon<T>) -> Sweep<T> {
+        let mut sweep = Sweep {
+            points: Vec::new(),
+            next: Vec::new(),
+            prev: Vec::new(),
+        };
+        for (i, ring) in polygon.rings().enumerate() {
+            let orientation = ring_orientation(ring);
+            if orientation == Orientation::Collinear {
+                if i == 0 {
+                    break;
+                }
+                continue;
+            }
+            let mut ring = ring.to_vec();
+            ring.dedup();
+            if ring.len() > 1 && ring.first() == ring.last() {
+                ring.pop();
+            }
+            if (orientation == Orientation::CounterClockwise) != (i == 0) {
+                ring.reverse();
+            }
+            let (first, n) = (sweep.points.len(), ring.len());
+            sweep.points.extend(ring);
+            sweep.next.extend((0..n).map(|j| first + (j + 1) % n));
+            sweep.prev.extend((0..n).map(|j| first + (j + n - 1) % n));
+        }
+        sweep
+    }
+
+    /// Whether vertex `a` comes before vertex `b` in the sweep
+    fn above(&self, a: usize, b: usize) -> bool {
+        self.order(a, b) == Ordering::Less
+    }
+
+    fn order(&self, a: usize, b: usize) -> Ordering {
+        let (p, q) = (self.points[a], self.points[b]);
+        // Not total_cmp, which would put -0 below 0 even to the left of it
+        (q.y, p.x)
+            .partial_cmp(&(p.y, q.x))
+            .unwrap_or(Ordering::Equal)
+    }
+
+    fn kind(&self, v: usize) -> Kind {
+        let (u, w) = (self.prev[v], self.next[v]);
+        let convex = orient2d(self.points[u], self.points[v], self.points[w]) > 0.0;
+        match (self.above(v, u), self.above(v, w), convex) {
+            (true, true, true) => Kind::Start,
+            (true, true, false) => Kind::Split,
+            (false, false, true) => Kind::End,
+            (false, false, false) => Kind::Merge,
+            _ => Kind::Regular,
+        }
+    }
+
+    /// Whether edge `e`, which crosses the sweep line, passes to the left of vertex
+    /// `v` on it
+    fn left_of(&self, e: usize, v: usize) -> bool {
+        let (a, b) = (self.points[e], self.points[self.next[e]]);
+        orient2d(a, b, self.points[v]) > 0.0
+    }
+
+    /// The diagonals that make every piece monotone
+    fn diagonals(&self) -> Vec<(usize, usize)> {
+        let n = self.points.len();
+        let mut events: Vec<usize> = (0..n).collect();
+        events.sort_by(|&a, &b| self.order(a, b));
+        let kinds: Vec<Kind> = (0..n).map(|v| self.kind(v)).collect();
+        // The edges crossing the sweep line with the inside to their right, from left
+        // to right, and the lowest vertex seen so far between each and the next
+        let mut status: Vec<usize> = Vec::new();
+        let mut helper = alloc::vec![0; n];
+        let mut diagonals = Vec::new();
+        for v in events {
+            let before = self.prev[v];
+            let mut finish = |status: &mut Vec<usize>, e: usize| {
+                if kinds[helper[e]] == Kind::Merge {
+                    diagonals.push((v, helper[e]));
+                }
+                if let Some(at) = status.iter().position(|&f| f == e) {
+                    status.remove(at);
+                }
+            };
+            match kinds[v] {
+                Kind::Start => {}
+                Kind::End => finish(&mut status, before),
+                Kind::Merge => finish(&mut status, before),
+                Kind::Regular if self.above(before, v) => finish(&mut status, before),
+                _ => {}
+            }
+            let left = status
+                .partition_point(|&e| self.left_of(e, v))
+                .checked_sub(1)
+                .map(|at| status[at]);
+            match kinds[v] {
+                Kind::Split | Kind::Merge => {
+                    if let Some(e) = left {
+                        if kinds[v] == Kind::Split || kinds[helper[e]] == Kind::Merge {
+                            diagonals.push((v, helper[e]));
+                        }
+                        helper[e] = v;
+                    }
+                }
+                Kind::Regular if !self.above(self.prev[v], v) => {
+                    if let Some(e) = left {
+                        if kinds[helper[e]] == Kind::Merge {
+                            diagonals.push((v, helper[e]));
+                        }
+                        helper[e] = v;
+                    }
+                }
+                _ => {}
+            }
+            let starts = match kinds[v] {
+                Kind::Start | Kind::Split => true,
+                Kind::Regular => self.above(self.prev[v], v),
+                _ => false,
+            };
+            if starts {
+                let at = status.partition_point(|&e| self.left_of(e, v));
+                status.insert(at, v);
+                helper[v] = v;
+            }
+        }
+        diagonals
+    }
+
+    /// The rings of vertices bounding the pieces left by the diagonals
+    fn pieces(&self) -> Vec<Vec<usize>> {
+        let n = self.points.len();
+        // Half edges leaving each vertex: the edges of the rings, and the diagonals
+        // both ways
+        let mut out: Vec<Vec<usize>> = (0..n).map(|v| alloc::vec![self.next[v]]).collect();
+        for (a, b) in self.diagonals() {
+            out[a].push(b);
+            out[b].push(a);
+        }
+        let mut used: Vec<Vec<bool>> = out.iter().map(|o| alloc::vec![false; o.len()]).collect();
+        let mut pieces = Vec::new();
+        for start in 0..n {
+            for k in 0..out[start].len() {
+                if used[start][k] {
+                    continue;
+                }
+                let mut piece = Vec::new();
+                let (mut u, mut j) = (start, k);
+                while !used[u][j] {
+                    used[u][j] = true;
+                    piece.push(u);
+                    let v = out[u][j];
+                    j = self.turn_after(&out[v], v, u);
+                    u = v;
+                }
+                pieces.push(piece);
+            }
+        }
+        pieces
+    }
+
+    /// Which of the half edges `out` leaving `v` comes first turning clockwise from
+    /// the way back to `u`, which keeps the inside of each piece on the left
+    fn turn_after(&self, out: &[usize], v: usize, u: usize) -> usize {
+        let p = |i: usize| self.points[i];
+        // How far round counter-clockwise from the way back each half edge lies
+        let sector = |w: usize| {
+            let turn = orient2d(p(v), p(u), p(w));
+            if turn > 0.0 {
+                1
+            } else if turn < 0.0 {
+                3
+            } else if (p(w) - p(v)).dot_product(&(p(u) - p(v))) > T::zero() {
+                0
+            } else {
+                2
+            }
+        };
+        (0..out.len())
+            .max_by(|&i, &j| {
+                let (a, b) = (out[i], out[j]);
+                sector(a)
+                    .cmp(&sector(b))
+                    .then_with(|| 0.0.partial_cmp(&orient2d(p(v), p(a), p(b))).unwrap())
+            })
+            .unwrap()
+    }
+
+    /// Triangulate a monotone piece by sweeping down its two chains, cutting off
+    /// each vertex that can see the one before it
+    fn triangulate_piece(&self, piece: &[usize], triangles: &mut Vec<Triangle<T>>) {
+        let n = piece.len();
+        if n < 3 {
+            return;
+        }
+        let top = (0..n)
+            .min_by(|&i, &j| self.order(piece[i], piece[j]))
+            .unwrap();
+        let bottom = (0..n)
+            .max_by(|&i, &j| self.order(piece[i], piece[j]))
+            .unwrap();
+        // Going on from the top leads down the left chain, and going back the right
+        let left: Vec<usize> = (1..n)
+            .map(|k| (top + k) % n)
+            .take_while(|&k| k != bottom)
+            .map(|k| piece[k])
+            .collect();
+        let right: Vec<usize> = (1..n)
+            .map(|k| (top + n - k) % n)
+            .take_while(|&k| k != bottom)
+            .map(|k| piece[k])
+            .collect();
+        let mut sorted = alloc::vec![(piece[top], true)];
+        let (mut i, mut j) = (0, 0);
+        while i < left.len() || j < right.len() {
+            if j == right.len() || i < left.len() && self.above(left[i], right[j]) {
+                sorted.push((left[i], true));
+                i += 1;
+            } else {
+                sorted.push((right[j], false));
+                j += 1;
+            }
+        }
+        sorted.push((piece[bottom], false));
+        let mut emit = |a: usize, b: usize, c: usize| {
+            let (a, b, c) = (self.points[a], self.points[b], self.points[c]);
+            match orient2d(a, b, c) {
+                turn if turn > 0.0 => triangles.push(Triangle::new(a, b, c)),
+                turn if turn < 0.0 => triangles.push(Triangle::new(a, c, b)),
+                _ => {}
+            }
+        };
+        let mut stack = alloc::vec![sorted[0], sorted[1]];
+        for &(u, on_left) in &sorted[2..n - 1] {
+            let &(_, top_left) = stack.last().unwrap();
+            if on_left != top_left {
+                // Everything on the stack can be seen across the piece
+                for pair in stack.windows(2) {
+                    emit(u, pair[0].0, pair[1].0);
+                }
+                let last = *stack.last().unwrap();
+                stack.clear();
+                stack.push(last);
+            } else {
+                let mut last = stack.pop().unwrap();
+                while let Some(&t) = stack.last() {
+                    let turn = orient2d(self.points[t.0], self.points[last.0], self.points[u]);
+                    let visible = if on_left { turn > 0.0 } else { turn < 0.0 };
+                    if !visible {
+                        break;
+                    }
+                    emit(t.0, last.0, u);
+                    last = stack.pop().unwrap();
+                }
+                stack.push(last);
+            }
+            stack.push((u, on_left));
+        }
+        let (u, _) = sorted[n - 1];
+        for pair in stack.windows(2) {
+            emit(pair[0].0, pair[1].0, u);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lcg;
+
+    fn is_monotone(piece: &Polygon) -> bool {
+        // Going round, the heights turn from falling to rising only once
+        let v = piece.vertices();
+        let n = v.len();
+        let falling = |i: usize| {
+            let (a, b) = (v[i], v[(i + 1) % n]);
+            b.y < a.y || b.y == a.y && b.x > a.x
+        };
+        (0..n)
+            .filter(|&i| falling(i) != falling((i + 1) % n))
+            .count()
+            <= 2
+    }
+
+    fn check(polygon: &Polygon, vertices: usize, holes: usize) {
+        let pieces = polygon.monotone_pieces();
+        let total: f64 = pieces.iter().map(|p| p.area()).sum();
+        assert!((total - polygon.area()).abs() <= 1e-9 * polygon.area());
+        for piece in &pieces {
+            assert!(is_monotone(piece), "{:?}", piece);
+            assert_eq!(Orientation::CounterClockwise, piece.orientation());
+        }
+        let triangles = polygon.triangulate_monotone();
+        // Where vertices line up the triangle between them is left out
+        assert!(vertices + 2 * holes - 2 >= triangles.len());
+        let total: f64 = triangles.iter().map(|t| t.area()).sum();
+        assert!((total - polygon.area()).abs() <= 1e-9 * polygon.area());
+        assert!(triangles.iter().all(|t| t.signed_area() > 0.0));
+    }
+
+    #[test]
+    fn test_combs() {
+        // A comb, with teeth pointing both up and down
+        let mut comb = Vec::new();
+        for i in 0..6 {
+            let x = 2.0 * i as f64;
+            comb.push(Point { x, y: 0.0 });
+            comb.push(Point {
+                x: x + 1.0,
+                y: -3.0,
+            });
+        }
+        comb.push(Point { x: 12.0, y: 0.0 });
+        for i in (0..6).rev() {
+            let x = 2.0 * i as f64;
+            comb.push(Point { x: x + 2.0, y: 1.0 });
+            comb.push(Point { x: x + 1.0, y: 4.0 });
+        }
+        comb.push(Point { x: 0.0, y: 1.0 });
+        let n = comb.len();
+        let comb = Polygon::new(comb);
+        check(&comb, n, 0);
+        let mut reversed = comb.clone();
+        reversed.reverse();
+        check(&reversed, n, 0);
+    }
+
+    #[test]
+    fn test_holes_and_level_vertices() {
+        // Rows of square holes, so many vertices share a height
+        let mut holes = Vec::new();
+        for i in 0..3 {
+            for j in 0..3 {
+                let (x, y) = (1.0 + 2.0 * i as f64, 1.0 + 2.0 * j as f64);
+                holes.push(vec![
+                    Point { x, y },
+                    Point { x: x + 1.0, y },
+                    Point {
+                        x: x + 1.0,
+                        y: y + 1.0,
+                    },
+                    Point { x, y: y + 1.0 },
+                ]);
+            }
+        }
+        let sheet = Polygon::with_holes(
+            vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 7.0, y: 0.0 },
+                Point { x: 7.0, y: 7.0 },
+                Point { x: 0.0, y: 7.0 },
+            ],
+            holes,
+        );
+        check(&sheet, 4 + 9 * 4, 9);
+    }
+
+    #[test]
+    fn test_random_stars() {
+        let mut rng = Lcg::new(17);
+        let mut next = || rng.unit();
+        for _ in 0..200 {
+            let n = 3 + (next() * 60.0) as usize;
+            let star: Vec<Point> = (0..n)
+                .map(|i| {
+                    let angle = core::f64::consts::TAU * i as f64 / n as f64;
+                    let r = 1.0 + 9.0 * next();
+                    Point {
+                        x: (r * angle.cos() * 4.0).round() / 4.0,
+                        y: (r * angle.sin() * 4.0).round() / 4.0,
+                    }
+                })
+                .collect();
+            let star = Polygon::new(star);
+            if star.is_simple() && star.vertices().len() == n {
+                check(&star, n, 0);
+            }
+        }
+    }
+}
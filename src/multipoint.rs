@@ -0,0 +1,27 @@
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+use crate::{Point, Scalar};
+
+/// An unordered collection of points treated as a single geometry
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiPoint<T = f64> {
+    points: Vec<Point<T>>,
+}
+
+impl<T: Scalar> MultiPoint<T> {
+    pub fn new(points: Vec<Point<T>>) -> MultiPoint<T> {
+        MultiPoint { points }
+    }
+
+    pub fn points(&self) -> &[Point<T>] {
+        &self.points
+    }
+}
+
+impl<T: Scalar> FromIterator<Point<T>> for MultiPoint<T> {
+    fn from_iter<I: IntoIterator<Item = Point<T>>>(iter: I) -> Self {
+        MultiPoint::new(iter.into_iter().collect())
+    }
+}
@@ -0,0 +1,243 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::FromIterator;
+
+use crate::predicates::orient2d;
+use crate::{FillRule, OrderedPoint, Point, Polygon, Ray, RayCast, RayHit, Scalar};
+
+/// A collection of polygons treated as a single region, such as a country made up of
+/// several islands. The polygons are expected not to overlap.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiPolygon<T = f64> {
+    polygons: Vec<Polygon<T>>,
+}
+
+impl<T: Scalar> MultiPolygon<T> {
+    pub fn new(polygons: Vec<Polygon<T>>) -> MultiPolygon<T> {
+        MultiPolygon { polygons }
+    }
+
+    pub fn polygons(&self) -> &[Polygon<T>] {
+        &self.polygons
+    }
+
+    /// The total area of the polygons, less their holes
+    pub fn area(&self) -> T {
+        self.polygons
+            .iter()
+            .fold(T::zero(), |acc, p| acc + p.area())
+    }
+
+    /// The center of mass of the whole region, in which each polygon is weighted by its
+    /// area. Returns None if the region covers no area.
+    /// ```
+    /// use rsgeo::{MultiPolygon, Point, Polygon};
+    /// let square = |x: f64, size: f64| Polygon::new(vec![
+    ///     Point{x, y: 0.0}, Point{x: x + size, y: 0.0},
+    ///     Point{x: x + size, y: size}, Point{x, y: size},
+    /// ]);
+    /// // The larger square pulls the centroid towards itself
+    /// let islands = MultiPolygon::new(vec![square(0.0, 1.0), square(4.0, 2.0)]);
+    /// assert!(islands.centroid().unwrap().isclose(Point{x: 4.1, y: 0.9}));
+    /// ```
+    pub fn centroid(&self) -> Option<Point<T>> {
+        let (sum, area) =
+            self.polygons
+                .iter()
+                .fold((Point::origin(), T::zero()), |(sum, area), p| {
+                    let (s, a) = p.moments();
+                    (sum + s, area + a)
+                });
+        if area == T::zero() {
+            None
+        } else {
+            Some(sum / area)
+        }
+    }
+
+    /// Whether `p` is inside or on the boundary of any of the polygons, using the
+    /// non-zero winding rule
+    pub fn contains(&self, p: Point<T>) -> bool {
+        self.contains_with(p, FillRule::NonZero)
+    }
+
+    /// Whether `p` is inside or on the boundary of any of the polygons, under the given
+    /// fill rule. See `Polygon::contains_with`.
+    pub fn contains_with(&self, p: Point<T>, rule: FillRule) -> bool {
+        self.polygons.iter().any(|poly| poly.contains_with(p, rule))
+    }
+
+    /// The polygons bounded by `edges`, each of which runs from its first point to its
+    /// second with the region on its left. Counter-clockwise rings of edges become
+    /// polygons, and clockwise ones holes in the smallest polygon around them, while
+    /// rings enclosing no area are left out. A pair of polygons, or a polygon and its
+    /// hole, can touch at a single corner.
+    pub(crate) fn from_boundary(edges: &[(Point<T>, Point<T>)]) -> MultiPolygon<T> {
+        let mut leaving: BTreeMap<OrderedPoint<T>, Vec<usize>> = BTreeMap::new();
+        for (e, &(a, _)) in edges.iter().enumerate() {
+            leaving.entry(a.into()).or_default().push(e);
+        }
+        // Where rings meet at a corner, several edges leave it. Following the one
+        // nearest clockwise from where we came keeps to the same ring. The turns are
+        // compared exactly, since edges that leave almost together can be crossed
+        // otherwise.
+        let next: Vec<Option<usize>> = edges
+            .iter()
+            .map(|&(a, b)| {
+                // Whether `c` is more than half a turn anticlockwise from `a`
+                let half = |c: Point<T>| {
+                    let o = orient2d(b, a, c);
+                    o < 0.0 || (o == 0.0 && (a - b).dot_product(&(c - b)) < T::zero())
+                };
+                let turn = |x: &usize, y: &usize| {
+                    let (c, d) = (edges[*x].1, edges[*y].1);
+                    half(c).cmp(&half(d)).then_with(|| {
+                        0.0.partial_cmp(&orient2d(b, c, d))
+                            .unwrap_or(Ordering::Equal)
+                    })
+                };
+                leaving
+                    .get(&b.into())?
+                    .iter()
+                    .max_by(|x, y| turn(x, y))
+                    .copied()
+            })
+            .collect();
+        let mut visited = alloc::vec![false; edges.len()];
+        let (mut outers, mut holes) = (Vec::new(), Vec::new());
+        for e in 0..edges.len() {
+            let mut ring = Vec::new();
+            let mut at = Some(e);
+            while let Some(i) = at.filter(|&i| !visited[i]) {
+                visited[i] = true;
+                ring.push(edges[i].0);
+                at = next[i];
+            }
+            let polygon = Polygon::new(ring);
+            match polygon.signed_area().partial_cmp(&T::zero()) {
+                Some(Ordering::Greater) => outers.push((polygon, Vec::new())),
+                Some(Ordering::Less) => holes.push(polygon.vertices().to_vec()),
+                _ => {}
+            }
+        }
+        // Each hole belongs to the smallest of the polygons around it
+        for hole in holes {
+            let around = outers
+                .iter()
+                .enumerate()
+                .filter(|(_, (outer, _))| {
+                    hole.iter()
+                        .find(|p| !outer.vertices().contains(p))
+                        .is_some_and(|&p| outer.contains(p))
+                })
+                .min_by(|(_, (a, _)), (_, (b, _))| {
+                    a.area().partial_cmp(&b.area()).unwrap_or(Ordering::Equal)
+                })
+                .map(|(i, _)| i);
+            if let Some(i) = around {
+                outers[i].1.push(hole);
+            }
+        }
+        outers
+            .into_iter()
+            .map(|(outer, holes)| Polygon::with_holes(outer.vertices().to_vec(), holes))
+            .collect()
+    }
+}
+
+impl<T: Scalar> From<Polygon<T>> for MultiPolygon<T> {
+    fn from(polygon: Polygon<T>) -> Self {
+        MultiPolygon::new(alloc::vec![polygon])
+    }
+}
+
+impl<T: Scalar> FromIterator<Polygon<T>> for MultiPolygon<T> {
+    fn from_iter<I: IntoIterator<Item = Polygon<T>>>(iter: I) -> Self {
+        MultiPolygon::new(iter.into_iter().collect())
+    }
+}
+
+impl<T: Scalar> RayCast<T> for MultiPolygon<T> {
+    /// The nearest hit against any of the polygons, each treated as a solid region
+    fn cast_ray(&self, ray: &Ray<T>) -> Option<RayHit<T>> {
+        self.polygons
+            .iter()
+            .filter_map(|p| p.cast_ray(ray))
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(lo: f64, hi: f64) -> Vec<Point> {
+        vec![
+            Point { x: lo, y: lo },
+            Point { x: hi, y: lo },
+            Point { x: hi, y: hi },
+            Point { x: lo, y: hi },
+        ]
+    }
+
+    fn frame() -> Polygon {
+        Polygon::with_holes(square(0.0, 4.0), vec![square(1.0, 3.0)])
+    }
+
+    #[test]
+    fn test_area_and_centroid_ignore_winding() {
+        let mut p = frame();
+        assert_eq!(12.0, p.area());
+        assert_eq!(Some(Point { x: 2.0, y: 2.0 }), p.centroid());
+        p.reverse();
+        assert_eq!(12.0, p.area());
+        assert_eq!(Some(Point { x: 2.0, y: 2.0 }), p.centroid());
+        // A hole filling the whole polygon leaves nothing
+        let filled = Polygon::with_holes(square(0.0, 1.0), vec![square(0.0, 1.0)]);
+        assert_eq!(0.0, filled.area());
+        assert_eq!(None, filled.centroid())
+    }
+
+    #[test]
+    fn test_contains_excludes_holes() {
+        let mut p = frame();
+        for _ in 0..2 {
+            for rule in &[FillRule::EvenOdd, FillRule::NonZero] {
+                assert!(p.contains_with(Point { x: 0.5, y: 2.0 }, *rule));
+                assert!(!p.contains_with(Point { x: 2.0, y: 2.0 }, *rule));
+                // The hole's boundary is part of the polygon's boundary
+                assert!(p.contains_with(Point { x: 1.0, y: 2.0 }, *rule));
+            }
+            // The same answers when the hole winds the same way as the exterior
+            let (outer, hole) = (p.vertices().to_vec(), p.holes()[0].clone());
+            p = Polygon::with_holes(outer, vec![hole.into_iter().rev().collect()]);
+        }
+    }
+
+    #[test]
+    fn test_make_ccw_orients_holes_clockwise() {
+        let mut p = frame();
+        p.make_ccw();
+        assert_eq!(0, p.winding_number(Point { x: 2.0, y: 2.0 }));
+        assert_eq!(1, p.winding_number(Point { x: 0.5, y: 2.0 }));
+        assert!(!p.is_convex())
+    }
+
+    #[test]
+    fn test_multipolygon() {
+        let islands: MultiPolygon = vec![frame(), Polygon::new(square(2.0, 2.5))]
+            .into_iter()
+            .collect();
+        assert_eq!(12.25, islands.area());
+        // The small island sits inside the hole of the frame
+        assert!(islands.contains(Point { x: 2.2, y: 2.2 }));
+        assert!(!islands.contains(Point { x: 1.5, y: 1.5 }));
+        let want = (Point { x: 2.0, y: 2.0 } * 12.0 + Point { x: 2.25, y: 2.25 } * 0.25) / 12.25;
+        assert!(islands.centroid().unwrap().isclose(want));
+        let ray = Ray::new(Point { x: 1.5, y: 2.2 }, Point { x: 1.0, y: 0.0 });
+        assert_eq!(Point { x: 2.0, y: 2.2 }, ray.cast(&islands).unwrap().point);
+        assert_eq!(None, MultiPolygon::<f64>::default().centroid())
+    }
+}
@@ -0,0 +1,240 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::delaunay::delaunay_triangulation;
+use crate::predicates::{incircle, orient2d};
+use crate::{Circle, Point, Polygon, Scalar, Tin, TriangleMesh};
+
+/// Natural neighbor interpolation of values at scattered points, by Sibson's method.
+/// The value at a query point is the average of the values at its natural neighbors,
+/// the sites whose Voronoi cells would shrink if it were added as a site, each
+/// weighted by the area its cell would lose. This gives back each sample at its site
+/// and any linear function exactly, and unlike a TIN has a smooth slope everywhere
+/// other than at the sites.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NaturalNeighbor<T = f64> {
+    /// The Delaunay triangulation of the sites, which finds the triangle under a point
+    tin: Tin<T>,
+    neighbors: Vec<[Option<usize>; 3]>,
+    /// The Voronoi vertex at the center of each Delaunay triangle
+    centers: Vec<Point<T>>,
+}
+
+impl<T: Scalar> NaturalNeighbor<T> {
+    /// The interpolant taking `values[i]` at `points[i]`, which takes O(n log n) time
+    /// to set up. Where points repeat, only the first is used. Panics if there is not
+    /// one value for each point.
+    /// ```
+    /// use rsgeo::{NaturalNeighbor, Point};
+    /// let points = [
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 2.0}, Point{x: 0.0, y: 2.0},
+    /// ];
+    /// let surface = NaturalNeighbor::new(&points, &[1.0, 2.0, 3.0, 6.0]);
+    /// // The middle is equally near all four, so their values are averaged
+    /// assert_eq!(surface.interpolate_at(Point{x: 1.0, y: 1.0}), Some(3.0));
+    /// assert_eq!(surface.interpolate_at(Point{x: 0.0, y: 2.0}), Some(6.0));
+    /// assert_eq!(surface.interpolate_at(Point{x: 3.0, y: 1.0}), None);
+    /// ```
+    pub fn new(points: &[Point<T>], values: &[T]) -> NaturalNeighbor<T> {
+        let delaunay = delaunay_triangulation(points);
+        let centers = (0..delaunay.triangles.len())
+            .map(|t| {
+                let [a, b, c] = delaunay.triangles[t].map(|k| points[k]);
+                circumcenter(a, b, c)
+            })
+            .collect();
+        let mesh = TriangleMesh {
+            points: points.to_vec(),
+            triangles: delaunay.triangles,
+        };
+        NaturalNeighbor {
+            tin: Tin::new(mesh, values.to_vec()),
+            neighbors: delaunay.neighbors,
+            centers,
+        }
+    }
+
+    /// The interpolated value at `p`, or None if it is outside the convex hull of the
+    /// sites. Along the hull this is the same as linear interpolation between the ends
+    /// of the edge, and it takes time in proportion to the number of natural neighbors.
+    pub fn interpolate_at(&self, p: Point<T>) -> Option<T> {
+        let values = self.tin.values();
+        let weights = self.weights(p)?;
+        Some(
+            weights
+                .iter()
+                .fold(T::zero(), |total, &(k, w)| total + w * values[k]),
+        )
+    }
+
+    /// The natural neighbors of `p` with their Sibson weights, which are positive and
+    /// add up to one. A point at a site has only that site as a neighbor, and a point
+    /// on the hull only the ends of that edge. None if `p` is outside the hull.
+    pub fn weights(&self, p: Point<T>) -> Option<Vec<(usize, T)>> {
+        let start = self.tin.locate(p)?;
+        let mesh = self.tin.mesh();
+        let points = &mesh.points;
+        let v = mesh.triangles[start];
+        if let Some(&k) = v.iter().find(|&&k| points[k] == p) {
+            return Some(alloc::vec![(k, T::one())]);
+        }
+        for j in 0..3 {
+            let (a, b) = (v[(j + 1) % 3], v[(j + 2) % 3]);
+            if self.neighbors[start][j].is_none() && orient2d(points[a], points[b], p) == 0.0 {
+                let along = (p - points[a]).magnitude() / (points[b] - points[a]).magnitude();
+                return Some(alloc::vec![(a, T::one() - along), (b, along)]);
+            }
+        }
+        // The triangles whose circumcircles hold `p`, which it would replace with a fan
+        // of triangles around itself
+        let mut cavity = alloc::vec![start];
+        let mut k = 0;
+        while k < cavity.len() {
+            let t = cavity[k];
+            k += 1;
+            for n in self.neighbors[t].iter().flatten() {
+                let [a, b, c] = mesh.triangles[*n].map(|k| points[k]);
+                if !cavity.contains(n) && incircle(a, b, c, p) > 0.0 {
+                    cavity.push(*n);
+                }
+            }
+        }
+        // The edges around the cavity counter-clockwise, by the point each leaves, with
+        // the triangle inside and its corner at that point
+        let mut rim = BTreeMap::new();
+        for &t in &cavity {
+            let v = mesh.triangles[t];
+            for j in 0..3 {
+                if self.neighbors[t][j].is_none_or(|n| !cavity.contains(&n)) {
+                    rim.insert(v[(j + 1) % 3], (v[(j + 2) % 3], t, (j + 1) % 3));
+                }
+            }
+        }
+        let center = |a: usize, b: usize| circumcenter(points[a], points[b], p);
+        let mut weights = Vec::with_capacity(rim.len());
+        let mut total = T::zero();
+        for (&from, &(to, _, _)) in &rim {
+            let (next, mut t, mut i) = rim[&to];
+            // The part of the neighbor's cell that the new point takes runs from the new
+            // Voronoi vertex on one side of it, back through the old vertices around
+            // it, to the new vertex on the other side
+            let mut cell = alloc::vec![center(to, next)];
+            loop {
+                cell.push(self.centers[t]);
+                let v = mesh.triangles[t];
+                match self.neighbors[t][(i + 1) % 3] {
+                    Some(n) if cavity.contains(&n) && v[(i + 2) % 3] != from => {
+                        t = n;
+                        i = mesh.triangles[n].iter().position(|&k| k == to).unwrap();
+                    }
+                    _ => break,
+                }
+            }
+            cell.push(center(from, to));
+            let area = Polygon::new(cell).area();
+            total += area;
+            weights.push((to, area));
+        }
+        for w in &mut weights {
+            w.1 /= total;
+        }
+        Some(weights)
+    }
+}
+
+/// The center of the circle through `a`, `b` and `c`, or the midpoint of the longest
+/// side if they are collinear, which is where the center goes as the triangle flattens
+fn circumcenter<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>) -> Point<T> {
+    match Circle::from_three_points(a, b, c) {
+        Some(circle) => circle.center,
+        None => [(a, b), (b, c), (c, a)]
+            .iter()
+            .max_by(|(p, q), (r, s)| {
+                let (pq, rs) = ((*q - *p).magnitude_squared(), (*s - *r).magnitude_squared());
+                pq.total_cmp(&rs)
+            })
+            .map(|(p, q)| p.midpoint(q))
+            .unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convex_hull, scattered};
+
+    #[test]
+    fn test_linear_precision() {
+        let points = scattered(200, 6);
+        let plane = |p: Point| 2.0 * p.x - 3.0 * p.y + 1.0;
+        let values: Vec<f64> = points.iter().map(|&p| plane(p)).collect();
+        let surface = NaturalNeighbor::new(&points, &values);
+        let hull = convex_hull(&points, false);
+        for q in scattered(1000, 3) {
+            match surface.interpolate_at(q) {
+                Some(z) => assert!((z - plane(q)).abs() < 1e-9, "{:?}", q),
+                None => assert!(!hull.contains(q)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_weights() {
+        let points = scattered(100, 12);
+        let surface = NaturalNeighbor::new(&points, &[0.0; 100]);
+        for q in scattered(300, 5) {
+            if let Some(weights) = surface.weights(q) {
+                let total: f64 = weights.iter().map(|w| w.1).sum();
+                assert!((total - 1.0).abs() < 1e-12);
+                assert!(weights.iter().all(|w| w.1 > 0.0));
+                // The weights are the coordinates of the point in terms of the sites
+                let mean = weights
+                    .iter()
+                    .fold(Point::origin(), |m, &(k, w)| m + points[k] * w);
+                assert!(mean.isclose_with(q, 1e-9, 0.0));
+            }
+        }
+        assert_eq!(Some(vec![(7, 1.0)]), surface.weights(points[7]));
+    }
+
+    #[test]
+    fn test_along_hull() {
+        let points = [
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 4.0, y: 0.0 },
+            Point { x: 4.0, y: 4.0 },
+            Point { x: 0.0, y: 4.0 },
+            Point { x: 1.0, y: 2.0 },
+        ];
+        let surface = NaturalNeighbor::new(&points, &[0.0, 4.0, 8.0, 4.0, 3.0]);
+        assert_eq!(Some(1.0), surface.interpolate_at(Point { x: 1.0, y: 0.0 }));
+        assert_eq!(Some(6.0), surface.interpolate_at(Point { x: 4.0, y: 2.0 }));
+        assert_eq!(None, surface.interpolate_at(Point { x: 4.5, y: 2.0 }));
+        // Inside, every site is a natural neighbor of a point near the middle
+        assert_eq!(5, surface.weights(Point { x: 2.0, y: 2.1 }).unwrap().len());
+    }
+
+    #[test]
+    fn test_flat_triangle() {
+        let (a, b, c) = (
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 3.0, y: 3.0 },
+        );
+        assert_eq!(Point { x: 1.5, y: 1.5 }, circumcenter(a, b, c));
+        assert_eq!(Point { x: 1.5, y: 1.5 }, circumcenter(b, a, c));
+        assert_eq!(
+            Point { x: 1.0, y: 0.0 },
+            circumcenter(a, Point { x: 2.0, y: 0.0 }, b)
+        );
+        // Sites all on a line have no triangles to interpolate over
+        let line: Vec<Point> = (0..5)
+            .map(|i| Point {
+                x: i as f64,
+                y: 0.5 * i as f64,
+            })
+            .collect();
+        let surface = NaturalNeighbor::new(&line, &[0.0; 5]);
+        assert_eq!(None, surface.interpolate_at(Point { x: 1.0, y: 1.0 }));
+    }
+}
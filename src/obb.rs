@@ -0,0 +1,186 @@
+use crate::{Angle, Point, Rect, Scalar};
+
+/// An oriented bounding box: a rectangle of size `2 * half_extents`, centered on
+/// `center` and rotated counter-clockwise by `rotation`. The boundary counts as part
+/// of the box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Obb<T = f64> {
+    pub center: Point<T>,
+    pub half_extents: Point<T>,
+    pub rotation: Angle<T>,
+}
+
+impl<T: Scalar> Obb<T> {
+    pub fn new(center: Point<T>, half_extents: Point<T>, rotation: impl Into<Angle<T>>) -> Obb<T> {
+        Obb {
+            center,
+            half_extents,
+            rotation: rotation.into(),
+        }
+    }
+
+    /// The unit vectors along the box's local x and y axes
+    pub fn axes(&self) -> [Point<T>; 2] {
+        let (s, c) = self.rotation.sin_cos();
+        [Point { x: c, y: s }, Point { x: -s, y: c }]
+    }
+
+    pub fn area(&self) -> T {
+        T::from_f64(4.0) * self.half_extents.x * self.half_extents.y
+    }
+
+    /// Whether `p` is inside the box or on its boundary
+    /// ```
+    /// use rsgeo::{Angle, Obb, Point};
+    /// let obb = Obb::new(Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.5}, Angle::from_degrees(45.0));
+    /// assert!(obb.contains(Point{x: 1.0, y: 1.0}));
+    /// assert!(!obb.contains(Point{x: 1.0, y: -1.0}));
+    /// ```
+    pub fn contains(&self, p: Point<T>) -> bool {
+        let [u, v] = self.axes();
+        let d = p - self.center;
+        d.dot_product(&u).abs() <= self.half_extents.x
+            && d.dot_product(&v).abs() <= self.half_extents.y
+    }
+
+    /// The four corners in counter-clockwise order
+    pub fn corners(&self) -> [Point<T>; 4] {
+        let [u, v] = self.axes();
+        let u = u * self.half_extents.x;
+        let v = v * self.half_extents.y;
+        let c = self.center;
+        [c - u - v, c + u - v, c + u + v, c - u + v]
+    }
+
+    /// The smallest axis-aligned rectangle containing the box
+    pub fn bounding_rect(&self) -> Rect<T> {
+        let [u, v] = self.axes();
+        let half = Point {
+            x: self.half_extents.x * u.x.abs() + self.half_extents.y * v.x.abs(),
+            y: self.half_extents.x * u.y.abs() + self.half_extents.y * v.y.abs(),
+        };
+        Rect::from_corners(self.center - half, self.center + half)
+    }
+
+    /// Half the length of the box's shadow when projected onto the unit vector `axis`
+    fn projected_radius(&self, axis: Point<T>) -> T {
+        let [u, v] = self.axes();
+        self.half_extents.x * u.dot_product(&axis).abs()
+            + self.half_extents.y * v.dot_product(&axis).abs()
+    }
+
+    /// Whether the two boxes overlap, using the separating axis theorem. Two convex
+    /// shapes are disjoint exactly when their projections onto some axis do not
+    /// overlap, and for boxes only the four edge normals need to be tried. Boxes that
+    /// only touch intersect.
+    /// ```
+    /// use rsgeo::{Obb, Point};
+    /// let a = Obb::new(Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 1.0}, 0.0);
+    /// let b = Obb::new(Point{x: 2.3, y: 0.0}, Point{x: 1.0, y: 1.0}, 0.0);
+    /// assert!(!a.intersects(&b));
+    /// // A diamond reaches further along the x axis
+    /// let b = Obb::new(Point{x: 2.3, y: 0.0}, Point{x: 1.0, y: 1.0}, std::f64::consts::FRAC_PI_4);
+    /// assert!(a.intersects(&b));
+    /// ```
+    pub fn intersects(&self, other: &Obb<T>) -> bool {
+        let d = other.center - self.center;
+        let [a1, a2] = self.axes();
+        let [b1, b2] = other.axes();
+        [a1, a2, b1, b2].iter().all(|&axis| {
+            d.dot_product(&axis).abs() <= self.projected_radius(axis) + other.projected_radius(axis)
+        })
+    }
+
+    /// Whether the box overlaps an axis-aligned rectangle
+    pub fn intersects_rect(&self, rect: &Rect<T>) -> bool {
+        self.intersects(&Obb::from(*rect))
+    }
+}
+
+impl<T: Scalar> From<Rect<T>> for Obb<T> {
+    /// An unrotated box covering the rectangle
+    fn from(rect: Rect<T>) -> Self {
+        Obb::new(
+            rect.center(),
+            (rect.max() - rect.min()) / T::from_f64(2.0),
+            T::zero(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corners_are_contained() {
+        let obb = Obb::new(
+            Point { x: 3.0, y: -1.0 },
+            Point { x: 2.0, y: 1.0 },
+            Angle::from_degrees(30.0),
+        );
+        let corners = obb.corners();
+        for c in &corners {
+            // Nudge each corner towards the center to absorb rounding
+            assert!(obb.contains(c.lerp(&obb.center, 1e-9)));
+            assert!(!obb.contains(c.lerp(&obb.center, -1e-3)));
+        }
+        let side = corners[0].distance(&corners[1]);
+        assert!(crate::scalar_isclose(4.0, side))
+    }
+
+    #[test]
+    fn test_bounding_rect_contains_corners() {
+        let obb = Obb::new(Point { x: 0.0, y: 0.0 }, Point { x: 3.0, y: 1.0 }, 1.0);
+        let rect = obb.bounding_rect();
+        for c in &obb.corners() {
+            assert!(rect.expand(1e-12).contains(*c));
+        }
+        let tight = Rect::from_points(obb.corners().iter().copied()).unwrap();
+        assert!(rect.min().isclose(tight.min()));
+        assert!(rect.max().isclose(tight.max()))
+    }
+
+    #[test]
+    fn test_intersects_rotated_boxes() {
+        // Two thin boxes forming an X
+        let a = Obb::new(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 5.0, y: 0.1 },
+            Angle::from_degrees(45.0),
+        );
+        let b = Obb::new(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 5.0, y: 0.1 },
+            Angle::from_degrees(-45.0),
+        );
+        assert!(a.intersects(&b));
+        // A parallel box beside `a`, whose bounding rect still overlaps that of `a`
+        let parallel = Obb::new(
+            Point { x: 1.0, y: -1.0 },
+            Point { x: 5.0, y: 0.1 },
+            Angle::from_degrees(45.0),
+        );
+        assert!(a.bounding_rect().intersects(&parallel.bounding_rect()));
+        assert!(!a.intersects(&parallel));
+        assert_eq!(a.intersects(&parallel), parallel.intersects(&a))
+    }
+
+    #[test]
+    fn test_intersects_rect() {
+        let rect = Rect::from_corners(Point { x: 0.0, y: 0.0 }, Point { x: 2.0, y: 2.0 });
+        let diamond = Obb::new(
+            Point { x: 3.2, y: 3.2 },
+            Point { x: 1.0, y: 1.0 },
+            Angle::from_degrees(45.0),
+        );
+        assert!(rect.intersects(&diamond.bounding_rect()));
+        assert!(!diamond.intersects_rect(&rect));
+        let closer = Obb {
+            center: Point { x: 2.6, y: 2.6 },
+            ..diamond
+        };
+        assert!(closer.intersects_rect(&rect))
+    }
+}
@@ -0,0 +1,426 @@
+use alloc::vec::Vec;
+
+use crate::polygon::ring_orientation;
+use crate::predicates::Orientation;
+use crate::{Arc, ArcDirection, BooleanOps, MultiPolygon, Point, Polygon, Polyline, Scalar};
+
+/// How the offset edges on either side of a corner are joined where they move apart
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Join<T = f64> {
+    /// Extend the edges until they meet, unless the tip would lie more than this many
+    /// times the offset distance from the corner, in which case the corner is bevelled
+    Miter(T),
+    /// Round the corner off with an arc, straying at most this far from the true circle
+    Round(T),
+    /// Cut straight across the corner
+    Bevel,
+}
+
+/// How the ends of a buffered polyline are closed off
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cap<T = f64> {
+    /// Round the end off with a half circle, straying at most this far from the true
+    /// circle
+    Round(T),
+    /// Cut straight across the end
+    Flat,
+    /// Cut straight across a square's half beyond the end
+    Square,
+}
+
+impl<T: Scalar> Join<T> {
+    fn check(&self) {
+        match *self {
+            Join::Miter(limit) => assert!(
+                limit >= T::one(),
+                "miter limit must be at least one, got {}",
+                limit
+            ),
+            Join::Round(tolerance) => assert!(
+                tolerance > T::zero(),
+                "tolerance must be positive, got {}",
+                tolerance
+            ),
+            Join::Bevel => {}
+        }
+    }
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// The polygon grown by `distance`, or shrunk if it is negative. Every edge moves
+    /// that far along its normal, and where the moved edges part at a corner they are
+    /// joined in the given style. Holes move the other way, so they shrink as the
+    /// polygon grows. Where parts of the boundary run into each other the result is
+    /// cleaned up, so narrow parts of the polygon vanish when it is shrunk, and holes
+    /// and gaps narrower than twice the distance close when it is grown.
+    ///
+    /// The result is the polygon joined with, or less, the bands swept by its edges and
+    /// the joins swept around its corners, so it never crosses itself. With round
+    /// joins it is everywhere within `distance` of the polygon, or no nearer its
+    /// boundary when shrinking, up to the tolerance of the arcs. Panics if a miter
+    /// limit is less than one or a tolerance is not positive.
+    /// ```
+    /// use rsgeo::{Join, Point, Polygon};
+    /// let square = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 4.0}, Point{x: 0.0, y: 4.0},
+    /// ]);
+    /// assert_eq!(square.offset(1.0, Join::Miter(2.0)).area(), 36.0);
+    /// assert_eq!(square.offset(1.0, Join::Bevel).area(), 34.0);
+    /// assert_eq!(square.offset(-1.0, Join::Bevel).area(), 4.0);
+    /// // The whole square is within two of its boundary
+    /// assert!(square.offset(-2.0, Join::Miter(2.0)).polygons().is_empty());
+    /// ```
+    pub fn offset(&self, distance: T, join: Join<T>) -> MultiPolygon<T> {
+        join.check();
+        let grow = distance > T::zero();
+        let mut bands = Vec::new();
+        for (i, ring) in self.rings().enumerate() {
+            let orientation = ring_orientation(ring);
+            if orientation == Orientation::Collinear {
+                continue;
+            }
+            let mut ring = ring.to_vec();
+            ring.dedup();
+            if ring.len() > 1 && ring.first() == ring.last() {
+                ring.pop();
+            }
+            // Sweep to the right of every edge, which is away from the interior when
+            // growing and into it when shrinking
+            let interior_left = (orientation == Orientation::CounterClockwise) == (i == 0);
+            if interior_left != grow {
+                ring.reverse();
+            }
+            sweep_right(&ring, true, distance.abs(), join, &mut bands);
+        }
+        let bands = MultiPolygon::new(bands);
+        if grow {
+            self.union(&bands)
+        } else {
+            self.difference(&bands)
+        }
+    }
+}
+
+impl<T: Scalar> Polyline<T> {
+    /// The corridor of points within `distance` of the path, with its corners joined
+    /// and its ends capped in the given styles. Where the path crosses or runs back
+    /// over itself the corridor is merged, so loops in the path can leave holes in
+    /// it. A path of a single point is buffered by its caps alone, as though heading
+    /// along the x axis. Panics if the distance is negative, a miter limit is less than
+    /// one or a tolerance is not positive.
+    /// ```
+    /// use rsgeo::{Cap, Join, Point, Polyline};
+    /// let path = Polyline::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 4.0},
+    /// ]);
+    /// assert_eq!(path.buffer(1.0, Cap::Flat, Join::Miter(2.0)).area(), 16.0);
+    /// assert_eq!(path.buffer(1.0, Cap::Square, Join::Bevel).area(), 19.5);
+    /// ```
+    pub fn buffer(&self, distance: T, cap: Cap<T>, join: Join<T>) -> MultiPolygon<T> {
+        assert!(
+            distance >= T::zero(),
+            "distance must not be negative, got {}",
+            distance
+        );
+        join.check();
+        if let Cap::Round(tolerance) = cap {
+            Join::Round(tolerance).check();
+        }
+        let mut path = self.points().to_vec();
+        path.dedup();
+        let mut bands = Vec::new();
+        if distance > T::zero() && !path.is_empty() {
+            sweep_right(&path, false, distance, join, &mut bands);
+            path.reverse();
+            sweep_right(&path, false, distance, join, &mut bands);
+            // Each end of the path, with the way out of it
+            let n = path.len();
+            let along = Point {
+                x: T::one(),
+                y: T::zero(),
+            };
+            let ends = match n {
+                1 => [(path[0], along), (path[0], -along)],
+                _ => [
+                    (path[0], (path[0] - path[1]).normalize()),
+                    (path[n - 1], (path[n - 1] - path[n - 2]).normalize()),
+                ],
+            };
+            for (v, u) in ends {
+                bands.extend(end_cap(v, u, distance, cap));
+            }
+        }
+        MultiPolygon::new(bands).union(&MultiPolygon::new(Vec::new()))
+    }
+}
+
+/// The right-hand normal of the edge from `a` to `b`
+fn right<T: Scalar>(a: Point<T>, b: Point<T>) -> Point<T> {
+    let u = (b - a).normalize();
+    Point { x: u.y, y: -u.x }
+}
+
+/// Add the band swept by moving each edge of a path `distance` to its right, and the
+/// joins swept around the corners where it turns left, so that the moved edges part.
+/// A closed path is a ring, with an edge from its last point back to its first.
+fn sweep_right<T: Scalar>(
+    path: &[Point<T>],
+    closed: bool,
+    distance: T,
+    join: Join<T>,
+    bands: &mut Vec<Polygon<T>>,
+) {
+    let n = path.len();
+    if n < 2 || distance == T::zero() {
+        return;
+    }
+    let edges = if closed { n } else { n - 1 };
+    for i in 0..edges {
+        let (a, b) = (path[i], path[(i + 1) % n]);
+        let m = right(a, b);
+        bands.push(Polygon::new(alloc::vec![
+            a,
+            a + m * distance,
+            b + m * distance,
+            b
+        ]));
+        if !closed && i + 2 >= n {
+            continue;
+        }
+        let c = path[(i + 2) % n];
+        let m2 = right(b, c);
+        // Where the path runs straight on the normals can still differ by rounding,
+        // which would leave a sliver between the bands
+        if (b - a).cross(&(c - b)) >= T::zero() && m != m2 {
+            bands.push(corner(b, m, m2, distance, join));
+        }
+    }
+}
+
+/// The cap at the end `v` of a path heading in the unit direction `u`
+fn end_cap<T: Scalar>(v: Point<T>, u: Point<T>, distance: T, cap: Cap<T>) -> Option<Polygon<T>> {
+    let m = Point { x: u.y, y: -u.x };
+    match cap {
+        Cap::Round(tolerance) => Some(corner(v, m, -m, distance, Join::Round(tolerance))),
+        Cap::Flat => None,
+        Cap::Square => {
+            let (side, ahead) = (m * distance, u * distance);
+            Some(Polygon::new(alloc::vec![
+                v + side,
+                v + side + ahead,
+                v - side + ahead,
+                v - side
+            ]))
+        }
+    }
+}
+
+/// The join at a corner `v` between the moved edges `v + m1 * distance` and
+/// `v + m2 * distance`, where `m2` is counter-clockwise from `m1`
+fn corner<T: Scalar>(
+    v: Point<T>,
+    m1: Point<T>,
+    m2: Point<T>,
+    distance: T,
+    join: Join<T>,
+) -> Polygon<T> {
+    let (a, b) = (v + m1 * distance, v + m2 * distance);
+    match join {
+        Join::Miter(limit) => {
+            // The tip lies 1 / cos(theta / 2) times the distance from the corner, where
+            // theta is the angle between the normals
+            let cos = m1.dot_product(&m2);
+            if (T::one() + cos) * limit * limit >= T::from_f64(2.0) {
+                let tip = v + (m1 + m2) * (distance / (T::one() + cos));
+                return Polygon::new(alloc::vec![v, a, tip, b]);
+            }
+        }
+        Join::Round(tolerance) => {
+            let (start, end) = (m1.angle(), m2.angle());
+            // Equal angles would describe a whole circle
+            if start.radians() != end.radians() {
+                let arc = Arc::new(v, distance, start, end, ArcDirection::CounterClockwise);
+                let mut fan = arc.approximate(tolerance);
+                let last = fan.len() - 1;
+                fan[0] = a;
+                fan[last] = b;
+                fan.insert(0, v);
+                return Polygon::new(fan);
+            }
+        }
+        Join::Bevel => {}
+    }
+    Polygon::new(alloc::vec![v, a, b])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoundingRect, Rect};
+
+    fn square(lo: f64, hi: f64) -> Vec<Point> {
+        vec![
+            Point { x: lo, y: lo },
+            Point { x: hi, y: lo },
+            Point { x: hi, y: hi },
+            Point { x: lo, y: hi },
+        ]
+    }
+
+    #[test]
+    fn test_square_joins() {
+        let poly = Polygon::new(square(0.0, 10.0));
+        let grown = poly.offset(1.0, Join::Miter(2.0));
+        assert_eq!(grown.polygons(), &[Polygon::new(square(-1.0, 11.0))]);
+        assert_eq!(142.0, poly.offset(1.0, Join::Bevel).area());
+        let round = poly.offset(1.0, Join::Round(0.001)).area();
+        assert!(round < 140.0 + core::f64::consts::PI);
+        assert!(round > 140.0 + core::f64::consts::PI - 0.01);
+        // Clockwise rings grow the same way
+        let mut cw = square(0.0, 10.0);
+        cw.reverse();
+        assert_eq!(142.0, Polygon::new(cw).offset(1.0, Join::Bevel).area());
+        for join in [Join::Miter(2.0), Join::Round(0.01), Join::Bevel] {
+            let shrunk = poly.offset(-1.0, join);
+            assert_eq!(shrunk.polygons(), &[Polygon::new(square(1.0, 9.0))]);
+        }
+        assert_eq!(poly.offset(0.0, Join::Bevel).polygons(), &[poly]);
+    }
+
+    #[test]
+    fn test_reflex_corner() {
+        let l = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 2.0, y: 1.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 1.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+        ]);
+        let mitered = l.offset(-0.25, Join::Miter(2.0));
+        assert_eq!(mitered.polygons()[0].vertices().len(), 6);
+        assert_eq!(1.25, mitered.area());
+        // Rounding the reflex corner takes off the corner of a square less a quarter
+        // of a circle
+        let round = l.offset(-0.25, Join::Round(1e-4)).area();
+        let expected = 1.25 + 0.0625 * (1.0 - core::f64::consts::FRAC_PI_4);
+        assert!((round - expected).abs() < 1e-4);
+        assert_eq!(1.25 + 0.03125, l.offset(-0.25, Join::Bevel).area());
+    }
+
+    #[test]
+    fn test_miter_limit() {
+        // A sharp spike, whose miter reaches far beyond its tip
+        let spike = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 10.0, y: 1.0 },
+            Point { x: 0.0, y: 2.0 },
+        ]);
+        let bevel = spike.offset(0.5, Join::Bevel).area();
+        assert_eq!(bevel, spike.offset(0.5, Join::Miter(1.0)).area());
+        let mitered = spike.offset(0.5, Join::Miter(100.0));
+        assert!(mitered.area() > bevel + 2.0);
+        assert!(mitered.contains(Point { x: 14.0, y: 1.0 }));
+    }
+
+    #[test]
+    fn test_holes_and_narrow_parts() {
+        let framed = Polygon::with_holes(square(0.0, 10.0), vec![square(4.0, 6.0)]);
+        let grown = framed.offset(0.5, Join::Miter(2.0));
+        assert_eq!(120.0, grown.area());
+        assert_eq!(1, grown.polygons()[0].holes().len());
+        // The hole closes up
+        let closed = framed.offset(1.0, Join::Miter(2.0));
+        assert_eq!(closed.polygons(), &[Polygon::new(square(-1.0, 11.0))]);
+        assert_eq!(48.0, framed.offset(-1.0, Join::Miter(2.0)).area());
+
+        // Two squares joined by a narrow bar come apart when shrunk
+        let dumbbell = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 4.0, y: 0.0 },
+            Point { x: 4.0, y: 1.5 },
+            Point { x: 6.0, y: 1.5 },
+            Point { x: 6.0, y: 0.0 },
+            Point { x: 10.0, y: 0.0 },
+            Point { x: 10.0, y: 4.0 },
+            Point { x: 6.0, y: 4.0 },
+            Point { x: 6.0, y: 2.5 },
+            Point { x: 4.0, y: 2.5 },
+            Point { x: 4.0, y: 4.0 },
+            Point { x: 0.0, y: 4.0 },
+        ]);
+        let apart = dumbbell.offset(-0.75, Join::Miter(2.0));
+        assert_eq!(2, apart.polygons().len());
+        assert_eq!(2.0 * 2.5 * 2.5, apart.area());
+        // Round joins leave the points beside the bar that are far enough from its
+        // corners
+        let rounded = dumbbell.offset(-0.75, Join::Round(0.01));
+        assert_eq!(2, rounded.polygons().len());
+        assert!(rounded.area() > apart.area());
+    }
+
+    #[test]
+    fn test_buffer_caps() {
+        let segment = Polyline::new(vec![Point { x: 0.0, y: 0.0 }, Point { x: 3.0, y: 4.0 }]);
+        let flat = segment.buffer(1.0, Cap::Flat, Join::Bevel);
+        assert!(crate::scalar_isclose(10.0, flat.area()));
+        let square = segment.buffer(1.0, Cap::Square, Join::Bevel);
+        assert!(crate::scalar_isclose(14.0, square.area()));
+        let round = segment.buffer(1.0, Cap::Round(1e-3), Join::Bevel).area();
+        assert!(round < 10.0 + core::f64::consts::PI);
+        assert!(round > 10.0 + core::f64::consts::PI - 0.01);
+
+        // A single point
+        let point = Polyline::new(vec![Point { x: 1.0, y: 1.0 }; 2]);
+        let disc = point.buffer(2.0, Cap::Round(1e-3), Join::Bevel).area();
+        assert!((disc - 4.0 * core::f64::consts::PI).abs() < 0.02);
+        let square = point.buffer(2.0, Cap::Square, Join::Bevel);
+        assert_eq!(
+            square.polygons()[0].bounding_rect(),
+            Some(Rect::from_corners(
+                Point { x: -1.0, y: -1.0 },
+                Point { x: 3.0, y: 3.0 }
+            ))
+        );
+        assert!(point
+            .buffer(2.0, Cap::Flat, Join::Bevel)
+            .polygons()
+            .is_empty());
+        assert!(segment
+            .buffer(0.0, Cap::Square, Join::Bevel)
+            .polygons()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_buffer_crossing_path() {
+        // A path that loops around and crosses itself, leaving a hole in the middle
+        let path = Polyline::new(vec![
+            Point { x: 2.0, y: -2.0 },
+            Point { x: 2.0, y: 8.0 },
+            Point { x: 8.0, y: 8.0 },
+            Point { x: 8.0, y: 2.0 },
+            Point { x: -2.0, y: 2.0 },
+        ]);
+        let corridor = path.buffer(1.0, Cap::Flat, Join::Miter(2.0));
+        assert_eq!(1, corridor.polygons().len());
+        let hole = &corridor.polygons()[0].holes()[0];
+        assert_eq!(16.0, Polygon::new(hole.clone()).area());
+        assert_eq!(64.0 - 16.0 + 2.0 * 6.0, corridor.area());
+        // Doubling straight back covers the same ground
+        let back = Polyline::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 5.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+        ]);
+        let doubled = back.buffer(1.0, Cap::Square, Join::Miter(2.0));
+        assert_eq!(doubled.area(), 12.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "distance must not be negative")]
+    fn test_buffer_negative() {
+        let segment = Polyline::new(vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }]);
+        segment.buffer(-1.0, Cap::Flat, Join::Bevel);
+    }
+}
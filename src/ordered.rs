@@ -0,0 +1,160 @@
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+use crate::{Point, Scalar};
+
+/// A wrapper around a Point that is totally ordered and hashable, so that points can be
+/// sorted, deduplicated, and used as keys in a `HashMap` or `BTreeMap`.
+///
+/// Points are ordered lexicographically, first by x and then by y, using the IEEE 754
+/// total order. On conversion `-0.0` is replaced by `0.0` and every NaN by the same
+/// canonical NaN, so that the two zeros compare equal and all NaNs sort after every
+/// other value.
+/// ```
+/// use rsgeo::{OrderedPoint, Point};
+/// let mut pts: Vec<OrderedPoint> = vec![
+///     Point{x: 1.0, y: 0.0}.into(),
+///     Point{x: 0.0, y: 2.0}.into(),
+///     Point{x: 1.0, y: -0.0}.into(),
+/// ];
+/// pts.sort();
+/// pts.dedup();
+/// assert_eq!(pts.len(), 2);
+/// assert_eq!(pts[0].point(), Point{x: 0.0, y: 2.0});
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        from = "Point<T>",
+        into = "Point<T>",
+        bound(
+            serialize = "T: Scalar + serde::Serialize",
+            deserialize = "T: Scalar + serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct OrderedPoint<T = f64>(Point<T>);
+
+impl<T: Scalar> OrderedPoint<T> {
+    /// Wrap a point, canonicalizing its signed zeros and NaNs
+    pub fn new(p: Point<T>) -> OrderedPoint<T> {
+        OrderedPoint(Point {
+            x: canonicalize(p.x),
+            y: canonicalize(p.y),
+        })
+    }
+
+    /// Get the wrapped point back out
+    pub fn point(&self) -> Point<T> {
+        self.0
+    }
+}
+
+fn canonicalize<T: Scalar>(v: T) -> T {
+    if v.is_nan() {
+        T::nan()
+    } else if v == T::zero() {
+        T::zero()
+    } else {
+        v
+    }
+}
+
+impl<T: Scalar> From<Point<T>> for OrderedPoint<T> {
+    fn from(p: Point<T>) -> Self {
+        OrderedPoint::new(p)
+    }
+}
+
+impl<T: Scalar> From<OrderedPoint<T>> for Point<T> {
+    fn from(p: OrderedPoint<T>) -> Self {
+        p.0
+    }
+}
+
+impl<T: Scalar> PartialEq for OrderedPoint<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Scalar> Eq for OrderedPoint<T> {}
+
+impl<T: Scalar> PartialOrd for OrderedPoint<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Scalar> Ord for OrderedPoint<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .x
+            .total_cmp(&other.0.x)
+            .then_with(|| self.0.y.total_cmp(&other.0.y))
+    }
+}
+
+impl<T: Scalar> Hash for OrderedPoint<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // The values are canonicalized on construction, so equal points have equal bits
+        self.0.x.to_bits_u64().hash(state);
+        self.0.y.to_bits_u64().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_signed_zeros_are_equal() {
+        let a = OrderedPoint::from(Point { x: 0.0, y: -0.0 });
+        let b = OrderedPoint::from(Point { x: -0.0, y: 0.0 });
+        assert_eq!(a, b);
+        let set: HashSet<OrderedPoint> = vec![a, b].into_iter().collect();
+        assert_eq!(1, set.len())
+    }
+
+    #[test]
+    fn test_nan_is_equal_to_itself_and_sorts_last() {
+        let nan = OrderedPoint::from(Point {
+            x: f64::NAN,
+            y: 0.0,
+        });
+        let inf = OrderedPoint::from(Point {
+            x: f64::INFINITY,
+            y: 0.0,
+        });
+        assert_eq!(nan, nan);
+        assert!(inf < nan)
+    }
+
+    #[test]
+    fn test_lexicographic_order() {
+        let a = OrderedPoint::from(Point { x: 1.0, y: 5.0 });
+        let b = OrderedPoint::from(Point { x: 2.0, y: -5.0 });
+        let c = OrderedPoint::from(Point { x: 2.0, y: 0.0 });
+        assert!(a < b);
+        assert!(b < c)
+    }
+
+    #[test]
+    fn test_f32_signed_zeros_are_equal() {
+        let a = OrderedPoint::from(Point { x: 0.0_f32, y: 1.0 });
+        let b = OrderedPoint::from(Point {
+            x: -0.0_f32,
+            y: 1.0,
+        });
+        assert_eq!(a, b)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let p = Point { x: 1.25, y: -3.5 };
+        assert_eq!(p, Point::from(OrderedPoint::from(p)))
+    }
+}
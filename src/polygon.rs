@@ -0,0 +1,791 @@
+use alloc::vec::Vec;
+
+use crate::predicates::{orient2d, orientation, Orientation};
+use crate::{LineSegment, OrderedPoint, Point, Ray, RayCast, RayHit, Scalar, SegmentIntersection};
+
+/// How to decide which points are inside a ring that crosses over itself. The two
+/// rules agree for rings that do not self-intersect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FillRule {
+    /// Inside if a ray from the point crosses the ring an odd number of times, so
+    /// regions wound twice are outside
+    EvenOdd,
+    /// Inside if the ring winds around the point at least once in either direction
+    NonZero,
+}
+
+/// A polygon bounded by an exterior ring of vertices, with any number of interior rings
+/// cutting holes out of it. Each ring is closed, with its last vertex implicitly joined
+/// back to its first, so the first vertex should not be repeated. Rings may wind in
+/// either direction.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polygon<T = f64> {
+    vertices: Vec<Point<T>>,
+    #[cfg_attr(feature = "serde", serde(default = "Vec::new"))]
+    holes: Vec<Vec<Point<T>>>,
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// A polygon without holes
+    pub fn new(vertices: Vec<Point<T>>) -> Polygon<T> {
+        Polygon {
+            vertices,
+            holes: Vec::new(),
+        }
+    }
+
+    /// A polygon with the given exterior ring and holes. The holes are expected to lie
+    /// inside the exterior ring and not to overlap each other.
+    pub fn with_holes(vertices: Vec<Point<T>>, holes: Vec<Vec<Point<T>>>) -> Polygon<T> {
+        Polygon { vertices, holes }
+    }
+
+    /// The vertices of the exterior ring
+    pub fn vertices(&self) -> &[Point<T>] {
+        &self.vertices
+    }
+
+    /// The interior rings
+    pub fn holes(&self) -> &[Vec<Point<T>>] {
+        &self.holes
+    }
+
+    /// Every ring, starting with the exterior
+    pub(crate) fn rings(&self) -> impl Iterator<Item = &[Point<T>]> + '_ {
+        core::iter::once(&self.vertices[..]).chain(self.holes.iter().map(|h| &h[..]))
+    }
+
+    /// Iterate over the edges of the exterior ring, including the closing edge from the
+    /// last vertex back to the first
+    pub fn edges(&self) -> impl Iterator<Item = LineSegment<T>> + '_ {
+        ring_edges(&self.vertices)
+    }
+
+    /// The signed area of the exterior ring by the shoelace formula, ignoring any
+    /// holes. Positive for counter-clockwise rings.
+    pub fn signed_area(&self) -> T {
+        ring_signed_area(&self.vertices)
+    }
+
+    /// The area covered by the polygon, i.e. the area of the exterior ring less the area
+    /// of the holes, regardless of the direction that each ring winds in
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let square = |lo: f64, hi: f64| vec![
+    ///     Point{x: lo, y: lo}, Point{x: hi, y: lo}, Point{x: hi, y: hi}, Point{x: lo, y: hi},
+    /// ];
+    /// let frame = Polygon::with_holes(square(0.0, 3.0), vec![square(1.0, 2.0)]);
+    /// assert_eq!(frame.area(), 8.0);
+    /// ```
+    pub fn area(&self) -> T {
+        self.holes.iter().fold(self.signed_area().abs(), |acc, h| {
+            acc - ring_signed_area(h).abs()
+        })
+    }
+
+    /// The center of mass of the region covered by the polygon, taking the holes into
+    /// account. Returns None if the polygon covers no area.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let square = |lo: f64, hi: f64| vec![
+    ///     Point{x: lo, y: lo}, Point{x: hi, y: lo}, Point{x: hi, y: hi}, Point{x: lo, y: hi},
+    /// ];
+    /// // Cutting a hole out of the left half moves the centroid right
+    /// let notched = Polygon::with_holes(square(0.0, 4.0), vec![square(1.0, 2.0)]);
+    /// assert!(notched.centroid().unwrap().x > 2.0);
+    /// ```
+    pub fn centroid(&self) -> Option<Point<T>> {
+        let (sum, area) = self.moments();
+        if area == T::zero() {
+            None
+        } else {
+            Some(sum / area)
+        }
+    }
+
+    /// The area and the area-weighted sum of the centroids of the rings, with holes
+    /// counted negatively
+    pub(crate) fn moments(&self) -> (Point<T>, T) {
+        let mut sum = Point::origin();
+        let mut total = T::zero();
+        for (i, ring) in self.rings().enumerate() {
+            let (centroid, signed) = ring_centroid(ring);
+            let area = if i == 0 { signed.abs() } else { -signed.abs() };
+            sum += centroid * area;
+            total += area;
+        }
+        (sum, total)
+    }
+
+    /// The winding direction of the exterior ring, or `Collinear` if it encloses no
+    /// area. For simple rings this is decided exactly, by the turn at the lowest
+    /// leftmost vertex, which is always convex. Rings where that turn is degenerate
+    /// fall back to the sign of the signed area.
+    /// ```
+    /// use rsgeo::{predicates::Orientation, Point, Polygon};
+    /// let mut tri = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 0.0, y: 1.0}, Point{x: 1.0, y: 0.0},
+    /// ]);
+    /// assert_eq!(tri.orientation(), Orientation::Clockwise);
+    /// tri.make_ccw();
+    /// assert_eq!(tri.orientation(), Orientation::CounterClockwise);
+    /// ```
+    pub fn orientation(&self) -> Orientation {
+        ring_orientation(&self.vertices)
+    }
+
+    /// Reverse the winding direction of every ring in place
+    pub fn reverse(&mut self) {
+        self.vertices.reverse();
+        for hole in &mut self.holes {
+            hole.reverse();
+        }
+    }
+
+    /// Reverse rings in place so that the exterior winds counter-clockwise and the holes
+    /// wind clockwise, which is the usual convention. Rings that enclose no area are left
+    /// alone.
+    pub fn make_ccw(&mut self) {
+        if self.orientation() == Orientation::Clockwise {
+            self.vertices.reverse();
+        }
+        for hole in &mut self.holes {
+            if ring_orientation(hole) == Orientation::CounterClockwise {
+                hole.reverse();
+            }
+        }
+    }
+
+    /// Whether the polygon is convex, in either winding order. Every turn must be in
+    /// the same direction, as decided by the exact orientation predicate, and the ring
+    /// must go around only once, which rules out stars like the pentagram. Straight
+    /// and repeated vertices are allowed, but rings that enclose no area are not
+    /// convex, and neither are polygons with holes.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let square = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.0}, Point{x: 1.0, y: 1.0}, Point{x: 0.0, y: 1.0},
+    /// ]);
+    /// assert!(square.is_convex());
+    /// let dented = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.0}, Point{x: 0.5, y: 0.5}, Point{x: 1.0, y: 1.0},
+    ///     Point{x: 0.0, y: 1.0},
+    /// ]);
+    /// assert!(!dented.is_convex());
+    /// ```
+    pub fn is_convex(&self) -> bool {
+        if !self.holes.is_empty() {
+            return false;
+        }
+        let v = &self.vertices;
+        let n = v.len();
+        let mut turn = Orientation::Collinear;
+        for i in 0..n {
+            match orientation(v[i], v[(i + 1) % n], v[(i + 2) % n]) {
+                Orientation::Collinear => {}
+                o if turn == Orientation::Collinear => turn = o,
+                o if o != turn => return false,
+                _ => {}
+            }
+        }
+        if turn == Orientation::Collinear {
+            return false;
+        }
+        // Walking once around a convex ring, the edges head right along one chain and
+        // left along the other, so the x direction flips exactly twice
+        let heading = |i: usize| {
+            let (a, b) = (v[i % n], v[(i + 1) % n]);
+            if b.x > a.x {
+                1
+            } else if b.x < a.x {
+                -1
+            } else {
+                0
+            }
+        };
+        let start = match (0..n).find(|&i| heading(i) != 0) {
+            Some(i) => i,
+            None => return false,
+        };
+        let mut last = heading(start);
+        let mut flips = 0;
+        for i in start + 1..=start + n {
+            let h = heading(i);
+            if h != 0 && h != last {
+                flips += 1;
+                last = h;
+            }
+        }
+        flips == 2
+    }
+
+    /// How many times the boundary winds counter-clockwise around `p`, counting
+    /// clockwise turns as negative, summed over every ring. Each edge crossing is
+    /// classified with the exact orientation predicate. After `make_ccw` this is zero
+    /// inside the holes. The result for points on the boundary is not meaningful.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let square = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.0}, Point{x: 1.0, y: 1.0}, Point{x: 0.0, y: 1.0},
+    /// ]);
+    /// assert_eq!(square.winding_number(Point{x: 0.5, y: 0.5}), 1);
+    /// let reversed = Polygon::new(square.vertices().iter().rev().copied().collect());
+    /// assert_eq!(reversed.winding_number(Point{x: 0.5, y: 0.5}), -1);
+    /// ```
+    pub fn winding_number(&self, p: Point<T>) -> i32 {
+        self.rings().map(|ring| ring_winding_number(ring, p)).sum()
+    }
+
+    /// Whether `p` is inside the polygon or on its boundary, using the non-zero winding
+    /// rule. See `contains_with` for the even-odd rule.
+    pub fn contains(&self, p: Point<T>) -> bool {
+        self.contains_with(p, FillRule::NonZero)
+    }
+
+    /// Whether `p` is inside the polygon under the given fill rule. The rule is applied
+    /// to each ring separately, and `p` is inside when it is inside the exterior ring
+    /// but not inside any of the holes, whichever way the rings wind. Points exactly
+    /// on the boundary of any ring, including the vertices, always count as inside,
+    /// which makes the answer independent of the direction a crossing ray is cast in.
+    /// ```
+    /// use rsgeo::{FillRule, Point, Polygon};
+    /// use std::f64::consts::PI;
+    /// // A pentagram: the central pentagon is wound twice
+    /// let star = Polygon::new((0..5)
+    ///     .map(|i| Point::from_polar(1.0, PI / 2.0 + 4.0 * PI * i as f64 / 5.0))
+    ///     .collect());
+    /// let center = Point{x: 0.0, y: 0.0};
+    /// assert!(star.contains_with(center, FillRule::NonZero));
+    /// assert!(!star.contains_with(center, FillRule::EvenOdd));
+    /// assert!(star.contains_with(star.vertices()[0], FillRule::EvenOdd));
+    /// ```
+    pub fn contains_with(&self, p: Point<T>, rule: FillRule) -> bool {
+        if self.rings().any(|ring| ring_on_boundary(ring, p)) {
+            return true;
+        }
+        ring_encloses(&self.vertices, p, rule)
+            && !self.holes.iter().any(|h| ring_encloses(h, p, rule))
+    }
+
+    /// The indices of the edges with nonzero length, in ring order. Repeated
+    /// consecutive vertices are skipped over rather than treated as a self-intersection.
+    fn proper_edges(&self) -> Vec<usize> {
+        let n = self.vertices.len();
+        (0..n)
+            .filter(|&i| self.vertices[i] != self.vertices[(i + 1) % n])
+            .collect()
+    }
+
+    /// How the `a`th and `b`th of the proper `edges` meet, if that makes the ring
+    /// non-simple. Neighbouring edges always share a vertex, so they only count if
+    /// they fold back over each other.
+    fn conflict(&self, edges: &[usize], a: usize, b: usize) -> Option<SegmentIntersection<T>> {
+        let m = edges.len();
+        let seg = |k: usize| {
+            let n = self.vertices.len();
+            LineSegment::new(self.vertices[edges[k]], self.vertices[(edges[k] + 1) % n])
+        };
+        let adjacent = (a + 1) % m == b || (b + 1) % m == a;
+        // Always intersect in the same order, so the rounded location does not depend
+        // on which edge the caller found first
+        let (a, b) = (a.min(b), a.max(b));
+        match seg(a).intersect(&seg(b)) {
+            SegmentIntersection::None => None,
+            SegmentIntersection::Point(_) if adjacent => None,
+            found => Some(found),
+        }
+    }
+
+    /// Whether the ring has at least three vertices and no two of its edges meet,
+    /// other than neighbouring edges at their shared vertex. Touching counts as
+    /// meeting, so a ring that pinches to a single point is not simple. Repeated
+    /// consecutive vertices are ignored.
+    ///
+    /// Uses the Shamos–Hoey sweep line, which stops at the first intersection it finds.
+    /// Every decision is made with exact orientation predicates.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let square = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.0}, Point{x: 1.0, y: 1.0}, Point{x: 0.0, y: 1.0},
+    /// ]);
+    /// assert!(square.is_simple());
+    /// let bowtie = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 1.0}, Point{x: 1.0, y: 0.0}, Point{x: 0.0, y: 1.0},
+    /// ]);
+    /// assert!(!bowtie.is_simple());
+    /// ```
+    pub fn is_simple(&self) -> bool {
+        let edges = self.proper_edges();
+        edges.len() >= 3 && !self.sweep_finds_conflict(&edges)
+    }
+
+    /// The Shamos–Hoey sweep. Edges are inserted into the sweep line status, which is
+    /// kept sorted from bottom to top, at their lexicographically smaller endpoint and
+    /// removed at the larger one. As long as no edges have crossed yet the status stays
+    /// consistently ordered, and the first crossing is between two edges that were
+    /// once neighbours in it, so only neighbours need to be compared.
+    fn sweep_finds_conflict(&self, edges: &[usize]) -> bool {
+        let n = self.vertices.len();
+        let ends: Vec<(Point<T>, Point<T>)> = edges
+            .iter()
+            .map(|&i| {
+                let (a, b) = (self.vertices[i], self.vertices[(i + 1) % n]);
+                if OrderedPoint::new(b) < OrderedPoint::new(a) {
+                    (b, a)
+                } else {
+                    (a, b)
+                }
+            })
+            .collect();
+        // Insertions sort before removals at the same point, so that edges which only
+        // touch at an endpoint are in the status together
+        let mut events: Vec<(OrderedPoint<T>, bool, usize)> = ends
+            .iter()
+            .enumerate()
+            .flat_map(|(k, &(l, r))| {
+                [
+                    (OrderedPoint::new(l), false, k),
+                    (OrderedPoint::new(r), true, k),
+                ]
+            })
+            .collect();
+        events.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+
+        let mut status: Vec<usize> = Vec::new();
+        for (_, is_removal, k) in events {
+            if is_removal {
+                let pos = status
+                    .iter()
+                    .position(|&e| e == k)
+                    .expect("every edge is inserted before it is removed");
+                status.remove(pos);
+                if pos > 0
+                    && pos < status.len()
+                    && self.conflict(edges, status[pos - 1], status[pos]).is_some()
+                {
+                    return true;
+                }
+            } else {
+                let (l, r) = ends[k];
+                // Whether the active edge `e` is below the new one at the sweep line. Ties,
+                // where the new edge starts on `e`, are broken by its other endpoint.
+                let pos = status.partition_point(|&e| {
+                    let (el, er) = ends[e];
+                    let turn = orient2d(el, er, l);
+                    if turn != 0.0 {
+                        turn > 0.0
+                    } else {
+                        orient2d(el, er, r) >= 0.0
+                    }
+                });
+                status.insert(pos, k);
+                let below = pos.checked_sub(1).map(|p| status[p]);
+                let above = status.get(pos + 1).copied();
+                if below
+                    .into_iter()
+                    .chain(above)
+                    .any(|other| self.conflict(edges, other, k).is_some())
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Every place where the ring meets itself, as `(i, j, intersection)` where `i < j`
+    /// index the edges as returned by `edges`, sorted by `i` and then `j`. Collinear
+    /// edges that overlap are reported with the shared segment. The pairs follow the
+    /// same rules as `is_simple`, which is true exactly when this is empty and the
+    /// ring has three or more distinct vertices.
+    ///
+    /// Candidate pairs are found by sweeping over the edges in order of their smallest x
+    /// coordinate, so only edges whose bounding boxes overlap are compared.
+    /// ```
+    /// use rsgeo::{Point, Polygon, SegmentIntersection};
+    /// let bowtie = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 2.0}, Point{x: 2.0, y: 0.0}, Point{x: 0.0, y: 2.0},
+    /// ]);
+    /// let found = bowtie.self_intersections();
+    /// assert_eq!(found, vec![(0, 2, SegmentIntersection::Point(Point{x: 1.0, y: 1.0}))]);
+    /// ```
+    pub fn self_intersections(&self) -> Vec<(usize, usize, SegmentIntersection<T>)> {
+        let edges = self.proper_edges();
+        let n = self.vertices.len();
+        let bounds: Vec<(T, T, T, T)> = edges
+            .iter()
+            .map(|&i| {
+                let (a, b) = (self.vertices[i], self.vertices[(i + 1) % n]);
+                (a.x.min(b.x), a.x.max(b.x), a.y.min(b.y), a.y.max(b.y))
+            })
+            .collect();
+        let mut order: Vec<usize> = (0..edges.len()).collect();
+        order.sort_by(|&a, &b| bounds[a].0.total_cmp(&bounds[b].0));
+
+        let mut found = Vec::new();
+        let mut active: Vec<usize> = Vec::new();
+        for &k in &order {
+            let (min_x, _, min_y, max_y) = bounds[k];
+            active.retain(|&e| bounds[e].1 >= min_x);
+            for &e in &active {
+                if bounds[e].2 > max_y || bounds[e].3 < min_y {
+                    continue;
+                }
+                if let Some(hit) = self.conflict(&edges, e, k) {
+                    let (i, j) = (edges[e].min(edges[k]), edges[e].max(edges[k]));
+                    found.push((i, j, hit));
+                }
+            }
+            active.push(k);
+        }
+        found.sort_by_key(|&(i, j, _)| (i, j));
+        found
+    }
+}
+
+impl<T: Scalar> RayCast<T> for Polygon<T> {
+    /// The polygon is treated as a solid region under the non-zero rule, so a ray
+    /// starting inside hits at its origin. A ray starting inside a hole hits the hole's
+    /// boundary.
+    fn cast_ray(&self, ray: &Ray<T>) -> Option<RayHit<T>> {
+        if self.contains(ray.origin) {
+            return Some(RayHit {
+                distance: T::zero(),
+                point: ray.origin,
+            });
+        }
+        self.rings()
+            .flat_map(ring_edges)
+            .filter_map(|e| e.cast_ray(ray))
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+}
+
+/// The edges of a closed ring, including the closing edge from the last vertex back to
+/// the first
+pub(crate) fn ring_edges<T: Scalar>(
+    ring: &[Point<T>],
+) -> impl Iterator<Item = LineSegment<T>> + '_ {
+    let n = ring.len();
+    (0..n).map(move |i| LineSegment::new(ring[i], ring[(i + 1) % n]))
+}
+
+fn ring_signed_area<T: Scalar>(ring: &[Point<T>]) -> T {
+    ring_centroid(ring).1
+}
+
+/// The centroid and signed area of a ring. The centroid is the origin if the ring
+/// encloses no area.
+fn ring_centroid<T: Scalar>(ring: &[Point<T>]) -> (Point<T>, T) {
+    // Work relative to the first vertex to limit the rounding error for rings far from
+    // the origin
+    let origin = match ring.first() {
+        Some(&v) => v,
+        None => return (Point::origin(), T::zero()),
+    };
+    let mut twice_area = T::zero();
+    let mut sum = Point::origin();
+    for e in ring_edges(ring) {
+        let (a, b) = (e.p1() - origin, e.p2() - origin);
+        let cross = a.cross(&b);
+        twice_area += cross;
+        sum += (a + b) * cross;
+    }
+    if twice_area == T::zero() {
+        return (Point::origin(), T::zero());
+    }
+    let centroid = origin + sum / (T::from_f64(3.0) * twice_area);
+    (centroid, twice_area / T::from_f64(2.0))
+}
+
+pub(crate) fn ring_orientation<T: Scalar>(ring: &[Point<T>]) -> Orientation {
+    let n = ring.len();
+    let lowest = (0..n).min_by(|&i, &j| {
+        let (a, b) = (ring[i], ring[j]);
+        a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+    });
+    let i = match lowest {
+        Some(i) if n >= 3 => i,
+        _ => return Orientation::Collinear,
+    };
+    match orientation(ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]) {
+        Orientation::Collinear => Orientation::from_determinant(ring_signed_area(ring).as_f64()),
+        turn => turn,
+    }
+}
+
+/// Whether `p` lies exactly on one of the ring's edges
+fn ring_on_boundary<T: Scalar>(ring: &[Point<T>], p: Point<T>) -> bool {
+    ring_edges(ring).any(|e| e.contains_point(p, T::zero()))
+}
+
+fn ring_winding_number<T: Scalar>(ring: &[Point<T>], p: Point<T>) -> i32 {
+    ring_edges(ring).map(|e| edge_winding(&e, p)).sum()
+}
+
+/// What edge `e` adds to the winding number of its ring around `p`
+pub(crate) fn edge_winding<T: Scalar>(e: &LineSegment<T>, p: Point<T>) -> i32 {
+    // Count upward edges passing to the right of `p` and downward edges passing to its
+    // left. Each edge covers its lower endpoint but not its upper one, so a vertex level
+    // with `p` is counted once.
+    let (a, b) = (e.p1(), e.p2());
+    if a.y <= p.y {
+        if b.y > p.y && orient2d(a, b, p) > 0.0 {
+            return 1;
+        }
+    } else if b.y <= p.y && orient2d(a, b, p) < 0.0 {
+        return -1;
+    }
+    0
+}
+
+/// The number of edges crossed by a ray from `p` in the +x direction, using the same
+/// half-open rule for vertices as `ring_winding_number`
+fn ring_crossing_number<T: Scalar>(ring: &[Point<T>], p: Point<T>) -> usize {
+    ring_edges(ring)
+        .filter(|e| {
+            let (a, b) = (e.p1(), e.p2());
+            if (a.y > p.y) == (b.y > p.y) {
+                return false;
+            }
+            // The crossing is to the right of `p` when `p` is on the left of the edge
+            // directed upwards
+            let turn = orient2d(a, b, p);
+            if a.y < b.y {
+                turn > 0.0
+            } else {
+                turn < 0.0
+            }
+        })
+        .count()
+}
+
+/// Whether `p`, which is not on the boundary, is inside the ring under `rule`
+fn ring_encloses<T: Scalar>(ring: &[Point<T>], p: Point<T>, rule: FillRule) -> bool {
+    match rule {
+        FillRule::EvenOdd => ring_crossing_number(ring, p) % 2 == 1,
+        FillRule::NonZero => ring_winding_number(ring, p) != 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test::Bencher;
+
+    fn pts(coords: &[(f64, f64)]) -> Vec<Point> {
+        coords.iter().map(|&p| Point::from(p)).collect()
+    }
+
+    /// A U shape opening upwards, whose notch is level with two of the vertices
+    fn u_shape() -> Polygon {
+        Polygon::new(pts(&[
+            (0.0, 0.0),
+            (3.0, 0.0),
+            (3.0, 3.0),
+            (2.0, 3.0),
+            (2.0, 1.0),
+            (1.0, 1.0),
+            (1.0, 3.0),
+            (0.0, 3.0),
+        ]))
+    }
+
+    #[test]
+    fn test_contains_concave() {
+        let u = u_shape();
+        for rule in &[FillRule::EvenOdd, FillRule::NonZero] {
+            assert!(u.contains_with(Point { x: 0.5, y: 2.0 }, *rule));
+            assert!(!u.contains_with(Point { x: 1.5, y: 2.0 }, *rule));
+            // Level with the bottom of the notch, where the crossing ray passes through
+            // two vertices
+            assert!(u.contains_with(Point { x: 0.5, y: 1.0 }, *rule));
+            assert!(!u.contains_with(Point { x: -0.5, y: 1.0 }, *rule));
+            // Level with the tops of the arms
+            assert!(!u.contains_with(Point { x: -1.0, y: 3.0 }, *rule));
+        }
+    }
+
+    #[test]
+    fn test_boundary_is_inside() {
+        let u = u_shape();
+        for v in u.vertices() {
+            assert!(u.contains(*v));
+        }
+        assert!(u.contains(Point { x: 1.5, y: 1.0 }));
+        assert!(u.contains(Point { x: 3.0, y: 0.1 }));
+        assert!(!u.contains(Point {
+            x: 1.5,
+            y: 1.0 + 1e-12
+        }))
+    }
+
+    #[test]
+    fn test_fill_rules_disagree_on_overlap() {
+        // A ring that goes around the unit square twice
+        let square = pts(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]);
+        let twice = Polygon::new([&square[..], &square[..]].concat());
+        let p = Point { x: 0.5, y: 0.5 };
+        assert_eq!(2, twice.winding_number(p));
+        assert!(twice.contains_with(p, FillRule::NonZero));
+        assert!(!twice.contains_with(p, FillRule::EvenOdd))
+    }
+
+    #[test]
+    fn test_cast_ray() {
+        let u = u_shape();
+        // Down into the notch
+        let ray = Ray::new(Point { x: 1.5, y: 5.0 }, Point { x: 0.0, y: -1.0 });
+        let hit = ray.cast(&u).unwrap();
+        assert_eq!(Point { x: 1.5, y: 1.0 }, hit.point);
+        assert_eq!(4.0, hit.distance);
+        // Across both arms, hitting the nearer one
+        let ray = Ray::new(Point { x: 5.0, y: 2.0 }, Point { x: -1.0, y: 0.0 });
+        assert_eq!(Point { x: 3.0, y: 2.0 }, ray.cast(&u).unwrap().point);
+        let inside = Ray::new(Point { x: 0.5, y: 0.5 }, Point { x: 1.0, y: 0.0 });
+        assert_eq!(0.0, inside.cast(&u).unwrap().distance);
+        let away = Ray::new(Point { x: 5.0, y: 2.0 }, Point { x: 1.0, y: 0.0 });
+        assert_eq!(None, away.cast(&u))
+    }
+
+    #[test]
+    fn test_orientation_and_make_ccw() {
+        let mut u = u_shape();
+        assert_eq!(Orientation::CounterClockwise, u.orientation());
+        assert_eq!(7.0, u.signed_area());
+        u.reverse();
+        assert_eq!(Orientation::Clockwise, u.orientation());
+        assert_eq!(-7.0, u.signed_area());
+        u.make_ccw();
+        assert_eq!(u_shape(), u);
+        // The neighbours of the lowest vertex of this ring are in line with it, so the
+        // area decides
+        let mut spike = Polygon::new(pts(&[(0.0, 0.0), (2.0, 0.0), (1.0, 1.0), (1.0, 0.0)]));
+        assert_eq!(Orientation::CounterClockwise, spike.orientation());
+        spike.reverse();
+        assert_eq!(Orientation::Clockwise, spike.orientation());
+        spike.make_ccw();
+        assert!(spike.signed_area() > 0.0);
+        let flat = Polygon::new(pts(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]));
+        assert_eq!(Orientation::Collinear, flat.orientation())
+    }
+
+    #[test]
+    fn test_is_convex() {
+        assert!(!u_shape().is_convex());
+        // Straight and repeated vertices are fine, in either winding order
+        let mut square = Polygon::new(pts(&[
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 2.0),
+            (2.0, 2.0),
+            (0.0, 2.0),
+        ]));
+        assert!(square.is_convex());
+        square.reverse();
+        assert!(square.is_convex());
+        // Every turn of a pentagram is to the left, but it winds twice
+        let star = Polygon::new(
+            (0..5)
+                .map(|i| Point::from_polar(1.0, f64::from(i) * 4.0 * core::f64::consts::PI / 5.0))
+                .collect(),
+        );
+        assert!(!star.is_convex());
+        let flat = Polygon::new(pts(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]));
+        assert!(!flat.is_convex())
+    }
+
+    #[test]
+    fn test_is_simple() {
+        assert!(u_shape().is_simple());
+        // Repeated vertices and straight corners are allowed
+        let square = pts(&[
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 2.0),
+            (0.0, 0.0),
+        ]);
+        assert!(Polygon::new(square).is_simple());
+        // A ring that touches itself at a vertex
+        let pinched = pts(&[
+            (0.0, 0.0),
+            (2.0, 0.0),
+            (1.0, 1.0),
+            (2.0, 2.0),
+            (0.0, 2.0),
+            (1.0, 1.0),
+        ]);
+        assert!(!Polygon::new(pinched).is_simple());
+        // A spike that folds back along the previous edge
+        let spike = pts(&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (2.0, 1.0), (0.0, 2.0)]);
+        assert!(!Polygon::new(spike.clone()).is_simple());
+        // It also touches the folded edge where it turns back
+        let found = Polygon::new(spike).self_intersections();
+        let fold: LineSegment = "(2, 1) -> (2, 2)".parse().unwrap();
+        assert_eq!((1, 2, SegmentIntersection::Segment(fold)), found[0]);
+        let touch = SegmentIntersection::Point(Point { x: 2.0, y: 1.0 });
+        assert_eq!(vec![found[0], (1, 3, touch)], found);
+        assert!(!Polygon::new(pts(&[(0.0, 0.0), (1.0, 1.0)])).is_simple());
+        // A vertex lying on a non-adjacent vertical edge
+        let touching = pts(&[
+            (0.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 3.0),
+            (1.0, 3.0),
+            (2.0, 1.5),
+            (0.0, 3.0),
+        ]);
+        assert!(!Polygon::new(touching).is_simple())
+    }
+
+    #[test]
+    fn test_is_simple_matches_brute_force() {
+        // Rings through pseudo-random points on a coarse grid, so that many of them
+        // cross, touch or overlap
+        let mut seed = 12345u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            f64::from((seed >> 16) % 5)
+        };
+        for size in 0..1000 {
+            let ring: Vec<Point> = (0..3 + size % 8)
+                .map(|_| Point {
+                    x: next(),
+                    y: next(),
+                })
+                .collect();
+            let poly = Polygon::new(ring);
+            let edges: Vec<LineSegment> = poly.edges().collect();
+            let found = poly.self_intersections();
+            let proper = poly.proper_edges();
+            let mut brute = Vec::new();
+            for a in 0..proper.len() {
+                for b in a + 1..proper.len() {
+                    if let Some(hit) = poly.conflict(&proper, a, b) {
+                        brute.push((proper[a], proper[b], hit));
+                    }
+                }
+            }
+            assert_eq!(brute, found);
+            assert_eq!(
+                proper.len() >= 3 && found.is_empty(),
+                poly.is_simple(),
+                "{:?}",
+                edges
+            );
+        }
+    }
+
+    #[bench]
+    fn bench_contains_64_gon(b: &mut Bencher) {
+        let ring: Vec<Point> = (0..64)
+            .map(|i| Point::from_polar(1.0 + 0.3 * f64::from(i % 2), f64::from(i) * 0.098))
+            .collect();
+        let poly = Polygon::new(ring);
+        b.iter(|| poly.contains(Point { x: 0.3, y: 0.2 }));
+    }
+}
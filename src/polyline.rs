@@ -0,0 +1,694 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::FromIterator;
+
+use crate::{LineSegment, Point, Scalar};
+
+/// An open chain of points joined by straight segments, such as a GPS trace. Unlike a
+/// polygon's ring, the last point is not joined back to the first.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polyline<T = f64> {
+    points: Vec<Point<T>>,
+}
+
+impl<T: Scalar> Polyline<T> {
+    pub fn new(points: Vec<Point<T>>) -> Polyline<T> {
+        Polyline { points }
+    }
+
+    pub fn points(&self) -> &[Point<T>] {
+        &self.points
+    }
+
+    /// Iterate over the segments joining consecutive points
+    pub fn segments(&self) -> impl Iterator<Item = LineSegment<T>> + '_ {
+        self.points.windows(2).map(|w| LineSegment::new(w[0], w[1]))
+    }
+
+    /// The total length of the segments
+    pub fn length(&self) -> T {
+        self.segments().fold(T::zero(), |acc, s| acc + s.length())
+    }
+
+    /// Reverse the direction of the polyline in place
+    pub fn reverse(&mut self) {
+        self.points.reverse();
+    }
+
+    /// Insert evenly spaced points into each segment longer than `max_spacing`, so that
+    /// no segment of the result is longer than that. The original points are all kept.
+    /// Panics if `max_spacing` is not positive.
+    /// ```
+    /// use rsgeo::{Point, Polyline};
+    /// let line = Polyline::new(vec![Point{x: 0.0, y: 0.0}, Point{x: 3.0, y: 0.0}]);
+    /// let dense = line.densify(1.4);
+    /// assert_eq!(dense.points(), &[
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 3.0, y: 0.0},
+    /// ]);
+    /// ```
+    pub fn densify(&self, max_spacing: T) -> Polyline<T> {
+        assert!(
+            max_spacing > T::zero(),
+            "max_spacing must be positive, got {}",
+            max_spacing
+        );
+        let mut points: Vec<Point<T>> = self.points.first().copied().into_iter().collect();
+        for seg in self.segments() {
+            let pieces = (seg.length() / max_spacing).ceil().max(T::one());
+            let n = pieces.as_f64() as usize;
+            for i in 1..n {
+                points.push(seg.p1().lerp(&seg.p2(), T::from_f64(i as f64) / pieces));
+            }
+            points.push(seg.p2());
+        }
+        Polyline::new(points)
+    }
+
+    /// `n` points spaced evenly by distance along the polyline, starting at its first
+    /// point and, for `n > 1`, ending at its last. The corners of the polyline are
+    /// generally not among them.
+    /// ```
+    /// use rsgeo::{Point, Polyline};
+    /// let corner = Polyline::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 1.0},
+    /// ]);
+    /// let even = corner.resample_n(4);
+    /// assert_eq!(even.points(), &[
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 1.0},
+    /// ]);
+    /// ```
+    pub fn resample_n(&self, n: usize) -> Polyline<T> {
+        let (first, last) = match (self.points.first(), self.points.last()) {
+            (Some(&first), Some(&last)) if n > 0 => (first, last),
+            _ => return Polyline::default(),
+        };
+        if n == 1 {
+            return Polyline::new(alloc::vec![first]);
+        }
+        let spacing = self.length() / T::from_f64((n - 1) as f64);
+        let mut points = Vec::with_capacity(n);
+        points.push(first);
+        // Walk along the segments, tracking the distance from the start to the
+        // beginning of the current one
+        let mut segments = self.segments().peekable();
+        let mut walked = T::zero();
+        for i in 1..n - 1 {
+            let target = spacing * T::from_f64(i as f64);
+            let point = loop {
+                let seg = match segments.peek() {
+                    Some(seg) => seg,
+                    // Only reached through rounding, or when every segment has zero length
+                    None => break last,
+                };
+                let len = seg.length();
+                if walked + len >= target {
+                    let t = if len > T::zero() {
+                        (target - walked) / len
+                    } else {
+                        T::zero()
+                    };
+                    break seg.p1().lerp(&seg.p2(), t);
+                }
+                walked += len;
+                segments.next();
+            };
+            points.push(point);
+        }
+        // The last point is placed exactly, rather than where the rounded walk ends up
+        points.push(last);
+        Polyline::new(points)
+    }
+
+    /// The segment containing the point `d` along the polyline, with the distance from
+    /// the start of that segment to the point. `d` is clamped to the length, and a
+    /// vertex belongs to the segment starting there unless it is the last. Segments of
+    /// zero length are skipped. None if every segment has zero length.
+    fn locate(&self, d: T) -> Option<(LineSegment<T>, T)> {
+        let mut walked = T::zero();
+        let mut last = None;
+        for seg in self.segments() {
+            let len = seg.length();
+            if len == T::zero() {
+                continue;
+            }
+            if d < walked + len {
+                return Some((seg, (d - walked).max(T::zero())));
+            }
+            walked += len;
+            last = Some((seg, len));
+        }
+        last
+    }
+
+    /// The point at distance `d` along the polyline from its first point. Distances
+    /// beyond either end are clamped to that end. None if the polyline has no points.
+    /// ```
+    /// use rsgeo::{Point, Polyline};
+    /// let route = Polyline::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 3.0},
+    /// ]);
+    /// assert_eq!(route.point_at_distance(3.5), Some(Point{x: 2.0, y: 1.5}));
+    /// assert_eq!(route.point_at_distance(-1.0), Some(Point{x: 0.0, y: 0.0}));
+    /// assert_eq!(route.point_at_distance(10.0), Some(Point{x: 2.0, y: 3.0}));
+    /// ```
+    pub fn point_at_distance(&self, d: T) -> Option<Point<T>> {
+        match self.locate(d) {
+            Some((seg, along)) => Some(seg.p1() + seg.direction() * (along / seg.length())),
+            None => self.points.first().copied(),
+        }
+    }
+
+    /// The unit direction of travel at distance `d` along the polyline. At a corner this
+    /// is the direction of the segment leaving it. None if the polyline has no segments
+    /// of nonzero length.
+    pub fn tangent_at_distance(&self, d: T) -> Option<Point<T>> {
+        self.locate(d).map(|(seg, _)| seg.direction().normalize())
+    }
+
+    /// Project `p` onto the closest point of the polyline, returning the distance along
+    /// the polyline to that point, the signed distance from it to `p`, and the point
+    /// itself. The offset is positive when `p` is to the left of the direction of
+    /// travel. None if the polyline has no points.
+    /// ```
+    /// use rsgeo::{Point, Polyline};
+    /// let route = Polyline::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 3.0},
+    /// ]);
+    /// let (along, offset, closest) = route.project(Point{x: 3.0, y: 1.0}).unwrap();
+    /// assert_eq!((along, offset, closest), (3.0, -1.0, Point{x: 2.0, y: 1.0}));
+    /// ```
+    pub fn project(&self, p: Point<T>) -> Option<(T, T, Point<T>)> {
+        let first = *self.points.first()?;
+        let mut best: Option<(T, T, T, Point<T>)> = None;
+        let mut walked = T::zero();
+        for seg in self.segments() {
+            let len = seg.length();
+            if len == T::zero() {
+                continue;
+            }
+            let closest = seg.closest_point(p);
+            let dist_sq = closest.distance_squared(&p);
+            if best.is_none_or(|(d, ..)| dist_sq < d) {
+                let offset = seg.direction().cross(&(p - closest)) / len;
+                best = Some((
+                    dist_sq,
+                    walked + seg.p1().distance(&closest),
+                    offset,
+                    closest,
+                ));
+            }
+            walked += len;
+        }
+        Some(match best {
+            Some((_, along, offset, closest)) => (along, offset, closest),
+            None => (T::zero(), first.distance(&p), first),
+        })
+    }
+
+    /// The part of the polyline between two distances along it, which are clamped to
+    /// its ends. If `from` is further along than `to` the slice runs backwards. The
+    /// result is empty if the polyline has no points.
+    /// ```
+    /// use rsgeo::{Point, Polyline};
+    /// let route = Polyline::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 3.0},
+    /// ]);
+    /// let middle = route.slice(1.0, 3.0);
+    /// assert_eq!(middle.points(), &[
+    ///     Point{x: 1.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 1.0},
+    /// ]);
+    /// assert_eq!(middle.length(), 2.0);
+    /// ```
+    pub fn slice(&self, from: T, to: T) -> Polyline<T> {
+        if from > to {
+            let mut backwards = self.slice(to, from);
+            backwards.reverse();
+            return backwards;
+        }
+        let (start, end) = match (self.point_at_distance(from), self.point_at_distance(to)) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return Polyline::default(),
+        };
+        let mut points = alloc::vec![start];
+        // Keep the vertices strictly between the two distances
+        let mut walked = T::zero();
+        for seg in self.segments() {
+            walked += seg.length();
+            if walked > from && walked < to {
+                points.push(seg.p2());
+            }
+        }
+        points.push(end);
+        Polyline::new(points)
+    }
+
+    /// The discrete Fréchet distance to `other`: the least, over all ways of walking
+    /// both sets of points in order without going back, of the largest distance between
+    /// the two current points. Unlike the Hausdorff distance this respects the direction
+    /// of travel. None if either polyline has no points.
+    /// ```
+    /// use rsgeo::{Point, Polyline};
+    /// let out = Polyline::new(vec![Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}]);
+    /// let mut back = out.clone();
+    /// back.reverse();
+    /// assert_eq!(out.frechet_discrete(&out), Some(0.0));
+    /// assert_eq!(out.frechet_discrete(&back), Some(4.0));
+    /// ```
+    pub fn frechet_discrete(&self, other: &Polyline<T>) -> Option<T> {
+        let (a, b) = (&self.points, &other.points);
+        if a.is_empty() || b.is_empty() {
+            return None;
+        }
+        // Only the previous row of the table is needed, in squared distances
+        let mut row: Vec<T> = Vec::with_capacity(b.len());
+        for (i, p) in a.iter().enumerate() {
+            let mut diagonal = T::infinity();
+            for (j, q) in b.iter().enumerate() {
+                let up = row.get(j).copied().unwrap_or(T::infinity());
+                let left = if j == 0 { T::infinity() } else { row[j - 1] };
+                let reach = if i == 0 && j == 0 {
+                    T::zero()
+                } else {
+                    diagonal.min(up).min(left)
+                };
+                let cell = reach.max(p.distance_squared(q));
+                diagonal = up;
+                if i == 0 {
+                    row.push(cell);
+                } else {
+                    row[j] = cell;
+                }
+            }
+        }
+        row.last().map(|d| d.sqrt())
+    }
+
+    /// The discrete Fréchet distance to `other` along with a walk that achieves it, as
+    /// pairs of indices into the points of `self` and `other`. The walk starts at
+    /// `(0, 0)`, ends at the last point of each, and each step advances one or both
+    /// indices by one. None if either polyline has no points.
+    pub fn frechet_discrete_coupling(
+        &self,
+        other: &Polyline<T>,
+    ) -> Option<(T, Vec<(usize, usize)>)> {
+        let (a, b) = (&self.points, &other.points);
+        if a.is_empty() || b.is_empty() {
+            return None;
+        }
+        let m = b.len();
+        let mut table: Vec<T> = Vec::with_capacity(a.len() * m);
+        for (i, p) in a.iter().enumerate() {
+            for (j, q) in b.iter().enumerate() {
+                let at = |i: usize, j: usize| table[i * m + j];
+                let reach = match (i, j) {
+                    (0, 0) => T::zero(),
+                    (0, _) => at(0, j - 1),
+                    (_, 0) => at(i - 1, 0),
+                    _ => at(i - 1, j - 1).min(at(i - 1, j)).min(at(i, j - 1)),
+                };
+                table.push(reach.max(p.distance_squared(q)));
+            }
+        }
+        // Walk back from the end, preferring to advance both indices at once
+        let (mut i, mut j) = (a.len() - 1, m - 1);
+        let mut coupling = alloc::vec![(i, j)];
+        while (i, j) != (0, 0) {
+            let steps = [(1, 1), (1, 0), (0, 1)];
+            let cost = |&(di, dj): &(usize, usize)| table[(i - di) * m + j - dj];
+            let (di, dj) = steps
+                .iter()
+                .copied()
+                .filter(|&(di, dj)| di <= i && dj <= j)
+                .min_by(|x, y| cost(x).partial_cmp(&cost(y)).unwrap_or(Ordering::Equal))
+                .unwrap();
+            i -= di;
+            j -= dj;
+            coupling.push((i, j));
+        }
+        coupling.reverse();
+        Some((table[a.len() * m - 1].sqrt(), coupling))
+    }
+
+    /// Whether the continuous Fréchet distance to `other` is at most `eps`: whether a
+    /// point can move forward along each polyline, from their first points to their
+    /// last, while always staying within `eps` of the other. This is the free space
+    /// decision procedure of Alt and Godau. False if either polyline has no points.
+    pub fn frechet_within(&self, other: &Polyline<T>, eps: T) -> bool {
+        let (a, b) = (&self.points, &other.points);
+        let (first, last) = match (a.first(), b.first(), a.last(), b.last()) {
+            (Some(&a0), Some(&b0), Some(&a1), Some(&b1)) => ((a0, b0), (a1, b1)),
+            _ => return false,
+        };
+        let eps2 = eps * eps;
+        if first.0.distance_squared(&first.1) > eps2 || last.0.distance_squared(&last.1) > eps2 {
+            return false;
+        }
+        // With a single point on one side, the other point has to stay within `eps` all
+        // the way, and the distance to a segment is greatest at one of its ends
+        if a.len() == 1 || b.len() == 1 {
+            let (single, many) = if a.len() == 1 { (a[0], b) } else { (b[0], a) };
+            return many.iter().all(|p| p.distance_squared(&single) <= eps2);
+        }
+        let (n, m) = (a.len() - 1, b.len() - 1);
+        // The reachable parts of the free space on the left edge of each cell, which
+        // runs along a segment of `other` at a point of `self`, and the bottom edge,
+        // which runs along a segment of `self` at a point of `other`
+        let mut left: Vec<Option<(T, T)>> = Vec::with_capacity((n + 1) * m);
+        let mut bottom: Vec<Option<(T, T)>> = alloc::vec![None; n * (m + 1)];
+        let from_start = |prev: Option<(T, T)>, free: Option<(T, T)>| match (prev, free) {
+            (Some((_, hi)), Some((lo, top))) if hi == T::one() && lo == T::zero() => {
+                Some((T::zero(), top))
+            }
+            _ => None,
+        };
+        let start = Some((T::one(), T::one()));
+        let mut prev = start;
+        for j in 0..m {
+            prev = from_start(prev, free_interval(b[j], b[j + 1], a[0], eps2));
+            left.push(prev);
+        }
+        left.resize((n + 1) * m, None);
+        prev = start;
+        for i in 0..n {
+            prev = from_start(prev, free_interval(a[i], a[i + 1], b[0], eps2));
+            bottom[i * (m + 1)] = prev;
+        }
+        for i in 0..n {
+            for j in 0..m {
+                let (from_left, from_below) = (left[i * m + j], bottom[i * (m + 1) + j]);
+                // Entering through the bottom edge, any of the right edge can be
+                // reached, but through the left edge only the part no lower than where
+                // the path entered, and likewise for the top edge
+                let onward =
+                    |free: Option<(T, T)>, across: Option<(T, T)>, along: Option<(T, T)>| {
+                        let (lo, hi) = free?;
+                        if across.is_some() {
+                            return Some((lo, hi));
+                        }
+                        let (from, _) = along?;
+                        if from <= hi {
+                            Some((lo.max(from), hi))
+                        } else {
+                            None
+                        }
+                    };
+                left[(i + 1) * m + j] = onward(
+                    free_interval(b[j], b[j + 1], a[i + 1], eps2),
+                    from_below,
+                    from_left,
+                );
+                bottom[i * (m + 1) + j + 1] = onward(
+                    free_interval(a[i], a[i + 1], b[j + 1], eps2),
+                    from_left,
+                    from_below,
+                );
+            }
+        }
+        let reaches_end = |edge: Option<(T, T)>| edge.is_some_and(|(_, hi)| hi == T::one());
+        reaches_end(left[(n + 1) * m - 1]) || reaches_end(bottom[n * (m + 1) - 1])
+    }
+
+    /// The continuous Fréchet distance to `other`, which is like `frechet_discrete` but
+    /// lets the two points move smoothly along the segments rather than jump between
+    /// vertices. It is found by bisection with `frechet_within`, and the result is no
+    /// less than the true distance and no more than `tolerance` above it. None if either
+    /// polyline has no points. Panics if `tolerance` is not positive.
+    /// ```
+    /// use rsgeo::{Point, Polyline};
+    /// let flat = Polyline::new(vec![Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}]);
+    /// let tent = Polyline::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 1.0}, Point{x: 4.0, y: 0.0},
+    /// ]);
+    /// // The apex of the tent is paired with the middle of the flat line
+    /// let d: f64 = flat.frechet(&tent, 1e-9).unwrap();
+    /// assert!((d - 1.0).abs() <= 1e-9);
+    /// assert!(flat.frechet_discrete(&tent).unwrap() > 2.0);
+    /// ```
+    pub fn frechet(&self, other: &Polyline<T>, tolerance: T) -> Option<T> {
+        assert!(
+            tolerance > T::zero(),
+            "tolerance must be positive, got {}",
+            tolerance
+        );
+        // The ends are always paired, and the discrete walk is one continuous walk
+        let (a, b) = (&self.points, &other.points);
+        let ends = a
+            .first()?
+            .distance(b.first()?)
+            .max(a.last()?.distance(b.last()?));
+        let mut hi = self.frechet_discrete(other)?;
+        let mut lo = ends;
+        if self.frechet_within(other, lo) {
+            return Some(lo);
+        }
+        while hi - lo > tolerance {
+            let mid = (lo + hi) / T::from_f64(2.0);
+            if self.frechet_within(other, mid) {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        Some(hi)
+    }
+}
+
+/// The part of the segment from `p1` to `p2`, as a range of fractions of the way along
+/// it, whose squared distance from `c` is at most `eps2`
+fn free_interval<T: Scalar>(p1: Point<T>, p2: Point<T>, c: Point<T>, eps2: T) -> Option<(T, T)> {
+    let (d, f) = (p2 - p1, p1 - c);
+    let a = d.dot_product(&d);
+    if a == T::zero() {
+        return if f.dot_product(&f) <= eps2 {
+            Some((T::zero(), T::one()))
+        } else {
+            None
+        };
+    }
+    // Solve |f + t d|² = eps2, which is a t² + 2 b t + c = 0
+    let b = f.dot_product(&d);
+    let disc = b * b - a * (f.dot_product(&f) - eps2);
+    if disc < T::zero() {
+        return None;
+    }
+    let root = disc.sqrt();
+    let lo = ((-b - root) / a).max(T::zero());
+    let hi = ((-b + root) / a).min(T::one());
+    if lo <= hi {
+        Some((lo, hi))
+    } else {
+        None
+    }
+}
+
+impl<T: Scalar> FromIterator<Point<T>> for Polyline<T> {
+    fn from_iter<I: IntoIterator<Item = Point<T>>>(iter: I) -> Self {
+        Polyline::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zigzag() -> Polyline {
+        Polyline::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 3.0, y: 4.0 },
+            Point { x: 3.0, y: 4.0 },
+            Point { x: 6.0, y: 0.0 },
+            Point { x: 6.0, y: 2.5 },
+        ])
+    }
+
+    #[test]
+    fn test_length_and_segments() {
+        let line = zigzag();
+        assert_eq!(4, line.segments().count());
+        assert_eq!(12.5, line.length());
+        let mut back = line.clone();
+        back.reverse();
+        assert_eq!(line.length(), back.length());
+        assert_eq!(Point { x: 6.0, y: 2.5 }, back.points()[0]);
+        assert_eq!(0, Polyline::<f64>::default().segments().count());
+        assert_eq!(0.0, Polyline::new(vec![Point { x: 1.0, y: 1.0 }]).length())
+    }
+
+    #[test]
+    fn test_densify() {
+        let line = zigzag();
+        let dense = line.densify(0.7);
+        for seg in dense.segments() {
+            assert!(seg.length() <= 0.7 + 1e-12);
+        }
+        assert!(crate::scalar_isclose(line.length(), dense.length()));
+        for p in line.points() {
+            assert!(dense.points().contains(p));
+        }
+    }
+
+    #[test]
+    fn test_resample_n_is_evenly_spaced() {
+        let line = zigzag();
+        let even = line.resample_n(11);
+        assert_eq!(11, even.points().len());
+        assert_eq!(line.points()[0], even.points()[0]);
+        assert_eq!(line.points()[4], even.points()[10]);
+        // Each resampled point lies on the line, 1.25 further along than the last
+        let on_line = |p: Point| line.segments().any(|s| s.distance_to_point(p) < 1e-12);
+        assert!(even.points().iter().all(|&p| on_line(p)));
+        assert!(Point { x: 1.5, y: 2.0 }.isclose(even.points()[2]));
+        assert!(Point { x: 6.0, y: 1.25 }.isclose(even.points()[9]))
+    }
+
+    #[test]
+    fn test_resample_n_edge_cases() {
+        let line = zigzag();
+        assert!(line.resample_n(0).points().is_empty());
+        assert_eq!(&line.points()[..1], line.resample_n(1).points());
+        assert_eq!(
+            &[line.points()[0], line.points()[4]],
+            line.resample_n(2).points()
+        );
+        let dot = Polyline::new(vec![Point { x: 1.0, y: 1.0 }]);
+        assert_eq!(
+            vec![Point { x: 1.0, y: 1.0 }; 3],
+            dot.resample_n(3).points()
+        )
+    }
+
+    #[test]
+    fn test_point_and_tangent_at_distance() {
+        let line = zigzag();
+        assert_eq!(Some(Point { x: 3.0, y: 4.0 }), line.point_at_distance(5.0));
+        assert!(line
+            .point_at_distance(7.5)
+            .unwrap()
+            .isclose(Point { x: 4.5, y: 2.0 }));
+        // At the repeated corner the tangent is that of the segment leaving it
+        assert!(line
+            .tangent_at_distance(5.0)
+            .unwrap()
+            .isclose(Point { x: 0.6, y: -0.8 }));
+        assert_eq!(
+            Some(Point { x: 0.0, y: 1.0 }),
+            line.tangent_at_distance(99.0)
+        );
+        let dot = Polyline::new(vec![Point { x: 1.0, y: 1.0 }]);
+        assert_eq!(Some(Point { x: 1.0, y: 1.0 }), dot.point_at_distance(2.0));
+        assert_eq!(None, dot.tangent_at_distance(0.0));
+        assert_eq!(None, Polyline::<f64>::default().point_at_distance(0.0))
+    }
+
+    #[test]
+    fn test_project_round_trips() {
+        let line = zigzag();
+        for i in 0..=25 {
+            let d = f64::from(i) * 0.5;
+            let on = line.point_at_distance(d).unwrap();
+            let (along, offset, closest) = line.project(on).unwrap();
+            assert!(closest.isclose(on));
+            assert!(offset.abs() < 1e-12);
+            // The apex of the zigzag is equally far along by both of its segments
+            assert!(crate::scalar_isclose(d, along));
+        }
+        let (_, offset, _) = line.project(Point { x: 3.0, y: 0.0 }).unwrap();
+        assert!(offset < 0.0);
+        let (_, offset, _) = line.project(Point { x: 7.0, y: 1.0 }).unwrap();
+        assert_eq!(-1.0, offset)
+    }
+
+    #[test]
+    fn test_slice() {
+        let line = zigzag();
+        let whole = line.slice(-1.0, 20.0);
+        assert_eq!(line.length(), whole.length());
+        let part = line.slice(2.5, 10.0);
+        assert!(crate::scalar_isclose(7.5, part.length()));
+        assert_eq!(4, part.points().len());
+        let back = line.slice(10.0, 2.5);
+        assert_eq!(part.points()[0], back.points()[3]);
+        let empty = line.slice(3.0, 3.0);
+        assert_eq!(0.0, empty.length());
+        assert_eq!(2, empty.points().len())
+    }
+
+    #[test]
+    fn test_frechet_discrete() {
+        let line = zigzag();
+        assert_eq!(Some(0.0), line.frechet_discrete(&line));
+        // Shifting every point up by one gives a walk in step at distance one
+        let up: Polyline = line
+            .points()
+            .iter()
+            .map(|&p| p + Point { x: 0.0, y: 1.0 })
+            .collect();
+        assert_eq!(Some(1.0), line.frechet_discrete(&up));
+        // Resampling keeps the shape, but the walk has to wait at a few points
+        let even = line.resample_n(11);
+        let d = line.frechet_discrete(&even).unwrap();
+        assert_eq!(Some(d), even.frechet_discrete(&line));
+        assert!(d > 0.0 && d <= 2.5);
+        assert_eq!(None, line.frechet_discrete(&Polyline::default()))
+    }
+
+    #[test]
+    fn test_frechet_discrete_coupling() {
+        let line = zigzag();
+        let even = line.resample_n(11);
+        let (d, coupling) = line.frechet_discrete_coupling(&even).unwrap();
+        assert_eq!(Some(d), line.frechet_discrete(&even));
+        assert_eq!((0, 0), coupling[0]);
+        assert_eq!((4, 10), *coupling.last().unwrap());
+        for w in coupling.windows(2) {
+            let (di, dj) = (w[1].0 - w[0].0, w[1].1 - w[0].1);
+            assert!(di <= 1 && dj <= 1 && di + dj > 0);
+        }
+        // The walk achieves the distance, and no pair is farther apart than it
+        let widest = coupling
+            .iter()
+            .map(|&(i, j)| line.points()[i].distance(&even.points()[j]))
+            .fold(0.0, f64::max);
+        assert_eq!(d, widest)
+    }
+
+    #[test]
+    fn test_frechet_within() {
+        let line = zigzag();
+        assert!(line.frechet_within(&line, 0.0));
+        let up: Polyline = line
+            .points()
+            .iter()
+            .map(|&p| p + Point { x: 0.0, y: 0.5 })
+            .collect();
+        assert!(line.frechet_within(&up, 0.5));
+        assert!(!line.frechet_within(&up, 0.49));
+        // Running the same path backwards pairs the far ends first
+        let mut back = line.clone();
+        back.reverse();
+        assert!(!line.frechet_within(&back, 6.0));
+        let dot = Polyline::new(vec![Point { x: 3.0, y: 2.0 }]);
+        assert!(dot.frechet_within(&line, 3.7) && !line.frechet_within(&dot, 3.6));
+        assert!(!line.frechet_within(&Polyline::default(), 100.0))
+    }
+
+    #[test]
+    fn test_frechet_between_bounds() {
+        let line = zigzag();
+        let even = line.densify(0.7);
+        let continuous = line.frechet(&even, 1e-9).unwrap();
+        // Both trace the same path, so the points can stay together the whole way
+        assert!(continuous <= 1e-9);
+        assert!(line.frechet_discrete(&even).unwrap() > 2.0);
+        let shifted: Polyline = even
+            .points()
+            .iter()
+            .map(|&p| p + Point { x: 0.3, y: -0.4 })
+            .collect();
+        let d = line.frechet(&shifted, 1e-9).unwrap();
+        assert!((d - 0.5).abs() <= 1e-9);
+        assert!(d <= line.frechet_discrete(&shifted).unwrap());
+        assert_eq!(None, line.frechet(&Polyline::default(), 1e-9))
+    }
+}
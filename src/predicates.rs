@@ -0,0 +1,481 @@
+//! Robust geometric predicates, following Shewchuk's "Adaptive Precision Floating-Point
+//! Arithmetic and Fast Robust Geometric Predicates". Each predicate first evaluates its
+//! determinant in plain floating point along with a bound on the rounding error. Only
+//! when the result is too close to zero to trust is it recomputed exactly, using
+//! floating point expansions. The sign of the result is therefore always correct, while
+//! the common case costs about the same as the naive formula.
+//!
+//! Coordinates of any `Scalar` type are widened to `f64`, which is exact.
+
+use num_traits::Float;
+
+use crate::{Point, Scalar};
+
+/// Half of the machine epsilon, i.e. the largest relative rounding error of one f64
+/// operation
+const EPSILON: f64 = f64::EPSILON / 2.0;
+const CCW_ERR_BOUND_A: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const ICC_ERR_BOUND_A: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+/// Which way three points turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    CounterClockwise,
+    Clockwise,
+    Collinear,
+}
+
+impl Orientation {
+    /// Classify the sign of a determinant such as the one returned by `orient2d`
+    pub fn from_determinant(det: f64) -> Orientation {
+        if det > 0.0 {
+            Orientation::CounterClockwise
+        } else if det < 0.0 {
+            Orientation::Clockwise
+        } else {
+            Orientation::Collinear
+        }
+    }
+
+    /// The orientation of the same points visited in reverse order
+    pub fn reverse(&self) -> Orientation {
+        match self {
+            Orientation::CounterClockwise => Orientation::Clockwise,
+            Orientation::Clockwise => Orientation::CounterClockwise,
+            Orientation::Collinear => Orientation::Collinear,
+        }
+    }
+}
+
+/// Returns a positive value if `a`, `b`, `c` occur in counter-clockwise order, a
+/// negative value if they are clockwise, and zero if they are exactly collinear. The
+/// magnitude approximates twice the signed area of the triangle, but only the sign is
+/// guaranteed to be exact.
+/// ```
+/// use rsgeo::{predicates::orient2d, Point};
+/// let a = Point{x: 0.0, y: 0.0};
+/// let b = Point{x: 1.0, y: 0.0};
+/// let c = Point{x: 0.0, y: 1.0};
+/// assert_eq!(orient2d(a, b, c), 1.0);
+/// assert_eq!(orient2d(a, c, b), -1.0);
+/// ```
+pub fn orient2d<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>) -> f64 {
+    let (ax, ay) = (a.x.as_f64(), a.y.as_f64());
+    let (bx, by) = (b.x.as_f64(), b.y.as_f64());
+    let (cx, cy) = (c.x.as_f64(), c.y.as_f64());
+
+    let detleft = (ax - cx) * (by - cy);
+    let detright = (ay - cy) * (bx - cx);
+    let det = detleft - detright;
+
+    let detsum = if detleft > 0.0 {
+        if detright <= 0.0 {
+            return det;
+        }
+        detleft + detright
+    } else if detleft < 0.0 {
+        if detright >= 0.0 {
+            return det;
+        }
+        -detleft - detright
+    } else {
+        return det;
+    };
+
+    let errbound = CCW_ERR_BOUND_A * detsum;
+    if det >= errbound || -det >= errbound {
+        return det;
+    }
+    orient2d_exact(ax, ay, bx, by, cx, cy)
+}
+
+/// The orientation of the three points, see `orient2d`
+pub fn orientation<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>) -> Orientation {
+    Orientation::from_determinant(orient2d(a, b, c))
+}
+
+/// Whether the three points lie on one line, to within `tol`. Exactly collinear points
+/// are always accepted, so a `tol` of zero gives the exact test. Otherwise the points
+/// count as collinear when the height of the triangle they form, measured from its
+/// longest side, is at most `tol`.
+/// ```
+/// use rsgeo::{predicates::collinear, Point};
+/// let a = Point{x: 0.0, y: 0.0};
+/// let b = Point{x: 10.0, y: 0.0};
+/// assert!(collinear(a, b, Point{x: 5.0, y: 0.0}, 0.0));
+/// assert!(!collinear(a, b, Point{x: 5.0, y: 0.01}, 0.0));
+/// assert!(collinear(a, b, Point{x: 5.0, y: 0.01}, 0.1));
+/// ```
+pub fn collinear<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>, tol: T) -> bool {
+    let det = orient2d(a, b, c);
+    if det == 0.0 {
+        return true;
+    }
+    let longest = a
+        .distance_squared(&b)
+        .max(b.distance_squared(&c))
+        .max(c.distance_squared(&a))
+        .as_f64();
+    det.abs() / longest.sqrt() <= tol.as_f64()
+}
+
+/// Returns a positive value if `d` lies inside the circle passing through `a`, `b`,
+/// and `c`, a negative value if it lies outside, and zero if the four points are
+/// exactly cocircular. `a`, `b`, `c` must be in counter-clockwise order, otherwise the
+/// sign is reversed. Only the sign of the result is guaranteed to be exact.
+/// ```
+/// use rsgeo::{predicates::incircle, Point};
+/// let a = Point{x: 1.0, y: 0.0};
+/// let b = Point{x: 0.0, y: 1.0};
+/// let c = Point{x: -1.0, y: 0.0};
+/// assert!(incircle(a, b, c, Point{x: 0.0, y: 0.0}) > 0.0);
+/// assert!(incircle(a, b, c, Point{x: 2.0, y: 0.0}) < 0.0);
+/// assert_eq!(incircle(a, b, c, Point{x: 0.0, y: -1.0}), 0.0);
+/// ```
+pub fn incircle<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>, d: Point<T>) -> f64 {
+    let (ax, ay) = (a.x.as_f64(), a.y.as_f64());
+    let (bx, by) = (b.x.as_f64(), b.y.as_f64());
+    let (cx, cy) = (c.x.as_f64(), c.y.as_f64());
+    let (dx, dy) = (d.x.as_f64(), d.y.as_f64());
+
+    let adx = ax - dx;
+    let bdx = bx - dx;
+    let cdx = cx - dx;
+    let ady = ay - dy;
+    let bdy = by - dy;
+    let cdy = cy - dy;
+
+    let bdxcdy = bdx * cdy;
+    let cdxbdy = cdx * bdy;
+    let alift = adx * adx + ady * ady;
+
+    let cdxady = cdx * ady;
+    let adxcdy = adx * cdy;
+    let blift = bdx * bdx + bdy * bdy;
+
+    let adxbdy = adx * bdy;
+    let bdxady = bdx * ady;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+    let errbound = ICC_ERR_BOUND_A * permanent;
+    if det > errbound || -det > errbound {
+        return det;
+    }
+    incircle_exact([ax, ay], [bx, by], [cx, cy], [dx, dy])
+}
+
+fn orient2d_exact(ax: f64, ay: f64, bx: f64, by: f64, cx: f64, cy: f64) -> f64 {
+    let acx = two_diff(ax, cx);
+    let bcy = two_diff(by, cy);
+    let acy = two_diff(ay, cy);
+    let bcx = two_diff(bx, cx);
+    let mut det: Expansion<16> = expansion_product(&acx, &bcy);
+    det.sub(expansion_product::<8>(&acy, &bcx).as_slice());
+    det.most_significant()
+}
+
+fn incircle_exact(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> f64 {
+    let adx = two_diff(a[0], d[0]);
+    let ady = two_diff(a[1], d[1]);
+    let bdx = two_diff(b[0], d[0]);
+    let bdy = two_diff(b[1], d[1]);
+    let cdx = two_diff(c[0], d[0]);
+    let cdy = two_diff(c[1], d[1]);
+
+    let lift = |x: &[f64], y: &[f64]| {
+        let mut h: Expansion<16> = expansion_product(x, x);
+        h.add(expansion_product::<8>(y, y).as_slice());
+        h
+    };
+    let cross = |x1: &[f64], y1: &[f64], x2: &[f64], y2: &[f64]| {
+        let mut h: Expansion<16> = expansion_product(x1, y2);
+        h.sub(expansion_product::<8>(y1, x2).as_slice());
+        h
+    };
+    let term = |lift: Expansion<16>, cross: Expansion<16>| -> Expansion<512> {
+        expansion_product(lift.as_slice(), cross.as_slice())
+    };
+
+    let mut det: Expansion<1536> =
+        Expansion::from_slice(term(lift(&adx, &ady), cross(&bdx, &bdy, &cdx, &cdy)).as_slice());
+    det.add(term(lift(&bdx, &bdy), cross(&cdx, &cdy, &adx, &ady)).as_slice());
+    det.add(term(lift(&cdx, &cdy), cross(&adx, &ady, &bdx, &bdy)).as_slice());
+    det.most_significant()
+}
+
+// Floating point expansions represent a number exactly as the sum of non-overlapping
+// f64 components, stored in order of increasing magnitude.
+
+/// An expansion with room for up to `N` components. Expansions live on the stack, so
+/// the exact fallbacks work without an allocator; `N` is chosen at each use from the
+/// worst case component count of the computation.
+#[derive(Clone, Copy)]
+struct Expansion<const N: usize> {
+    len: usize,
+    components: [f64; N],
+}
+
+impl<const N: usize> Expansion<N> {
+    fn zero() -> Self {
+        Expansion {
+            len: 1,
+            components: [0.0; N],
+        }
+    }
+
+    fn from_slice(e: &[f64]) -> Self {
+        let mut h = Expansion {
+            len: e.len(),
+            components: [0.0; N],
+        };
+        h.components[..e.len()].copy_from_slice(e);
+        h
+    }
+
+    fn as_slice(&self) -> &[f64] {
+        &self.components[..self.len]
+    }
+
+    fn push(&mut self, component: f64) {
+        self.components[self.len] = component;
+        self.len += 1;
+    }
+
+    /// Add a single value in place, dropping zero components. Each component is read
+    /// before its slot can be overwritten, so no scratch space is needed.
+    fn grow(&mut self, b: f64) {
+        let mut q = b;
+        let mut len = 0;
+        for i in 0..self.len {
+            let (sum, err) = two_sum(q, self.components[i]);
+            if err != 0.0 {
+                self.components[len] = err;
+                len += 1;
+            }
+            q = sum;
+        }
+        if q != 0.0 || len == 0 {
+            self.components[len] = q;
+            len += 1;
+        }
+        self.len = len;
+    }
+
+    /// Add another expansion in place
+    fn add(&mut self, f: &[f64]) {
+        for &component in f {
+            self.grow(component);
+        }
+    }
+
+    /// Subtract another expansion in place
+    fn sub(&mut self, f: &[f64]) {
+        for &component in f {
+            self.grow(-component);
+        }
+    }
+
+    /// The largest component. Since the components do not overlap, it has the same
+    /// sign as the whole expansion and approximates its value.
+    fn most_significant(&self) -> f64 {
+        self.as_slice()
+            .iter()
+            .rev()
+            .copied()
+            .find(|&v| v != 0.0)
+            .unwrap_or(0.0)
+    }
+}
+
+/// `a + b` exactly, as `(sum, error)`
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let bv = x - a;
+    let av = x - bv;
+    (x, (a - av) + (b - bv))
+}
+
+/// `a + b` exactly, as `(sum, error)`, assuming `|a| >= |b|`
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    (x, b - (x - a))
+}
+
+/// `a - b` exactly, as a two component expansion
+fn two_diff(a: f64, b: f64) -> [f64; 2] {
+    let x = a - b;
+    let bv = a - x;
+    let av = x + bv;
+    [(a - av) + (bv - b), x]
+}
+
+/// `a * b` exactly, as `(product, error)`. A fused multiply-add computes the rounding
+/// error of the product exactly.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, Float::mul_add(a, b, -x))
+}
+
+/// Multiply an expansion by a single value, dropping zero components. The result has
+/// at most `2 * e.len()` components.
+fn scale_expansion<const N: usize>(e: &[f64], b: f64) -> Expansion<N> {
+    let mut h = Expansion {
+        len: 0,
+        components: [0.0; N],
+    };
+    let (mut q, err) = two_product(e[0], b);
+    if err != 0.0 {
+        h.push(err);
+    }
+    for &enow in &e[1..] {
+        let (product1, product0) = two_product(enow, b);
+        let (sum, err) = two_sum(q, product0);
+        if err != 0.0 {
+            h.push(err);
+        }
+        let (sum, err) = fast_two_sum(product1, sum);
+        if err != 0.0 {
+            h.push(err);
+        }
+        q = sum;
+    }
+    if q != 0.0 || h.len == 0 {
+        h.push(q);
+    }
+    h
+}
+
+/// The product of two expansions, with at most `2 * e.len() * f.len()` components
+fn expansion_product<const N: usize>(e: &[f64], f: &[f64]) -> Expansion<N> {
+    let mut h = Expansion::zero();
+    for &component in f {
+        h.add(scale_expansion::<N>(e, component).as_slice());
+    }
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test::Bencher;
+
+    #[test]
+    fn test_orient2d_nearly_collinear_grid() {
+        // Points a tiny perturbation away from the line y = x, where the naive
+        // determinant gets the sign wrong for many of the perturbations
+        let ulp = 0.5_f64.powi(53);
+        let b = Point { x: 12.0, y: 12.0 };
+        let c = Point { x: 24.0, y: 24.0 };
+        for i in 0..64_i32 {
+            for j in 0..64_i32 {
+                let a = Point {
+                    x: 0.5 + f64::from(i) * ulp,
+                    y: 0.5 + f64::from(j) * ulp,
+                };
+                let want = match j.cmp(&i) {
+                    std::cmp::Ordering::Greater => Orientation::CounterClockwise,
+                    std::cmp::Ordering::Less => Orientation::Clockwise,
+                    std::cmp::Ordering::Equal => Orientation::Collinear,
+                };
+                assert_eq!(
+                    want,
+                    orientation(a, b, c),
+                    "wrong sign for perturbation ({}, {})",
+                    i,
+                    j
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn test_orient2d_f32() {
+        let a = Point { x: 0.0_f32, y: 0.0 };
+        let b = Point { x: 1.0_f32, y: 1.0 };
+        assert_eq!(0.0, orient2d(a, b, Point { x: 3.0, y: 3.0 }));
+        assert!(orient2d(a, b, Point { x: 3.0, y: 3.0001 }) > 0.0)
+    }
+
+    #[test]
+    fn test_collinear_is_symmetric() {
+        let a = Point { x: 0.0, y: 0.0 };
+        let b = Point { x: 4.0, y: 0.0 };
+        let c = Point { x: 2.0, y: 0.5 };
+        for &(p, q, r) in &[(a, b, c), (b, c, a), (c, a, b), (b, a, c)] {
+            assert!(collinear(p, q, r, 0.5));
+            assert!(!collinear(p, q, r, 0.4))
+        }
+    }
+
+    #[test]
+    fn test_collinear_exact_with_zero_tolerance() {
+        let a = Point { x: 0.1, y: 0.1 };
+        let b = Point { x: 0.3, y: 0.3 };
+        let c = Point { x: 1e9, y: 1e9 };
+        assert!(collinear(a, b, c, 0.0));
+        // Repeated points are collinear with anything
+        assert!(collinear(a, a, Point { x: 5.0, y: -2.0 }, 0.0))
+    }
+
+    #[test]
+    fn test_incircle_cocircular_far_from_origin() {
+        let o = 1e9;
+        let a = Point { x: o + 1.0, y: o };
+        let b = Point { x: o, y: o + 1.0 };
+        let c = Point { x: o - 1.0, y: o };
+        let d = Point { x: o, y: o - 1.0 };
+        assert_eq!(0.0, incircle(a, b, c, d));
+        let inside = Point {
+            x: o,
+            y: o - 1.0 + 1e-7,
+        };
+        assert!(incircle(a, b, c, inside) > 0.0);
+        let outside = Point {
+            x: o,
+            y: o - 1.0 - 1e-7,
+        };
+        assert!(incircle(a, b, c, outside) < 0.0)
+    }
+
+    #[test]
+    fn test_incircle_clockwise_flips_sign() {
+        let a = Point { x: 1.0, y: 0.0 };
+        let b = Point { x: 0.0, y: 1.0 };
+        let c = Point { x: -1.0, y: 0.0 };
+        let d = Point { x: 0.1, y: 0.1 };
+        assert!(incircle(a, b, c, d) > 0.0);
+        assert!(incircle(c, b, a, d) < 0.0)
+    }
+
+    #[test]
+    fn test_expansions_are_exact() {
+        // (1 + 2^-60)^2 = 1 + 2^-59 + 2^-120, which needs three components
+        let e = [0.5_f64.powi(60), 1.0];
+        let sq: Expansion<8> = expansion_product(&e, &e);
+        let sq = sq.as_slice();
+        let sum: f64 = sq.iter().sum();
+        assert_eq!(1.0 + 0.5_f64.powi(59), sum);
+        assert_eq!(0.5_f64.powi(120), sq[0])
+    }
+
+    #[bench]
+    fn bench_orient2d_general_position(b: &mut Bencher) {
+        let p = Point { x: 0.1, y: 0.2 };
+        let q = Point { x: 5.0, y: -3.0 };
+        let r = Point { x: 2.0, y: 7.0 };
+        b.iter(|| orient2d(p, q, r));
+    }
+
+    #[bench]
+    fn bench_orient2d_degenerate(b: &mut Bencher) {
+        let p = Point { x: 0.5, y: 0.5 };
+        let q = Point { x: 12.0, y: 12.0 };
+        let r = Point { x: 24.0, y: 24.0 };
+        b.iter(|| orient2d(p, q, r));
+    }
+}
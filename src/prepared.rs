@@ -0,0 +1,658 @@
+use alloc::vec::Vec;
+
+use num_traits::Float;
+
+use crate::polygon::ring_edges;
+use crate::predicates::orient2d;
+use crate::{Intersects, LineSegment, Point, Polygon, Polyline, Rect, Scalar, SegmentIntersection};
+
+/// A polygon made ready for testing many points and shapes against it. Its edges are
+/// sorted into a grid of cells about as many as the edges, and for a point in each cell
+/// the winding numbers of the rings around it are worked out ahead of time. Whether a
+/// point is in the polygon then follows from the edges crossed on the way from the
+/// point of its cell, and a segment is tested only against the edges in the cells it
+/// passes through, so that each test looks at a few edges rather than all of them,
+/// and gives the same answer as the same test on the polygon itself. Preparing takes
+/// time in proportion to the number of edges, for edges that are short next to the
+/// size of the polygon.
+/// ```
+/// use rsgeo::{Intersects, LineSegment, Point, Polygon, PreparedPolygon};
+/// let frame = Polygon::with_holes(
+///     vec![Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 4.0}, Point{x: 0.0, y: 4.0}],
+///     vec![vec![Point{x: 1.0, y: 1.0}, Point{x: 3.0, y: 1.0}, Point{x: 3.0, y: 3.0}, Point{x: 1.0, y: 3.0}]],
+/// );
+/// let prepared = PreparedPolygon::new(&frame);
+/// assert!(prepared.contains(Point{x: 0.5, y: 2.0}));
+/// assert!(!prepared.contains(Point{x: 2.0, y: 2.0}));
+/// let inside_hole: LineSegment = "(1.5, 2) -> (2.5, 2)".parse().unwrap();
+/// assert!(!prepared.intersects(&inside_hole));
+/// let across: LineSegment = "(2, 2) -> (2, 5)".parse().unwrap();
+/// assert!(prepared.intersects(&across));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedPolygon<T = f64> {
+    edges: EdgeIndex<T>,
+    /// The winding numbers at a point in each cell, or None for a polygon without any
+    /// area
+    interior: Option<Interior<T>>,
+}
+
+/// A polyline made ready for testing many points and shapes against it, with its
+/// segments sorted into a grid of cells in the same way as the edges of a
+/// `PreparedPolygon`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedPolyline<T = f64> {
+    edges: EdgeIndex<T>,
+    /// The first point, which is all there is to a polyline without any length
+    first: Option<Point<T>>,
+}
+
+/// Edges sorted into a grid of cells over their bounds, each edge listed in every cell
+/// it meets, in the same layout as the triangles of a `Tin`
+#[derive(Debug, Clone, PartialEq)]
+struct EdgeIndex<T> {
+    /// Each edge with the ring it belongs to, in ring order
+    edges: Vec<(LineSegment<T>, usize)>,
+    bounds: Option<Rect<T>>,
+    columns: usize,
+    rows: usize,
+    cell_width: T,
+    cell_height: T,
+    /// How far to grow each cell when finding the edges that meet it, so that rounding
+    /// can't leave out an edge that only just touches it
+    slack: T,
+    /// Where the edges of each cell, in row-major order, start in `members`, with one
+    /// more at the end
+    starts: Vec<usize>,
+    /// The edges of each cell, in ring order
+    members: Vec<usize>,
+}
+
+/// The winding numbers of the rings of a polygon at a chosen point in each cell, none of
+/// them on an edge
+#[derive(Debug, Clone, PartialEq)]
+struct Interior<T> {
+    anchors: Vec<Point<T>>,
+    /// The winding number of the exterior ring at each anchor
+    exterior: Vec<i32>,
+    /// The number of holes that wind around each anchor
+    holes: Vec<usize>,
+    /// Where the rings of each cell start in `windings`, with one more at the end
+    starts: Vec<usize>,
+    /// The rings with edges in each cell, in order, with their winding numbers at the
+    /// anchor of the cell
+    windings: Vec<(usize, i32)>,
+}
+
+impl<T: Scalar> EdgeIndex<T> {
+    fn new(edges: Vec<(LineSegment<T>, usize)>) -> EdgeIndex<T> {
+        let bounds = Rect::from_points(edges.iter().flat_map(|(e, _)| [e.p1(), e.p2()]));
+        let (width, height) = bounds.map_or((T::zero(), T::zero()), |b| (b.width(), b.height()));
+        // About as many square cells as edges, or a single row or column of them for
+        // edges that are all in line
+        let n = edges.len().max(1) as f64;
+        let (w, h) = (width.as_f64(), height.as_f64());
+        let (columns, rows) = match (w > 0.0, h > 0.0) {
+            (true, true) => {
+                let size = Float::sqrt(w * h / n);
+                (Float::ceil(w / size).min(n), Float::ceil(h / size).min(n))
+            }
+            (true, false) => (n, 1.0),
+            (false, true) => (1.0, n),
+            (false, false) => (1.0, 1.0),
+        };
+        let (columns, rows) = ((columns as usize).max(1), (rows as usize).max(1));
+        let reach = bounds.map_or(T::zero(), |b| {
+            let (lo, hi) = (b.min(), b.max());
+            lo.x.abs().max(lo.y.abs()).max(hi.x.abs()).max(hi.y.abs())
+        });
+        let mut index = EdgeIndex {
+            edges,
+            bounds,
+            columns,
+            rows,
+            cell_width: width / T::from_f64(columns as f64),
+            cell_height: height / T::from_f64(rows as f64),
+            slack: T::epsilon() * T::from_f64(64.0) * (reach + width + height),
+            starts: alloc::vec![0; columns * rows + 1],
+            members: Vec::new(),
+        };
+        let mut listed: Vec<(usize, usize)> = Vec::new();
+        for (i, (e, _)) in index.edges.iter().enumerate() {
+            listed.extend(index.cells(e).map(|cell| (cell, i)));
+        }
+        for &(cell, _) in &listed {
+            index.starts[cell + 1] += 1;
+        }
+        for cell in 0..columns * rows {
+            index.starts[cell + 1] += index.starts[cell];
+        }
+        let mut next = index.starts.clone();
+        index.members = alloc::vec![0; listed.len()];
+        for (cell, i) in listed {
+            index.members[next[cell]] = i;
+            next[cell] += 1;
+        }
+        index
+    }
+
+    /// Where the line before column or row `i` is, from the lowest coordinate `lo`
+    fn line(lo: T, size: T, i: usize) -> T {
+        lo + size * T::from_f64(i as f64)
+    }
+
+    /// The column or row of `v` out of `count`, where the lines between them belong to
+    /// the one above, found against the same lines that bound the cells
+    fn slot(v: T, lo: T, size: T, count: usize) -> usize {
+        if size <= T::zero() {
+            return 0;
+        }
+        let mut i = ((v - lo) / size).as_f64().max(0.0).min((count - 1) as f64) as usize;
+        while i + 1 < count && v >= EdgeIndex::line(lo, size, i + 1) {
+            i += 1;
+        }
+        while i > 0 && v < EdgeIndex::line(lo, size, i) {
+            i -= 1;
+        }
+        i
+    }
+
+    fn column(&self, x: T) -> usize {
+        let lo = self.bounds.map_or(T::zero(), |b| b.min().x);
+        EdgeIndex::slot(x, lo, self.cell_width, self.columns)
+    }
+
+    fn row(&self, y: T) -> usize {
+        let lo = self.bounds.map_or(T::zero(), |b| b.min().y);
+        EdgeIndex::slot(y, lo, self.cell_height, self.rows)
+    }
+
+    /// The cell holding `p`, which should be in the bounds
+    fn cell_of(&self, p: Point<T>) -> usize {
+        self.row(p.y) * self.columns + self.column(p.x)
+    }
+
+    /// The rectangle that a cell covers, where the last row and column reach exactly to
+    /// the bounds
+    fn cell_rect(&self, cell: usize) -> Rect<T> {
+        let b = self.bounds.unwrap();
+        let (column, row) = (cell % self.columns, cell / self.columns);
+        let x = |i: usize| match i == self.columns {
+            true => b.max().x,
+            false => EdgeIndex::line(b.min().x, self.cell_width, i),
+        };
+        let y = |i: usize| match i == self.rows {
+            true => b.max().y,
+            false => EdgeIndex::line(b.min().y, self.cell_height, i),
+        };
+        Rect::from_corners(
+            Point {
+                x: x(column),
+                y: y(row),
+            },
+            Point {
+                x: x(column + 1),
+                y: y(row + 1),
+            },
+        )
+    }
+
+    /// The cells that `s` meets, or comes within the slack of, working along each row
+    /// it passes through
+    fn cells<'a>(&'a self, s: &LineSegment<T>) -> impl Iterator<Item = usize> + 'a {
+        let s = *s;
+        let (p, q) = (s.p1(), s.p2());
+        let (r0, r1) = (self.row(p.y.min(q.y)), self.row(p.y.max(q.y)));
+        let lo = self.bounds.map_or(T::zero(), |b| b.min().y);
+        (r0..=r1).flat_map(move |row| {
+            // The part of the segment level with the row, one column wider each way to
+            // make up for rounding
+            let (mut x0, mut x1) = (p.x.min(q.x), p.x.max(q.x));
+            if p.y != q.y {
+                let at = |y: T| p.x + (q.x - p.x) * ((y - p.y) / (q.y - p.y));
+                let (y0, y1) = (
+                    EdgeIndex::line(lo, self.cell_height, row),
+                    EdgeIndex::line(lo, self.cell_height, row + 1),
+                );
+                let (a, b) = (at(y0.max(p.y.min(q.y))), at(y1.min(p.y.max(q.y))));
+                x0 = a.min(b);
+                x1 = a.max(b);
+            }
+            let c0 = self.column(x0).saturating_sub(1);
+            let c1 = (self.column(x1) + 1).min(self.columns - 1);
+            (c0..=c1)
+                .map(move |column| row * self.columns + column)
+                .filter(move |&cell| {
+                    let rect = self.cell_rect(cell).expand(self.slack);
+                    rect.clip_segment(&s).is_some()
+                })
+        })
+    }
+
+    /// The edges listed in `cell`, with their indices
+    fn listed(&self, cell: usize) -> impl Iterator<Item = (usize, &(LineSegment<T>, usize))> + '_ {
+        self.members[self.starts[cell]..self.starts[cell + 1]]
+            .iter()
+            .map(move |&i| (i, &self.edges[i]))
+    }
+
+    /// Whether `p` is on any of the edges
+    fn touches_point(&self, p: Point<T>) -> bool {
+        self.bounds.is_some_and(|b| b.contains(p))
+            && self
+                .listed(self.cell_of(p))
+                .any(|(_, (e, _))| e.contains_point(p, T::zero()))
+    }
+
+    /// Whether `s` meets any of the edges
+    fn meets(&self, s: &LineSegment<T>) -> bool {
+        let reach = Rect::from_corners(s.p1(), s.p2());
+        if !self.bounds.is_some_and(|b| b.intersects(&reach)) {
+            return false;
+        }
+        self.cells(s).any(|cell| {
+            self.listed(cell)
+                .map(|(_, (e, _))| e)
+                .filter(|e| Rect::from_corners(e.p1(), e.p2()).intersects(&reach))
+                .any(|e| !matches!(e.intersect(s), SegmentIntersection::None))
+        })
+    }
+
+    /// A point on the edges, if there are any
+    fn anchor(&self) -> Option<Point<T>> {
+        self.edges.first().map(|(e, _)| e.p1())
+    }
+}
+
+/// How the winding number of the ring of `e` around a point changes on the way from
+/// `from` to `to`, neither of which is on `e`. Vertices on the line through the two
+/// count as being on its right, as if the path were moved a little to the left, so
+/// that a path through a vertex is counted once.
+fn crossing<T: Scalar>(e: &LineSegment<T>, from: Point<T>, to: Point<T>) -> i32 {
+    let (a, b) = (e.p1(), e.p2());
+    let left = |v: Point<T>| orient2d(from, to, v) > 0.0;
+    if left(a) == left(b) {
+        return 0;
+    }
+    match (orient2d(a, b, from) > 0.0, orient2d(a, b, to) > 0.0) {
+        (false, true) => 1,
+        (true, false) => -1,
+        _ => 0,
+    }
+}
+
+impl<T: Scalar> Interior<T> {
+    /// The winding numbers at an anchor in each cell of `index`, found by walking along
+    /// each row from a point outside every ring, one cell at a time
+    fn new(index: &EdgeIndex<T>) -> Interior<T> {
+        let cells = index.columns * index.rows;
+        let rings = index.edges.last().map_or(0, |(_, r)| r + 1);
+        let mut interior = Interior {
+            anchors: (0..cells)
+                .map(|cell| Interior::anchor(index, cell))
+                .collect(),
+            exterior: alloc::vec![0; cells],
+            holes: alloc::vec![0; cells],
+            starts: alloc::vec![0; cells + 1],
+            windings: Vec::new(),
+        };
+        let mut winding = alloc::vec![0; rings];
+        let outside = index.bounds.unwrap().min().x - index.cell_width - T::one();
+        for row in 0..index.rows {
+            let first = row * index.columns;
+            let mut from = Point {
+                x: outside,
+                y: interior.anchors[first].y,
+            };
+            let mut holes = 0;
+            for cell in first..first + index.columns {
+                let to = interior.anchors[cell];
+                // The path from the last anchor to this one stays in the two cells, and
+                // an edge in both of them is only crossed once
+                let before = match cell > first {
+                    true => &index.members[index.starts[cell - 1]..index.starts[cell]],
+                    false => &[][..],
+                };
+                let here = &index.members[index.starts[cell]..index.starts[cell + 1]];
+                let fresh = here.iter().filter(|i| before.binary_search(i).is_err());
+                for &i in before.iter().chain(fresh) {
+                    let (e, ring) = &index.edges[i];
+                    let change = crossing(e, from, to);
+                    if change != 0 {
+                        let was = winding[*ring] != 0;
+                        winding[*ring] += change;
+                        if *ring > 0 && was != (winding[*ring] != 0) {
+                            holes = if was { holes - 1 } else { holes + 1 };
+                        }
+                    }
+                }
+                interior.exterior[cell] = winding.first().copied().unwrap_or(0);
+                interior.holes[cell] = holes;
+                for (_, (_, ring)) in index.listed(cell) {
+                    if interior.windings.last().map(|w| w.0) != Some(*ring)
+                        || interior.windings.len() == interior.starts[cell]
+                    {
+                        interior.windings.push((*ring, winding[*ring]));
+                    }
+                }
+                interior.starts[cell + 1] = interior.windings.len();
+                from = to;
+            }
+            // Every ring is left behind on the way out of the row
+            for (_, (_, ring)) in (first..first + index.columns).flat_map(|c| index.listed(c)) {
+                winding[*ring] = 0;
+            }
+        }
+        interior
+    }
+
+    /// A point in `cell` that isn't on any of its edges, trying its center first
+    fn anchor(index: &EdgeIndex<T>, cell: usize) -> Point<T> {
+        let rect = index.cell_rect(cell);
+        let (lo, size) = (rect.min(), rect.max() - rect.min());
+        let free = |p: &Point<T>| {
+            !index
+                .listed(cell)
+                .any(|(_, (e, _))| e.contains_point(*p, T::zero()))
+        };
+        (0..64)
+            .map(|k| {
+                let spread = |step: f64| T::from_f64(Float::fract(0.5 + k as f64 * step));
+                lo + Point {
+                    x: size.x * spread(0.618_033_988_7),
+                    y: size.y * spread(0.414_213_562_4),
+                }
+            })
+            .find(free)
+            .unwrap_or_else(|| rect.center())
+    }
+}
+
+impl<T: Scalar> PreparedPolygon<T> {
+    /// Prepare `polygon`
+    pub fn new(polygon: &Polygon<T>) -> PreparedPolygon<T> {
+        let edges = polygon
+            .rings()
+            .enumerate()
+            .flat_map(|(ring, points)| ring_edges(points).map(move |e| (e, ring)))
+            .collect();
+        let edges = EdgeIndex::new(edges);
+        let area = edges.cell_width > T::zero() && edges.cell_height > T::zero();
+        PreparedPolygon {
+            interior: if area {
+                Some(Interior::new(&edges))
+            } else {
+                None
+            },
+            edges,
+        }
+    }
+
+    /// The bounding rectangle of the polygon, or None if it is empty
+    pub fn bounds(&self) -> Option<Rect<T>> {
+        self.edges.bounds
+    }
+
+    /// Whether `p` is inside the polygon or on its boundary, using the non-zero winding
+    /// rule, as `Polygon::contains` does
+    pub fn contains(&self, p: Point<T>) -> bool {
+        if !self.bounds().is_some_and(|b| b.contains(p)) {
+            return false;
+        }
+        let cell = self.edges.cell_of(p);
+        if self
+            .edges
+            .listed(cell)
+            .any(|(_, (e, _))| e.contains_point(p, T::zero()))
+        {
+            return true;
+        }
+        let interior = match &self.interior {
+            Some(interior) => interior,
+            None => return false,
+        };
+        let anchor = interior.anchors[cell];
+        let (mut exterior, mut holes) = (interior.exterior[cell], interior.holes[cell]);
+        // The rings of the cell's edges come in the same order as the cell's windings
+        let windings = &interior.windings[interior.starts[cell]..interior.starts[cell + 1]];
+        let mut listed = self.edges.listed(cell).peekable();
+        for &(ring, was) in windings {
+            let mut now = was;
+            while let Some((_, (e, _))) = listed.next_if(|(_, (_, r))| *r == ring) {
+                now += crossing(e, anchor, p);
+            }
+            match ring {
+                0 => exterior = now,
+                _ if (was != 0) != (now != 0) => {
+                    holes = if was != 0 { holes - 1 } else { holes + 1 };
+                }
+                _ => {}
+            }
+        }
+        exterior != 0 && holes == 0
+    }
+}
+
+impl<T: Scalar> PreparedPolyline<T> {
+    /// Prepare `polyline`
+    pub fn new(polyline: &Polyline<T>) -> PreparedPolyline<T> {
+        let edges = polyline
+            .segments()
+            .filter(|s| s.p1() != s.p2())
+            .map(|s| (s, 0))
+            .collect();
+        PreparedPolyline {
+            edges: EdgeIndex::new(edges),
+            first: polyline.points().first().copied(),
+        }
+    }
+
+    /// The bounding rectangle of the polyline, or None if it is empty
+    pub fn bounds(&self) -> Option<Rect<T>> {
+        self.edges
+            .bounds
+            .or_else(|| Some(Rect::from_corners(self.first?, self.first?)))
+    }
+
+    /// Whether `p` is on the polyline
+    pub fn contains(&self, p: Point<T>) -> bool {
+        match self.edges.edges.is_empty() {
+            true => self.first == Some(p),
+            false => self.edges.touches_point(p),
+        }
+    }
+
+    /// Whether `s` meets the polyline
+    fn meets(&self, s: &LineSegment<T>) -> bool {
+        match self.edges.edges.is_empty() {
+            true => self.first.is_some_and(|p| s.contains_point(p, T::zero())),
+            false => self.edges.meets(s),
+        }
+    }
+}
+
+impl<T: Scalar> Intersects<Point<T>> for PreparedPolygon<T> {
+    fn intersects(&self, other: &Point<T>) -> bool {
+        self.contains(*other)
+    }
+}
+
+impl<T: Scalar> Intersects<LineSegment<T>> for PreparedPolygon<T> {
+    fn intersects(&self, other: &LineSegment<T>) -> bool {
+        self.contains(other.p1()) || self.edges.meets(other)
+    }
+}
+
+impl<T: Scalar> Intersects<Polyline<T>> for PreparedPolygon<T> {
+    fn intersects(&self, other: &Polyline<T>) -> bool {
+        match other.points() {
+            [] => false,
+            [p] => self.contains(*p),
+            _ => other.segments().any(|s| self.intersects(&s)),
+        }
+    }
+}
+
+impl<T: Scalar> Intersects<Polygon<T>> for PreparedPolygon<T> {
+    fn intersects(&self, other: &Polygon<T>) -> bool {
+        if other.vertices().is_empty() || self.edges.edges.is_empty() {
+            return false;
+        }
+        // Without crossing boundaries, one polygon meets the other only by lying
+        // inside it
+        other
+            .rings()
+            .flat_map(ring_edges)
+            .any(|e| self.edges.meets(&e))
+            || self.contains(other.vertices()[0])
+            || self.edges.anchor().is_some_and(|p| other.contains(p))
+    }
+}
+
+impl<T: Scalar> Intersects<Point<T>> for PreparedPolyline<T> {
+    fn intersects(&self, other: &Point<T>) -> bool {
+        self.contains(*other)
+    }
+}
+
+impl<T: Scalar> Intersects<LineSegment<T>> for PreparedPolyline<T> {
+    fn intersects(&self, other: &LineSegment<T>) -> bool {
+        self.meets(other)
+    }
+}
+
+impl<T: Scalar> Intersects<Polyline<T>> for PreparedPolyline<T> {
+    fn intersects(&self, other: &Polyline<T>) -> bool {
+        match other.points() {
+            [] => false,
+            [p] => self.contains(*p),
+            _ => other.segments().any(|s| self.meets(&s)),
+        }
+    }
+}
+
+impl<T: Scalar> Intersects<Polygon<T>> for PreparedPolyline<T> {
+    fn intersects(&self, other: &Polygon<T>) -> bool {
+        match self.first {
+            Some(p) if !other.vertices().is_empty() => {
+                other.contains(p) || other.rings().flat_map(ring_edges).any(|e| self.meets(&e))
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scattered;
+
+    /// A star with many spikes around a hole, and a second hole that winds the same
+    /// way as the outside
+    fn spiky() -> Polygon {
+        let star = (0..200)
+            .map(|i| {
+                let angle = i as f64 * core::f64::consts::PI / 100.0;
+                let r = if i % 2 == 0 { 4.5 } else { 3.0 };
+                Point {
+                    x: 5.0 + r * angle.cos(),
+                    y: 5.0 + r * angle.sin(),
+                }
+            })
+            .collect();
+        let square = |x: f64, y: f64, d: f64| {
+            vec![
+                Point { x, y },
+                Point { x: x + d, y },
+                Point { x: x + d, y: y + d },
+                Point { x, y: y + d },
+            ]
+        };
+        let mut reversed = square(3.0, 3.0, 1.0);
+        reversed.reverse();
+        Polygon::with_holes(star, vec![reversed, square(5.0, 5.0, 1.5)])
+    }
+
+    #[test]
+    fn test_contains_matches_polygon() {
+        let polygon = spiky();
+        let prepared = PreparedPolygon::new(&polygon);
+        let mut points = scattered(5000, 3);
+        // Points on vertices, along edges and level with vertices
+        points.extend(polygon.rings().flatten().copied());
+        points.extend(polygon.rings().flat_map(ring_edges).map(|e| e.midpoint()));
+        points.extend(
+            polygon
+                .vertices()
+                .iter()
+                .map(|v| *v + Point { x: 0.01, y: 0.0 }),
+        );
+        let inside = points.iter().filter(|&&p| polygon.contains(p)).count();
+        assert!(inside > 1000 && inside < 4000);
+        for p in points {
+            assert_eq!(polygon.contains(p), prepared.contains(p), "{:?}", p);
+        }
+        assert!(!PreparedPolygon::new(&Polygon::<f64>::new(vec![])).contains(Point::origin()));
+    }
+
+    #[test]
+    fn test_intersects_matches_polygon() {
+        let polygon = spiky();
+        let prepared = PreparedPolygon::new(&polygon);
+        let ends = scattered(600, 5);
+        for pair in ends.chunks(2) {
+            let s = LineSegment::new(pair[0], pair[0] + (pair[1] - pair[0]) * 0.1);
+            assert_eq!(polygon.intersects(&s), prepared.intersects(&s));
+            let line = Polyline::new(vec![pair[0], pair[1], pair[0] * 0.5]);
+            assert_eq!(polygon.intersects(&line), prepared.intersects(&line));
+            let triangle = Polygon::new(vec![s.p1(), s.p2(), s.p1() + Point { x: 0.2, y: 0.3 }]);
+            assert_eq!(
+                polygon.intersects(&triangle),
+                prepared.intersects(&triangle)
+            );
+        }
+        // One polygon inside the other, and the other way around
+        let tiny = Polygon::new(vec![
+            Point { x: 8.0, y: 5.0 },
+            Point { x: 8.2, y: 5.0 },
+            Point { x: 8.0, y: 5.2 },
+        ]);
+        assert!(prepared.intersects(&tiny));
+        let huge = Polygon::new(vec![
+            Point { x: -1.0, y: -1.0 },
+            Point { x: 11.0, y: -1.0 },
+            Point { x: 5.0, y: 11.0 },
+        ]);
+        assert!(prepared.intersects(&huge));
+    }
+
+    #[test]
+    fn test_polylines() {
+        let zigzag = Polyline::new(
+            (0..100)
+                .map(|i| Point {
+                    x: i as f64 * 0.1,
+                    y: 5.0 + (i % 2) as f64,
+                })
+                .collect(),
+        );
+        let prepared = PreparedPolyline::new(&zigzag);
+        let ends = scattered(600, 7);
+        for pair in ends.chunks(2) {
+            let s = LineSegment::new(pair[0], pair[1]);
+            assert_eq!(zigzag.intersects(&s), prepared.intersects(&s));
+            let square = Polygon::new(vec![
+                pair[0],
+                pair[0] + Point { x: 0.3, y: 0.0 },
+                pair[0] + Point { x: 0.3, y: 0.3 },
+                pair[0] + Point { x: 0.0, y: 0.3 },
+            ]);
+            assert_eq!(zigzag.intersects(&square), prepared.intersects(&square));
+        }
+        assert!(prepared.contains(Point { x: 0.05, y: 5.5 }));
+        assert!(!prepared.contains(Point { x: 0.05, y: 5.0 }));
+        let dot = PreparedPolyline::new(&Polyline::new(vec![Point { x: 1.0, y: 1.0 }]));
+        assert!(dot.intersects(&Point { x: 1.0, y: 1.0 }));
+        assert!(dot.intersects(&LineSegment::new(Point::origin(), Point { x: 2.0, y: 2.0 })));
+    }
+}
@@ -0,0 +1,310 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::{BoundingRect, Point, Polygon, Rect, Scalar};
+
+/// A quadtree over points in a fixed rectangle, each carrying a payload. Each node
+/// covers a rectangle, and once a leaf holds more than `capacity` points it is split
+/// into four quarters, unless it is already `max_depth` levels down. Removing points
+/// merges quarters back together once they hold few enough between them, so the
+/// shape of the tree, which `nodes` walks through, follows how the points are spread.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quadtree<T = f64, D = usize> {
+    root: Node<T, D>,
+    capacity: usize,
+    max_depth: usize,
+    len: usize,
+}
+
+/// A node of a quadtree as `Quadtree::nodes` gives it
+type NodeView<'a, T, D> = (Rect<T>, usize, &'a [(Point<T>, D)]);
+
+#[derive(Debug, Clone, PartialEq)]
+struct Node<T, D> {
+    bounds: Rect<T>,
+    depth: usize,
+    /// The points in a leaf, which is empty for a node split into quarters
+    items: Vec<(Point<T>, D)>,
+    /// The bottom left, bottom right, top left and top right quarters
+    children: Option<Box<[Node<T, D>; 4]>>,
+}
+
+impl<T: Scalar, D> Node<T, D> {
+    fn new(bounds: Rect<T>, depth: usize) -> Node<T, D> {
+        Node {
+            bounds,
+            depth,
+            items: Vec::new(),
+            children: None,
+        }
+    }
+
+    /// Which quarter `p` belongs in, those on the lines between them going up and right
+    fn quarter(&self, p: Point<T>) -> usize {
+        let center = self.bounds.center();
+        usize::from(p.x >= center.x) + 2 * usize::from(p.y >= center.y)
+    }
+
+    fn insert(&mut self, p: Point<T>, payload: D, capacity: usize, max_depth: usize) {
+        let q = self.quarter(p);
+        if let Some(children) = &mut self.children {
+            return children[q].insert(p, payload, capacity, max_depth);
+        }
+        self.items.push((p, payload));
+        if self.items.len() > capacity && self.depth < max_depth {
+            let (lo, hi, center) = (self.bounds.min(), self.bounds.max(), self.bounds.center());
+            let quarter = |x0: T, y0: T, x1: T, y1: T| {
+                let corners = (Point { x: x0, y: y0 }, Point { x: x1, y: y1 });
+                Node::new(Rect::from_corners(corners.0, corners.1), self.depth + 1)
+            };
+            self.children = Some(Box::new([
+                quarter(lo.x, lo.y, center.x, center.y),
+                quarter(center.x, lo.y, hi.x, center.y),
+                quarter(lo.x, center.y, center.x, hi.y),
+                quarter(center.x, center.y, hi.x, hi.y),
+            ]));
+            for (p, payload) in core::mem::take(&mut self.items) {
+                self.insert(p, payload, capacity, max_depth);
+            }
+        }
+    }
+
+    fn remove(&mut self, p: Point<T>, capacity: usize) -> Option<D> {
+        let q = self.quarter(p);
+        let children = match &mut self.children {
+            Some(children) => children,
+            None => {
+                let i = self.items.iter().position(|item| item.0 == p)?;
+                return Some(self.items.remove(i).1);
+            }
+        };
+        let removed = children[q].remove(p, capacity)?;
+        // Quarters that are all leaves and hold few enough points between them are
+        // folded back into this node
+        let leaves = children.iter().all(|c| c.children.is_none());
+        if leaves && children.iter().map(|c| c.items.len()).sum::<usize>() <= capacity {
+            let children = self.children.take().unwrap();
+            for child in *children {
+                self.items.extend(child.items);
+            }
+        }
+        Some(removed)
+    }
+}
+
+impl<T: Scalar, D> Quadtree<T, D> {
+    /// An empty tree over `bounds`, splitting its leaves once they hold more than
+    /// `capacity` points until they are `max_depth` levels below the root. Panics if
+    /// `capacity` is zero.
+    /// ```
+    /// use rsgeo::{Point, Quadtree, Rect};
+    /// let bounds = Rect::from_corners(Point{x: 0.0, y: 0.0}, Point{x: 8.0, y: 8.0});
+    /// let mut tree = Quadtree::new(bounds, 2, 8);
+    /// for (i, x) in [1.0, 2.0, 3.0, 7.0].iter().enumerate() {
+    ///     assert!(tree.insert(Point{x: *x, y: 1.0}, i));
+    /// }
+    /// assert!(!tree.insert(Point{x: 9.0, y: 1.0}, 4));
+    /// let found = tree.query_rect(&Rect::from_corners(Point{x: 1.5, y: 0.0}, Point{x: 5.0, y: 2.0}));
+    /// assert_eq!(found.len(), 2);
+    /// // The root and its quarters, and the quarters of the bottom left one
+    /// assert_eq!(tree.nodes().count(), 9);
+    /// assert_eq!(tree.remove(Point{x: 2.0, y: 1.0}), Some(1));
+    /// ```
+    pub fn new(bounds: Rect<T>, capacity: usize, max_depth: usize) -> Quadtree<T, D> {
+        assert!(capacity > 0, "capacity must be positive, got {}", capacity);
+        Quadtree {
+            root: Node::new(bounds, 0),
+            capacity,
+            max_depth,
+            len: 0,
+        }
+    }
+
+    /// The rectangle the tree covers
+    pub fn bounds(&self) -> Rect<T> {
+        self.root.bounds
+    }
+
+    /// The number of points in the tree
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the tree has no points
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Add `p` to the tree with its payload. Returns false, leaving the tree as it was,
+    /// if `p` is outside the bounds of the tree.
+    pub fn insert(&mut self, p: Point<T>, payload: D) -> bool {
+        if !self.root.bounds.contains(p) {
+            return false;
+        }
+        self.root.insert(p, payload, self.capacity, self.max_depth);
+        self.len += 1;
+        true
+    }
+
+    /// Take a point at `p` out of the tree, giving back its payload, or None if there
+    /// is no point there. Where several points are at `p`, only one is taken out.
+    pub fn remove(&mut self, p: Point<T>) -> Option<D> {
+        if !self.root.bounds.contains(p) {
+            return None;
+        }
+        let removed = self.root.remove(p, self.capacity)?;
+        self.len -= 1;
+        Some(removed)
+    }
+
+    /// The points inside `rect` or on its boundary, with their payloads
+    pub fn query_rect(&self, rect: &Rect<T>) -> Vec<(Point<T>, &D)> {
+        let mut found = Vec::new();
+        let mut stack = alloc::vec![&self.root];
+        while let Some(node) = stack.pop() {
+            if !node.bounds.intersects(rect) {
+                continue;
+            }
+            match &node.children {
+                Some(children) => stack.extend(children.iter()),
+                None => found.extend(
+                    node.items
+                        .iter()
+                        .filter(|item| rect.contains(item.0))
+                        .map(|(p, d)| (*p, d)),
+                ),
+            }
+        }
+        found
+    }
+
+    /// The points inside `rect` or on its boundary, with their payloads. This is
+    /// `query_rect` under the name it shares with the other spatial indexes.
+    pub fn range_query(&self, rect: &Rect<T>) -> Vec<(Point<T>, &D)> {
+        self.query_rect(rect)
+    }
+
+    /// The points inside `polygon` or on its boundary, with their payloads. Only the
+    /// points in the bounding rectangle of the polygon are tested against the polygon
+    /// itself.
+    pub fn range_query_polygon(&self, polygon: &Polygon<T>) -> Vec<(Point<T>, &D)> {
+        let mut found = match polygon.bounding_rect() {
+            Some(rect) => self.query_rect(&rect),
+            None => Vec::new(),
+        };
+        found.retain(|f| polygon.contains(f.0));
+        found
+    }
+
+    /// Every node of the tree from the root down, each parent before its quarters,
+    /// given as the rectangle it covers, how far down it is, and the points in it if
+    /// it is a leaf
+    pub fn nodes(&self) -> impl Iterator<Item = NodeView<'_, T, D>> + '_ {
+        let mut stack = alloc::vec![&self.root];
+        core::iter::from_fn(move || {
+            let node = stack.pop()?;
+            if let Some(children) = &node.children {
+                stack.extend(children.iter().rev());
+            }
+            Some((node.bounds, node.depth, &node.items[..]))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scattered;
+
+    fn square(lo: f64, hi: f64) -> Rect {
+        Rect::from_corners(Point { x: lo, y: lo }, Point { x: hi, y: hi })
+    }
+
+    #[test]
+    fn test_queries_match_brute_force() {
+        let points = scattered(2000, 4);
+        let mut tree = Quadtree::new(square(0.0, 10.0), 8, 12);
+        for (i, &p) in points.iter().enumerate() {
+            assert!(tree.insert(p, i));
+        }
+        // Take out every third point
+        for (i, &p) in points.iter().enumerate().filter(|(i, _)| i % 3 == 0) {
+            assert_eq!(Some(i), tree.remove(p));
+        }
+        assert_eq!(2000 - 667, tree.len());
+        let corners = scattered(100, 9);
+        for pair in corners.chunks(2) {
+            let rect = Rect::from_corners(pair[0], pair[1]);
+            let mut found: Vec<usize> = tree.query_rect(&rect).iter().map(|f| *f.1).collect();
+            found.sort_unstable();
+            let expected: Vec<usize> = (0..points.len())
+                .filter(|&i| i % 3 != 0 && rect.contains(points[i]))
+                .collect();
+            assert_eq!(expected, found);
+        }
+        let star = Polygon::new(
+            (0..10)
+                .map(|i| {
+                    let angle = i as f64 * core::f64::consts::PI / 5.0;
+                    let r = if i % 2 == 0 { 4.0 } else { 1.5 };
+                    Point {
+                        x: 5.0 + r * angle.cos(),
+                        y: 5.0 + r * angle.sin(),
+                    }
+                })
+                .collect(),
+        );
+        let mut found: Vec<usize> = tree
+            .range_query_polygon(&star)
+            .iter()
+            .map(|f| *f.1)
+            .collect();
+        found.sort_unstable();
+        let expected: Vec<usize> = (0..points.len())
+            .filter(|&i| i % 3 != 0 && star.contains(points[i]))
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(expected, found);
+        let rect = square(2.0, 7.0);
+        assert_eq!(tree.query_rect(&rect), tree.range_query(&rect));
+    }
+
+    #[test]
+    fn test_structure() {
+        let mut tree = Quadtree::new(square(0.0, 16.0), 1, 3);
+        // Points that can't be told apart stay together at the deepest level
+        for i in 0..5 {
+            tree.insert(Point { x: 1.0, y: 1.0 }, i);
+        }
+        tree.insert(Point { x: 15.0, y: 15.0 }, 5);
+        let nodes: Vec<_> = tree.nodes().collect();
+        assert_eq!(13, nodes.len());
+        assert!(nodes.iter().all(|n| n.1 <= 3));
+        let deepest = nodes.iter().find(|n| n.2.len() == 5).unwrap();
+        assert_eq!((square(0.0, 2.0), 3), (deepest.0, deepest.1));
+        // Every point is in a leaf whose rectangle holds it
+        for (rect, _, items) in &nodes {
+            assert!(items.iter().all(|item| rect.contains(item.0)));
+        }
+        assert_eq!(6, nodes.iter().map(|n| n.2.len()).sum::<usize>());
+    }
+
+    #[test]
+    fn test_removal_merges() {
+        let points = scattered(100, 2);
+        let mut tree = Quadtree::new(square(0.0, 10.0), 4, 10);
+        for &p in &points {
+            tree.insert(p, ());
+        }
+        assert!(tree.nodes().count() > 1);
+        for &p in &points[4..] {
+            assert_eq!(Some(()), tree.remove(p));
+        }
+        assert_eq!(None, tree.remove(points[50]));
+        assert_eq!(None, tree.remove(Point { x: 20.0, y: 0.0 }));
+        // With few enough points left, the tree folds back into its root
+        assert_eq!(1, tree.nodes().count());
+        assert_eq!(4, tree.len());
+        assert!(!tree.is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::predicates::{orientation, Orientation};
+use crate::{LineSegment, Point, Scalar};
+
+/// A half-line starting at `origin` and running forever along `direction`. The
+/// direction does not need to be normalized, but must not be zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ray<T = f64> {
+    pub origin: Point<T>,
+    pub direction: Point<T>,
+}
+
+/// Where a ray first meets a shape
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit<T = f64> {
+    /// The distance from the ray's origin to `point`, in the same units as the
+    /// coordinates regardless of the magnitude of the ray's direction
+    pub distance: T,
+    pub point: Point<T>,
+}
+
+/// Shapes that a `Ray` can be cast against
+pub trait RayCast<T: Scalar> {
+    /// The first point along the ray that touches the shape, or None if the ray misses
+    fn cast_ray(&self, ray: &Ray<T>) -> Option<RayHit<T>>;
+}
+
+impl<T: Scalar> Ray<T> {
+    pub fn new(origin: Point<T>, direction: Point<T>) -> Ray<T> {
+        Ray { origin, direction }
+    }
+
+    /// Find the nearest hit against any shape implementing `RayCast`
+    /// ```
+    /// use rsgeo::{LineSegment, Point, Ray};
+    /// let ray = Ray::new(Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.0});
+    /// let wall: LineSegment = "(3, -1) -> (3, 1)".parse().unwrap();
+    /// let hit = ray.cast(&wall).unwrap();
+    /// assert_eq!(hit.point, Point{x: 3.0, y: 0.0});
+    /// assert_eq!(hit.distance, 3.0);
+    /// ```
+    pub fn cast<S: RayCast<T> + ?Sized>(&self, target: &S) -> Option<RayHit<T>> {
+        target.cast_ray(self)
+    }
+
+    /// Whether `p` is at or ahead of the origin rather than behind it
+    fn is_ahead(&self, p: Point<T>) -> bool {
+        (p - self.origin).dot_product(&self.direction) >= T::zero()
+    }
+
+    fn hit(&self, point: Point<T>) -> RayHit<T> {
+        RayHit {
+            distance: self.origin.distance(&point),
+            point,
+        }
+    }
+}
+
+impl<T: Scalar> RayCast<T> for LineSegment<T> {
+    /// The decision of whether the ray hits uses exact orientation predicates, so rays
+    /// passing exactly through an endpoint are not missed
+    fn cast_ray(&self, ray: &Ray<T>) -> Option<RayHit<T>> {
+        let (p1, p2) = (self.p1(), self.p2());
+        let o1 = orientation(ray.origin, ray.origin + ray.direction, p1);
+        let o2 = orientation(ray.origin, ray.origin + ray.direction, p2);
+
+        let point = match (o1, o2) {
+            (Orientation::Collinear, Orientation::Collinear) => {
+                // The segment lies along the ray. The ray first touches it at the origin
+                // if it starts inside, and otherwise at the nearer endpoint ahead.
+                if self.contains_point(ray.origin, T::zero()) {
+                    ray.origin
+                } else if ray.origin.distance_squared(&p1) <= ray.origin.distance_squared(&p2) {
+                    p1
+                } else {
+                    p2
+                }
+            }
+            (Orientation::Collinear, _) => p1,
+            (_, Orientation::Collinear) => p2,
+            _ if o1 == o2 => return None,
+            _ => {
+                let e = self.direction();
+                let t = (p1 - ray.origin).cross(&e) / ray.direction.cross(&e);
+                ray.origin + ray.direction * t
+            }
+        };
+        if ray.is_ahead(point) {
+            Some(ray.hit(point))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cast_segment_distance_ignores_direction_magnitude() {
+        let wall: LineSegment = "(0, 5) -> (10, 5)".parse().unwrap();
+        let slow = Ray::new(Point { x: 2.0, y: 1.0 }, Point { x: 0.0, y: 0.25 });
+        let fast = Ray::new(Point { x: 2.0, y: 1.0 }, Point { x: 0.0, y: 40.0 });
+        let want = RayHit {
+            distance: 4.0,
+            point: Point { x: 2.0, y: 5.0 },
+        };
+        assert_eq!(Some(want), slow.cast(&wall));
+        assert_eq!(Some(want), fast.cast(&wall))
+    }
+
+    #[test]
+    fn test_cast_segment_misses() {
+        let wall: LineSegment = "(0, 5) -> (10, 5)".parse().unwrap();
+        // Pointing away
+        let away = Ray::new(Point { x: 2.0, y: 1.0 }, Point { x: 0.0, y: -1.0 });
+        assert_eq!(None, away.cast(&wall));
+        // Passing beside the end of the wall
+        let beside = Ray::new(Point { x: 11.0, y: 1.0 }, Point { x: 0.0, y: 1.0 });
+        assert_eq!(None, beside.cast(&wall));
+        // Parallel
+        let parallel = Ray::new(Point { x: 0.0, y: 1.0 }, Point { x: 1.0, y: 0.0 });
+        assert_eq!(None, parallel.cast(&wall))
+    }
+
+    #[test]
+    fn test_cast_through_endpoint() {
+        let wall: LineSegment = "(1, 1) -> (4, 2)".parse().unwrap();
+        let ray = Ray::new(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 });
+        let hit = ray.cast(&wall).unwrap();
+        assert_eq!(Point { x: 1.0, y: 1.0 }, hit.point)
+    }
+
+    #[test]
+    fn test_cast_along_segment() {
+        let wall: LineSegment = "(5, 0) -> (3, 0)".parse().unwrap();
+        let ray = Ray::new(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 });
+        assert_eq!(Point { x: 3.0, y: 0.0 }, ray.cast(&wall).unwrap().point);
+        let inside = Ray::new(Point { x: 4.0, y: 0.0 }, Point { x: -1.0, y: 0.0 });
+        assert_eq!(0.0, inside.cast(&wall).unwrap().distance);
+        let behind = Ray::new(Point { x: 6.0, y: 0.0 }, Point { x: 1.0, y: 0.0 });
+        assert_eq!(None, behind.cast(&wall))
+    }
+}
@@ -0,0 +1,376 @@
+use crate::{Intersects, LineSegment, Point, Scalar};
+
+/// An axis-aligned rectangle, such as a bounding box. The boundary counts as part of
+/// the rectangle, and rectangles with zero width or height are allowed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect<T = f64> {
+    min: Point<T>,
+    max: Point<T>,
+}
+
+impl<T: Scalar> Rect<T> {
+    /// The rectangle with opposite corners `a` and `b`, given in any order
+    /// ```
+    /// use rsgeo::{Point, Rect};
+    /// let r = Rect::from_corners(Point{x: 3.0, y: 0.0}, Point{x: 1.0, y: 2.0});
+    /// assert_eq!(r.min(), Point{x: 1.0, y: 0.0});
+    /// assert_eq!(r.max(), Point{x: 3.0, y: 2.0});
+    /// ```
+    pub fn from_corners(a: Point<T>, b: Point<T>) -> Rect<T> {
+        Rect {
+            min: Point {
+                x: a.x.min(b.x),
+                y: a.y.min(b.y),
+            },
+            max: Point {
+                x: a.x.max(b.x),
+                y: a.y.max(b.y),
+            },
+        }
+    }
+
+    /// The smallest rectangle containing all of the points, or None if there are none
+    /// ```
+    /// use rsgeo::{Point, Rect};
+    /// let pts = [Point{x: 1.0, y: 5.0}, Point{x: -2.0, y: 3.0}, Point{x: 0.0, y: 4.0}];
+    /// let r = Rect::from_points(pts.iter().copied()).unwrap();
+    /// assert_eq!(r, Rect::from_corners(Point{x: -2.0, y: 3.0}, Point{x: 1.0, y: 5.0}));
+    /// ```
+    pub fn from_points<I: IntoIterator<Item = Point<T>>>(points: I) -> Option<Rect<T>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Rect::from_corners(first, first), |r, p| {
+            r.union(&Rect::from_corners(p, p))
+        }))
+    }
+
+    /// The rectangle of the given size centered on `center`
+    pub fn from_center_size(center: Point<T>, width: T, height: T) -> Rect<T> {
+        let half = Point {
+            x: width.abs(),
+            y: height.abs(),
+        } / T::from_f64(2.0);
+        Rect {
+            min: center - half,
+            max: center + half,
+        }
+    }
+
+    /// The corner with the smallest coordinates
+    pub fn min(&self) -> Point<T> {
+        self.min
+    }
+
+    /// The corner with the largest coordinates
+    pub fn max(&self) -> Point<T> {
+        self.max
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y
+    }
+
+    pub fn center(&self) -> Point<T> {
+        self.min.midpoint(&self.max)
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    /// Whether `p` is inside the rectangle or on its boundary
+    pub fn contains(&self, p: Point<T>) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x && p.y >= self.min.y && p.y <= self.max.y
+    }
+
+    /// Whether the rectangle and `other`, which may be any shape implementing
+    /// `Intersects`, overlap. Rectangles that only share an edge or a corner intersect.
+    pub fn intersects<G>(&self, other: &G) -> bool
+    where
+        Self: Intersects<G>,
+    {
+        Intersects::intersects(self, other)
+    }
+
+    /// The smallest rectangle containing both rectangles
+    pub fn union(&self, other: &Rect<T>) -> Rect<T> {
+        Rect {
+            min: Point {
+                x: self.min.x.min(other.min.x),
+                y: self.min.y.min(other.min.y),
+            },
+            max: Point {
+                x: self.max.x.max(other.max.x),
+                y: self.max.y.max(other.max.y),
+            },
+        }
+    }
+
+    /// The region covered by both rectangles, or None if they do not intersect
+    /// ```
+    /// use rsgeo::{Point, Rect};
+    /// let a = Rect::from_corners(Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 2.0});
+    /// let b = Rect::from_corners(Point{x: 1.0, y: 1.0}, Point{x: 3.0, y: 3.0});
+    /// let both = a.intersection(&b).unwrap();
+    /// assert_eq!(both.area(), 1.0);
+    /// ```
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        if !self.intersects(other) {
+            return None;
+        }
+        Some(Rect {
+            min: Point {
+                x: self.min.x.max(other.min.x),
+                y: self.min.y.max(other.min.y),
+            },
+            max: Point {
+                x: self.max.x.min(other.max.x),
+                y: self.max.y.min(other.max.y),
+            },
+        })
+    }
+
+    /// Grow the rectangle by `by` on every side. A negative value shrinks it, stopping
+    /// once the width or height reaches zero.
+    pub fn expand(&self, by: T) -> Rect<T> {
+        let center = self.center();
+        let clamp = |lo: T, hi: T, mid: T| ((lo - by).min(mid), (hi + by).max(mid));
+        let (min_x, max_x) = clamp(self.min.x, self.max.x, center.x);
+        let (min_y, max_y) = clamp(self.min.y, self.max.y, center.y);
+        Rect {
+            min: Point { x: min_x, y: min_y },
+            max: Point { x: max_x, y: max_y },
+        }
+    }
+
+    /// The four corners in counter-clockwise order, starting from `min`
+    pub fn corners(&self) -> [Point<T>; 4] {
+        [
+            self.min,
+            Point {
+                x: self.max.x,
+                y: self.min.y,
+            },
+            self.max,
+            Point {
+                x: self.min.x,
+                y: self.max.y,
+            },
+        ]
+    }
+
+    /// The four edges in counter-clockwise order, starting with the bottom edge
+    pub fn edges(&self) -> impl Iterator<Item = LineSegment<T>> {
+        let c = self.corners();
+        (0..4).map(move |i| LineSegment::new(c[i], c[(i + 1) % 4]))
+    }
+
+    /// The part of `seg` inside the rectangle, found by the Liang–Barsky algorithm,
+    /// which narrows the range of the segment's parameter to each of the four sides'
+    /// slabs in turn. The result runs in the same direction as `seg`, ends that are
+    /// inside are kept exactly, and new ends are moved onto the boundary should they
+    /// round to just outside it. A segment that only touches the rectangle is clipped
+    /// to the point where it does. None if the segment misses the rectangle.
+    /// ```
+    /// use rsgeo::{LineSegment, Point, Rect};
+    /// let r = Rect::from_corners(Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 2.0});
+    /// let seg = LineSegment::new(Point{x: -2.0, y: 1.0}, Point{x: 2.0, y: 3.0});
+    /// let inside = r.clip_segment(&seg).unwrap();
+    /// assert_eq!(inside, LineSegment::new(Point{x: 0.0, y: 2.0}, Point{x: 0.0, y: 2.0}));
+    /// let seg = LineSegment::new(Point{x: -2.0, y: 0.0}, Point{x: 2.0, y: 2.0});
+    /// let inside = r.clip_segment(&seg).unwrap();
+    /// assert_eq!(inside, LineSegment::new(Point{x: 0.0, y: 1.0}, Point{x: 2.0, y: 2.0}));
+    /// assert_eq!(None, r.clip_segment(&LineSegment::new(seg.p1(), Point{x: -1.0, y: 5.0})));
+    /// ```
+    pub fn clip_segment(&self, seg: &LineSegment<T>) -> Option<LineSegment<T>> {
+        let (p, d) = (seg.p1(), seg.direction());
+        let (mut enter, mut leave) = (T::zero(), T::one());
+        for &(start, step, min, max) in &[
+            (p.x, d.x, self.min.x, self.max.x),
+            (p.y, d.y, self.min.y, self.max.y),
+        ] {
+            if step == T::zero() {
+                if start < min || start > max {
+                    return None;
+                }
+                continue;
+            }
+            let (a, b) = ((min - start) / step, (max - start) / step);
+            enter = enter.max(a.min(b));
+            leave = leave.min(a.max(b));
+            if enter > leave {
+                return None;
+            }
+        }
+        let at = |t: T, end: Point<T>| {
+            if t == T::zero() {
+                p
+            } else if t == T::one() {
+                end
+            } else {
+                let q = p + d * t;
+                Point {
+                    x: q.x.max(self.min.x).min(self.max.x),
+                    y: q.y.max(self.min.y).min(self.max.y),
+                }
+            }
+        };
+        Some(LineSegment::new(at(enter, seg.p2()), at(leave, seg.p2())))
+    }
+
+    /// The parts of `segments` inside the rectangle, in order, leaving out those that
+    /// miss it. See `clip_segment`.
+    /// ```
+    /// use rsgeo::{LineSegment, Point, Rect};
+    /// let r = Rect::from_corners(Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 1.0});
+    /// let segments: Vec<LineSegment> = ["(-1, 0.5) -> (2, 0.5)", "(2, 2) -> (3, 3)"]
+    ///     .iter()
+    ///     .map(|s| s.parse().unwrap())
+    ///     .collect();
+    /// let clipped: Vec<_> = r.clip_segments(segments.iter().copied()).collect();
+    /// assert_eq!(clipped, vec!["(0, 0.5) -> (1, 0.5)".parse().unwrap()]);
+    /// ```
+    pub fn clip_segments<'a, I>(&'a self, segments: I) -> impl Iterator<Item = LineSegment<T>> + 'a
+    where
+        I: IntoIterator<Item = LineSegment<T>>,
+        I::IntoIter: 'a,
+    {
+        segments
+            .into_iter()
+            .filter_map(move |seg| self.clip_segment(&seg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lcg;
+
+    fn rect(x1: f64, y1: f64, x2: f64, y2: f64) -> Rect {
+        Rect::from_corners(Point { x: x1, y: y1 }, Point { x: x2, y: y2 })
+    }
+
+    #[test]
+    fn test_from_center_size() {
+        let r = Rect::from_center_size(Point { x: 1.0, y: 1.0 }, 4.0, 2.0);
+        assert_eq!(rect(-1.0, 0.0, 3.0, 2.0), r);
+        assert_eq!(Point { x: 1.0, y: 1.0 }, r.center());
+        assert_eq!(8.0, r.area())
+    }
+
+    #[test]
+    fn test_from_points_empty() {
+        assert_eq!(None, Rect::<f64>::from_points(Vec::new()))
+    }
+
+    #[test]
+    fn test_contains_includes_boundary() {
+        let r = rect(0.0, 0.0, 2.0, 1.0);
+        assert!(r.contains(Point { x: 1.0, y: 0.5 }));
+        assert!(r.contains(Point { x: 2.0, y: 1.0 }));
+        assert!(!r.contains(Point { x: 2.1, y: 1.0 }))
+    }
+
+    #[test]
+    fn test_intersects_and_intersection() {
+        let a = rect(0.0, 0.0, 2.0, 2.0);
+        let touching = rect(2.0, 2.0, 3.0, 3.0);
+        assert!(a.intersects(&touching));
+        assert_eq!(0.0, a.intersection(&touching).unwrap().area());
+        let apart = rect(2.5, 0.0, 3.0, 1.0);
+        assert!(!a.intersects(&apart));
+        assert_eq!(None, a.intersection(&apart));
+        assert_eq!(rect(0.0, 0.0, 3.0, 2.0), a.union(&apart))
+    }
+
+    #[test]
+    fn test_expand() {
+        let r = rect(0.0, 0.0, 4.0, 2.0);
+        assert_eq!(rect(-1.0, -1.0, 5.0, 3.0), r.expand(1.0));
+        // Shrinking stops at zero height, but the width keeps shrinking
+        let thin = r.expand(-1.5);
+        assert_eq!(rect(1.5, 1.0, 2.5, 1.0), thin)
+    }
+
+    #[test]
+    fn test_corners_and_edges() {
+        let r = rect(0.0, 0.0, 2.0, 1.0);
+        let corners = r.corners();
+        assert_eq!(Point { x: 2.0, y: 0.0 }, corners[1]);
+        assert_eq!(Point { x: 0.0, y: 1.0 }, corners[3]);
+        let perimeter: f64 = r.edges().map(|e| e.length()).sum();
+        assert_eq!(6.0, perimeter);
+        let last = r.edges().last().unwrap();
+        assert_eq!(corners[0], last.p2())
+    }
+
+    #[test]
+    fn test_clip_segment() {
+        let r = rect(0.0, 0.0, 4.0, 2.0);
+        let seg = |s: &str| -> LineSegment { s.parse().unwrap() };
+        // Inside, and kept exactly
+        let inner = seg("(0.1, 0.3) -> (3.7, 1.9)");
+        assert_eq!(Some(inner), r.clip_segment(&inner));
+        // Crossing the whole rectangle, backwards
+        assert_eq!(
+            Some(seg("(4, 1) -> (0, 1)")),
+            r.clip_segment(&seg("(9, 1) -> (-3, 1)"))
+        );
+        // Along an edge, and along the line of an edge but beyond the corner
+        assert_eq!(
+            Some(seg("(0, 2) -> (4, 2)")),
+            r.clip_segment(&seg("(-1, 2) -> (5, 2)"))
+        );
+        assert_eq!(None, r.clip_segment(&seg("(5, 2) -> (6, 2)")));
+        // Vertical, and a single point
+        assert_eq!(
+            Some(seg("(3, 0) -> (3, 2)")),
+            r.clip_segment(&seg("(3, -1) -> (3, 8)"))
+        );
+        let point = seg("(1, 1) -> (1, 1)");
+        assert_eq!(Some(point), r.clip_segment(&point));
+        assert_eq!(None, r.clip_segment(&seg("(5, 1) -> (5, 1)")));
+        // Passing just outside a corner
+        assert_eq!(None, r.clip_segment(&seg("(3, -1) -> (5.1, 1)")))
+    }
+
+    #[test]
+    fn test_clip_segment_stays_inside() {
+        let r = rect(0.1, 0.2, 0.7, 0.9);
+        let mut rng = Lcg::new(11);
+        let mut coord = || rng.unit() * 1.2 - 0.1;
+        let segments: Vec<LineSegment> = (0..500)
+            .map(|_| {
+                let p1 = Point {
+                    x: coord(),
+                    y: coord(),
+                };
+                LineSegment::new(
+                    p1,
+                    Point {
+                        x: coord(),
+                        y: coord(),
+                    },
+                )
+            })
+            .collect();
+        let mut clipped = 0;
+        for seg in &segments {
+            match r.clip_segment(seg) {
+                Some(inside) => {
+                    clipped += 1;
+                    assert!(r.contains(inside.p1()) && r.contains(inside.p2()));
+                    assert!(seg.distance_to(&inside).0 < 1e-12);
+                    assert!(inside.direction().dot_product(&seg.direction()) >= 0.0);
+                }
+                None => assert!(!seg.intersects(&r)),
+            }
+        }
+        assert_eq!(clipped, r.clip_segments(segments.iter().copied()).count())
+    }
+}
@@ -0,0 +1,466 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::predicates::{incircle, orient2d};
+use crate::{Angle, Circle, Point, Polygon, Scalar, Triangle};
+
+/// Points joined up into triangles, each given as the indices of its corners in
+/// counter-clockwise order
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TriangleMesh<T = f64> {
+    pub points: Vec<Point<T>>,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl<T: Scalar> TriangleMesh<T> {
+    /// The triangle `t` with its corners in place
+    pub fn triangle(&self, t: usize) -> Triangle<T> {
+        let [a, b, c] = self.triangles[t];
+        Triangle::new(self.points[a], self.points[b], self.points[c])
+    }
+
+    /// The smallest angle in any of the triangles, or None if there are none. Angles
+    /// that are not a number, as at a corner with a coordinate that is not, are skipped.
+    pub fn min_angle(&self) -> Option<Angle<T>> {
+        self.triangles
+            .iter()
+            .flat_map(|v| {
+                (0..3).map(move |i| {
+                    let [a, b, c] = [v[i], v[(i + 1) % 3], v[(i + 2) % 3]].map(|k| self.points[k]);
+                    (b - a).angle_between(&(c - a))
+                })
+            })
+            .filter(|a| !a.is_nan())
+            .min_by(|a, b| a.total_cmp(b))
+            .map(Angle::from_radians)
+    }
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// A mesh of the polygon whose triangles have no angle smaller than `min_angle`
+    /// and, if `max_area` is given, no area larger than it, found by Ruppert's
+    /// Delaunay refinement. Starting from the constrained Delaunay triangulation of the
+    /// polygon, a point is added at the middle of any boundary edge that has a vertex
+    /// inside the circle on it as diameter, and otherwise at the center of the circle
+    /// around any triangle that is too thin or too large, unless that point would be
+    /// on such a circle, in which case the edge is split instead. Each new point keeps
+    /// the triangulation constrained Delaunay.
+    ///
+    /// This is sure to finish up to about 20.7 degrees and in practice does up to 33.
+    /// Where two edges of the polygon meet at an angle smaller than `min_angle` no
+    /// mesh can do better, so the triangles in that corner are left, and the edges
+    /// there are split at powers of two from the corner so that the splits on one do
+    /// not keep encroaching on the other. Panics if `min_angle` is negative or 34
+    /// degrees or more, or if `max_area` is not positive.
+    /// ```
+    /// use rsgeo::{Angle, Point, Polygon};
+    /// let square: Polygon = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 4.0}, Point{x: 0.0, y: 4.0},
+    /// ]);
+    /// let mesh = square.quality_mesh(Angle::from_degrees(30.0), Some(0.5));
+    /// assert!(mesh.min_angle().unwrap().degrees() >= 30.0);
+    /// assert!((0..mesh.triangles.len()).all(|t| mesh.triangle(t).area() <= 0.5));
+    /// ```
+    pub fn quality_mesh(
+        &self,
+        min_angle: impl Into<Angle<T>>,
+        max_area: Option<T>,
+    ) -> TriangleMesh<T> {
+        let min_angle = min_angle.into();
+        let degrees = min_angle.degrees();
+        assert!(
+            degrees >= T::zero() && degrees < T::from_f64(34.0),
+            "min_angle must be at least 0 and below 34 degrees, got {}",
+            degrees
+        );
+        if let Some(area) = max_area {
+            assert!(area > T::zero(), "max_area must be positive, got {}", area);
+        }
+        let mut refiner = Refiner::new(self);
+        refiner.ratio = T::one() / (T::from_f64(2.0) * min_angle.sin());
+        refiner.max_area = max_area;
+        refiner.refine();
+        refiner.finish()
+    }
+}
+
+/// A boundary edge, as a triangle and the corner of it opposite the edge
+type Segment = (usize, usize);
+
+/// An edge around a cavity, as the triangle inside, its corner opposite the edge and
+/// the triangle outside
+type Rim = (usize, usize, Option<usize>);
+
+/// A constrained Delaunay triangulation of the inside of a polygon, with the edges of
+/// the polygon along the boundary, that points are being added to
+struct Refiner<T> {
+    points: Vec<Point<T>>,
+    /// How many of the points are vertices of the polygon
+    corners: usize,
+    triangles: Vec<[usize; 3]>,
+    /// The triangle across the edge opposite each corner, or None at the boundary
+    neighbors: Vec<[Option<usize>; 3]>,
+    alive: Vec<bool>,
+    /// The largest ratio of a triangle's circumradius to its shortest edge that gives
+    /// no angle below the minimum
+    ratio: T,
+    max_area: Option<T>,
+}
+
+impl<T: Scalar> Refiner<T> {
+    fn new(polygon: &Polygon<T>) -> Refiner<T> {
+        let (points, mesh) = polygon.constrained_delaunay();
+        let inside: Vec<bool> = (0..mesh.triangles.len())
+            .map(|t| polygon.contains(mesh.triangle(&points, t).centroid()))
+            .collect();
+        let neighbors = mesh
+            .neighbors
+            .iter()
+            .map(|n| n.map(|n| n.filter(|&n| inside[n])))
+            .collect();
+        Refiner {
+            corners: points.len(),
+            points,
+            alive: inside,
+            triangles: mesh.triangles,
+            neighbors,
+            ratio: T::infinity(),
+            max_area: None,
+        }
+    }
+
+    fn refine(&mut self) {
+        let mut queue: Vec<usize> = (0..self.triangles.len()).collect();
+        while let Some(t) = queue.pop() {
+            if !self.alive[t] {
+                continue;
+            }
+            let encroached = (0..3).find(|&i| {
+                let [a, b] = self.ends((t, i));
+                let apex = self.points[self.triangles[t][i]];
+                self.neighbors[t][i].is_none()
+                    && (self.points[a] - apex).dot_product(&(self.points[b] - apex)) < T::zero()
+            });
+            if let Some(i) = encroached {
+                queue.extend(self.split((t, i)));
+                continue;
+            }
+            if !self.is_bad(t) {
+                continue;
+            }
+            let [a, b, c] = self.triangles[t].map(|k| self.points[k]);
+            let center = match Circle::from_three_points(a, b, c) {
+                Some(circle) => circle.center,
+                None => continue,
+            };
+            let found = match self.locate(t, center) {
+                Ok(found) => found,
+                Err(segment) => {
+                    // The center is outside, past an edge that it must encroach on
+                    queue.push(t);
+                    queue.extend(self.split(segment));
+                    continue;
+                }
+            };
+            if self.triangles[found]
+                .iter()
+                .any(|&k| self.points[k] == center)
+            {
+                continue;
+            }
+            let (cavity, rim) = self.cavity(center, found, None);
+            let encroached = rim.iter().find(|&&(old, j, outside)| {
+                let [a, b] = self.ends((old, j));
+                outside.is_none()
+                    && (self.points[a] - center).dot_product(&(self.points[b] - center)) < T::zero()
+            });
+            if let Some(&(old, j, _)) = encroached {
+                queue.push(t);
+                queue.extend(self.split((old, j)));
+                continue;
+            }
+            self.points.push(center);
+            queue.extend(self.fill(&cavity, &rim));
+        }
+    }
+
+    /// The points at the ends of the edge opposite a corner of a triangle, in order
+    /// counter-clockwise around it
+    fn ends(&self, (t, i): Segment) -> [usize; 2] {
+        let v = self.triangles[t];
+        [v[(i + 1) % 3], v[(i + 2) % 3]]
+    }
+
+    /// Whether triangle `t` has too small an angle or too large an area
+    fn is_bad(&self, t: usize) -> bool {
+        let [a, b, c] = self.triangles[t].map(|k| self.points[k]);
+        if self
+            .max_area
+            .is_some_and(|area| Triangle::new(a, b, c).area() > area)
+        {
+            return true;
+        }
+        let lengths = [b.distance(&c), c.distance(&a), a.distance(&b)];
+        let shortest = (0..3)
+            .min_by(|&i, &j| lengths[i].total_cmp(&lengths[j]))
+            .unwrap();
+        let radius = match Circle::from_three_points(a, b, c) {
+            Some(circle) => circle.radius,
+            None => return false,
+        };
+        // The smallest angle is opposite the shortest edge. When it lies between two
+        // boundary edges it is as the polygon made it.
+        let between_boundaries = self.neighbors[t][(shortest + 1) % 3].is_none()
+            && self.neighbors[t][(shortest + 2) % 3].is_none();
+        radius > self.ratio * lengths[shortest] && !between_boundaries
+    }
+
+    /// Walk in a straight line from the middle of triangle `from` to the triangle
+    /// holding `p`, or to the boundary edge in the way
+    fn locate(&self, from: usize, p: Point<T>) -> Result<usize, Segment> {
+        let [a, b, c] = self.triangles[from].map(|k| self.points[k]);
+        let start = Triangle::new(a, b, c).centroid();
+        let mut t = from;
+        loop {
+            let beyond: Vec<usize> = (0..3)
+                .filter(|&i| {
+                    let [u, w] = self.ends((t, i)).map(|k| self.points[k]);
+                    orient2d(u, w, p) < 0.0
+                })
+                .collect();
+            let i = match beyond
+                .iter()
+                .find(|&&i| {
+                    let [u, w] = self.ends((t, i)).map(|k| self.points[k]);
+                    orient2d(start, p, u) <= 0.0 && orient2d(start, p, w) >= 0.0
+                })
+                .or(beyond.first())
+            {
+                Some(&i) => i,
+                None => return Ok(t),
+            };
+            match self.neighbors[t][i] {
+                Some(n) => t = n,
+                None => return Err((t, i)),
+            }
+        }
+    }
+
+    /// Split a boundary edge, at its middle or, if just one end is a vertex of the
+    /// polygon, at the power of two nearest that from the vertex. Returns the new
+    /// triangles.
+    fn split(&mut self, segment: Segment) -> Vec<usize> {
+        let [a, b] = self.ends(segment);
+        let (pa, pb) = (self.points[a], self.points[b]);
+        let (from, to) = match (a < self.corners, b < self.corners) {
+            (true, false) => (pa, pb),
+            (false, true) => (pb, pa),
+            _ => (pa, pb),
+        };
+        let length = from.distance(&to);
+        let at = if (a < self.corners) == (b < self.corners) {
+            T::from_f64(0.5)
+        } else {
+            // The power of two between a third and two thirds of the way along
+            (length * T::from_f64(2.0 / 3.0)).log2().floor().exp2() / length
+        };
+        let p = from.lerp(&to, at);
+        let (cavity, rim) = self.cavity(p, segment.0, Some(segment.1));
+        self.points.push(p);
+        self.fill(&cavity, &rim)
+    }
+
+    /// The triangles whose circumcircles hold `p` that can be reached from `start`
+    /// without crossing the boundary, and the edges around them with the triangle
+    /// inside each, the corner opposite it and the triangle outside. The edge
+    /// opposite corner `skip` of `start` is left out of the rim, for a point on it.
+    fn cavity(&self, p: Point<T>, start: usize, skip: Option<usize>) -> (Vec<usize>, Vec<Rim>) {
+        let mut cavity = alloc::vec![start];
+        let mut rim = Vec::new();
+        let mut k = 0;
+        while k < cavity.len() {
+            let t = cavity[k];
+            k += 1;
+            for j in 0..3 {
+                if t == start && skip == Some(j) {
+                    continue;
+                }
+                match self.neighbors[t][j] {
+                    Some(n) if cavity.contains(&n) => {}
+                    Some(n) if self.conflicts(n, p) => cavity.push(n),
+                    outside => rim.push((t, j, outside)),
+                }
+            }
+        }
+        (cavity, rim)
+    }
+
+    fn conflicts(&self, t: usize, p: Point<T>) -> bool {
+        let [a, b, c] = self.triangles[t].map(|k| self.points[k]);
+        incircle(a, b, c, p) > 0.0
+    }
+
+    /// Replace the `cavity` with a fan of triangles from the newest point to each edge
+    /// of its `rim`, returning them
+    fn fill(&mut self, cavity: &[usize], rim: &[Rim]) -> Vec<usize> {
+        let p = self.points.len() - 1;
+        for &t in cavity {
+            self.alive[t] = false;
+        }
+        let mut created = Vec::with_capacity(rim.len());
+        let mut edges = BTreeMap::new();
+        for &(old, j, outside) in rim {
+            let [u, w] = self.ends((old, j));
+            let t = self.triangles.len();
+            self.triangles.push([u, w, p]);
+            self.neighbors.push([None, None, outside]);
+            self.alive.push(true);
+            if let Some(o) = outside {
+                for k in 0..3 {
+                    if self.neighbors[o][k] == Some(old) {
+                        self.neighbors[o][k] = Some(t);
+                    }
+                }
+            }
+            edges.insert((w, p), (t, 0));
+            edges.insert((p, u), (t, 1));
+            created.push(t);
+        }
+        for (&(x, y), &(t, k)) in &edges {
+            if let Some(&(n, _)) = edges.get(&(y, x)) {
+                self.neighbors[t][k] = Some(n);
+            }
+        }
+        created
+    }
+
+    /// The triangles left, with the points they use
+    fn finish(self) -> TriangleMesh<T> {
+        let mut index = alloc::vec![None; self.points.len()];
+        let mut mesh = TriangleMesh::default();
+        for t in (0..self.triangles.len()).filter(|&t| self.alive[t]) {
+            let corners = self.triangles[t].map(|k| {
+                *index[k].get_or_insert_with(|| {
+                    mesh.points.push(self.points[k]);
+                    mesh.points.len() - 1
+                })
+            });
+            mesh.triangles.push(corners);
+        }
+        mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar_isclose;
+
+    fn check(polygon: &Polygon, mesh: &TriangleMesh) {
+        let mut area = 0.0;
+        for t in 0..mesh.triangles.len() {
+            let triangle = mesh.triangle(t);
+            assert!(triangle.signed_area() > 0.0);
+            assert!(polygon.contains(triangle.centroid()));
+            area += triangle.area();
+        }
+        assert!(scalar_isclose(area, polygon.area()));
+        for ring in polygon.rings() {
+            assert!(ring.iter().all(|p| mesh.points.contains(p)));
+        }
+    }
+
+    #[test]
+    fn test_min_angle() {
+        let mut comb = vec![Point { x: 0.0, y: 0.0 }, Point { x: 9.0, y: 0.0 }];
+        for i in (0..5).rev() {
+            let x = 2.0 * i as f64;
+            comb.push(Point { x: x + 1.0, y: 3.0 });
+            comb.push(Point { x, y: 3.0 });
+            if i > 0 {
+                comb.push(Point { x, y: 1.0 });
+                comb.push(Point { x: x - 1.0, y: 1.0 });
+            }
+        }
+        let comb = Polygon::with_holes(
+            comb,
+            vec![vec![
+                Point { x: 4.0, y: 0.2 },
+                Point { x: 3.0, y: 0.7 },
+                Point { x: 5.0, y: 0.6 },
+            ]],
+        );
+        assert_eq!(None, TriangleMesh::<f64>::default().min_angle());
+        let broken = TriangleMesh {
+            points: vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 1.0, y: 0.0 },
+                Point { x: 0.0, y: 1.0 },
+                Point {
+                    x: f64::NAN,
+                    y: 1.0,
+                },
+            ],
+            triangles: vec![[1, 3, 2], [0, 1, 2]],
+        };
+        assert!(crate::scalar_isclose(
+            45.0,
+            broken.min_angle().unwrap().degrees()
+        ));
+        for degrees in [20.0, 30.0, 33.0] {
+            let mesh = comb.quality_mesh(Angle::from_degrees(degrees), None);
+            check(&comb, &mesh);
+            assert!(mesh.min_angle().unwrap().degrees() >= degrees);
+        }
+    }
+
+    #[test]
+    fn test_max_area() {
+        let strip = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 10.0, y: 0.0 },
+            Point { x: 10.0, y: 1.0 },
+            Point { x: 0.0, y: 1.0 },
+        ]);
+        // With no angle to keep to, only the area is refined
+        let mesh = strip.quality_mesh(0.0, Some(0.05));
+        check(&strip, &mesh);
+        assert!(mesh.triangles.len() >= 200);
+        assert!((0..mesh.triangles.len()).all(|t| mesh.triangle(t).area() <= 0.05));
+        assert_eq!(2, strip.quality_mesh(0.0, None).triangles.len());
+    }
+
+    #[test]
+    fn test_small_input_angle() {
+        // A wedge far sharper than the angle asked for, which is kept at its tip
+        let tip = Point { x: 0.0, y: 0.0 };
+        let wedge = Polygon::new(vec![
+            tip,
+            Point { x: 10.0, y: 0.0 },
+            Point::from_polar(10.0, Angle::from_degrees(8.0)),
+        ]);
+        let mesh = wedge.quality_mesh(Angle::from_degrees(25.0), None);
+        check(&wedge, &mesh);
+        let bound = Angle::from_degrees(25.0).radians();
+        for v in &mesh.triangles {
+            let one = TriangleMesh {
+                points: mesh.points.clone(),
+                triangles: vec![*v],
+            };
+            if one.min_angle().unwrap().radians() < bound {
+                assert!(v.iter().any(|&k| mesh.points[k] == tip));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_angle_too_large() {
+        Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 0.0, y: 1.0 },
+        ])
+        .quality_mesh(Angle::from_degrees(35.0), None);
+    }
+}
@@ -0,0 +1,490 @@
+//! The Dimensionally Extended 9-Intersection Model (DE-9IM), which describes how two
+//! shapes meet by the dimension of each intersection between the interior, boundary
+//! and exterior of one and those of the other.
+//!
+//! `relate` computes the matrix for any two of `Point`, `LineSegment`, `Polyline`,
+//! `Polygon` and `Rect`, using the same boundary convention as the predicates in
+//! `Intersects` and friends. Circles have no counterpart in the model and are left out.
+//! The matrix formats as the nine-character string PostGIS's `ST_Relate` returns, and
+//! `matches` accepts the same patterns.
+//! ```
+//! use rsgeo::{relate, Point, Polygon};
+//! let square = |lo: f64, hi: f64| Polygon::new(vec![
+//!     Point{x: lo, y: lo}, Point{x: hi, y: lo}, Point{x: hi, y: hi}, Point{x: lo, y: hi},
+//! ]);
+//! let m = relate(&square(0.0, 2.0), &square(1.0, 3.0));
+//! assert_eq!(m.to_string(), "212101212");
+//! assert!(m.matches("T*T***T**") && m.is_overlaps());
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::topology::{boundary, shared_edge_sides, AsShape, Shape};
+use crate::{LineSegment, Point, Polygon, Polyline, Rect, Scalar, SegmentIntersection};
+
+/// One of the three parts of a shape that the DE-9IM compares
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    Interior,
+    Boundary,
+    Exterior,
+}
+
+/// The dimension of an intersection between two parts, ordered from empty up to an
+/// area
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Dimension {
+    /// The parts do not meet, written `F`
+    Empty,
+    /// The parts meet only at points, written `0`
+    Zero,
+    /// The parts share a curve, written `1`
+    One,
+    /// The parts share an area, written `2`
+    Two,
+}
+
+/// The DE-9IM matrix of a pair of shapes `a` and `b`, in which the rows are the parts
+/// of `a` and the columns are the parts of `b`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntersectionMatrix {
+    cells: [[Dimension; 3]; 3],
+}
+
+impl IntersectionMatrix {
+    /// The dimension of the intersection of part `a` of the first shape with part `b`
+    /// of the second
+    pub fn get(&self, a: Part, b: Part) -> Dimension {
+        self.cells[a as usize][b as usize]
+    }
+
+    /// The matrix with the two shapes swapped
+    pub fn transpose(&self) -> IntersectionMatrix {
+        let mut cells = self.cells;
+        for (i, row) in cells.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = self.cells[j][i];
+            }
+        }
+        IntersectionMatrix { cells }
+    }
+
+    /// Whether the matrix matches a pattern of nine characters, read row by row. Each
+    /// is `T` for any non-empty intersection, `F` for an empty one, `0`, `1` or `2` for
+    /// that exact dimension, or `*` for anything. Panics if the pattern is malformed.
+    /// ```
+    /// use rsgeo::{relate, LineSegment};
+    /// let a: LineSegment = "(0, 0) -> (2, 2)".parse().unwrap();
+    /// let b: LineSegment = "(0, 2) -> (2, 0)".parse().unwrap();
+    /// assert!(relate(&a, &b).matches("0F1FF0102"));
+    /// assert!(relate(&a, &b).matches("T********"));
+    /// ```
+    pub fn matches(&self, pattern: &str) -> bool {
+        assert!(
+            pattern.chars().count() == 9 && pattern.chars().all(|c| "TF*012".contains(c)),
+            "invalid DE-9IM pattern {:?}",
+            pattern
+        );
+        self.cells
+            .iter()
+            .flatten()
+            .zip(pattern.chars())
+            .all(|(&d, c)| match c {
+                'T' => d != Dimension::Empty,
+                'F' => d == Dimension::Empty,
+                '0' => d == Dimension::Zero,
+                '1' => d == Dimension::One,
+                '2' => d == Dimension::Two,
+                _ => true,
+            })
+    }
+
+    /// The dimension of the first shape, which its interior shares with some part of
+    /// the second
+    fn dimension_a(&self) -> Dimension {
+        self.cells[Part::Interior as usize]
+            .iter()
+            .copied()
+            .max()
+            .unwrap()
+    }
+
+    fn dimension_b(&self) -> Dimension {
+        self.transpose().dimension_a()
+    }
+
+    pub fn is_disjoint(&self) -> bool {
+        self.matches("FF*FF****")
+    }
+
+    pub fn is_intersects(&self) -> bool {
+        !self.is_disjoint()
+    }
+
+    /// Whether the shapes meet, but their interiors do not
+    pub fn is_touches(&self) -> bool {
+        ["FT*******", "F**T*****", "F***T****"]
+            .iter()
+            .any(|p| self.matches(p))
+    }
+
+    /// Whether `a` lies in `b` and their interiors meet, so that a shape lying on
+    /// another's boundary is not within it
+    pub fn is_within(&self) -> bool {
+        self.matches("T*F**F***")
+    }
+
+    /// Whether `b` lies in `a` and their interiors meet
+    pub fn is_contains(&self) -> bool {
+        self.transpose().is_within()
+    }
+
+    /// Whether every point of `b` lies in `a`, which is what `Contains` tests
+    pub fn is_covers(&self) -> bool {
+        ["T*****FF*", "*T****FF*", "***T**FF*", "****T*FF*"]
+            .iter()
+            .any(|p| self.matches(p))
+    }
+
+    /// Whether every point of `a` lies in `b`, which is what `Within` tests
+    pub fn is_covered_by(&self) -> bool {
+        self.transpose().is_covers()
+    }
+
+    /// Whether the shapes cover exactly the same points
+    pub fn is_equal(&self) -> bool {
+        self.matches("T*F**FFF*")
+    }
+
+    /// Whether the interiors meet in a shape of lower dimension than the larger of the
+    /// two, such as two curves crossing at a point or a curve passing through an area
+    pub fn is_crosses(&self) -> bool {
+        let (a, b) = (self.dimension_a(), self.dimension_b());
+        if a == Dimension::Empty || b == Dimension::Empty {
+            false
+        } else if a < b {
+            self.matches("T*T******")
+        } else if a > b {
+            self.matches("T*****T**")
+        } else {
+            a == Dimension::One && self.matches("0********")
+        }
+    }
+
+    /// Whether shapes of the same dimension share part of their interiors, and each has
+    /// some part outside the other
+    pub fn is_overlaps(&self) -> bool {
+        let (a, b) = (self.dimension_a(), self.dimension_b());
+        match a {
+            _ if a != b => false,
+            Dimension::Empty => false,
+            Dimension::One => self.matches("1*T***T**"),
+            _ => self.matches("T*T***T**"),
+        }
+    }
+
+    fn raise(&mut self, a: Part, b: Part, dim: Dimension) {
+        let cell = &mut self.cells[a as usize][b as usize];
+        *cell = (*cell).max(dim);
+    }
+}
+
+impl fmt::Display for IntersectionMatrix {
+    /// Format the matrix row by row as nine characters, such as `212101212`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &d in self.cells.iter().flatten() {
+            let c = match d {
+                Dimension::Empty => 'F',
+                Dimension::Zero => '0',
+                Dimension::One => '1',
+                Dimension::Two => '2',
+            };
+            fmt::Write::write_char(f, c)?;
+        }
+        Ok(())
+    }
+}
+
+pub trait Relate<Rhs = Self> {
+    /// The DE-9IM matrix describing how the shape meets `other`
+    fn relate(&self, other: &Rhs) -> IntersectionMatrix;
+}
+
+/// The DE-9IM matrix describing how `a` meets `b`. See `IntersectionMatrix`.
+pub fn relate<A: Relate<B>, B>(a: &A, b: &B) -> IntersectionMatrix {
+    a.relate(b)
+}
+
+fn locate<T: Scalar>(shape: &Shape<T>, p: Point<T>) -> Part {
+    let on = |e: LineSegment<T>| e.contains_point(p, T::zero());
+    match shape {
+        Shape::Empty => Part::Exterior,
+        Shape::Point(q) if *q == p => Part::Interior,
+        Shape::Point(_) => Part::Exterior,
+        Shape::Curve(_, ends) if ends.contains(&p) => Part::Boundary,
+        Shape::Curve(segments, _) if segments.iter().copied().any(on) => Part::Interior,
+        Shape::Curve(..) => Part::Exterior,
+        Shape::Region(r) if boundary(r).any(on) => Part::Boundary,
+        Shape::Region(r) if r.contains(p) => Part::Interior,
+        Shape::Region(_) => Part::Exterior,
+        Shape::Disk(_) => unreachable!("circles are not related"),
+    }
+}
+
+/// Record how the points and curves making up `a` meet `b`, as rows of the matrix if
+/// `a` is the first shape or as columns if it is the second
+fn add_features<T: Scalar>(m: &mut IntersectionMatrix, a: &Shape<T>, b: &Shape<T>, swap: bool) {
+    let mut raise = |pa: Part, pb: Part, dim: Dimension| {
+        if swap {
+            m.raise(pb, pa, dim)
+        } else {
+            m.raise(pa, pb, dim)
+        }
+    };
+    let (points, lines): (Vec<_>, Vec<_>) = match a {
+        Shape::Empty => (Vec::new(), Vec::new()),
+        Shape::Point(p) => (alloc::vec![(Part::Interior, *p)], Vec::new()),
+        Shape::Curve(segments, ends) => (
+            ends.iter().map(|&p| (Part::Boundary, p)).collect(),
+            segments.iter().map(|&s| (Part::Interior, s)).collect(),
+        ),
+        Shape::Region(r) => (
+            Vec::new(),
+            boundary(r).map(|e| (Part::Boundary, e)).collect(),
+        ),
+        Shape::Disk(_) => unreachable!("circles are not related"),
+    };
+    for (part, p) in points {
+        raise(part, locate(b, p), Dimension::Zero);
+    }
+
+    // The curves of `b`, and the points of `b` that do not lie on them
+    let (linework, loose): (Vec<_>, Vec<_>) = match b {
+        Shape::Point(p) => (Vec::new(), alloc::vec![*p]),
+        Shape::Curve(segments, _) => (segments.clone(), Vec::new()),
+        Shape::Region(r) => (boundary(r).collect(), Vec::new()),
+        _ => (Vec::new(), Vec::new()),
+    };
+    for (part, seg) in lines {
+        // Cut the segment wherever it meets `b`, so that each piece between the cuts
+        // either runs along a curve of `b` or lies in a single part of it
+        let d = seg.direction();
+        let len2 = d.dot_product(&d);
+        let param = |q: Point<T>| {
+            ((q - seg.p1()).dot_product(&d) / len2)
+                .max(T::zero())
+                .min(T::one())
+        };
+        let mut cuts = alloc::vec![T::zero(), T::one()];
+        let mut along = Vec::new();
+        for e in &linework {
+            match seg.intersect(e) {
+                SegmentIntersection::None => {}
+                SegmentIntersection::Point(q) => {
+                    cuts.push(param(q));
+                    raise(locate(a, q), locate(b, q), Dimension::Zero);
+                }
+                SegmentIntersection::Segment(s) => {
+                    let (lo, hi) = (param(s.p1()), param(s.p2()));
+                    cuts.push(lo);
+                    cuts.push(hi);
+                    along.push((lo.min(hi), lo.max(hi)));
+                }
+            }
+        }
+        cuts.extend(
+            loose
+                .iter()
+                .filter(|&&q| seg.contains_point(q, T::zero()))
+                .map(|&q| param(q)),
+        );
+        cuts.sort_by(|a, b| a.total_cmp(b));
+        cuts.dedup();
+        for w in cuts.windows(2) {
+            let on_linework = along.iter().any(|&(lo, hi)| lo <= w[0] && w[1] <= hi);
+            let pb = match b {
+                Shape::Curve(..) if on_linework => Part::Interior,
+                Shape::Region(_) if on_linework => Part::Boundary,
+                _ => locate(b, seg.p1() + d * ((w[0] + w[1]) / T::from_f64(2.0))),
+            };
+            raise(part, pb, Dimension::One);
+        }
+    }
+}
+
+/// Fill in the intersections between the interiors and exteriors of two regions, from
+/// where their boundaries lie
+fn add_areas<T: Scalar>(m: &mut IntersectionMatrix, a: &Polygon<T>, b: &Polygon<T>) {
+    use Part::*;
+    let crosses = |m: &IntersectionMatrix, pa, pb| m.get(pa, pb) == Dimension::One;
+    let sides = shared_edge_sides(a, b);
+    // Each side of a boundary curve is in a different part of its own shape, so where
+    // the curve runs through a part of the other shape, that part meets both
+    let interiors =
+        crosses(m, Boundary, Interior) || crosses(m, Interior, Boundary) || sides.contains(&true);
+    let a_outside =
+        crosses(m, Boundary, Exterior) || crosses(m, Interior, Boundary) || sides.contains(&false);
+    let b_outside =
+        crosses(m, Exterior, Boundary) || crosses(m, Boundary, Interior) || sides.contains(&false);
+    for &(pa, pb, meets) in &[
+        (Interior, Interior, interiors),
+        (Interior, Exterior, a_outside),
+        (Exterior, Interior, b_outside),
+    ] {
+        if meets {
+            m.raise(pa, pb, Dimension::Two);
+        }
+    }
+}
+
+fn relate_shapes<T: Scalar>(a: &Shape<T>, b: &Shape<T>) -> IntersectionMatrix {
+    let mut m = IntersectionMatrix {
+        cells: [[Dimension::Empty; 3]; 3],
+    };
+    m.raise(Part::Exterior, Part::Exterior, Dimension::Two);
+    add_features(&mut m, a, b, false);
+    add_features(&mut m, b, a, true);
+    match (a, b) {
+        (Shape::Region(r), Shape::Region(s)) => add_areas(&mut m, r, s),
+        (Shape::Region(_), _) => m.raise(Part::Interior, Part::Exterior, Dimension::Two),
+        (_, Shape::Region(_)) => m.raise(Part::Exterior, Part::Interior, Dimension::Two),
+        _ => {}
+    }
+    m
+}
+
+macro_rules! impl_relate {
+    ($a:ident, $b:ident) => {
+        impl<T: Scalar> Relate<$b<T>> for $a<T> {
+            fn relate(&self, other: &$b<T>) -> IntersectionMatrix {
+                relate_shapes(&self.shape(), &other.shape())
+            }
+        }
+    };
+}
+
+for_pairs!(impl_relate;
+    [Point, LineSegment, Polyline, Polygon, Rect] x [Point, LineSegment, Polyline, Polygon, Rect]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Intersects, Touches, Within};
+
+    fn square(lo: f64, hi: f64) -> Vec<Point> {
+        vec![
+            Point { x: lo, y: lo },
+            Point { x: hi, y: lo },
+            Point { x: hi, y: hi },
+            Point { x: lo, y: hi },
+        ]
+    }
+
+    fn line(points: &[(f64, f64)]) -> Polyline {
+        points.iter().map(|&(x, y)| Point { x, y }).collect()
+    }
+
+    #[test]
+    fn test_matches_postgis() {
+        let poly = Polygon::new(square(0.0, 2.0));
+        let corner = Polygon::new(square(2.0, 3.0));
+        let cases = [
+            (relate(&poly, &corner), "FF2F01212"),
+            (
+                relate(&line(&[(-1.0, 1.0), (3.0, 1.0)]), &poly),
+                "101FF0212",
+            ),
+            (relate(&Point { x: 1.0, y: 1.0 }, &poly), "0FFFFF212"),
+            (relate(&Point { x: 0.0, y: 1.0 }, &poly), "F0FFFF212"),
+            (
+                relate(
+                    &line(&[(0.0, 0.0), (1.0, 0.0)]),
+                    &line(&[(1.0, 0.0), (2.0, 1.0)]),
+                ),
+                "FF1F00102",
+            ),
+            (
+                relate(
+                    &line(&[(0.0, 0.0), (2.0, 0.0)]),
+                    &line(&[(1.0, 0.0), (3.0, 0.0)]),
+                ),
+                "1010F0102",
+            ),
+            (relate(&poly, &poly), "2FFF1FFF2"),
+        ];
+        for (m, want) in &cases {
+            assert_eq!(*want, m.to_string());
+        }
+    }
+
+    #[test]
+    fn test_holes() {
+        let hole = square(1.0, 3.0);
+        let frame = Polygon::with_holes(square(0.0, 4.0), vec![hole.clone()]);
+        let plug = Polygon::new(hole);
+        assert_eq!("FF2F112F2", relate(&frame, &plug).to_string());
+        let block = Polygon::new(square(0.0, 4.0));
+        assert_eq!("2FF11F2F2", relate(&frame, &block).to_string());
+        let m = relate(
+            &frame,
+            &Rect::from_corners(Point::origin(), Point { x: 2.0, y: 2.0 }),
+        );
+        assert!(m.is_overlaps() && !m.is_touches())
+    }
+
+    #[test]
+    fn test_derived_predicates() {
+        let poly = Polygon::new(square(0.0, 2.0));
+        let through = line(&[(-1.0, 1.0), (3.0, 1.0)]);
+        assert!(relate(&through, &poly).is_crosses());
+        assert!(relate(&poly, &through).is_crosses());
+        let edge: LineSegment = "(0, 0) -> (2, 0)".parse().unwrap();
+        let m = relate(&edge, &poly);
+        assert!(m.is_touches() && m.is_covered_by() && !m.is_within());
+        assert!(relate(
+            &line(&[(0.0, 0.0), (2.0, 2.0)]),
+            &line(&[(0.0, 2.0), (2.0, 0.0)])
+        )
+        .is_crosses());
+        assert!(relate(&Polygon::new(square(0.5, 1.5)), &poly).is_within());
+        assert!(relate(
+            &poly,
+            &Rect::from_corners(Point::origin(), Point { x: 2.0, y: 2.0 })
+        )
+        .is_equal())
+    }
+
+    #[test]
+    fn test_agrees_with_predicate_traits() {
+        let poly = Polygon::with_holes(square(0.0, 6.0), vec![square(2.0, 4.0)]);
+        for i in 0..8 {
+            for j in 0..8 {
+                let (x, y) = (f64::from(i) - 1.0, f64::from(j) - 1.0);
+                let rect = Rect::from_corners(
+                    Point { x, y },
+                    Point {
+                        x: x + 2.0,
+                        y: y + 1.0,
+                    },
+                );
+                let seg = LineSegment::new(Point { x, y }, Point { x: 3.0, y: y + 1.0 });
+                let m = relate(&rect, &poly);
+                assert_eq!(m.is_intersects(), rect.intersects(&poly));
+                assert_eq!(m.is_touches(), rect.touches(&poly));
+                assert_eq!(m.is_covered_by(), rect.within(&poly));
+                let m = relate(&seg, &poly);
+                assert_eq!(m.is_intersects(), seg.intersects(&poly));
+                assert_eq!(m.is_touches(), seg.touches(&poly));
+                assert_eq!(m.is_covered_by(), seg.within(&poly));
+                assert_eq!(m.transpose(), relate(&poly, &seg));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid DE-9IM pattern")]
+    fn test_malformed_pattern() {
+        relate(&Point::<f64>::origin(), &Point::origin()).matches("T*F");
+    }
+}
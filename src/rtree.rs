@@ -0,0 +1,493 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use num_traits::Float;
+
+use crate::{BoundingRect, Point, Polygon, Rect, Scalar};
+
+/// The most entries a node holds before it is split
+const MAX_ENTRIES: usize = 16;
+/// The fewest entries a node other than the root holds once things are taken out
+const MIN_ENTRIES: usize = 6;
+
+/// An R-tree over rectangles, each carrying a payload, for finding those that meet a
+/// query rectangle. Any shape can be indexed by its bounding rectangle. Each node
+/// holds up to 16 entries with the rectangle around each one, and every leaf is the
+/// same distance from the root.
+///
+/// Loading everything at once with `bulk_load` packs the nodes full by sort-tile-
+/// recursive packing, which is much faster than inserting one at a time and gives
+/// nodes that overlap less.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RTree<T = f64, D = usize> {
+    root: Node<T, D>,
+    len: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node<T, D> {
+    Leaf(Vec<(Rect<T>, D)>),
+    Inner(Vec<(Rect<T>, Node<T, D>)>),
+}
+
+/// The rectangle around all of `entries`, or None if there are none
+fn bounds<T: Scalar, X>(entries: &[(Rect<T>, X)]) -> Option<Rect<T>> {
+    let mut rects = entries.iter().map(|e| e.0);
+    let first = rects.next()?;
+    Some(rects.fold(first, |all, r| all.union(&r)))
+}
+
+fn by_center<T: Scalar>(axis: fn(Point<T>) -> T) -> impl Fn(&Rect<T>, &Rect<T>) -> Ordering {
+    move |a, b| {
+        axis(a.center())
+            .partial_cmp(&axis(b.center()))
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Group `entries` into nodes by sort-tile-recursive packing: sorted into vertical
+/// slices by the x of their centers, and each slice into runs of nodes by y
+fn pack<T: Scalar, X>(mut entries: Vec<(Rect<T>, X)>) -> Vec<Vec<(Rect<T>, X)>> {
+    let nodes = entries.len().div_ceil(MAX_ENTRIES);
+    let slices = Float::ceil(Float::sqrt(nodes as f64)) as usize;
+    let (x, y) = (by_center(|p: Point<T>| p.x), by_center(|p: Point<T>| p.y));
+    entries.sort_by(|a, b| x(&a.0, &b.0));
+    let mut groups = Vec::with_capacity(nodes);
+    let mut rest = entries.into_iter();
+    loop {
+        let mut slice: Vec<_> = rest.by_ref().take(slices * MAX_ENTRIES).collect();
+        if slice.is_empty() {
+            return groups;
+        }
+        slice.sort_by(|a, b| y(&a.0, &b.0));
+        let mut run = slice.into_iter();
+        loop {
+            let group: Vec<_> = run.by_ref().take(MAX_ENTRIES).collect();
+            if group.is_empty() {
+                break;
+            }
+            groups.push(group);
+        }
+    }
+}
+
+/// Split an overfull node, keeping the entries on the low side of its longer spread
+/// of centers and giving back the rest
+fn split<T: Scalar, X>(entries: &mut Vec<(Rect<T>, X)>) -> Vec<(Rect<T>, X)> {
+    let centers = entries.iter().map(|e| e.0.center());
+    let spread = Rect::from_points(centers).unwrap();
+    let axis: fn(Point<T>) -> T = match spread.width() >= spread.height() {
+        true => |p| p.x,
+        false => |p| p.y,
+    };
+    let order = by_center(axis);
+    entries.sort_by(|a, b| order(&a.0, &b.0));
+    entries.split_off(entries.len() / 2)
+}
+
+impl<T: Scalar, D> Node<T, D> {
+    fn len(&self) -> usize {
+        match self {
+            Node::Leaf(entries) => entries.len(),
+            Node::Inner(entries) => entries.len(),
+        }
+    }
+
+    fn bounds(&self) -> Option<Rect<T>> {
+        match self {
+            Node::Leaf(entries) => bounds(entries),
+            Node::Inner(entries) => bounds(entries),
+        }
+    }
+
+    /// Add an entry below this node, giving back the new node split off from it if it
+    /// overflows
+    fn insert(&mut self, rect: Rect<T>, payload: D) -> Option<Node<T, D>> {
+        match self {
+            Node::Leaf(entries) => {
+                entries.push((rect, payload));
+                (entries.len() > MAX_ENTRIES).then(|| Node::Leaf(split(entries)))
+            }
+            Node::Inner(entries) => {
+                // The child that grows least to take the rectangle in, and then the
+                // smallest
+                let growth = |r: &Rect<T>| {
+                    let area = r.area();
+                    (r.union(&rect).area() - area, area)
+                };
+                let (best, _) = entries
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| (i, growth(&e.0)))
+                    .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+                    .unwrap();
+                let child = &mut entries[best];
+                match child.1.insert(rect, payload) {
+                    None => child.0 = child.0.union(&rect),
+                    Some(sibling) => {
+                        child.0 = child.1.bounds().unwrap();
+                        entries.push((sibling.bounds().unwrap(), sibling));
+                    }
+                }
+                (entries.len() > MAX_ENTRIES).then(|| Node::Inner(split(entries)))
+            }
+        }
+    }
+
+    /// Take out an entry for `rect` with `payload`, adding the entries of any node left
+    /// underfull to `orphans`
+    fn remove(&mut self, rect: &Rect<T>, payload: &D, orphans: &mut Vec<(Rect<T>, D)>) -> Option<D>
+    where
+        D: PartialEq,
+    {
+        match self {
+            Node::Leaf(entries) => {
+                let i = entries
+                    .iter()
+                    .position(|e| e.0 == *rect && e.1 == *payload)?;
+                Some(entries.remove(i).1)
+            }
+            Node::Inner(entries) => {
+                for i in 0..entries.len() {
+                    if !entries[i].0.intersects(rect) {
+                        continue;
+                    }
+                    if let Some(removed) = entries[i].1.remove(rect, payload, orphans) {
+                        if entries[i].1.len() < MIN_ENTRIES {
+                            entries.swap_remove(i).1.drain_into(orphans);
+                        } else {
+                            entries[i].0 = entries[i].1.bounds().unwrap();
+                        }
+                        return Some(removed);
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    /// Move every leaf entry below this node into `out`
+    fn drain_into(self, out: &mut Vec<(Rect<T>, D)>) {
+        match self {
+            Node::Leaf(entries) => out.extend(entries),
+            Node::Inner(entries) => entries.into_iter().for_each(|e| e.1.drain_into(out)),
+        }
+    }
+}
+
+impl<T: Scalar, D> Default for RTree<T, D> {
+    fn default() -> Self {
+        RTree::new()
+    }
+}
+
+impl<T: Scalar> RTree<T, usize> {
+    /// The tree over the bounding rectangles of `shapes`, loaded all at once, with the
+    /// index of each shape as its payload. Empty shapes are left out.
+    pub fn from_shapes<S: BoundingRect<T>>(shapes: &[S]) -> RTree<T, usize> {
+        let entries = shapes.iter().enumerate();
+        RTree::bulk_load(
+            entries
+                .filter_map(|(i, s)| Some((s.bounding_rect()?, i)))
+                .collect(),
+        )
+    }
+}
+
+impl<T: Scalar, D> RTree<T, D> {
+    /// An empty tree
+    pub fn new() -> RTree<T, D> {
+        RTree {
+            root: Node::Leaf(Vec::new()),
+            len: 0,
+        }
+    }
+
+    /// The tree over `entries`, each a rectangle with its payload, packed with the
+    /// sort-tile-recursive method in O(n log n) time
+    /// ```
+    /// use rsgeo::{Point, RTree, Rect};
+    /// let square = |x: f64, y: f64| Rect::from_corners(Point{x, y}, Point{x: x + 1.0, y: y + 1.0});
+    /// let entries = (0..1000).map(|i| (square((i % 40) as f64 * 2.0, (i / 40) as f64 * 2.0), i));
+    /// let mut tree = RTree::bulk_load(entries.collect());
+    /// let view = Rect::from_corners(Point{x: 0.5, y: 1.5}, Point{x: 4.5, y: 2.5});
+    /// let mut found: Vec<usize> = tree.query_rect(&view).into_iter().map(|(_, &i)| i).collect();
+    /// found.sort();
+    /// assert_eq!(found, vec![40, 41, 42]);
+    /// assert_eq!(tree.remove(&square(2.0, 2.0), &41), Some(41));
+    /// assert_eq!(tree.query_rect(&view).len(), 2);
+    /// ```
+    pub fn bulk_load(entries: Vec<(Rect<T>, D)>) -> RTree<T, D> {
+        let len = entries.len();
+        let mut nodes: Vec<(Rect<T>, Node<T, D>)> = pack(entries)
+            .into_iter()
+            .map(|group| (bounds(&group).unwrap(), Node::Leaf(group)))
+            .collect();
+        while nodes.len() > 1 {
+            nodes = pack(nodes)
+                .into_iter()
+                .map(|group| (bounds(&group).unwrap(), Node::Inner(group)))
+                .collect();
+        }
+        match nodes.pop() {
+            Some((_, root)) => RTree { root, len },
+            None => RTree::new(),
+        }
+    }
+
+    /// The number of entries in the tree
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the tree has no entries
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The rectangle around every entry, or None if there are none
+    pub fn bounds(&self) -> Option<Rect<T>> {
+        self.root.bounds()
+    }
+
+    /// Add an entry for `rect` with its payload, in O(log n) time
+    pub fn insert(&mut self, rect: Rect<T>, payload: D) {
+        if let Some(sibling) = self.root.insert(rect, payload) {
+            let root = core::mem::replace(&mut self.root, Node::Leaf(Vec::new()));
+            let entries = [root, sibling].map(|node| (node.bounds().unwrap(), node));
+            self.root = Node::Inner(entries.into());
+        }
+        self.len += 1;
+    }
+
+    /// Take out an entry for `rect` with `payload`, giving back its payload, or None
+    /// if there is no such entry. Nodes left with too few entries are dissolved and
+    /// their entries put back in.
+    pub fn remove(&mut self, rect: &Rect<T>, payload: &D) -> Option<D>
+    where
+        D: PartialEq,
+    {
+        let mut orphans = Vec::new();
+        let removed = self.root.remove(rect, payload, &mut orphans)?;
+        self.len -= 1 + orphans.len();
+        loop {
+            match &mut self.root {
+                Node::Inner(entries) if entries.len() == 1 => self.root = entries.pop().unwrap().1,
+                Node::Inner(entries) if entries.is_empty() => self.root = Node::Leaf(Vec::new()),
+                _ => break,
+            }
+        }
+        for (rect, payload) in orphans {
+            self.insert(rect, payload);
+        }
+        Some(removed)
+    }
+
+    /// The entries whose rectangles meet `rect`, including those that only touch it
+    pub fn query_rect(&self, rect: &Rect<T>) -> Vec<(Rect<T>, &D)> {
+        let mut found = Vec::new();
+        self.visit_rect(rect, |r, d| found.push((r, d)));
+        found
+    }
+
+    /// Call `f` on the entries that `query_rect` would give, without gathering them
+    pub(crate) fn visit_rect<'a, F: FnMut(Rect<T>, &'a D)>(&'a self, rect: &Rect<T>, mut f: F) {
+        let mut stack = alloc::vec![&self.root];
+        while let Some(node) = stack.pop() {
+            match node {
+                Node::Leaf(entries) => entries
+                    .iter()
+                    .filter(|e| e.0.intersects(rect))
+                    .for_each(|(r, d)| f(*r, d)),
+                Node::Inner(entries) => stack.extend(
+                    entries
+                        .iter()
+                        .filter(|e| e.0.intersects(rect))
+                        .map(|e| &e.1),
+                ),
+            }
+        }
+    }
+
+    /// The entries whose rectangles meet `rect`, including those that only touch it.
+    /// This is `query_rect` under the name it shares with the other spatial indexes.
+    pub fn range_query(&self, rect: &Rect<T>) -> Vec<(Rect<T>, &D)> {
+        self.query_rect(rect)
+    }
+
+    /// The entries whose rectangles meet `polygon`, including those that only touch
+    /// it. Only the entries that meet the bounding rectangle of the polygon are tested
+    /// against the polygon itself.
+    /// ```
+    /// use rsgeo::{Point, Polygon, RTree, Rect};
+    /// let tree = RTree::from_shapes(&[
+    ///     Rect::from_corners(Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 1.0}),
+    ///     Rect::from_corners(Point{x: 3.0, y: 3.0}, Point{x: 4.0, y: 4.0}),
+    ///     Rect::from_corners(Point{x: 2.0, y: 2.0}, Point{x: 3.0, y: 3.0}),
+    /// ]);
+    /// let triangle = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 0.0, y: 4.0},
+    /// ]);
+    /// // The middle box is in the bounding rectangle but not the triangle, and the last
+    /// // only touches it at a corner
+    /// let mut found: Vec<usize> = tree.range_query_polygon(&triangle).iter().map(|f| *f.1).collect();
+    /// found.sort_unstable();
+    /// assert_eq!(found, vec![0, 2]);
+    /// ```
+    pub fn range_query_polygon(&self, polygon: &Polygon<T>) -> Vec<(Rect<T>, &D)> {
+        let mut found = match polygon.bounding_rect() {
+            Some(rect) => self.query_rect(&rect),
+            None => Vec::new(),
+        };
+        found.retain(|f| f.0.intersects(polygon));
+        found
+    }
+
+    /// Every entry in the tree, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Rect<T>, &D)> + '_ {
+        let mut stack = alloc::vec![&self.root];
+        let mut leaf: core::slice::Iter<'_, (Rect<T>, D)> = [].iter();
+        core::iter::from_fn(move || loop {
+            if let Some((r, d)) = leaf.next() {
+                return Some((*r, d));
+            }
+            match stack.pop()? {
+                Node::Leaf(entries) => leaf = entries.iter(),
+                Node::Inner(entries) => stack.extend(entries.iter().map(|e| &e.1)),
+            }
+        })
+    }
+
+    /// How many levels of nodes there are from the root down to the leaves
+    pub fn height(&self) -> usize {
+        let mut node = &self.root;
+        let mut height = 1;
+        while let Node::Inner(entries) = node {
+            node = &entries[0].1;
+            height += 1;
+        }
+        height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lcg;
+
+    fn boxes(n: usize, seed: u64) -> Vec<Rect> {
+        let mut rng = Lcg::new(seed);
+        let mut next = || rng.unit();
+        (0..n)
+            .map(|_| {
+                let (x, y) = (next() * 100.0, next() * 100.0);
+                let (w, h) = (next() * 3.0, next() * 3.0);
+                Rect::from_corners(Point { x, y }, Point { x: x + w, y: y + h })
+            })
+            .collect()
+    }
+
+    fn check_found(tree: &RTree, rects: &[Rect], alive: &[bool]) {
+        for view in boxes(50, 17).iter().map(|r| r.expand(5.0)) {
+            let mut found: Vec<usize> = tree.query_rect(&view).iter().map(|f| *f.1).collect();
+            found.sort_unstable();
+            let expected: Vec<usize> = (0..rects.len())
+                .filter(|&i| alive[i] && rects[i].intersects(&view))
+                .collect();
+            assert_eq!(expected, found);
+        }
+    }
+
+    #[test]
+    fn test_bulk_load() {
+        let rects = boxes(5000, 1);
+        let tree = RTree::from_shapes(&rects);
+        assert_eq!(5000, tree.len());
+        // Packed full, 5000 entries need only 313 leaves, under 20 nodes, under 2
+        assert_eq!(4, tree.height());
+        check_found(&tree, &rects, &[true; 5000]);
+        assert_eq!(5000, tree.iter().count());
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let rects = boxes(3000, 5);
+        let mut tree = RTree::new();
+        for (i, r) in rects.iter().enumerate() {
+            tree.insert(*r, i);
+        }
+        let mut alive = vec![true; rects.len()];
+        check_found(&tree, &rects, &alive);
+        for i in (0..rects.len()).filter(|i| i % 4 != 1) {
+            assert_eq!(Some(i), tree.remove(&rects[i], &i));
+            alive[i] = false;
+        }
+        assert_eq!(None, tree.remove(&rects[0], &0));
+        assert_eq!(750, tree.len());
+        check_found(&tree, &rects, &alive);
+        for i in (0..rects.len()).filter(|i| i % 4 == 1) {
+            assert_eq!(Some(i), tree.remove(&rects[i], &i));
+        }
+        assert!(tree.is_empty());
+        assert_eq!((None, 1), (tree.bounds(), tree.height()));
+    }
+
+    #[test]
+    fn test_shapes_and_points() {
+        let shapes = vec![
+            crate::Circle::new(Point { x: 0.0, y: 0.0 }, 1.0),
+            crate::Circle::new(Point { x: 4.0, y: 0.0 }, 1.0),
+        ];
+        let tree = RTree::from_shapes(&shapes);
+        // Touching counts as meeting
+        let probe = Rect::from_corners(Point { x: 1.0, y: 0.0 }, Point { x: 2.0, y: 0.5 });
+        assert_eq!(
+            vec![0],
+            tree.query_rect(&probe)
+                .iter()
+                .map(|f| *f.1)
+                .collect::<Vec<_>>()
+        );
+        assert!(RTree::<f64>::bulk_load(vec![])
+            .query_rect(&probe)
+            .is_empty());
+        // Many copies of the same rectangle still split into nodes
+        let mut same = RTree::new();
+        for i in 0..100 {
+            same.insert(probe, i);
+        }
+        assert_eq!(100, same.query_rect(&probe).len());
+        assert!(same.height() > 1);
+    }
+
+    #[test]
+    fn test_polygon_query() {
+        let rects = boxes(2000, 9);
+        let tree = RTree::from_shapes(&rects);
+        let ring = Polygon::with_holes(
+            vec![
+                Point { x: 10.0, y: 10.0 },
+                Point { x: 90.0, y: 20.0 },
+                Point { x: 50.0, y: 90.0 },
+            ],
+            vec![vec![
+                Point { x: 40.0, y: 30.0 },
+                Point { x: 50.0, y: 60.0 },
+                Point { x: 60.0, y: 30.0 },
+            ]],
+        );
+        let mut found: Vec<usize> = tree
+            .range_query_polygon(&ring)
+            .iter()
+            .map(|f| *f.1)
+            .collect();
+        found.sort_unstable();
+        let expected: Vec<usize> = (0..rects.len())
+            .filter(|&i| rects[i].intersects(&ring))
+            .collect();
+        assert_eq!(expected, found);
+        // Boxes wholly in the hole are left out
+        assert!(expected.len() < tree.range_query(&ring.bounding_rect().unwrap()).len());
+        assert!(tree.range_query_polygon(&Polygon::new(vec![])).is_empty());
+    }
+}
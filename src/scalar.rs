@@ -0,0 +1,134 @@
+use core::fmt::{Debug, Display};
+use core::num::ParseFloatError;
+use core::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+use core::str::FromStr;
+
+use approx::{RelativeEq, UlpsEq};
+use num_traits::float::{FloatConst, TotalOrder};
+use num_traits::Float;
+
+/// The floating point type used for coordinates. It is implemented for `f32` and `f64`,
+/// and every geometry type defaults to `f64` when no scalar type is given.
+pub trait Scalar:
+    Float
+    + FloatConst
+    + TotalOrder
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + RelativeEq<Epsilon = Self>
+    + UlpsEq<Epsilon = Self>
+    + FromStr<Err = ParseFloatError>
+    + Debug
+    + Display
+    + Default
+    + Send
+    + Sync
+    + 'static
+{
+    /// The default absolute tolerance used by the `isclose` methods at this precision
+    const DEFAULT_ATOL: Self;
+    /// The default relative tolerance used by the `isclose` methods at this precision
+    const DEFAULT_RTOL: Self;
+
+    /// Convert an `f64` to this scalar type, rounding if needed. This is mostly used for
+    /// constants written as `f64` literals.
+    fn from_f64(v: f64) -> Self;
+
+    /// Convert this scalar to an `f64`. This is exact for both `f32` and `f64`.
+    fn as_f64(self) -> f64;
+
+    /// The raw bit pattern of the value, widened to 64 bits
+    fn to_bits_u64(self) -> u64;
+
+    /// The number of representable values of this type between `self` and `other`. The
+    /// two zeros are zero ulps apart, and any comparison with NaN is `u64::MAX` ulps
+    /// apart.
+    fn ulps_distance(self, other: Self) -> u64;
+}
+
+impl Scalar for f64 {
+    const DEFAULT_ATOL: f64 = crate::ATOL;
+    const DEFAULT_RTOL: f64 = crate::RTOL;
+
+    fn from_f64(v: f64) -> Self {
+        v
+    }
+
+    fn as_f64(self) -> f64 {
+        self
+    }
+
+    fn to_bits_u64(self) -> u64 {
+        self.to_bits()
+    }
+
+    fn ulps_distance(self, other: Self) -> u64 {
+        if self.is_nan() || other.is_nan() {
+            return u64::MAX;
+        }
+        // Map the bit patterns onto integers that are ordered the same way as the floats
+        fn ordered(v: f64) -> i128 {
+            let bits = v.to_bits() as i64;
+            if bits < 0 {
+                i128::from(i64::MIN) - i128::from(bits)
+            } else {
+                i128::from(bits)
+            }
+        }
+        // Both ordered values are within the i64 range, so the difference fits in a u64
+        (ordered(self) - ordered(other)).unsigned_abs() as u64
+    }
+}
+
+impl Scalar for f32 {
+    // f32 carries about 7 significant digits, so the f64 defaults would be far too strict
+    const DEFAULT_ATOL: f32 = 1e-5;
+    const DEFAULT_RTOL: f32 = 1e-4;
+
+    fn from_f64(v: f64) -> Self {
+        v as f32
+    }
+
+    fn as_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    fn to_bits_u64(self) -> u64 {
+        u64::from(self.to_bits())
+    }
+
+    fn ulps_distance(self, other: Self) -> u64 {
+        if self.is_nan() || other.is_nan() {
+            return u64::MAX;
+        }
+        fn ordered(v: f32) -> i64 {
+            let bits = v.to_bits() as i32;
+            if bits < 0 {
+                i64::from(i32::MIN) - i64::from(bits)
+            } else {
+                i64::from(bits)
+            }
+        }
+        (ordered(self) - ordered(other)).unsigned_abs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f32_ulps_distance() {
+        assert_eq!(1, 1.0_f32.ulps_distance(1.0 + f32::EPSILON));
+        assert_eq!(0, (-0.0_f32).ulps_distance(0.0));
+        assert_eq!(u64::MAX, f32::NAN.ulps_distance(1.0))
+    }
+
+    #[test]
+    fn test_conversions_are_exact_for_f32() {
+        let v = 0.1_f32;
+        assert_eq!(v, f32::from_f64(v.as_f64()))
+    }
+}
@@ -0,0 +1,173 @@
+use crate::polygon::ring_edges;
+use crate::{Circle, Grid, GridSpec, MultiPolygon, Point, Polygon, Polyline, Scalar};
+
+/// The distance from a point to the outline of a shape, negative inside shapes that
+/// cover an area and positive outside them. Shapes that cover no area, like lines,
+/// are positive everywhere off them.
+pub trait SignedDistance<T: Scalar> {
+    /// The signed distance from `p` to the shape, which is zero on its outline and
+    /// infinite if the shape is empty
+    fn signed_distance(&self, p: Point<T>) -> T;
+}
+
+impl<T: Scalar> SignedDistance<T> for Circle<T> {
+    fn signed_distance(&self, p: Point<T>) -> T {
+        (p - self.center).magnitude() - self.radius
+    }
+}
+
+impl<T: Scalar> SignedDistance<T> for Polyline<T> {
+    fn signed_distance(&self, p: Point<T>) -> T {
+        match self.points() {
+            [] => T::infinity(),
+            [q] => q.distance(&p),
+            _ => self
+                .segments()
+                .map(|s| s.distance_squared_to_point(p))
+                .fold(T::infinity(), T::min)
+                .sqrt(),
+        }
+    }
+}
+
+impl<T: Scalar> SignedDistance<T> for Polygon<T> {
+    fn signed_distance(&self, p: Point<T>) -> T {
+        if self.vertices().is_empty() {
+            return T::infinity();
+        }
+        let d = self
+            .rings()
+            .flat_map(ring_edges)
+            .map(|e| e.distance_squared_to_point(p))
+            .fold(T::infinity(), T::min)
+            .sqrt();
+        if self.contains(p) {
+            -d
+        } else {
+            d
+        }
+    }
+}
+
+impl<T: Scalar> SignedDistance<T> for MultiPolygon<T> {
+    fn signed_distance(&self, p: Point<T>) -> T {
+        let d = self
+            .polygons()
+            .iter()
+            .map(|poly| poly.signed_distance(p).abs())
+            .fold(T::infinity(), T::min);
+        if self.contains(p) {
+            -d
+        } else {
+            d
+        }
+    }
+}
+
+/// The signed distance field of `shape` over the points of `grid`, holding the exact
+/// signed distance from each point to the shape. This takes time in proportion to the
+/// number of points times the number of edges of the shape, but doesn't pick up the
+/// errors of methods that sweep distances across the grid from one point to the next.
+/// ```
+/// use rsgeo::{sdf, Circle, GridSpec, Point};
+/// let spec = GridSpec{origin: Point{x: -2.0, y: -2.0}, spacing: 1.0, columns: 5, rows: 5};
+/// let field = sdf(&Circle::new(Point::origin(), 1.5), spec);
+/// assert_eq!(field.get(2, 2), -1.5);
+/// assert_eq!(field.get(4, 2), 0.5);
+/// ```
+pub fn sdf<T: Scalar, S: SignedDistance<T> + ?Sized>(shape: &S, grid: GridSpec<T>) -> Grid<T> {
+    Grid::from_fn(grid.origin, grid.spacing, grid.columns, grid.rows, |p| {
+        shape.signed_distance(p)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoundingRect, Distance};
+
+    fn square(lo: f64, hi: f64) -> Vec<Point> {
+        vec![
+            Point { x: lo, y: lo },
+            Point { x: hi, y: lo },
+            Point { x: hi, y: hi },
+            Point { x: lo, y: hi },
+        ]
+    }
+
+    #[test]
+    fn test_polygon_with_hole() {
+        let frame = Polygon::with_holes(square(0.0, 6.0), vec![square(2.0, 4.0)]);
+        let field = sdf(
+            &frame,
+            GridSpec::covering(frame.bounding_rect().unwrap().expand(1.0), 0.5),
+        );
+        assert_eq!(Point { x: -1.0, y: -1.0 }, field.origin());
+        let at = |x: f64, y: f64| field.get((2.0 * (x + 1.0)) as usize, (2.0 * (y + 1.0)) as usize);
+        assert_eq!(-1.0, at(1.0, 3.0));
+        assert_eq!(1.0, at(3.0, 3.0));
+        assert_eq!(0.0, at(2.0, 2.5));
+        assert_eq!(2.0f64.sqrt(), at(7.0, 7.0));
+        assert_eq!(-0.5, at(5.5, 0.5));
+    }
+
+    #[test]
+    fn test_matches_distance_outside() {
+        let shape = MultiPolygon::new(vec![
+            Polygon::new(square(0.0, 1.0)),
+            Polygon::new(vec![
+                Point { x: 2.0, y: 0.0 },
+                Point { x: 4.0, y: 1.0 },
+                Point { x: 2.5, y: 3.0 },
+            ]),
+        ]);
+        let spec = GridSpec {
+            origin: Point { x: -1.0, y: -1.0 },
+            spacing: 0.1,
+            columns: 60,
+            rows: 50,
+        };
+        let field = sdf(&shape, spec);
+        for row in 0..spec.rows {
+            for column in 0..spec.columns {
+                let p = field.point(column, row);
+                let d = field.get(column, row);
+                let outside = shape
+                    .polygons()
+                    .iter()
+                    .map(|poly| p.min_distance(poly).unwrap());
+                let outside = outside.fold(f64::INFINITY, f64::min);
+                // Inside, the distance is to the nearest edge
+                match shape.contains(p) {
+                    true => assert!(d <= 0.0 && outside == 0.0),
+                    false => assert_eq!(outside, d),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lines_and_circles() {
+        let line = Polyline::new(vec![Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 0.0 }]);
+        let spec = GridSpec {
+            origin: Point { x: -2.0, y: -2.0 },
+            spacing: 1.0,
+            columns: 9,
+            rows: 5,
+        };
+        let field = sdf(&line, spec);
+        assert_eq!(2.0, field.get(4, 0));
+        assert_eq!(0.0, field.get(3, 2));
+        assert_eq!(8.0f64.sqrt(), field.get(0, 0));
+        let ring = sdf(&Circle::new(Point { x: 2.0, y: 0.0 }, 2.0), spec);
+        assert!(ring
+            .values()
+            .iter()
+            .zip(field.values())
+            .all(|(r, l)| r <= l));
+        assert_eq!(
+            f64::INFINITY,
+            Polyline::<f64>::new(vec![]).signed_distance(Point::origin())
+        );
+    }
+}
@@ -0,0 +1,670 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::predicates::{orientation, Orientation};
+use crate::{Line, ParseError, Point, Scalar};
+
+/// A straight line segment between two points
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineSegment<T = f64> {
+    p1: Point<T>,
+    p2: Point<T>,
+}
+
+impl<T: Scalar> LineSegment<T> {
+    /// Create the segment running from `p1` to `p2`
+    /// ```
+    /// use rsgeo::{LineSegment, Point};
+    /// let seg = LineSegment::new(Point{x: 0.0, y: 0.0}, Point{x: 3.0, y: 4.0});
+    /// assert_eq!(seg.length(), 5.0);
+    /// ```
+    pub fn new(p1: Point<T>, p2: Point<T>) -> LineSegment<T> {
+        LineSegment { p1, p2 }
+    }
+
+    /// The start point of the segment
+    pub fn p1(&self) -> Point<T> {
+        self.p1
+    }
+
+    /// The end point of the segment
+    pub fn p2(&self) -> Point<T> {
+        self.p2
+    }
+
+    pub fn isclose(&self, other: &LineSegment<T>) -> bool {
+        self.p1.isclose(other.p1) && self.p2.isclose(other.p2)
+    }
+
+    /// Check if both endpoints are close, using the given absolute and relative
+    /// tolerances. See `Point::isclose_with`.
+    pub fn isclose_with(&self, other: &LineSegment<T>, atol: T, rtol: T) -> bool {
+        self.p1.isclose_with(other.p1, atol, rtol) && self.p2.isclose_with(other.p2, atol, rtol)
+    }
+
+    /// The distance between the two endpoints
+    pub fn length(&self) -> T {
+        self.p1.distance(&self.p2)
+    }
+
+    /// The squared length of the segment, avoiding the sqrt
+    pub fn length_squared(&self) -> T {
+        self.p1.distance_squared(&self.p2)
+    }
+
+    /// The point halfway between the two endpoints
+    pub fn midpoint(&self) -> Point<T> {
+        self.p1.midpoint(&self.p2)
+    }
+
+    /// The vector from `p1` to `p2`. It is not normalized, so its magnitude is the length
+    /// of the segment.
+    pub fn direction(&self) -> Point<T> {
+        self.p2 - self.p1
+    }
+
+    /// The slope of the line through the segment, i.e. the change in y per unit of x.
+    /// Vertical segments have a slope of Inf, and zero length segments a slope of NaN.
+    /// ```
+    /// let seg: rsgeo::LineSegment = "(1, 1) -> (3, 0)".parse().unwrap();
+    /// assert_eq!(seg.slope(), -0.5);
+    /// let vertical: rsgeo::LineSegment = "(1, 1) -> (1, -3)".parse().unwrap();
+    /// assert_eq!(vertical.slope(), f64::INFINITY);
+    /// ```
+    pub fn slope(&self) -> T {
+        let d = self.direction();
+        let m = d.y / d.x;
+        if m.is_infinite() {
+            T::infinity()
+        } else {
+            m
+        }
+    }
+
+    /// The same segment, running from `p2` to `p1`
+    pub fn reverse(&self) -> LineSegment<T> {
+        LineSegment::new(self.p2, self.p1)
+    }
+
+    /// Move both endpoints by the vector `offset`
+    pub fn translate(&self, offset: Point<T>) -> LineSegment<T> {
+        LineSegment::new(self.p1 + offset, self.p2 + offset)
+    }
+
+    /// The line of points equidistant from both endpoints. It passes through the
+    /// midpoint and runs 90 degrees counter-clockwise from the segment's direction.
+    /// ```
+    /// use rsgeo::{LineSegment, Point};
+    /// let seg: LineSegment = "(0, 0) -> (4, 0)".parse().unwrap();
+    /// let bisector = seg.perpendicular_bisector();
+    /// assert_eq!(bisector.point(), Point{x: 2.0, y: 0.0});
+    /// assert_eq!(bisector.direction(), Point{x: 0.0, y: 4.0});
+    /// ```
+    pub fn perpendicular_bisector(&self) -> Line<T> {
+        Line::new(self.midpoint(), self.direction().perp())
+    }
+
+    /// Split the segment into two at the point a fraction `t` of the way from `p1` to
+    /// `p2`. `t` is clamped to the range [0, 1].
+    pub fn split_at(&self, t: T) -> (LineSegment<T>, LineSegment<T>) {
+        let mid = self.p1.lerp_clamped(&self.p2, t);
+        (
+            LineSegment::new(self.p1, mid),
+            LineSegment::new(mid, self.p2),
+        )
+    }
+
+    /// Lengthen the segment by moving each endpoint `by_length` further away from the
+    /// other, so the total length grows by twice `by_length`. A negative value shortens
+    /// it. Zero length segments have no direction to extend along, and are returned
+    /// unchanged.
+    /// ```
+    /// let seg: rsgeo::LineSegment = "(1, 0) -> (3, 0)".parse().unwrap();
+    /// assert_eq!(seg.extend(0.5).to_string(), "(0.5, 0.0) -> (3.5, 0.0)");
+    /// ```
+    pub fn extend(&self, by_length: T) -> LineSegment<T> {
+        if self.p1 == self.p2 {
+            return *self;
+        }
+        let step = self.direction().normalize() * by_length;
+        LineSegment::new(self.p1 - step, self.p2 + step)
+    }
+
+    /// Move the segment sideways by `distance`, to the left when looking from `p1`
+    /// towards `p2`. A negative distance moves it to the right. Zero length segments
+    /// have no left side, and are returned unchanged.
+    /// ```
+    /// let seg: rsgeo::LineSegment = "(0, 0) -> (2, 0)".parse().unwrap();
+    /// assert_eq!(seg.offset(1.0).to_string(), "(0.0, 1.0) -> (2.0, 1.0)");
+    /// ```
+    pub fn offset(&self, distance: T) -> LineSegment<T> {
+        if self.p1 == self.p2 {
+            return *self;
+        }
+        self.translate(self.direction().perp().normalize() * distance)
+    }
+
+    /// reflect_point mirrors `p` across the infinite line passing through both ends of
+    /// the segment
+    pub fn reflect_point(&self, p: Point<T>) -> Point<T> {
+        (p - self.p1).reflect(self.p2 - self.p1) + self.p1
+    }
+
+    /// The point on the segment nearest to `p`. Points beyond either end of the segment
+    /// snap to that endpoint.
+    /// ```
+    /// use rsgeo::{LineSegment, Point};
+    /// let seg: LineSegment = "(0, 0) -> (4, 0)".parse().unwrap();
+    /// assert_eq!(seg.closest_point(Point{x: 1.0, y: 3.0}), Point{x: 1.0, y: 0.0});
+    /// assert_eq!(seg.closest_point(Point{x: 9.0, y: 3.0}), Point{x: 4.0, y: 0.0});
+    /// ```
+    pub fn closest_point(&self, p: Point<T>) -> Point<T> {
+        let d = self.direction();
+        let len2 = d.magnitude_squared();
+        if len2 == T::zero() {
+            return self.p1;
+        }
+        let t = (p - self.p1).dot_product(&d) / len2;
+        self.p1.lerp_clamped(&self.p2, t)
+    }
+
+    /// The shortest distance from `p` to any point of the segment
+    pub fn distance_to_point(&self, p: Point<T>) -> T {
+        self.distance_squared_to_point(p).sqrt()
+    }
+
+    /// The squared distance from `p` to the segment, avoiding the sqrt
+    pub fn distance_squared_to_point(&self, p: Point<T>) -> T {
+        self.closest_point(p).distance_squared(&p)
+    }
+
+    /// The minimum distance between two segments, along with the witness points on
+    /// `self` and on `other` that are that distance apart. Intersecting segments are a
+    /// distance of zero apart, and both witnesses are a point they share.
+    /// ```
+    /// use rsgeo::{LineSegment, Point};
+    /// let a: LineSegment = "(0, 0) -> (4, 0)".parse().unwrap();
+    /// let b: LineSegment = "(2, 1) -> (6, 5)".parse().unwrap();
+    /// let (distance, on_a, on_b) = a.distance_to(&b);
+    /// assert_eq!(distance, 1.0);
+    /// assert_eq!(on_a, Point{x: 2.0, y: 0.0});
+    /// assert_eq!(on_b, Point{x: 2.0, y: 1.0});
+    /// ```
+    pub fn distance_to(&self, other: &LineSegment<T>) -> (T, Point<T>, Point<T>) {
+        match self.intersect(other) {
+            SegmentIntersection::Point(p) => return (T::zero(), p, p),
+            SegmentIntersection::Segment(s) => return (T::zero(), s.p1, s.p1),
+            SegmentIntersection::None => {}
+        }
+        // Segments that do not cross are closest at an endpoint of one or the other
+        let candidates = [
+            (self.p1, other.closest_point(self.p1)),
+            (self.p2, other.closest_point(self.p2)),
+            (self.closest_point(other.p1), other.p1),
+            (self.closest_point(other.p2), other.p2),
+        ];
+        let (mut best, mut best_d2) = (candidates[0], T::infinity());
+        for &(a, b) in &candidates {
+            let d2 = a.distance_squared(&b);
+            if d2 < best_d2 {
+                best = (a, b);
+                best_d2 = d2;
+            }
+        }
+        (best_d2.sqrt(), best.0, best.1)
+    }
+
+    /// Whether `p` lies on the segment, to within a distance of `tol`. Points exactly on
+    /// the segment are always accepted, so a `tol` of zero gives the exact test.
+    /// ```
+    /// use rsgeo::{LineSegment, Point};
+    /// let seg: LineSegment = "(0, 0) -> (0.3, 0.9)".parse().unwrap();
+    /// assert!(seg.contains_point(Point{x: 0.1, y: 0.3}, 1e-9));
+    /// assert!(!seg.contains_point(Point{x: 0.4, y: 1.2}, 1e-9));
+    /// ```
+    pub fn contains_point(&self, p: Point<T>, tol: T) -> bool {
+        let within_bounds = p.x >= self.p1.x.min(self.p2.x)
+            && p.x <= self.p1.x.max(self.p2.x)
+            && p.y >= self.p1.y.min(self.p2.y)
+            && p.y <= self.p1.y.max(self.p2.y);
+        if within_bounds && orientation(self.p1, self.p2, p) == Orientation::Collinear {
+            return true;
+        }
+        self.distance_squared_to_point(p) <= tol * tol
+    }
+
+    /// Find where two segments meet. The decision of whether they meet uses exact
+    /// orientation predicates, so touching and collinear cases are classified correctly
+    /// even for nearly parallel segments. When an endpoint of one segment lies on the
+    /// other, that endpoint is returned exactly. Otherwise the crossing point is
+    /// rounded, though never beyond the ends of `self`.
+    /// ```
+    /// use rsgeo::{LineSegment, Point, SegmentIntersection};
+    /// let a: LineSegment = "(0, 0) -> (2, 2)".parse().unwrap();
+    /// let b: LineSegment = "(0, 2) -> (2, 0)".parse().unwrap();
+    /// assert_eq!(a.intersect(&b), SegmentIntersection::Point(Point{x: 1.0, y: 1.0}));
+    ///
+    /// let c: LineSegment = "(1, 1) -> (5, 5)".parse().unwrap();
+    /// assert_eq!(
+    ///     a.intersect(&c),
+    ///     SegmentIntersection::Segment("(1, 1) -> (2, 2)".parse().unwrap())
+    /// );
+    /// ```
+    pub fn intersect(&self, other: &LineSegment<T>) -> SegmentIntersection<T> {
+        let (p1, p2, q1, q2) = (self.p1, self.p2, other.p1, other.p2);
+        let o1 = orientation(p1, p2, q1);
+        let o2 = orientation(p1, p2, q2);
+        let o3 = orientation(q1, q2, p1);
+        let o4 = orientation(q1, q2, p2);
+
+        use Orientation::Collinear;
+        if o1 == Collinear && o2 == Collinear && o3 == Collinear && o4 == Collinear {
+            return self.collinear_overlap(other);
+        }
+        if o1 == o2 || o3 == o4 {
+            return SegmentIntersection::None;
+        }
+
+        let p = if o1 == Collinear {
+            q1
+        } else if o2 == Collinear {
+            q2
+        } else if o3 == Collinear {
+            p1
+        } else if o4 == Collinear {
+            p2
+        } else {
+            let d = self.direction();
+            let t = (q1 - p1).cross(&other.direction()) / d.cross(&other.direction());
+            if t.is_finite() {
+                p1 + d * t.max(T::zero()).min(T::one())
+            } else {
+                // Too close to parallel for the division, so the crossing is taken to
+                // be the end nearest the other segment
+                let ends = [(p1, other), (p2, other), (q1, self), (q2, self)];
+                let off = |&(p, s): &(Point<T>, &LineSegment<T>)| s.distance_squared_to_point(p);
+                ends.iter()
+                    .min_by(|a, b| {
+                        off(a)
+                            .partial_cmp(&off(b))
+                            .unwrap_or(core::cmp::Ordering::Equal)
+                    })
+                    .map_or(p1, |&(p, _)| p)
+            }
+        };
+        SegmentIntersection::Point(p)
+    }
+
+    /// The overlap of two segments that lie on the same line. Collinear points are
+    /// ordered along the line by comparing their coordinates lexicographically, which
+    /// needs no arithmetic and so is exact.
+    fn collinear_overlap(&self, other: &LineSegment<T>) -> SegmentIntersection<T> {
+        let key = |p: Point<T>| (p.x, p.y);
+        let sorted = |s: &LineSegment<T>| {
+            if key(s.p1) <= key(s.p2) {
+                (s.p1, s.p2)
+            } else {
+                (s.p2, s.p1)
+            }
+        };
+        let (a_lo, a_hi) = sorted(self);
+        let (b_lo, b_hi) = sorted(other);
+        let lo = if key(a_lo) >= key(b_lo) { a_lo } else { b_lo };
+        let hi = if key(a_hi) <= key(b_hi) { a_hi } else { b_hi };
+
+        if key(lo) > key(hi) {
+            SegmentIntersection::None
+        } else if lo == hi {
+            SegmentIntersection::Point(lo)
+        } else if key(self.p1) <= key(self.p2) {
+            SegmentIntersection::Segment(LineSegment::new(lo, hi))
+        } else {
+            // Keep the direction of `self`
+            SegmentIntersection::Segment(LineSegment::new(hi, lo))
+        }
+    }
+}
+
+/// The result of `LineSegment::intersect`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SegmentIntersection<T = f64> {
+    /// The segments do not meet
+    None,
+    /// The segments cross or touch at a single point
+    Point(Point<T>),
+    /// The segments are collinear and share this piece, which runs in the same
+    /// direction as the segment `intersect` was called on
+    Segment(LineSegment<T>),
+}
+
+impl<T: Scalar> fmt::Display for LineSegment<T> {
+    /// Format a segment as `(x1, y1) -> (x2, y2)`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.p1, f)?;
+        f.write_str(" -> ")?;
+        fmt::Display::fmt(&self.p2, f)
+    }
+}
+
+impl<T: Scalar> FromStr for LineSegment<T> {
+    type Err = ParseError;
+
+    /// Parse a segment from two points separated by `->`, where each point is in one of
+    /// the forms accepted by `Point::from_str`
+    /// ```
+    /// let seg: rsgeo::LineSegment = "(0, 0) -> 3 4".parse().unwrap();
+    /// assert_eq!(seg.to_string(), "(0.0, 0.0) -> (3.0, 4.0)");
+    ///```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (p1, p2) = s.split_once("->").ok_or(ParseError::MissingSeparator)?;
+        Ok(LineSegment {
+            p1: p1.parse()?,
+            p2: p2.parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_from_str() {
+        let seg: LineSegment = "1,2->(3, 4)".parse().unwrap();
+        let expected = LineSegment::new(Point { x: 1.0, y: 2.0 }, Point { x: 3.0, y: 4.0 });
+        assert_eq!(expected, seg);
+        assert_eq!(
+            Err(ParseError::MissingSeparator),
+            "1 2 3 4".parse::<LineSegment>()
+        )
+    }
+
+    #[test]
+    fn test_accessors() {
+        let p1 = Point { x: 1.0, y: 2.0 };
+        let p2 = Point { x: 4.0, y: -2.0 };
+        let seg = LineSegment::new(p1, p2);
+        assert_eq!(p1, seg.p1());
+        assert_eq!(p2, seg.p2());
+        assert_eq!(Point { x: 3.0, y: -4.0 }, seg.direction());
+        assert_eq!(Point { x: 2.5, y: 0.0 }, seg.midpoint())
+    }
+
+    #[test]
+    fn test_length() {
+        let seg = LineSegment::new(Point { x: 1.0, y: 1.0 }, Point { x: 4.0, y: 5.0 });
+        assert_eq!(5.0, seg.length());
+        assert_eq!(25.0, seg.length_squared());
+        assert_eq!(seg.length(), seg.reverse().length())
+    }
+
+    #[test]
+    fn test_slope_is_independent_of_direction() {
+        let seg: LineSegment = "(0, 0) -> (2, 1)".parse().unwrap();
+        assert_eq!(0.5, seg.slope());
+        assert_eq!(0.5, seg.reverse().slope());
+        let vertical: LineSegment = "(2, 3) -> (2, 1)".parse().unwrap();
+        assert_eq!(f64::INFINITY, vertical.slope());
+        assert_eq!(f64::INFINITY, vertical.reverse().slope());
+        let degenerate: LineSegment = "(2, 3) -> (2, 3)".parse().unwrap();
+        assert!(degenerate.slope().is_nan())
+    }
+
+    #[test]
+    fn test_reverse_and_translate() {
+        let seg: LineSegment = "(0, 0) -> (1, 2)".parse().unwrap();
+        let rev = seg.reverse();
+        assert_eq!(seg.p1(), rev.p2());
+        assert_eq!(seg.p2(), rev.p1());
+        assert_eq!(seg, rev.reverse());
+        let moved = seg.translate(Point { x: -1.0, y: 3.0 });
+        assert_eq!("(-1.0, 3.0) -> (0.0, 5.0)", moved.to_string());
+        assert_eq!(seg.direction(), moved.direction())
+    }
+
+    #[test]
+    fn test_closest_point_clamps_to_endpoints() {
+        let seg: LineSegment = "(1, 1) -> (3, 3)".parse().unwrap();
+        assert_eq!(seg.p1(), seg.closest_point(Point { x: -4.0, y: 0.0 }));
+        assert_eq!(seg.p2(), seg.closest_point(Point { x: 3.0, y: 10.0 }));
+        let mid = seg.closest_point(Point { x: 1.0, y: 3.0 });
+        assert!(mid.isclose(Point { x: 2.0, y: 2.0 }))
+    }
+
+    #[test]
+    fn test_distance_to_point() {
+        let seg: LineSegment = "(0, 0) -> (0, 10)".parse().unwrap();
+        assert_eq!(3.0, seg.distance_to_point(Point { x: 3.0, y: 4.0 }));
+        // Past the end, the distance is to the endpoint
+        assert_eq!(5.0, seg.distance_to_point(Point { x: 3.0, y: 14.0 }));
+        assert_eq!(
+            25.0,
+            seg.distance_squared_to_point(Point { x: 3.0, y: 14.0 })
+        );
+        assert_eq!(0.0, seg.distance_to_point(Point { x: 0.0, y: 7.0 }))
+    }
+
+    #[test]
+    fn test_closest_point_degenerate_segment() {
+        let p = Point { x: 2.0, y: -1.0 };
+        let seg = LineSegment::new(p, p);
+        assert_eq!(p, seg.closest_point(Point { x: 5.0, y: 3.0 }));
+        assert_eq!(5.0, seg.distance_to_point(Point { x: 5.0, y: 3.0 }))
+    }
+
+    #[test]
+    fn test_distance_to_parallel_segments() {
+        let a: LineSegment = "(0, 0) -> (4, 0)".parse().unwrap();
+        let b: LineSegment = "(6, 3) -> (10, 3)".parse().unwrap();
+        let (d, on_a, on_b) = a.distance_to(&b);
+        assert!(crate::scalar_isclose(13.0_f64.sqrt(), d));
+        assert_eq!(a.p2(), on_a);
+        assert_eq!(b.p1(), on_b);
+        let (d_rev, on_b_rev, on_a_rev) = b.distance_to(&a);
+        assert_eq!(d, d_rev);
+        assert_eq!((on_a, on_b), (on_a_rev, on_b_rev))
+    }
+
+    #[test]
+    fn test_distance_to_intersecting_is_zero() {
+        let a: LineSegment = "(0, 0) -> (2, 2)".parse().unwrap();
+        let b: LineSegment = "(0, 2) -> (2, 0)".parse().unwrap();
+        let (d, on_a, on_b) = a.distance_to(&b);
+        assert_eq!(0.0, d);
+        assert_eq!(Point { x: 1.0, y: 1.0 }, on_a);
+        assert_eq!(on_a, on_b);
+        let overlapping: LineSegment = "(1, 1) -> (3, 3)".parse().unwrap();
+        assert_eq!(0.0, a.distance_to(&overlapping).0)
+    }
+
+    #[test]
+    fn test_distance_to_witness_in_middle_of_other() {
+        let a: LineSegment = "(-5, 2) -> (5, 2)".parse().unwrap();
+        let b: LineSegment = "(0, 1) -> (1, -3)".parse().unwrap();
+        let (d, on_a, on_b) = a.distance_to(&b);
+        assert_eq!(1.0, d);
+        assert_eq!(Point { x: 0.0, y: 2.0 }, on_a);
+        assert_eq!(b.p1(), on_b)
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let seg: LineSegment = "(1, 1) -> (5, 3)".parse().unwrap();
+        assert!(seg.contains_point(Point { x: 3.0, y: 2.0 }, 0.0));
+        assert!(seg.contains_point(seg.p1(), 0.0));
+        assert!(seg.contains_point(seg.p2(), 0.0));
+        // On the line, but past the end
+        assert!(!seg.contains_point(Point { x: 7.0, y: 4.0 }, 0.0));
+        assert!(!seg.contains_point(Point { x: 3.0, y: 2.1 }, 0.0));
+        assert!(seg.contains_point(Point { x: 3.0, y: 2.1 }, 0.1))
+    }
+
+    #[test]
+    fn test_intersect_crossing() {
+        let a: LineSegment = "(0, 0) -> (4, 2)".parse().unwrap();
+        let b: LineSegment = "(0, 2) -> (4, 0)".parse().unwrap();
+        assert_eq!(
+            SegmentIntersection::Point(Point { x: 2.0, y: 1.0 }),
+            a.intersect(&b)
+        );
+        assert_eq!(a.intersect(&b), b.intersect(&a))
+    }
+
+    #[test]
+    fn test_intersect_touching_endpoint_is_exact() {
+        let a: LineSegment = "(0, 0) -> (3, 0.1)".parse().unwrap();
+        let b = LineSegment::new(a.p2(), Point { x: 1.0, y: 7.0 });
+        assert_eq!(SegmentIntersection::Point(a.p2()), a.intersect(&b));
+        // T junction, where an endpoint of b lies on the middle of c
+        let c: LineSegment = "(-1, -1) -> (1, 1)".parse().unwrap();
+        let d: LineSegment = "(0, 0) -> (1, -5)".parse().unwrap();
+        assert_eq!(
+            SegmentIntersection::Point(Point { x: 0.0, y: 0.0 }),
+            c.intersect(&d)
+        )
+    }
+
+    #[test]
+    fn test_intersect_nearly_parallel() {
+        // Close enough to parallel that the crossing cannot be found by division
+        let a = LineSegment::new(
+            Point {
+                x: 6.35333290164937,
+                y: 3.8630898459983682,
+            },
+            Point {
+                x: 2.7008371633833534,
+                y: 2.795538288049096,
+            },
+        );
+        let b = LineSegment::new(
+            Point {
+                x: 2.303609715409264,
+                y: 2.679436638291588,
+            },
+            Point {
+                x: 6.112739169671374,
+                y: 3.7927691031936486,
+            },
+        );
+        for hit in [a.intersect(&b), b.intersect(&a)] {
+            match hit {
+                SegmentIntersection::Point(p) => {
+                    assert!(a.distance_to_point(p) < 1e-12 && b.distance_to_point(p) < 1e-12)
+                }
+                other => panic!("expected a crossing, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_intersect_disjoint() {
+        let a: LineSegment = "(0, 0) -> (1, 1)".parse().unwrap();
+        // Parallel
+        let b: LineSegment = "(0, 1) -> (1, 2)".parse().unwrap();
+        assert_eq!(SegmentIntersection::None, a.intersect(&b));
+        // The lines cross, but beyond the end of `a`
+        let c: LineSegment = "(3, 0) -> (0, 3)".parse().unwrap();
+        assert_eq!(SegmentIntersection::None, a.intersect(&c));
+        // Collinear with a gap
+        let d: LineSegment = "(2, 2) -> (3, 3)".parse().unwrap();
+        assert_eq!(SegmentIntersection::None, a.intersect(&d))
+    }
+
+    #[test]
+    fn test_intersect_collinear_overlap_keeps_direction() {
+        let a: LineSegment = "(4, 2) -> (0, 0)".parse().unwrap();
+        let b: LineSegment = "(2, 1) -> (6, 3)".parse().unwrap();
+        let want: LineSegment = "(4, 2) -> (2, 1)".parse().unwrap();
+        assert_eq!(SegmentIntersection::Segment(want), a.intersect(&b));
+        assert_eq!(
+            SegmentIntersection::Segment(want.reverse()),
+            b.intersect(&a)
+        );
+        // Collinear segments that only share an endpoint
+        let c: LineSegment = "(4, 2) -> (8, 4)".parse().unwrap();
+        assert_eq!(
+            SegmentIntersection::Point(Point { x: 4.0, y: 2.0 }),
+            a.intersect(&c)
+        )
+    }
+
+    #[test]
+    fn test_intersect_degenerate() {
+        let a: LineSegment = "(0, 0) -> (2, 2)".parse().unwrap();
+        let on = LineSegment::new(Point { x: 1.0, y: 1.0 }, Point { x: 1.0, y: 1.0 });
+        assert_eq!(
+            SegmentIntersection::Point(Point { x: 1.0, y: 1.0 }),
+            a.intersect(&on)
+        );
+        assert_eq!(
+            SegmentIntersection::Point(Point { x: 1.0, y: 1.0 }),
+            on.intersect(&a)
+        );
+        let off = LineSegment::new(Point { x: 1.0, y: 0.0 }, Point { x: 1.0, y: 0.0 });
+        assert_eq!(SegmentIntersection::None, a.intersect(&off));
+        assert_eq!(SegmentIntersection::None, off.intersect(&a))
+    }
+
+    #[test]
+    fn test_perpendicular_bisector_is_equidistant() {
+        let seg: LineSegment = "(1, 2) -> (4, -3)".parse().unwrap();
+        let bisector = seg.perpendicular_bisector();
+        for &t in &[-3.0, 0.0, 0.5, 10.0] {
+            let p = bisector.point() + bisector.direction() * t;
+            assert!(crate::scalar_isclose(
+                p.distance(&seg.p1()),
+                p.distance(&seg.p2())
+            ))
+        }
+    }
+
+    #[test]
+    fn test_split_at() {
+        let seg: LineSegment = "(0, 0) -> (4, 8)".parse().unwrap();
+        let (a, b) = seg.split_at(0.25);
+        assert_eq!(seg.p1(), a.p1());
+        assert_eq!(Point { x: 1.0, y: 2.0 }, a.p2());
+        assert_eq!(a.p2(), b.p1());
+        assert_eq!(seg.p2(), b.p2());
+        // Out of range values are clamped
+        let (a, b) = seg.split_at(2.0);
+        assert_eq!(seg, a);
+        assert_eq!(0.0, b.length())
+    }
+
+    #[test]
+    fn test_extend_and_offset() {
+        let seg: LineSegment = "(0, 0) -> (3, 4)".parse().unwrap();
+        let longer = seg.extend(2.5);
+        assert!(crate::scalar_isclose(10.0, longer.length()));
+        assert!(longer.midpoint().isclose(seg.midpoint()));
+        let shorter = seg.extend(-1.0);
+        assert!(crate::scalar_isclose(3.0, shorter.length()));
+
+        let left = seg.offset(5.0);
+        assert!(left.p1().isclose(Point { x: -4.0, y: 3.0 }));
+        assert!(crate::scalar_isclose(
+            5.0,
+            Line::from(seg).distance_to_point(left.p2())
+        ));
+        let right = seg.offset(-5.0);
+        assert!(right.p1().isclose(Point { x: 4.0, y: -3.0 }));
+
+        let point = LineSegment::new(Point { x: 1.0, y: 1.0 }, Point { x: 1.0, y: 1.0 });
+        assert_eq!(point, point.extend(1.0));
+        assert_eq!(point, point.offset(1.0))
+    }
+
+    #[test]
+    fn test_segment_reflect_point() {
+        // The horizontal line y = 1
+        let seg = LineSegment::new(Point { x: 0.0, y: 1.0 }, Point { x: 2.0, y: 1.0 });
+        let p = Point { x: 5.0, y: 4.0 };
+        assert!(seg.reflect_point(p).isclose(Point { x: 5.0, y: -2.0 }));
+        // Points on the line are left in place
+        let on = Point { x: -3.0, y: 1.0 };
+        assert!(seg.reflect_point(on).isclose(on))
+    }
+}
@@ -0,0 +1,113 @@
+//! A compact serde representation that stores a point as a `[x, y]` array instead of a
+//! `{"x": .., "y": ..}` map. Use it on fields with `#[serde(with = "...")]`:
+//! ```
+//! use rsgeo::Point;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Waypoint {
+//!     #[serde(with = "rsgeo::serde_compact")]
+//!     position: Point,
+//!     #[serde(with = "rsgeo::serde_compact::vec")]
+//!     path: Vec<Point>,
+//! }
+//!
+//! let w = Waypoint {
+//!     position: Point{x: 1.0, y: 2.0},
+//!     path: vec![Point{x: 0.0, y: 0.0}, Point{x: 1.5, y: 2.0}],
+//! };
+//! let json = serde_json::to_string(&w).unwrap();
+//! assert_eq!(json, r#"{"position":[1.0,2.0],"path":[[0.0,0.0],[1.5,2.0]]}"#);
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Point, Scalar};
+
+pub fn serialize<T, S>(p: &Point<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Scalar + Serialize,
+    S: Serializer,
+{
+    [p.x, p.y].serialize(serializer)
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Point<T>, D::Error>
+where
+    T: Scalar + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    <[T; 2]>::deserialize(deserializer).map(Point::from)
+}
+
+/// The compact representation for a `Vec` of points, such as a ring or a path
+#[cfg(feature = "alloc")]
+pub mod vec {
+    use alloc::vec::Vec;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{Point, Scalar};
+
+    pub fn serialize<T, S>(points: &[Point<T>], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Scalar + Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(points.iter().map(|p| [p.x, p.y]))
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vec<Point<T>>, D::Error>
+    where
+        T: Scalar + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let coords = Vec::<[T; 2]>::deserialize(deserializer)?;
+        Ok(coords.into_iter().map(Point::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IPolygon, LineSegment, OrderedPoint, Point, Polygon};
+
+    #[test]
+    fn test_default_representation_round_trips() {
+        let seg: LineSegment = "(1, 2) -> (3.5, -4)".parse().unwrap();
+        let json = serde_json::to_string(&seg).unwrap();
+        assert_eq!(r#"{"p1":{"x":1.0,"y":2.0},"p2":{"x":3.5,"y":-4.0}}"#, json);
+        assert_eq!(seg, serde_json::from_str(&json).unwrap())
+    }
+
+    #[test]
+    fn test_ordered_point_canonicalizes_on_deserialize() {
+        let p: OrderedPoint = serde_json::from_str(r#"{"x":-0.0,"y":1.0}"#).unwrap();
+        assert_eq!(OrderedPoint::new(Point { x: 0.0, y: 1.0 }), p);
+        assert!(p.point().x.is_sign_positive())
+    }
+
+    #[test]
+    fn test_compact_rejects_wrong_length() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Wrapper(#[serde(with = "crate::serde_compact")] Point<f32>);
+
+        let w: Wrapper = serde_json::from_str("[1.5, 2]").unwrap();
+        assert_eq!(Point { x: 1.5, y: 2.0 }, w.0);
+        assert!(serde_json::from_str::<Wrapper>("[1, 2, 3]").is_err())
+    }
+
+    #[test]
+    fn test_integer_polygon_round_trips() {
+        let poly = IPolygon::new(vec![(0, 0).into(), (4, 0).into(), (0, 3).into()]);
+        let json = serde_json::to_string(&poly).unwrap();
+        assert_eq!(poly, serde_json::from_str(&json).unwrap())
+    }
+
+    #[test]
+    fn test_polygon_holes_default_to_empty() {
+        let json = r#"{"vertices":[{"x":0.0,"y":0.0},{"x":4.0,"y":0.0},{"x":0.0,"y":3.0}]}"#;
+        let poly: Polygon = serde_json::from_str(json).unwrap();
+        assert!(poly.holes().is_empty());
+        assert_eq!(6.0, poly.area())
+    }
+}
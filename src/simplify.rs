@@ -0,0 +1,766 @@
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{
+    HashGrid, LineSegment, OrderedPoint, Point, Polygon, Polyline, Rect, Scalar,
+    SegmentIntersection,
+};
+
+impl<T: Scalar> Polyline<T> {
+    /// The polyline thinned by the Douglas–Peucker algorithm, keeping only the points
+    /// needed to stay within `epsilon` of every point left out. The first and last
+    /// points are always kept. Panics if `epsilon` is negative.
+    /// ```
+    /// use rsgeo::{Point, Polyline};
+    /// let trace = Polyline::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.1}, Point{x: 2.0, y: -0.1},
+    ///     Point{x: 3.0, y: 5.0}, Point{x: 4.0, y: 6.0}, Point{x: 5.0, y: 7.0},
+    /// ]);
+    /// assert_eq!(trace.simplify_dp(0.5).points(), &[
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: -0.1}, Point{x: 3.0, y: 5.0}, Point{x: 5.0, y: 7.0},
+    /// ]);
+    /// ```
+    pub fn simplify_dp(&self, epsilon: T) -> Polyline<T> {
+        self.simplify_dp_indices(epsilon)
+            .into_iter()
+            .map(|i| self.points()[i])
+            .collect()
+    }
+
+    /// The indices of the points that `simplify_dp` keeps, in order. The spans still to
+    /// be split are kept on a stack of their own, so a trace of any length can be
+    /// simplified without running out of call stack.
+    pub fn simplify_dp_indices(&self, epsilon: T) -> Vec<usize> {
+        let points = self.points();
+        check_epsilon(epsilon);
+        let n = points.len();
+        if n < 3 {
+            return (0..n).collect();
+        }
+        let mut kept = alloc::vec![false; n];
+        kept[0] = true;
+        kept[n - 1] = true;
+        let mut spans = alloc::vec![(0, n - 1)];
+        while let Some((start, end)) = spans.pop() {
+            if let Some(split) = farthest(points, start, end, epsilon) {
+                kept[split] = true;
+                spans.push((split, end));
+                spans.push((start, split));
+            }
+        }
+        (0..n).filter(|&i| kept[i]).collect()
+    }
+
+    /// The same indices as `simplify_dp_indices`, found by splitting each span with a
+    /// recursive call. This is the textbook form of the algorithm, but the depth of the
+    /// recursion can reach the number of points on a trace that bends the same way
+    /// throughout.
+    pub fn simplify_dp_indices_recursive(&self, epsilon: T) -> Vec<usize> {
+        let points = self.points();
+        check_epsilon(epsilon);
+        let n = points.len();
+        if n < 3 {
+            return (0..n).collect();
+        }
+        let mut kept = alloc::vec![0];
+        split_dp(points, 0, n - 1, epsilon, &mut kept);
+        kept.push(n - 1);
+        kept
+    }
+
+    /// The polyline thinned by the Visvalingam–Whyatt algorithm, which keeps taking out
+    /// the point that makes the smallest triangle with its neighbors until every point
+    /// left makes one of at least `epsilon_area`. This is the same as keeping the points
+    /// whose `effective_areas` are at least `epsilon_area`. The first and last points are
+    /// always kept. Panics if `epsilon_area` is negative.
+    /// ```
+    /// use rsgeo::{Point, Polyline};
+    /// let coast = Polyline::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.2}, Point{x: 2.0, y: 0.0},
+    ///     Point{x: 3.0, y: 3.0}, Point{x: 4.0, y: 0.0},
+    /// ]);
+    /// assert_eq!(coast.simplify_vw(1.0).points(), &[
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 3.0, y: 3.0}, Point{x: 4.0, y: 0.0},
+    /// ]);
+    /// ```
+    pub fn simplify_vw(&self, epsilon_area: T) -> Polyline<T> {
+        assert!(
+            epsilon_area >= T::zero(),
+            "epsilon_area must not be negative, got {}",
+            epsilon_area
+        );
+        self.points()
+            .iter()
+            .zip(self.effective_areas())
+            .filter(|&(_, area)| area >= epsilon_area)
+            .map(|(&p, _)| p)
+            .collect()
+    }
+
+    /// The effective area of each point, which is the area of the triangle it makes
+    /// with its neighbors at the time the Visvalingam–Whyatt algorithm takes it out, or
+    /// the area at which the last point before it was taken out if that is larger. The
+    /// areas only grow as points are taken out, so the points kept at any threshold are
+    /// those whose areas reach it, and one pass can serve every threshold, such as one
+    /// for each zoom level of a map. The first and last points are given infinite area.
+    /// ```
+    /// use rsgeo::{Point, Polyline};
+    /// let coast = Polyline::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.2}, Point{x: 2.0, y: 0.0},
+    ///     Point{x: 3.0, y: 3.0}, Point{x: 4.0, y: 0.0},
+    /// ]);
+    /// let areas = coast.effective_areas();
+    /// assert_eq!(areas[1..4], [0.2, 3.0, 6.0]);
+    /// assert!(areas[0] == f64::INFINITY && areas[4] == f64::INFINITY);
+    /// ```
+    pub fn effective_areas(&self) -> Vec<T> {
+        let points = self.points();
+        let n = points.len();
+        let mut areas = alloc::vec![T::infinity(); n];
+        if n < 3 {
+            return areas;
+        }
+        let mut prev: Vec<usize> = (0..n).map(|i| i.wrapping_sub(1)).collect();
+        let mut next: Vec<usize> = (1..=n).collect();
+        // Each point is given a new entry on the heap whenever its triangle changes, and
+        // the entries made before that are passed over when they come off
+        let mut current = alloc::vec![T::infinity(); n];
+        let mut heap = BinaryHeap::new();
+        for i in 1..n - 1 {
+            current[i] = triangle_area(points[i - 1], points[i], points[i + 1]);
+            heap.push(Corner {
+                area: current[i],
+                vertex: i,
+            });
+        }
+        let mut floor = T::zero();
+        while let Some(Corner { area, vertex }) = heap.pop() {
+            if area != current[vertex] || areas[vertex].is_finite() {
+                continue;
+            }
+            floor = floor.max(area);
+            areas[vertex] = floor;
+            let (before, after) = (prev[vertex], next[vertex]);
+            next[before] = after;
+            prev[after] = before;
+            for i in [before, after] {
+                if i != 0 && i != n - 1 {
+                    current[i] = triangle_area(points[prev[i]], points[i], points[next[i]]);
+                    heap.push(Corner {
+                        area: current[i],
+                        vertex: i,
+                    });
+                }
+            }
+        }
+        areas
+    }
+}
+
+/// The polygons of a coverage, such as the regions of a country, each thinned by the
+/// Douglas–Peucker algorithm to within `epsilon` while keeping the coverage whole.
+/// Neighboring polygons are expected to share the exact vertices along the boundary
+/// between them. The rings are cut into arcs at the points where three or more of
+/// them meet, each arc is simplified once, and the simplified arcs are put back
+/// together, so each neighbor gets the same new boundary and no gaps or overlaps open
+/// up between them. A shortcut is only taken if it would neither cross nor touch
+/// another arc, nor pass over any vertex of one, so rings cannot come to cross each
+/// other or collapse, and islands and holes stay on their side of the boundaries
+/// around them. Panics if `epsilon` is negative.
+/// ```
+/// use rsgeo::{simplify_coverage, Point, Polygon};
+/// let p = |x: f64, y: f64| Point{x, y};
+/// // Two fields either side of a slightly crooked fence from (2, 0) to (2, 4)
+/// let fence = [p(2.0, 0.0), p(2.1, 1.0), p(1.9, 2.0), p(2.1, 3.0), p(2.0, 4.0)];
+/// let mut west = vec![p(0.0, 4.0), p(0.0, 0.0)];
+/// west.extend(&fence);
+/// let mut east = vec![p(4.0, 0.0), p(4.0, 4.0)];
+/// east.extend(fence.iter().rev());
+/// let fields = vec![Polygon::new(west), Polygon::new(east)];
+/// let simple = simplify_coverage(&fields, 0.5);
+/// assert_eq!(simple[0].vertices(), &[p(0.0, 4.0), p(0.0, 0.0), p(2.0, 0.0), p(2.0, 4.0)]);
+/// assert_eq!(simple[1].vertices(), &[p(4.0, 0.0), p(4.0, 4.0), p(2.0, 4.0), p(2.0, 0.0)]);
+/// ```
+pub fn simplify_coverage<T: Scalar>(polygons: &[Polygon<T>], epsilon: T) -> Vec<Polygon<T>> {
+    check_epsilon(epsilon);
+    let rings: Vec<Vec<Vec<Point<T>>>> = polygons
+        .iter()
+        .map(|polygon| polygon.rings().map(clean_ring).collect())
+        .collect();
+    let nodes = nodes(rings.iter().flatten());
+    let mut arcs = Arcs::default();
+    let pieces: Vec<Vec<Vec<(usize, bool)>>> = rings
+        .iter()
+        .map(|polygon| {
+            polygon
+                .iter()
+                .map(|ring| arcs.split(ring, &nodes))
+                .collect()
+        })
+        .collect();
+    let kept = arcs.simplify(epsilon);
+    rings
+        .iter()
+        .zip(&pieces)
+        .map(|(polygon, pieces)| {
+            let mut rings = polygon.iter().zip(pieces).map(|(ring, pieces)| {
+                if ring.len() < 3 {
+                    return ring.clone();
+                }
+                let mut joined = Vec::new();
+                for &(arc, reversed) in pieces {
+                    let mut points: Vec<Point<T>> =
+                        kept[arc].iter().map(|&i| arcs.points[arc][i]).collect();
+                    if reversed {
+                        points.reverse();
+                    }
+                    points.pop();
+                    joined.extend(points);
+                }
+                // Start where the ring did, if that point was kept
+                if let Some(at) = joined.iter().position(|&p| p == ring[0]) {
+                    joined.rotate_left(at);
+                }
+                joined
+            });
+            let exterior = rings.next().unwrap_or_default();
+            Polygon::with_holes(exterior, rings.collect())
+        })
+        .collect()
+}
+
+/// The ring without repeated points, including a last point repeating the first
+fn clean_ring<T: Scalar>(ring: &[Point<T>]) -> Vec<Point<T>> {
+    let mut ring = ring.to_vec();
+    ring.dedup();
+    while ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    ring
+}
+
+/// The points where the arcs of a coverage end, which are those joined to other than
+/// two points by the edges of the rings. Along an arc between them every ring that
+/// uses one edge uses the next as well.
+fn nodes<'a, T: Scalar>(
+    rings: impl Iterator<Item = &'a Vec<Point<T>>>,
+) -> BTreeSet<OrderedPoint<T>> {
+    let mut edges = Vec::new();
+    for ring in rings.filter(|ring| ring.len() >= 3) {
+        for (i, &p) in ring.iter().enumerate() {
+            let q = ring[(i + 1) % ring.len()];
+            edges.push((OrderedPoint::from(p), OrderedPoint::from(q)));
+            edges.push((OrderedPoint::from(q), OrderedPoint::from(p)));
+        }
+    }
+    edges.sort();
+    edges.dedup();
+    let mut nodes = BTreeSet::new();
+    let mut start = 0;
+    for end in 1..=edges.len() {
+        if end == edges.len() || edges[end].0 != edges[start].0 {
+            if end - start != 2 {
+                nodes.insert(edges[start].0);
+            }
+            start = end;
+        }
+    }
+    nodes
+}
+
+/// The arcs of a coverage, each kept once whichever rings run along it
+#[derive(Default)]
+struct Arcs<T> {
+    points: Vec<Vec<Point<T>>>,
+    /// The first edge of each arc, in whichever direction sorts first, and the arc
+    known: BTreeMap<(OrderedPoint<T>, OrderedPoint<T>), usize>,
+}
+
+impl<T: Scalar> Arcs<T> {
+    /// The arcs that `ring` is made of, in order, each with whether the ring runs along
+    /// it backwards. A ring with no node on it is one arc starting at its least point,
+    /// so the rings running either way round it start at the same place.
+    fn split(
+        &mut self,
+        ring: &[Point<T>],
+        nodes: &BTreeSet<OrderedPoint<T>>,
+    ) -> Vec<(usize, bool)> {
+        let n = ring.len();
+        if n < 3 {
+            return Vec::new();
+        }
+        let mut starts: Vec<usize> = (0..n)
+            .filter(|&i| nodes.contains(&OrderedPoint::from(ring[i])))
+            .collect();
+        if starts.is_empty() {
+            let least = (0..n).min_by_key(|&i| OrderedPoint::from(ring[i]));
+            starts.extend(least);
+        }
+        (0..starts.len())
+            .map(|k| {
+                let (from, to) = (starts[k], starts[(k + 1) % starts.len()]);
+                let steps = (to + n - from - 1) % n + 1;
+                let arc: Vec<Point<T>> = (0..=steps).map(|i| ring[(from + i) % n]).collect();
+                let key = |a: Point<T>, b: Point<T>| (OrderedPoint::from(a), OrderedPoint::from(b));
+                let forward = key(arc[0], arc[1]);
+                let backward = key(arc[steps], arc[steps - 1]);
+                let reversed = backward < forward;
+                let next = self.points.len();
+                let index = *self.known.entry(forward.min(backward)).or_insert(next);
+                if index == next {
+                    let mut arc = arc;
+                    if reversed {
+                        arc.reverse();
+                    }
+                    self.points.push(arc);
+                }
+                (index, reversed)
+            })
+            .collect()
+    }
+
+    /// The indices of the points kept on each arc. The current edges of every arc are
+    /// held in a grid, at first as they are and then with each shortcut taken in place
+    /// of the edges it skips, and each shortcut is checked against the edges as they
+    /// are when it is taken, so no two edges of the result cross.
+    fn simplify(&self, epsilon: T) -> Vec<Vec<usize>> {
+        let mut edges: Vec<(LineSegment<T>, bool)> = Vec::new();
+        let mut first = Vec::with_capacity(self.points.len());
+        for arc in &self.points {
+            first.push(edges.len());
+            edges.extend(arc.windows(2).map(|w| (LineSegment::new(w[0], w[1]), true)));
+        }
+        let total = edges.iter().fold(T::zero(), |acc, e| acc + e.0.length());
+        let mean = total / T::from_f64(edges.len().max(1) as f64);
+        let mut grid = HashGrid::new(if mean > T::zero() { mean } else { T::one() });
+        for (i, edge) in edges.iter().enumerate() {
+            grid.insert_segment(edge.0, i);
+        }
+        let mut kept = Vec::with_capacity(self.points.len());
+        for (arc, points) in self.points.iter().enumerate() {
+            let n = points.len();
+            let mut keep = alloc::vec![false; n];
+            keep[0] = true;
+            keep[n - 1] = true;
+            let mut spans = alloc::vec![(0, n - 1)];
+            while let Some((start, end)) = spans.pop() {
+                if end == start + 1 {
+                    continue;
+                }
+                let skipped = first[arc] + start..first[arc] + end;
+                let shortcut = LineSegment::new(points[start], points[end]);
+                let fits = points[start] != points[end]
+                    && farthest(points, start, end, epsilon).is_none()
+                    && clear(&grid, &edges, &skipped, shortcut, &points[start..=end]);
+                if fits {
+                    for edge in &mut edges[skipped] {
+                        edge.1 = false;
+                    }
+                    grid.insert_segment(shortcut, edges.len());
+                    edges.push((shortcut, true));
+                    continue;
+                }
+                let split = farthest(points, start, end, T::zero()).unwrap_or((start + end) / 2);
+                keep[split] = true;
+                spans.push((split, end));
+                spans.push((start, split));
+            }
+            kept.push((0..n).filter(|&i| keep[i]).collect());
+        }
+        kept
+    }
+}
+
+/// Whether `shortcut` can replace the edges in `skipped`, which run along `path`: it
+/// meets no other current edge except at its ends, and no other vertex lies between it
+/// and the path
+fn clear<T: Scalar>(
+    grid: &HashGrid<T, usize>,
+    edges: &[(LineSegment<T>, bool)],
+    skipped: &core::ops::Range<usize>,
+    shortcut: LineSegment<T>,
+    path: &[Point<T>],
+) -> bool {
+    let ends = [shortcut.p1(), shortcut.p2()];
+    let others = |rect: &Rect<T>| {
+        grid.query_rect(rect)
+            .into_iter()
+            .filter(|&&i| edges[i].1 && !skipped.contains(&i))
+            .map(|&i| edges[i].0)
+            .collect::<Vec<_>>()
+    };
+    let crossed = others(&Rect::from_corners(ends[0], ends[1]))
+        .iter()
+        .any(|edge| match shortcut.intersect(edge) {
+            SegmentIntersection::None => false,
+            SegmentIntersection::Point(p) => !ends.contains(&p),
+            SegmentIntersection::Segment(_) => true,
+        });
+    if crossed {
+        return false;
+    }
+    let bounds = match Rect::from_points(path.iter().copied()) {
+        Some(bounds) => bounds,
+        None => return true,
+    };
+    let between = Polygon::new(path.to_vec());
+    others(&bounds)
+        .iter()
+        .flat_map(|edge| [edge.p1(), edge.p2()])
+        .all(|p| ends.contains(&p) || !between.contains(p))
+}
+
+fn check_epsilon<T: Scalar>(epsilon: T) {
+    assert!(
+        epsilon >= T::zero(),
+        "epsilon must not be negative, got {}",
+        epsilon
+    );
+}
+
+/// The first of the points strictly between `start` and `end` that lies farthest from
+/// the segment joining them, if it is more than `epsilon` away. The distance is to the
+/// segment rather than to its line, so that a span whose ends meet, as on a trace that
+/// comes back to where it started, still measures how far the points stray.
+fn farthest<T: Scalar>(points: &[Point<T>], start: usize, end: usize, epsilon: T) -> Option<usize> {
+    let chord = LineSegment::new(points[start], points[end]);
+    let mut best = None;
+    let mut widest = epsilon * epsilon;
+    for (i, &p) in points.iter().enumerate().take(end).skip(start + 1) {
+        let d = chord.distance_squared_to_point(p);
+        if d > widest {
+            widest = d;
+            best = Some(i);
+        }
+    }
+    best
+}
+
+/// Push the indices kept strictly between `start` and `end` onto `kept`, in order
+fn split_dp<T: Scalar>(
+    points: &[Point<T>],
+    start: usize,
+    end: usize,
+    epsilon: T,
+    kept: &mut Vec<usize>,
+) {
+    if let Some(split) = farthest(points, start, end, epsilon) {
+        split_dp(points, start, split, epsilon, kept);
+        kept.push(split);
+        split_dp(points, split, end, epsilon, kept);
+    }
+}
+
+/// Half the magnitude of the cross product of the sides of the triangle meeting at `b`
+fn triangle_area<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>) -> T {
+    (a - b).cross(&(c - b)).abs() / T::from_f64(2.0)
+}
+
+/// A point waiting on the heap in `effective_areas`, with the area of its triangle
+struct Corner<T> {
+    area: T,
+    vertex: usize,
+}
+
+impl<T: Scalar> PartialEq for Corner<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Scalar> Eq for Corner<T> {}
+
+impl<T: Scalar> PartialOrd for Corner<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Scalar> Ord for Corner<T> {
+    /// Smaller is greater, so that the smallest triangle comes off the heap first, and
+    /// of equal ones the earliest along the line
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .area
+            .total_cmp(&self.area)
+            .then(other.vertex.cmp(&self.vertex))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lcg;
+
+    /// A noisy trace that winds back and forth
+    fn trace(n: usize) -> Polyline {
+        let mut rng = Lcg::new(7);
+        let mut noise = || rng.unit() * 0.2 - 0.1;
+        (0..n)
+            .map(|i| {
+                let t = i as f64 * 0.05;
+                Point {
+                    x: t + noise(),
+                    y: (t * 0.7).sin() * 3.0 + noise(),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_stays_within_epsilon() {
+        let line = trace(2000);
+        for &epsilon in &[0.0, 0.05, 0.3, 1.0, 10.0] {
+            let kept = line.simplify_dp_indices(epsilon);
+            assert_eq!(kept, line.simplify_dp_indices_recursive(epsilon));
+            assert_eq!(0, kept[0]);
+            assert_eq!(1999, *kept.last().unwrap());
+            // Every point left out is close to the piece of the result that skips it
+            for w in kept.windows(2) {
+                let chord = LineSegment::new(line.points()[w[0]], line.points()[w[1]]);
+                for &p in &line.points()[w[0] + 1..w[1]] {
+                    assert!(chord.distance_to_point(p) <= epsilon);
+                }
+            }
+            let simple = line.simplify_dp(epsilon);
+            assert_eq!(kept.len(), simple.points().len());
+        }
+        assert_eq!(2, line.simplify_dp(10.0).points().len());
+        assert!(line.simplify_dp(0.3).points().len() < 100);
+    }
+
+    #[test]
+    fn test_collinear_and_short() {
+        let straight: Polyline = (0..10)
+            .map(|i| Point {
+                x: i as f64,
+                y: 2.0 * i as f64,
+            })
+            .collect();
+        assert_eq!(vec![0, 9], straight.simplify_dp_indices(1e-12));
+        assert!(Polyline::<f64>::default()
+            .simplify_dp_indices(1.0)
+            .is_empty());
+        let dot = Polyline::new(vec![Point { x: 1.0, y: 1.0 }]);
+        assert_eq!(vec![0], dot.simplify_dp_indices_recursive(1.0));
+        // A loop whose ends meet keeps its far side
+        let ring = Polyline::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 0.0, y: 0.0 },
+        ]);
+        assert_eq!(vec![0, 2, 3], ring.simplify_dp_indices(0.8));
+        assert_eq!(vec![0, 1, 2, 3], ring.simplify_dp_indices(0.1));
+    }
+
+    #[test]
+    fn test_vw_removes_smallest_first() {
+        let line = trace(500);
+        let areas = line.effective_areas();
+        assert_eq!(500, areas.len());
+        assert!(areas[0].is_infinite() && areas[499].is_infinite());
+        // Simplifying by hand, one smallest triangle at a time, takes the points out in
+        // the order of their areas
+        let mut left: Vec<usize> = (0..500).collect();
+        let mut floor = 0.0;
+        while left.len() > 2 {
+            let (k, area) = (1..left.len() - 1)
+                .map(|k| {
+                    let p = |j: usize| line.points()[left[j]];
+                    (k, triangle_area(p(k - 1), p(k), p(k + 1)))
+                })
+                .fold(
+                    (0, f64::INFINITY),
+                    |best, c| if c.1 < best.1 { c } else { best },
+                );
+            floor = f64::max(floor, area);
+            assert_eq!(floor, areas[left[k]]);
+            left.remove(k);
+        }
+    }
+
+    #[test]
+    fn test_vw_thresholds() {
+        let line = trace(2000);
+        let areas = line.effective_areas();
+        let mut last = 2000;
+        for &epsilon in &[0.0, 0.001, 0.01, 0.1, 1.0] {
+            let simple = line.simplify_vw(epsilon);
+            let expected = areas.iter().filter(|&&a| a >= epsilon).count();
+            assert_eq!(expected, simple.points().len());
+            assert!(expected <= last);
+            last = expected;
+            assert_eq!(line.points()[0], simple.points()[0]);
+        }
+        assert_eq!(2000, line.simplify_vw(0.0).points().len());
+        assert!(last < 60);
+        assert!(Polyline::<f64>::default().effective_areas().is_empty());
+        let pair = Polyline::new(vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }]);
+        assert_eq!(pair, pair.simplify_vw(100.0));
+    }
+
+    /// A `side` by `side` grid of cells whose shared sides wobble in the same way from
+    /// either cell
+    fn wobbly_grid(side: usize) -> Vec<Polygon> {
+        // Ten steps from a to b, each pushed off to one side or the other depending
+        // only on where the side is
+        let wobble = |a: Point, b: Point| -> Vec<Point> {
+            let seed = (a.x * 7.0 + a.y * 13.0 + b.x * 17.0 + b.y * 19.0) as u64;
+            let d = b - a;
+            let normal = Point { x: -d.y, y: d.x };
+            (0..=10)
+                .map(|i| {
+                    let off = match i {
+                        0 | 10 => 0.0,
+                        _ => {
+                            let mut h = (seed * 11 + i).wrapping_mul(6364136223846793005);
+                            h = (h ^ (h >> 29)).wrapping_mul(6364136223846793005);
+                            (h >> 11) as f64 / (1u64 << 53) as f64 * 0.1 - 0.05
+                        }
+                    };
+                    a + d * (i as f64 / 10.0) + normal * off
+                })
+                .collect()
+        };
+        // Each side is laid out from its lower left end, and run backwards as needed
+        let side_points = |a: Point, b: Point| {
+            if (a.x, a.y) <= (b.x, b.y) {
+                wobble(a, b)
+            } else {
+                let mut points = wobble(b, a);
+                points.reverse();
+                points
+            }
+        };
+        let mut cells = Vec::new();
+        for row in 0..side {
+            for column in 0..side {
+                let (x, y) = (column as f64, row as f64);
+                let corners = [
+                    Point { x, y },
+                    Point { x: x + 1.0, y },
+                    Point {
+                        x: x + 1.0,
+                        y: y + 1.0,
+                    },
+                    Point { x, y: y + 1.0 },
+                ];
+                let mut ring = Vec::new();
+                for k in 0..4 {
+                    let mut points = side_points(corners[k], corners[(k + 1) % 4]);
+                    points.pop();
+                    ring.extend(points);
+                }
+                cells.push(Polygon::new(ring));
+            }
+        }
+        cells
+    }
+
+    #[test]
+    fn test_coverage_stays_whole() {
+        let cells = wobbly_grid(4);
+        for &epsilon in &[0.0, 0.02, 0.1, 1.0] {
+            let simple = simplify_coverage(&cells, epsilon);
+            assert_eq!(16, simple.len());
+            // Every edge inside the grid is run along once each way, so the cells
+            // neither overlap nor leave gaps, and still fill the grid
+            let mut edges: Vec<_> = simple
+                .iter()
+                .flat_map(|c| c.edges())
+                .map(|e| (OrderedPoint::from(e.p1()), OrderedPoint::from(e.p2())))
+                .collect();
+            edges.sort();
+            let outer = edges
+                .iter()
+                .filter(|&&(a, b)| edges.binary_search(&(b, a)).is_err())
+                .count();
+            assert!(outer > 0 && outer < edges.len() / 2);
+            // No two edges cross, or meet other than at their ends
+            let mut once: Vec<_> = edges.iter().map(|&(a, b)| (a.min(b), a.max(b))).collect();
+            once.sort();
+            once.dedup();
+            let segments: Vec<LineSegment> = once
+                .iter()
+                .map(|&(a, b)| LineSegment::new(a.point(), b.point()))
+                .collect();
+            for (i, a) in segments.iter().enumerate() {
+                for b in &segments[i + 1..] {
+                    match a.intersect(b) {
+                        SegmentIntersection::None => {}
+                        SegmentIntersection::Point(p) => {
+                            assert!([a.p1(), a.p2()].contains(&p) && [b.p1(), b.p2()].contains(&p))
+                        }
+                        SegmentIntersection::Segment(_) => panic!("{} overlaps {}", a, b),
+                    }
+                }
+            }
+            let area: f64 = simple.iter().map(|c| c.area()).sum();
+            let before: f64 = cells.iter().map(|c| c.area()).sum();
+            assert!((area - before).abs() < 16.0 * epsilon + 1e-9);
+            for (cell, original) in simple.iter().zip(&cells) {
+                assert!(cell.is_simple());
+                assert!(cell.vertices().len() >= 3);
+                assert!(cell.vertices().len() <= original.vertices().len());
+            }
+        }
+        let counts = |epsilon: f64| -> usize {
+            simplify_coverage(&cells, epsilon)
+                .iter()
+                .map(|c| c.vertices().len())
+                .sum()
+        };
+        assert_eq!(
+            cells.iter().map(|c| c.vertices().len()).sum::<usize>(),
+            counts(0.0)
+        );
+        assert!(counts(1.0) < counts(0.02));
+        assert!(counts(0.02) < counts(0.0));
+    }
+
+    #[test]
+    fn test_coverage_keeps_islands_on_their_side() {
+        let p = |x: f64, y: f64| Point { x, y };
+        // A bay whose mouth a shortcut would close, with an island in the middle of it
+        let land = Polygon::with_holes(
+            vec![
+                p(-10.0, -10.0),
+                p(10.0, -10.0),
+                p(10.0, 10.0),
+                p(-10.0, 10.0),
+            ],
+            vec![vec![
+                p(-5.0, -1.0),
+                p(0.0, -0.2),
+                p(5.0, -1.0),
+                p(5.0, 1.0),
+                p(-5.0, 1.0),
+            ]],
+        );
+        let island = Polygon::new(vec![p(-0.1, -0.6), p(0.1, -0.6), p(0.0, -0.5)]);
+        let lake = Polygon::with_holes(
+            // The hole of the land, run the other way round
+            vec![
+                p(-5.0, -1.0),
+                p(-5.0, 1.0),
+                p(5.0, 1.0),
+                p(5.0, -1.0),
+                p(0.0, -0.2),
+            ],
+            vec![island.vertices().iter().rev().copied().collect()],
+        );
+        let coverage = vec![land.clone(), lake, island.clone()];
+        let simple = simplify_coverage(&coverage, 1.5);
+        // The bay's point at (0, -0.2) can't be cut off without passing over the island
+        assert!(simple[0].holes()[0].contains(&p(0.0, -0.2)));
+        assert_eq!(island.vertices(), simple[2].vertices());
+        assert_eq!(land.vertices(), simple[0].vertices());
+        // Without the island in the way the shortcut is taken
+        let dry = simplify_coverage(&coverage[..1], 1.5);
+        assert!(!dry[0].holes()[0].contains(&p(0.0, -0.2)));
+        assert_eq!(4, dry[0].holes()[0].len());
+        assert!(simplify_coverage::<f64>(&[], 1.0).is_empty());
+    }
+}
@@ -0,0 +1,574 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::boolean::tidy_ring;
+use crate::polygon::ring_orientation;
+use crate::predicates::Orientation;
+use crate::{LineSegment, MultiPolygon, OrderedPoint, Point, Polygon, Scalar};
+
+/// The straight skeleton of a polygon, traced by its corners as every edge moves into
+/// the polygon at the same speed. Where an edge shrinks away its neighbours meet, and
+/// where a reflex corner runs into another edge the polygon is split in two, until
+/// nothing is left. Each point of the face swept by an edge is as far from that
+/// edge's line as the time at which the edge passed it, which is the height of a roof
+/// of equal pitch over the polygon.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StraightSkeleton<T = f64> {
+    /// The paths of the corners, each from where the corner formed to where it
+    /// vanished
+    pub edges: Vec<LineSegment<T>>,
+    /// The region swept by each edge of the polygon, starting from the ends of that
+    /// edge, with the faces of the exterior ring first and then those of each hole
+    pub faces: Vec<Polygon<T>>,
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// The straight skeleton of the polygon. Each event is found by comparing every
+    /// reflex corner with every edge, so this takes O(n^2) time per event. Repeated
+    /// vertices are ignored, and rings enclosing no area are left out.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let rect = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 2.0}, Point{x: 0.0, y: 2.0},
+    /// ]);
+    /// let skeleton = rect.straight_skeleton();
+    /// // A hip from each corner and the ridge along the middle
+    /// assert_eq!(skeleton.edges.len(), 5);
+    /// assert!(skeleton.edges.iter().any(|e| e.length() == 2.0));
+    /// // The long sides sweep trapezoids, and the short ones triangles
+    /// let areas: Vec<f64> = skeleton.faces.iter().map(|f| f.area()).collect();
+    /// assert_eq!(areas, [3.0, 1.0, 3.0, 1.0]);
+    /// ```
+    pub fn straight_skeleton(&self) -> StraightSkeleton<T> {
+        let mut front = Wavefront::new(self);
+        while let Some((time, event)) = front.next_event() {
+            front.handle(time, event);
+        }
+        let edges = front
+            .arcs
+            .iter()
+            .filter(|arc| arc.0 != arc.1)
+            .map(|&(a, b, _)| LineSegment::new(a, b))
+            .collect();
+        let faces = (0..front.lines.len()).map(|k| front.face(k)).collect();
+        StraightSkeleton { edges, faces }
+    }
+
+    /// The polygon shrunk by moving every edge `distance` into it, with the corners
+    /// mitered however sharp they are. This is the wavefront of the straight skeleton
+    /// at that time, so parts of the polygon split off as they narrow, and it is empty
+    /// once the distance reaches the radius of the largest circle inside.
+    /// Non-positive distances give the polygon unchanged.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let l = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 1.0},
+    ///     Point{x: 1.0, y: 1.0}, Point{x: 1.0, y: 2.0}, Point{x: 0.0, y: 2.0},
+    /// ]);
+    /// // The reflex corner moves to (0.75, 0.75), where a round join would cut it off
+    /// let shrunk = l.wavefront(0.25);
+    /// assert_eq!(shrunk.polygons()[0].vertices().len(), 6);
+    /// assert_eq!(shrunk.area(), 1.25);
+    /// ```
+    pub fn wavefront(&self, distance: T) -> MultiPolygon<T> {
+        let mut front = Wavefront::new(self);
+        while let Some((time, event)) = front.next_event() {
+            if time > distance {
+                break;
+            }
+            front.handle(time, event);
+        }
+        front.now = front.now.max(distance);
+        let mut boundary = Vec::new();
+        let mut seen = alloc::vec![false; front.corners.len()];
+        for start in 0..front.corners.len() {
+            if seen[start] || !front.corners[start].alive {
+                continue;
+            }
+            let mut ring = Vec::new();
+            let mut i = start;
+            while !seen[i] {
+                seen[i] = true;
+                ring.push(front.position(i));
+                i = front.corners[i].next;
+            }
+            let n = ring.len();
+            boundary.extend((0..n).map(|j| (ring[j], ring[(j + 1) % n])));
+        }
+        MultiPolygon::from_boundary(&boundary)
+            .polygons()
+            .iter()
+            .map(|poly| {
+                let holes = poly.holes().iter().map(|h| tidy_ring(h)).collect();
+                Polygon::with_holes(tidy_ring(poly.vertices()), holes)
+            })
+            .collect()
+    }
+}
+
+/// An edge of the polygon, whose line at time t is where `normal . x = offset + t`
+struct MovingLine<T> {
+    a: Point<T>,
+    b: Point<T>,
+    direction: Point<T>,
+    normal: Point<T>,
+    offset: T,
+}
+
+/// A corner of the wavefront, between the lines of the edges before and after it
+struct Corner<T> {
+    origin: Point<T>,
+    born: T,
+    velocity: Point<T>,
+    before: usize,
+    after: usize,
+    prev: usize,
+    next: usize,
+    alive: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Event {
+    /// The edge after a corner shrinks away
+    Collapse(usize),
+    /// A reflex corner runs into the edge after another corner
+    Split(usize, usize),
+}
+
+struct Wavefront<T> {
+    lines: Vec<MovingLine<T>>,
+    corners: Vec<Corner<T>>,
+    now: T,
+    /// The paths of the corners, with the lines of the faces on either side
+    arcs: Vec<(Point<T>, Point<T>, [usize; 2])>,
+    /// How nearly lines must be parallel, or events coincide, to be taken as such
+    tol: T,
+}
+
+impl<T: Scalar> Wavefront<T> {
+    fn new(polygon: &Polygon<T>) -> Wavefront<T> {
+        let scale = polygon
+            .rings()
+            .flatten()
+            .fold(T::zero(), |m, p| m.max(p.x.abs()).max(p.y.abs()));
+        let mut front = Wavefront {
+            lines: Vec::new(),
+            corners: Vec::new(),
+            now: T::zero(),
+            arcs: Vec::new(),
+            tol: scale.max(T::one()) * T::epsilon() * T::from_f64(64.0),
+        };
+        for (i, ring) in polygon.rings().enumerate() {
+            let orientation = ring_orientation(ring);
+            if orientation == Orientation::Collinear {
+                continue;
+            }
+            let mut ring = ring.to_vec();
+            ring.dedup();
+            if ring.len() > 1 && ring.first() == ring.last() {
+                ring.pop();
+            }
+            // Every edge runs with the interior on its left
+            if (orientation == Orientation::CounterClockwise) != (i == 0) {
+                ring.reverse();
+            }
+            let (n, first) = (ring.len(), front.lines.len());
+            for j in 0..n {
+                let (a, b) = (ring[j], ring[(j + 1) % n]);
+                let direction = (b - a).normalize();
+                let normal = direction.perp();
+                let offset = normal.dot_product(&a);
+                front.lines.push(MovingLine {
+                    a,
+                    b,
+                    direction,
+                    normal,
+                    offset,
+                });
+            }
+            for (j, &origin) in ring.iter().enumerate() {
+                let before = first + (j + n - 1) % n;
+                let velocity = front.velocity(before, first + j);
+                front.corners.push(Corner {
+                    origin,
+                    born: T::zero(),
+                    velocity,
+                    before,
+                    after: first + j,
+                    prev: first + (j + n - 1) % n,
+                    next: first + (j + 1) % n,
+                    alive: true,
+                });
+            }
+        }
+        front
+    }
+
+    /// How fast a corner between two lines moves, so as to stay on both
+    fn velocity(&self, before: usize, after: usize) -> Point<T> {
+        let (m, n) = (self.lines[before].normal, self.lines[after].normal);
+        let cross = m.cross(&n);
+        if cross.abs() <= self.tol {
+            // The lines run on together, or fold back on each other
+            return (m + n) / T::from_f64(2.0);
+        }
+        Point {
+            x: n.y - m.y,
+            y: m.x - n.x,
+        } / cross
+    }
+
+    fn position_at(&self, i: usize, time: T) -> Point<T> {
+        let c = &self.corners[i];
+        c.origin + c.velocity * (time - c.born)
+    }
+
+    fn position(&self, i: usize) -> Point<T> {
+        self.position_at(i, self.now)
+    }
+
+    fn is_reflex(&self, i: usize) -> bool {
+        let c = &self.corners[i];
+        let turn = self.lines[c.before]
+            .direction
+            .cross(&self.lines[c.after].direction);
+        turn < -self.tol
+    }
+
+    /// The earliest event to come, and when it happens
+    fn next_event(&self) -> Option<(T, Event)> {
+        let mut best: Option<(T, Event)> = None;
+        let mut consider = |time: T, event: Event| {
+            if best.as_ref().is_none_or(|&(t, _)| time < t) {
+                best = Some((time, event));
+            }
+        };
+        let alive: Vec<usize> = (0..self.corners.len())
+            .filter(|&i| self.corners[i].alive)
+            .collect();
+        for &a in &alive {
+            let (c, b) = (&self.corners[a], self.corners[a].next);
+            let direction = self.lines[c.after].direction;
+            let length = (self.position(b) - self.position(a)).dot_product(&direction);
+            let rate = (self.corners[b].velocity - c.velocity).dot_product(&direction);
+            // Corners that rounding has left a hair apart, or crossed, are taken to
+            // have met
+            if length <= self.tol {
+                consider(self.now, Event::Collapse(a));
+            } else if rate < T::zero() {
+                consider(self.now - length / rate, Event::Collapse(a));
+            }
+        }
+        for &r in alive.iter().filter(|&&r| self.is_reflex(r)) {
+            let (p, v) = (self.position(r), self.corners[r].velocity);
+            for &c in &alive {
+                let k = self.corners[c].after;
+                if k == self.corners[r].before || k == self.corners[r].after {
+                    continue;
+                }
+                let line = &self.lines[k];
+                let gap = line.normal.dot_product(&p) - line.offset - self.now;
+                let approach = T::one() - line.normal.dot_product(&v);
+                // Rounding can leave a corner a hair past a line it is just now meeting
+                if gap < -self.tol || approach <= T::zero() {
+                    continue;
+                }
+                let wait = gap.max(T::zero()) / approach;
+                let (time, hit) = (self.now + wait, p + v * wait);
+                let d = self.corners[c].next;
+                let from = (hit - self.position_at(c, time)).dot_product(&line.direction);
+                let to = (self.position_at(d, time) - hit).dot_product(&line.direction);
+                // Corners that have just come out of the same event have already met
+                let fresh = |i: usize| {
+                    self.corners[i].born == self.now && self.position(i).distance(&p) <= self.tol
+                };
+                if from >= -self.tol && to >= -self.tol && !(fresh(r) && (fresh(c) || fresh(d))) {
+                    consider(time, Event::Split(r, c));
+                }
+            }
+        }
+        best
+    }
+
+    fn handle(&mut self, time: T, event: Event) {
+        self.now = self.now.max(time);
+        match event {
+            Event::Collapse(a) => {
+                let b = self.corners[a].next;
+                let q = (self.position(a) + self.position(b)) / T::from_f64(2.0);
+                let (prev, next) = (self.corners[a].prev, self.corners[b].next);
+                let (before, after) = (self.corners[a].before, self.corners[b].after);
+                self.retire(a, q);
+                self.retire(b, q);
+                let m = self.add(q, before, after, prev, next);
+                self.tidy(m);
+            }
+            Event::Split(r, c) => {
+                let q = self.position(r);
+                let d = self.corners[c].next;
+                // A corner that runs into the end of the edge meets the corner there
+                for end in [c, d] {
+                    let (prev, next) = (self.corners[r].prev, self.corners[r].next);
+                    if end != prev && end != next && self.position(end).distance(&q) <= self.tol {
+                        self.meet(r, end, q);
+                        return;
+                    }
+                }
+                let k = self.corners[c].after;
+                let corner = &self.corners[r];
+                let (before, after, prev, next) =
+                    (corner.before, corner.after, corner.prev, corner.next);
+                self.retire(r, q);
+                // The corner's two sides go each to one end of the edge, splitting the
+                // wavefront in two, or joining two parts of it
+                let m1 = self.add(q, before, k, prev, d);
+                let m2 = self.add(q, k, after, c, next);
+                self.tidy(m1);
+                self.tidy(m2);
+            }
+        }
+    }
+
+    /// Two corners meet head on, and each goes on with a side of the other
+    fn meet(&mut self, r: usize, c: usize, q: Point<T>) {
+        let (one, other) = (&self.corners[r], &self.corners[c]);
+        let (before, prev) = (one.before, one.prev);
+        let (after, next) = (one.after, one.next);
+        let (other_before, other_after) = (other.before, other.after);
+        let (other_prev, other_next) = (other.prev, other.next);
+        self.retire(r, q);
+        self.retire(c, q);
+        let m1 = self.add(q, before, other_after, prev, other_next);
+        let m2 = self.add(q, other_before, after, other_prev, next);
+        self.tidy(m1);
+        self.tidy(m2);
+    }
+
+    fn add(
+        &mut self,
+        origin: Point<T>,
+        before: usize,
+        after: usize,
+        prev: usize,
+        next: usize,
+    ) -> usize {
+        let i = self.corners.len();
+        let velocity = self.velocity(before, after);
+        self.corners.push(Corner {
+            origin,
+            born: self.now,
+            velocity,
+            before,
+            after,
+            prev,
+            next,
+            alive: true,
+        });
+        self.corners[prev].next = i;
+        self.corners[next].prev = i;
+        i
+    }
+
+    fn retire(&mut self, i: usize, end: Point<T>) {
+        let c = &mut self.corners[i];
+        c.alive = false;
+        self.arcs.push((c.origin, end, [c.before, c.after]));
+    }
+
+    /// Clear away the parts of the wavefront around a new corner that enclose nothing
+    fn tidy(&mut self, i: usize) {
+        if !self.corners[i].alive {
+            return;
+        }
+        let (prev, next) = (self.corners[i].prev, self.corners[i].next);
+        if next == i {
+            let p = self.position(i);
+            self.retire(i, p);
+            return;
+        }
+        if prev == next {
+            // A ring of two corners is a ridge between the two faces
+            let (p, q) = (self.position(i), self.position(next));
+            let faces = [self.corners[i].before, self.corners[i].after];
+            self.retire(i, p);
+            self.retire(next, q);
+            self.arcs.push((p, q, faces));
+            return;
+        }
+        let c = &self.corners[i];
+        let (m, n) = (self.lines[c.before].normal, self.lines[c.after].normal);
+        if m.dot_product(&n) >= T::zero() || m.cross(&n).abs() > self.tol {
+            return;
+        }
+        // The corner is the tip of a spike of no width, which is cut back to the
+        // nearer of its neighbours
+        let p = self.position(i);
+        let (to_prev, to_next) = (
+            self.position(prev).distance(&p),
+            self.position(next).distance(&p),
+        );
+        let m = if to_next <= to_prev {
+            let end = self.position(next);
+            let (before, after, beyond) =
+                (c.before, self.corners[next].after, self.corners[next].next);
+            self.retire(i, end);
+            self.retire(next, end);
+            self.add(end, before, after, prev, beyond)
+        } else {
+            let end = self.position(prev);
+            let (before, after, beyond) =
+                (self.corners[prev].before, c.after, self.corners[prev].prev);
+            self.retire(i, end);
+            self.retire(prev, end);
+            self.add(end, before, after, beyond, next)
+        };
+        self.tidy(m);
+    }
+
+    /// The region swept by the `k`th line, bounded by its edge and the paths of the
+    /// corners beside it
+    fn face(&self, k: usize) -> Polygon<T> {
+        let mut touching: BTreeMap<OrderedPoint<T>, Vec<usize>> = BTreeMap::new();
+        for (i, &(a, b, faces)) in self.arcs.iter().enumerate() {
+            if a != b && faces.contains(&k) {
+                touching.entry(a.into()).or_default().push(i);
+                touching.entry(b.into()).or_default().push(i);
+            }
+        }
+        let (start, end) = (self.lines[k].a, self.lines[k].b);
+        let mut ring = alloc::vec![start, end];
+        let mut used = alloc::vec![false; self.arcs.len()];
+        let mut at = end;
+        while at != start {
+            let arc = touching
+                .get(&at.into())
+                .and_then(|arcs| arcs.iter().copied().find(|&i| !used[i]));
+            let i = match arc {
+                Some(i) => i,
+                None => break,
+            };
+            used[i] = true;
+            let (a, b, _) = self.arcs[i];
+            at = if a == at { b } else { a };
+            if at != start {
+                ring.push(at);
+            }
+        }
+        Polygon::new(ring)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_faces_cover(polygon: &Polygon, skeleton: &StraightSkeleton) {
+        let total: f64 = skeleton.faces.iter().map(|f| f.area()).sum();
+        assert!(
+            (total - polygon.area()).abs() < 1e-9 * polygon.area(),
+            "{} {}",
+            total,
+            polygon.area()
+        );
+        for face in &skeleton.faces {
+            assert_eq!(face.orientation(), Orientation::CounterClockwise);
+        }
+    }
+
+    #[test]
+    fn test_square_and_rectangle() {
+        let square = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 0.0, y: 2.0 },
+        ]);
+        let skeleton = square.straight_skeleton();
+        assert_faces_cover(&square, &skeleton);
+        assert_eq!(4, skeleton.edges.len());
+        for e in &skeleton.edges {
+            assert!(e.p1() == Point { x: 1.0, y: 1.0 } || e.p2() == Point { x: 1.0, y: 1.0 });
+        }
+        assert_eq!(3, skeleton.faces[0].vertices().len());
+        assert!(square.wavefront(1.0).polygons().is_empty());
+        let half = square.wavefront(0.5);
+        assert_eq!(1.0, half.area());
+    }
+
+    #[test]
+    fn test_reflex_split() {
+        // A U shape, whose arms split apart as it shrinks
+        let u = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 6.0, y: 0.0 },
+            Point { x: 6.0, y: 6.0 },
+            Point { x: 4.0, y: 6.0 },
+            Point { x: 4.0, y: 2.0 },
+            Point { x: 2.0, y: 2.0 },
+            Point { x: 2.0, y: 6.0 },
+            Point { x: 0.0, y: 6.0 },
+        ]);
+        let skeleton = u.straight_skeleton();
+        assert_faces_cover(&u, &skeleton);
+        assert_eq!(8, skeleton.faces.len());
+        let shrunk = u.wavefront(0.5);
+        assert_eq!(1, shrunk.polygons().len());
+        assert_eq!(8, shrunk.polygons()[0].vertices().len());
+        // The mitered corners stay sharp, so this is the U with its sides moved in
+        assert!(crate::scalar_isclose(5.0 * 5.0 - 3.0 * 4.0, shrunk.area()));
+        let arms = u.wavefront(0.9);
+        assert_eq!(1, arms.polygons().len());
+        // The arms and base are all two wide
+        assert!(u.wavefront(1.05).polygons().is_empty());
+    }
+
+    #[test]
+    fn test_holes() {
+        let framed = Polygon::with_holes(
+            vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 10.0, y: 0.0 },
+                Point { x: 10.0, y: 10.0 },
+                Point { x: 0.0, y: 10.0 },
+            ],
+            vec![vec![
+                Point { x: 3.0, y: 3.0 },
+                Point { x: 7.0, y: 3.0 },
+                Point { x: 7.0, y: 7.0 },
+                Point { x: 3.0, y: 7.0 },
+            ]],
+        );
+        let skeleton = framed.straight_skeleton();
+        assert_faces_cover(&framed, &skeleton);
+        assert_eq!(8, skeleton.faces.len());
+        let shrunk = framed.wavefront(1.0);
+        assert_eq!(1, shrunk.polygons()[0].holes().len());
+        assert!(crate::scalar_isclose(64.0 - 36.0, shrunk.area()));
+        assert!(framed.wavefront(1.6).polygons().is_empty());
+    }
+
+    #[test]
+    fn test_simultaneous_events() {
+        // Nubs, a neck and reflex corners all meeting other corners at the same times
+        let coords = [
+            0, 3, 1, 3, 1, 2, 5, 2, 5, 4, 7, 4, 7, 5, 5, 5, 5, 6, 4, 6, 4, 7, 5, 7, 5, 9, 2, 9, 2,
+            7, 3, 7, 3, 6, 1, 6, 1, 4, 0, 4,
+        ];
+        let poly = Polygon::new(
+            coords
+                .chunks(2)
+                .map(|xy| Point {
+                    x: xy[0] as f64,
+                    y: xy[1] as f64,
+                })
+                .collect(),
+        );
+        let skeleton = poly.straight_skeleton();
+        assert_faces_cover(&poly, &skeleton);
+        // With only right angles the wavefront is the mitered offset
+        for &t in &[0.25, 0.5, 1.0, 1.5] {
+            let offset = poly.offset(-t, crate::Join::Miter(2.0)).area();
+            assert!(crate::scalar_isclose(offset, poly.wavefront(t).area()));
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use alloc::vec::Vec;
+
+use crate::{Point, Polygon, Polyline, Scalar};
+
+impl<T: Scalar> Polyline<T> {
+    /// The polyline with its corners cut `iterations` times by Chaikin's algorithm,
+    /// which replaces each segment by the two points a quarter and three quarters of the
+    /// way along it. Each pass doubles the number of points, and the result
+    /// tends to a smooth quadratic B-spline with the points as its control polygon. The
+    /// first and last points are kept, so the smoothed path still runs between them.
+    /// ```
+    /// use rsgeo::{Point, Polyline};
+    /// let corner = Polyline::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 4.0},
+    /// ]);
+    /// assert_eq!(corner.smooth_chaikin(1).points(), &[
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 0.0}, Point{x: 3.0, y: 0.0},
+    ///     Point{x: 4.0, y: 1.0}, Point{x: 4.0, y: 3.0}, Point{x: 4.0, y: 4.0},
+    /// ]);
+    /// ```
+    pub fn smooth_chaikin(&self, iterations: usize) -> Polyline<T> {
+        let mut points = self.points().to_vec();
+        for _ in 0..iterations {
+            if points.len() < 3 {
+                break;
+            }
+            let mut cut = Vec::with_capacity(2 * points.len());
+            cut.push(points[0]);
+            for w in points.windows(2) {
+                cut.extend(cut_corners(w[0], w[1]));
+            }
+            cut.push(points[points.len() - 1]);
+            points = cut;
+        }
+        Polyline::new(points)
+    }
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// The polygon with the corners of each ring cut `iterations` times by Chaikin's
+    /// algorithm, as for `Polyline::smooth_chaikin` but with every vertex taken as a
+    /// corner, so each ring tends to a closed smooth curve and no point of the original
+    /// is kept. Each pass doubles the number of vertices. The corners are cut inwards on
+    /// convex stretches and outwards on concave ones, so the area shrinks on a convex
+    /// ring.
+    /// ```
+    /// use rsgeo::{Point, Polygon};
+    /// let square = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 4.0}, Point{x: 0.0, y: 4.0},
+    /// ]);
+    /// let octagon = square.smooth_chaikin(1);
+    /// assert_eq!(octagon.vertices().len(), 8);
+    /// assert_eq!(octagon.area(), 14.0);
+    /// ```
+    pub fn smooth_chaikin(&self, iterations: usize) -> Polygon<T> {
+        let mut rings = self.rings().map(|ring| {
+            let mut points = ring.to_vec();
+            for _ in 0..iterations {
+                if points.len() < 3 {
+                    break;
+                }
+                let n = points.len();
+                points = (0..n)
+                    .flat_map(|i| cut_corners(points[i], points[(i + 1) % n]))
+                    .collect();
+            }
+            points
+        });
+        let exterior = rings.next().unwrap_or_default();
+        Polygon::with_holes(exterior, rings.collect())
+    }
+}
+
+/// The points a quarter and three quarters of the way from `a` to `b`
+fn cut_corners<T: Scalar>(a: Point<T>, b: Point<T>) -> [Point<T>; 2] {
+    [a.lerp(&b, T::from_f64(0.25)), a.lerp(&b, T::from_f64(0.75))]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zigzag() -> Polyline {
+        (0..9)
+            .map(|i| Point {
+                x: i as f64,
+                y: (i % 2) as f64,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_open_smoothing() {
+        let line = zigzag();
+        assert_eq!(line, line.smooth_chaikin(0));
+        let mut last = line.clone();
+        for k in 1..=4 {
+            let smooth = line.smooth_chaikin(k);
+            assert_eq!(2 * last.points().len(), smooth.points().len());
+            assert_eq!(line.points()[0], smooth.points()[0]);
+            assert_eq!(line.points()[8], *smooth.points().last().unwrap());
+            // Cutting corners makes the path shorter and keeps it in the same band
+            assert!(smooth.length() < last.length());
+            assert!(smooth
+                .points()
+                .iter()
+                .all(|p| (0.0..=1.0).contains(&p.y) && (0.0..=8.0).contains(&p.x)));
+            last = smooth;
+        }
+        // The sharpest turn left is gentle
+        let turns = last.points().windows(3).map(|w| {
+            let (a, b) = (w[1] - w[0], w[2] - w[1]);
+            a.cross(&b).abs() / (a.magnitude() * b.magnitude())
+        });
+        assert!(turns.fold(0.0, f64::max) < 0.5);
+        let pair = Polyline::new(vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }]);
+        assert_eq!(pair, pair.smooth_chaikin(3));
+    }
+
+    #[test]
+    fn test_closed_smoothing() {
+        let square = Polygon::with_holes(
+            vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 8.0, y: 0.0 },
+                Point { x: 8.0, y: 8.0 },
+                Point { x: 0.0, y: 8.0 },
+            ],
+            vec![vec![
+                Point { x: 3.0, y: 3.0 },
+                Point { x: 3.0, y: 5.0 },
+                Point { x: 5.0, y: 5.0 },
+                Point { x: 5.0, y: 3.0 },
+            ]],
+        );
+        let smooth = square.smooth_chaikin(5);
+        assert_eq!(128, smooth.vertices().len());
+        assert_eq!(128, smooth.holes()[0].len());
+        assert!(smooth.is_simple());
+        // The rings tend to the quadratic B-spline, which stays within the squares and
+        // passes through the middle of each side
+        let centroid = smooth.centroid().unwrap();
+        assert!(centroid.isclose(Point { x: 4.0, y: 4.0 }));
+        assert!(smooth.area() < square.area());
+        assert!(smooth.contains(Point { x: 4.0, y: 0.5 }));
+        assert!(!smooth.contains(Point { x: 0.5, y: 0.5 }));
+        assert!(!smooth.contains(Point { x: 4.0, y: 4.0 }));
+        assert_eq!(square, square.smooth_chaikin(0));
+    }
+}
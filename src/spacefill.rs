@@ -0,0 +1,193 @@
+use crate::{Point, Rect, Scalar};
+
+/// A curve that passes through every cell of a square grid once, so that ordering
+/// points by where the curve reaches their cells keeps points that are near each other
+/// mostly near each other in the order too
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpaceFillingCurve {
+    /// The Hilbert curve, which only ever steps to a neighboring cell, and so keeps
+    /// near points together better
+    Hilbert,
+    /// The Z-order curve, whose index is the bits of the column and row interleaved,
+    /// which is quicker to work out
+    Morton,
+}
+
+/// The position along the Hilbert curve of the cell holding `p`, in a grid of
+/// 2<sup>`order`</sup> by 2<sup>`order`</sup> cells over `bounds`. The curve starts in
+/// the bottom left cell and ends in the bottom right one. Points outside the bounds are
+/// taken to the nearest cell. Panics if `order` is more than 32.
+/// ```
+/// use rsgeo::{hilbert_index, Point, Rect};
+/// let bounds = Rect::from_corners(Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 2.0});
+/// let order: Vec<u64> = [(0.5, 0.5), (0.5, 1.5), (1.5, 1.5), (1.5, 0.5)]
+///     .iter()
+///     .map(|&p| hilbert_index(Point::from(p), &bounds, 1))
+///     .collect();
+/// assert_eq!(order, vec![0, 1, 2, 3]);
+/// ```
+pub fn hilbert_index<T: Scalar>(p: Point<T>, bounds: &Rect<T>, order: u32) -> u64 {
+    let (mut x, mut y) = cell(p, bounds, order);
+    let mut index = 0;
+    for level in (0..order).rev() {
+        let s = 1u64 << level;
+        let (rx, ry) = (u64::from(x & s != 0), u64::from(y & s != 0));
+        index += s * s * ((3 * rx) ^ ry);
+        // Turn the quarter around so that the curve in it runs the same way as the
+        // whole curve does
+        if ry == 0 {
+            if rx == 1 {
+                x = s.wrapping_sub(1).wrapping_sub(x);
+                y = s.wrapping_sub(1).wrapping_sub(y);
+            }
+            core::mem::swap(&mut x, &mut y);
+        }
+    }
+    index
+}
+
+/// The position along the Z-order curve of the cell holding `p`, in a grid of
+/// 2<sup>`order`</sup> by 2<sup>`order`</sup> cells over `bounds`, which has the bits
+/// of the column in its even places and those of the row in its odd places. Points
+/// outside the bounds are taken to the nearest cell. Panics if `order` is more than 32.
+/// ```
+/// use rsgeo::{morton_index, Point, Rect};
+/// let bounds = Rect::from_corners(Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 4.0});
+/// // Column 3 and row 1 are 0b11 and 0b01
+/// assert_eq!(morton_index(Point{x: 3.5, y: 1.5}, &bounds, 2), 0b0111);
+/// ```
+pub fn morton_index<T: Scalar>(p: Point<T>, bounds: &Rect<T>, order: u32) -> u64 {
+    let (x, y) = cell(p, bounds, order);
+    spread(x) | (spread(y) << 1)
+}
+
+/// Sort `points` by their position along `curve`, over a grid of 2<sup>32</sup> by
+/// 2<sup>32</sup> cells on their bounding rectangle. Points in the same cell keep no
+/// particular order.
+/// ```
+/// use rsgeo::{sort_by_space_filling_curve, Point, SpaceFillingCurve};
+/// let mut points: Vec<Point> = [(1.0, 0.0), (0.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+///     .iter()
+///     .map(|&p| Point::from(p))
+///     .collect();
+/// sort_by_space_filling_curve(&mut points, SpaceFillingCurve::Hilbert);
+/// assert_eq!(points[..2], [Point{x: 0.0, y: 0.0}, Point{x: 0.0, y: 1.0}]);
+/// ```
+pub fn sort_by_space_filling_curve<T: Scalar>(points: &mut [Point<T>], curve: SpaceFillingCurve) {
+    let bounds = match Rect::from_points(points.iter().copied()) {
+        Some(bounds) => bounds,
+        None => return,
+    };
+    match curve {
+        SpaceFillingCurve::Hilbert => {
+            points.sort_unstable_by_key(|&p| hilbert_index(p, &bounds, 32))
+        }
+        SpaceFillingCurve::Morton => points.sort_unstable_by_key(|&p| morton_index(p, &bounds, 32)),
+    }
+}
+
+/// The column and row of the cell holding `p`
+fn cell<T: Scalar>(p: Point<T>, bounds: &Rect<T>, order: u32) -> (u64, u64) {
+    assert!(order <= 32, "order must be at most 32, got {}", order);
+    let side = (1u64 << order) as f64;
+    let slot = |v: T, lo: T, size: T| {
+        if size <= T::zero() {
+            return 0;
+        }
+        let t = ((v - lo) / size).as_f64() * side;
+        t.max(0.0).min(side - 1.0) as u64
+    };
+    let (lo, hi) = (bounds.min(), bounds.max());
+    (slot(p.x, lo.x, hi.x - lo.x), slot(p.y, lo.y, hi.y - lo.y))
+}
+
+/// The bits of `v`, which fits in 32 bits, moved to the even places
+fn spread(v: u64) -> u64 {
+    let mut v = v & 0xffff_ffff;
+    v = (v | (v << 16)) & 0x0000_ffff_0000_ffff;
+    v = (v | (v << 8)) & 0x00ff_00ff_00ff_00ff;
+    v = (v | (v << 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    v = (v | (v << 2)) & 0x3333_3333_3333_3333;
+    (v | (v << 1)) & 0x5555_5555_5555_5555
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(side: f64) -> Rect {
+        Rect::from_corners(Point::origin(), Point { x: side, y: side })
+    }
+
+    #[test]
+    fn test_hilbert_steps_to_neighbors() {
+        let order = 4;
+        let bounds = grid(16.0);
+        let mut cells = [(0, 0); 256];
+        let mut seen = [false; 256];
+        for column in 0..16 {
+            for row in 0..16 {
+                let center = Point {
+                    x: column as f64 + 0.5,
+                    y: row as f64 + 0.5,
+                };
+                let i = hilbert_index(center, &bounds, order) as usize;
+                assert!(!seen[i]);
+                seen[i] = true;
+                cells[i] = (column, row);
+            }
+        }
+        assert_eq!((0, 0), cells[0]);
+        assert_eq!((15, 0), cells[255]);
+        for pair in cells.windows(2) {
+            let (a, b): ((i32, i32), (i32, i32)) = (pair[0], pair[1]);
+            assert_eq!(1, (a.0 - b.0).abs() + (a.1 - b.1).abs());
+        }
+    }
+
+    #[test]
+    fn test_morton_interleaves() {
+        let bounds = grid(256.0);
+        let at = |x: f64, y: f64| morton_index(Point { x, y }, &bounds, 8);
+        assert_eq!(0, at(0.0, 0.0));
+        assert_eq!(1, at(1.0, 0.0));
+        assert_eq!(2, at(0.0, 1.0));
+        assert_eq!(0b1010_0101, at(3.0, 12.0));
+        assert_eq!(u64::from(u16::MAX), at(255.5, 255.5));
+        // Outside the bounds is taken to the edge, and the top edge is in the last row
+        assert_eq!(at(255.0, 0.0), at(1000.0, -5.0));
+        assert_eq!(at(0.0, 255.0), at(0.0, 256.0));
+        let full = grid(1.0);
+        assert_eq!(u64::MAX, morton_index(Point { x: 1.0, y: 1.0 }, &full, 32));
+        assert_eq!(
+            u64::MAX / 3,
+            morton_index(Point { x: 1.0, y: 0.0 }, &full, 32)
+        );
+    }
+
+    #[test]
+    fn test_sorting_keeps_neighbors_close() {
+        // A 64 by 64 grid of points, shuffled
+        let mut points: Vec<Point> = (0..4096)
+            .map(|i| (i * 37) % 4096)
+            .map(|i| Point {
+                x: (i % 64) as f64,
+                y: (i / 64) as f64,
+            })
+            .collect();
+        let mean_step = |points: &[Point]| {
+            let total: f64 = points.windows(2).map(|w| w[0].distance(&w[1])).sum();
+            total / (points.len() - 1) as f64
+        };
+        assert!(mean_step(&points) > 10.0);
+        // Every step along the Hilbert curve is to a neighbor
+        sort_by_space_filling_curve(&mut points, SpaceFillingCurve::Hilbert);
+        assert_eq!(1.0, mean_step(&points));
+        sort_by_space_filling_curve(&mut points, SpaceFillingCurve::Morton);
+        assert_eq!(Point::origin(), points[0]);
+        assert!(mean_step(&points) < 2.0);
+        let mut empty: Vec<Point> = Vec::new();
+        sort_by_space_filling_curve(&mut empty, SpaceFillingCurve::Morton);
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,221 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::boolean::{cut_edges, oriented_edges, tidy_ring, windings_above};
+use crate::{Line, LineSegment, MultiPolygon, OrderedPoint, Point, Polygon, Polyline, Scalar};
+
+impl<T: Scalar> Polygon<T> {
+    /// The pieces that `line` cuts the polygon into, each winding counter-clockwise
+    /// with its holes clockwise. A line that misses the polygon, or only touches it,
+    /// leaves it in one piece. See `split_by_polyline`.
+    /// ```
+    /// use rsgeo::{Line, Point, Polygon};
+    /// let square = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 2.0}, Point{x: 0.0, y: 2.0},
+    /// ]);
+    /// let diagonal = Line::from_points(Point{x: 0.0, y: 0.0}, Point{x: 1.0, y: 1.0});
+    /// let halves = square.split_by_line(&diagonal);
+    /// assert_eq!(halves.len(), 2);
+    /// assert!(halves.iter().all(|h| h.vertices().len() == 3 && h.area() == 2.0));
+    /// ```
+    pub fn split_by_line(&self, line: &Line<T>) -> Vec<Polygon<T>> {
+        // The part of the line across the polygon's shadow on it, and as far again
+        // either side, which reaches beyond the polygon
+        let (p, d) = (line.point(), line.direction());
+        let along = |v: &Point<T>| (*v - p).dot_product(&d) / d.dot_product(&d);
+        let (lo, hi) = self
+            .vertices()
+            .iter()
+            .map(along)
+            .fold((T::infinity(), T::neg_infinity()), |(lo, hi), t| {
+                (lo.min(t), hi.max(t))
+            });
+        if lo > hi {
+            return Vec::new();
+        }
+        let pad = hi - lo;
+        split(
+            self,
+            &[LineSegment::new(p + d * (lo - pad), p + d * (hi + pad))],
+        )
+    }
+
+    /// The pieces that `cut` divides the polygon into, each winding counter-clockwise
+    /// with its holes clockwise. The boundary and the cut are split wherever they meet,
+    /// by the same sweep as `BooleanOps`, and each region they enclose inside the
+    /// polygon becomes a piece. Parts of the cut that do not divide the polygon, such
+    /// as an end left inside it, are ignored.
+    /// ```
+    /// use rsgeo::{Point, Polygon, Polyline};
+    /// let square = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 4.0}, Point{x: 0.0, y: 4.0},
+    /// ]);
+    /// // In through the bottom and out again, cutting off a triangle
+    /// let cut = Polyline::new(vec![
+    ///     Point{x: 1.0, y: -1.0}, Point{x: 2.0, y: 1.0}, Point{x: 3.0, y: -1.0},
+    /// ]);
+    /// let pieces = square.split_by_polyline(&cut);
+    /// assert_eq!(pieces.len(), 2);
+    /// assert_eq!(pieces.iter().map(Polygon::area).sum::<f64>(), 16.0);
+    /// ```
+    pub fn split_by_polyline(&self, cut: &Polyline<T>) -> Vec<Polygon<T>> {
+        split(self, &cut.segments().collect::<Vec<_>>())
+    }
+}
+
+fn split<T: Scalar>(poly: &Polygon<T>, cuts: &[LineSegment<T>]) -> Vec<Polygon<T>> {
+    let mut edges: Vec<LineSegment<T>> = oriented_edges(core::slice::from_ref(poly)).collect();
+    let mut shapes = alloc::vec![0; edges.len()];
+    edges.extend(cuts.iter().filter(|c| c.p1() != c.p2()));
+    shapes.resize(edges.len(), 1);
+    let pieces = cut_edges(&edges, &shapes);
+    let above = windings_above(&pieces);
+    // The polygon's boundary, with its interior on the left, and the pieces of the
+    // cuts with the interior on both sides
+    let (mut boundary, mut inner) = (Vec::new(), Vec::new());
+    for (&((left, right), rise), up) in pieces.iter().zip(above) {
+        match (up[0] - rise[0] > 0, up[0] > 0) {
+            (false, true) => boundary.push((left, right)),
+            (true, false) => boundary.push((right, left)),
+            (true, true) if rise[0] == 0 => inner.push((left, right)),
+            _ => {}
+        }
+    }
+
+    // Cuts that end inside the polygon divide nothing, so prune them back from their
+    // loose ends
+    let mut degree: BTreeMap<OrderedPoint<T>, usize> = BTreeMap::new();
+    let mut touching: BTreeMap<OrderedPoint<T>, Vec<usize>> = BTreeMap::new();
+    for &(a, b) in boundary.iter().chain(&inner) {
+        *degree.entry(a.into()).or_default() += 1;
+        *degree.entry(b.into()).or_default() += 1;
+    }
+    for (k, &(a, b)) in inner.iter().enumerate() {
+        touching.entry(a.into()).or_default().push(k);
+        touching.entry(b.into()).or_default().push(k);
+    }
+    let mut pruned = alloc::vec![false; inner.len()];
+    let mut loose: Vec<OrderedPoint<T>> = degree
+        .iter()
+        .filter(|&(_, &d)| d == 1)
+        .map(|(&p, _)| p)
+        .collect();
+    while let Some(p) = loose.pop() {
+        for &k in touching.get(&p).into_iter().flatten() {
+            if pruned[k] {
+                continue;
+            }
+            pruned[k] = true;
+            for end in [inner[k].0, inner[k].1] {
+                let d = degree.get_mut(&end.into()).unwrap();
+                *d -= 1;
+                if *d == 1 {
+                    loose.push(end.into());
+                }
+            }
+        }
+    }
+    for (&(a, b), _) in inner.iter().zip(pruned).filter(|(_, pruned)| !pruned) {
+        boundary.push((a, b));
+        boundary.push((b, a));
+    }
+    MultiPolygon::from_boundary(&boundary)
+        .polygons()
+        .iter()
+        .map(|piece| {
+            let holes = piece.holes().iter().map(|h| tidy_ring(h)).collect();
+            Polygon::with_holes(tidy_ring(piece.vertices()), holes)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::predicates::Orientation;
+
+    fn square(lo: f64, hi: f64) -> Vec<Point> {
+        vec![
+            Point { x: lo, y: lo },
+            Point { x: hi, y: lo },
+            Point { x: hi, y: hi },
+            Point { x: lo, y: hi },
+        ]
+    }
+
+    fn path(points: &[(f64, f64)]) -> Polyline {
+        points.iter().map(|&(x, y)| Point { x, y }).collect()
+    }
+
+    #[test]
+    fn test_split_by_line() {
+        let mut hole = square(2.0, 4.0);
+        hole[1..].reverse();
+        let frame = Polygon::with_holes(square(0.0, 6.0), vec![hole]);
+        let across = Line::new(Point { x: 0.0, y: 3.0 }, Point { x: 1.0, y: 0.0 });
+        // Through the hole, leaving two U shapes
+        let pieces = frame.split_by_line(&across);
+        assert_eq!(2, pieces.len());
+        for piece in &pieces {
+            assert_eq!(8, piece.vertices().len());
+            assert_eq!(16.0, piece.area());
+            assert!(piece.holes().is_empty());
+        }
+        // Past the hole, which stays in the lower piece
+        let low = Line::new(Point { x: 0.0, y: 5.0 }, Point { x: -1.0, y: 0.0 });
+        let pieces = frame.split_by_line(&low);
+        assert_eq!(2, pieces.len());
+        let with_hole: Vec<_> = pieces.iter().filter(|p| !p.holes().is_empty()).collect();
+        assert_eq!(1, with_hole.len());
+        assert_eq!(30.0 - 4.0, with_hole[0].area());
+        // Missing, or running along an edge
+        let miss = Line::new(Point { x: 0.0, y: 9.0 }, Point { x: 1.0, y: 1.0 });
+        assert_eq!(vec![frame.clone()], frame.split_by_line(&miss));
+        let edge = Line::new(Point { x: 6.0, y: 0.0 }, Point { x: 0.0, y: 1.0 });
+        assert_eq!(vec![frame.clone()], frame.split_by_line(&edge))
+    }
+
+    #[test]
+    fn test_split_concave() {
+        // A U, cut across both arms
+        let u = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 3.0, y: 0.0 },
+            Point { x: 3.0, y: 3.0 },
+            Point { x: 2.0, y: 3.0 },
+            Point { x: 2.0, y: 1.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 1.0, y: 3.0 },
+            Point { x: 0.0, y: 3.0 },
+        ]);
+        let across = Line::new(Point { x: 0.0, y: 2.0 }, Point { x: 1.0, y: 0.0 });
+        let pieces = u.split_by_line(&across);
+        assert_eq!(3, pieces.len());
+        assert_eq!(7.0, pieces.iter().map(Polygon::area).sum::<f64>());
+        for piece in &pieces {
+            assert_eq!(Orientation::CounterClockwise, piece.orientation());
+        }
+    }
+
+    #[test]
+    fn test_split_by_polyline() {
+        let poly = Polygon::new(square(0.0, 4.0));
+        // A zigzag right across, and one that stops halfway
+        let zigzag = path(&[(-1.0, 1.0), (1.0, 3.0), (3.0, 1.0), (5.0, 3.0)]);
+        let pieces = poly.split_by_polyline(&zigzag);
+        assert_eq!(2, pieces.len());
+        assert!(crate::scalar_isclose(
+            16.0,
+            pieces.iter().map(Polygon::area).sum()
+        ));
+        let halfway = path(&[(-1.0, 2.0), (2.0, 2.0), (2.0, 3.0)]);
+        assert_eq!(vec![poly.clone()], poly.split_by_polyline(&halfway));
+        // Across, with a branch left dangling inside off the end of a loop
+        let looped = path(&[(2.0, -1.0), (2.0, 5.0), (3.0, 5.0), (3.0, 4.0), (3.0, 3.0)]);
+        let pieces = poly.split_by_polyline(&looped);
+        assert_eq!(2, pieces.len());
+        assert!(pieces
+            .iter()
+            .all(|p| p.area() == 8.0 && p.vertices().len() == 4))
+    }
+}
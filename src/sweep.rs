@@ -0,0 +1,565 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::predicates::orient2d;
+use crate::{LineSegment, OrderedPoint, Point, Scalar, SegmentIntersection};
+
+/// Every place where two of `segments` meet, as `(i, j, intersection)` where `i < j`
+/// index `segments`, sorted by `i` and then `j`. Collinear segments that overlap are
+/// reported with the shared segment, and segments that only touch, at an end or
+/// otherwise, are reported with the point where they touch.
+///
+/// Uses the Bentley–Ottmann sweep line, which takes O((n + k) log n) time for `n`
+/// segments meeting in `k` places. Events are ordered lexicographically by their
+/// coordinates, so vertical segments and events that share an x coordinate need no
+/// special handling. Segments are only ever compared at the ends of segments, with
+/// exact orientation predicates, and where two cross the sweep swaps them in the
+/// status rather than comparing them at the rounded crossing point. Whether a pair
+/// has yet to cross is also decided exactly, so each pair swaps at most once. A pair
+/// whose crossing rounds to beyond an event it passes close by is put in order there.
+/// ```
+/// use rsgeo::{segment_intersections, LineSegment, Point, SegmentIntersection};
+/// let segments = [
+///     LineSegment::new(Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 2.0}),
+///     LineSegment::new(Point{x: 0.0, y: 2.0}, Point{x: 2.0, y: 0.0}),
+///     LineSegment::new(Point{x: 3.0, y: 0.0}, Point{x: 3.0, y: 2.0}),
+/// ];
+/// let found = segment_intersections(&segments);
+/// assert_eq!(found, vec![(0, 1, SegmentIntersection::Point(Point{x: 1.0, y: 1.0}))]);
+/// ```
+pub fn segment_intersections<T: Scalar>(
+    segments: &[LineSegment<T>],
+) -> Vec<(usize, usize, SegmentIntersection<T>)> {
+    let mut sweep = Sweep {
+        segments,
+        ends: Vec::with_capacity(segments.len()),
+        events: BTreeMap::new(),
+        status: Status::new(segments.len()),
+        active: alloc::vec![false; segments.len()],
+        pairs: BTreeSet::new(),
+        near: 0.0,
+    };
+    for (i, s) in segments.iter().enumerate() {
+        let (a, b) = (OrderedPoint::new(s.p1()), OrderedPoint::new(s.p2()));
+        let (left, right) = if b < a { (b, a) } else { (a, b) };
+        sweep.ends.push((left.point(), right.point()));
+        sweep.events.entry(left).or_default().starts.push(i);
+        if right != left {
+            sweep.events.entry(right).or_default().ends.push(i);
+        }
+    }
+    let scale = sweep
+        .ends
+        .iter()
+        .flat_map(|&(a, b)| [a, b])
+        .fold(0.0, |m: f64, p| {
+            m.max(p.x.as_f64().abs()).max(p.y.as_f64().abs())
+        });
+    sweep.near = scale * f64::EPSILON * 64.0;
+    while let Some((at, event)) = sweep.events.pop_first() {
+        sweep.handle(at, event);
+    }
+
+    sweep
+        .pairs
+        .into_iter()
+        .filter_map(|(i, j)| match segments[i].intersect(&segments[j]) {
+            SegmentIntersection::None => None,
+            hit => Some((i, j, hit)),
+        })
+        .collect()
+}
+
+/// What happens at a point of the sweep: the segments that start and end there, and
+/// the pairs of neighbouring segments, below and above, that cross there
+#[derive(Default)]
+struct Event {
+    starts: Vec<usize>,
+    ends: Vec<usize>,
+    crossings: Vec<(usize, usize)>,
+}
+
+struct Sweep<'a, T> {
+    segments: &'a [LineSegment<T>],
+    /// The ends of each segment, in sweep order
+    ends: Vec<(Point<T>, Point<T>)>,
+    events: BTreeMap<OrderedPoint<T>, Event>,
+    /// The segments crossing the sweep line, from bottom to top
+    status: Status,
+    active: Vec<bool>,
+    /// Pairs of segments that have been found to meet, or might
+    pairs: BTreeSet<(usize, usize)>,
+    /// How far from a point a segment can pass and still be out of order around it
+    near: f64,
+}
+
+impl<'a, T: Scalar> Sweep<'a, T> {
+    /// Which side of segment `s` the point `p` is on: Greater for above
+    fn side(&self, s: usize, p: Point<T>) -> Ordering {
+        let (left, right) = self.ends[s];
+        orient2d(left, right, p)
+            .partial_cmp(&0.0)
+            .unwrap_or(Ordering::Equal)
+    }
+
+    fn meet(&mut self, a: usize, b: usize) {
+        self.pairs.insert((a.min(b), a.max(b)));
+    }
+
+    fn handle(&mut self, at: OrderedPoint<T>, event: Event) {
+        let p = at.point();
+        let mut touched = self.settle(p);
+        // The segments passing through `p` sit together in the status
+        let passing = |sweep: &Self, on: bool| {
+            sweep.status.partition_point(|s| {
+                let side = sweep.side(s, p);
+                side == Ordering::Greater || (on && side == Ordering::Equal)
+            })
+        };
+        let (lo, hi) = (passing(self, false), passing(self, true));
+        let mut meeting: BTreeSet<usize> = (lo..hi).map(|k| self.status.select(k)).collect();
+        meeting.extend(event.ends.iter().filter(|&&s| self.active[s]));
+        let everyone: Vec<usize> = meeting.iter().chain(&event.starts).copied().collect();
+        for (k, &a) in everyone.iter().enumerate() {
+            for &b in &everyone[k + 1..] {
+                self.meet(a, b);
+            }
+        }
+
+        // Take out the segments through `p` and put back those that carry on, ordered
+        // as they leave it, along with those that start there
+        for &s in &meeting {
+            touched.extend(self.remove(s));
+        }
+        let mut leaving: Vec<usize> = meeting
+            .into_iter()
+            .filter(|&s| OrderedPoint::new(self.ends[s].1) > at)
+            .chain(event.starts.into_iter().filter(|&s| {
+                let (left, right) = self.ends[s];
+                left != right
+            }))
+            .collect();
+        leaving.sort_by(|&a, &b| {
+            let (left, right) = self.ends[a];
+            if self.side(b, left) == Ordering::Equal && self.side(b, right) == Ordering::Equal {
+                a.cmp(&b)
+            } else {
+                self.side(b, right)
+            }
+        });
+        let at_rank = passing(self, false);
+        for (k, &s) in leaving.iter().enumerate() {
+            self.status.insert(at_rank + k, s);
+            self.active[s] = true;
+        }
+        touched.extend(leaving);
+
+        // Swap the pairs that cross here, unless something has come between them or
+        // they have already crossed
+        for (a, b) in event.crossings {
+            if !(self.active[a] && self.active[b]) {
+                continue;
+            }
+            let k = self.status.rank(a);
+            if self.status.get(k + 1) == Some(b) && self.crosses_ahead(a, b) {
+                self.status.remove(b);
+                self.status.insert(k, b);
+                touched.extend([a, b]);
+            }
+        }
+
+        // Only segments that have just become neighbours can newly be found to cross
+        for s in touched {
+            if !self.active[s] {
+                continue;
+            }
+            let k = self.status.rank(s);
+            if let Some(below) = k.checked_sub(1).and_then(|k| self.status.get(k)) {
+                self.check(at, below, s);
+            }
+            // Checking below can swap `s` down straight away
+            if let Some(above) = self.status.get(self.status.rank(s) + 1) {
+                self.check(at, s, above);
+            }
+        }
+    }
+
+    /// A pair that crosses just before `p` can still be waiting to swap at a rounded
+    /// point beyond it, and then the status is out of order around `p`. Such pairs pass
+    /// within rounding of `p`, so the segments close to it are put in order of which
+    /// side of them it is on, and noted as meeting. Returns the segments that moved.
+    fn settle(&mut self, p: Point<T>) -> Vec<usize> {
+        let close = |sweep: &Self, s: usize| {
+            let (left, right) = sweep.ends[s];
+            orient2d(left, right, p).abs() <= sweep.near * (right - left).magnitude().as_f64()
+        };
+        let k = self
+            .status
+            .partition_point(|s| self.side(s, p) == Ordering::Greater);
+        let mut lo = k;
+        while lo > 0 && close(self, self.status.select(lo - 1)) {
+            lo -= 1;
+        }
+        let mut hi = k;
+        while self.status.get(hi).is_some_and(|s| close(self, s)) {
+            hi += 1;
+        }
+        let block: Vec<usize> = (lo..hi).map(|k| self.status.select(k)).collect();
+        let mut sorted = block.clone();
+        sorted.sort_by_key(|&s| core::cmp::Reverse(self.side(s, p)));
+        if sorted == block {
+            return Vec::new();
+        }
+        // Segments that were out of order have crossed
+        for (k, &a) in block.iter().enumerate() {
+            for &b in &block[k + 1..] {
+                self.meet(a, b);
+            }
+        }
+        for &s in &block {
+            self.status.remove(s);
+        }
+        for (k, &s) in sorted.iter().enumerate() {
+            self.status.insert(lo + k, s);
+        }
+        block
+    }
+
+    /// Take segment `s` out of the status, returning its neighbours
+    fn remove(&mut self, s: usize) -> Vec<usize> {
+        if !self.active[s] {
+            return Vec::new();
+        }
+        let k = self.status.rank(s);
+        let neighbours = k.checked_sub(1).into_iter().chain([k + 1]);
+        let found = neighbours.filter_map(|k| self.status.get(k)).collect();
+        self.status.remove(s);
+        self.active[s] = false;
+        found
+    }
+
+    /// Whether `a`, just below `b`, ends above it while `b` ends below `a`, so that
+    /// they have yet to cross
+    fn crosses_ahead(&self, a: usize, b: usize) -> bool {
+        self.side(b, self.ends[a].1) == Ordering::Greater
+            && self.side(a, self.ends[b].1) == Ordering::Less
+    }
+
+    /// Note whether neighbours `a`, below, and `b`, above, meet, and if they have yet to
+    /// cross arrange for them to swap where they do. A crossing point that rounds to
+    /// the sweep line or behind it is dealt with straight away, since an event there
+    /// would look for segments through the point with the pair already swapped.
+    fn check(&mut self, at: OrderedPoint<T>, a: usize, b: usize) {
+        let q = match self.segments[a].intersect(&self.segments[b]) {
+            SegmentIntersection::None => return,
+            SegmentIntersection::Segment(_) => return self.meet(a, b),
+            SegmentIntersection::Point(q) => OrderedPoint::new(q),
+        };
+        self.meet(a, b);
+        if self.crosses_ahead(a, b) {
+            let end = OrderedPoint::new(self.ends[a].1).min(OrderedPoint::new(self.ends[b].1));
+            let when = q.min(end);
+            if when > at {
+                self.events.entry(when).or_default().crossings.push((a, b));
+            } else if self.status.get(self.status.rank(a) + 1) == Some(b) {
+                let k = self.status.rank(a);
+                self.status.remove(b);
+                self.status.insert(k, b);
+                if let Some(below) = k.checked_sub(1).and_then(|k| self.status.get(k)) {
+                    self.check(at, below, b);
+                }
+                if let Some(above) = self.status.get(self.status.rank(a) + 1) {
+                    self.check(at, a, above);
+                }
+            }
+        }
+    }
+}
+
+/// A sequence of segments kept as a treap, a binary search tree balanced by random
+/// priorities, ordered by position rather than by key. Each segment has at most one
+/// node, which is found directly, so segments can be removed, moved and ranked
+/// without comparing them with anything.
+pub(crate) struct Status {
+    nodes: Vec<Node>,
+    root: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Node {
+    left: usize,
+    right: usize,
+    parent: usize,
+    size: usize,
+    priority: u64,
+}
+
+/// Stands in for a missing node
+const NIL: usize = usize::MAX;
+
+impl Status {
+    pub(crate) fn new(n: usize) -> Status {
+        let nodes = (0..n as u64)
+            .map(|i| Node {
+                left: NIL,
+                right: NIL,
+                parent: NIL,
+                size: 1,
+                // Any well mixed function of the index will do
+                priority: (i + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15).rotate_left(31),
+            })
+            .collect();
+        Status { nodes, root: NIL }
+    }
+
+    fn size(&self, t: usize) -> usize {
+        if t == NIL {
+            0
+        } else {
+            self.nodes[t].size
+        }
+    }
+
+    /// Recompute the size of `t` and point its children back at it
+    fn update(&mut self, t: usize) {
+        let Node { left, right, .. } = self.nodes[t];
+        self.nodes[t].size = 1 + self.size(left) + self.size(right);
+        for child in [left, right] {
+            if child != NIL {
+                self.nodes[child].parent = t;
+            }
+        }
+    }
+
+    /// Join two trees, with every node of `a` coming first
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL || b == NIL {
+            return if a == NIL { b } else { a };
+        }
+        if self.nodes[a].priority > self.nodes[b].priority {
+            let right = self.merge(self.nodes[a].right, b);
+            self.nodes[a].right = right;
+            self.update(a);
+            a
+        } else {
+            let left = self.merge(a, self.nodes[b].left);
+            self.nodes[b].left = left;
+            self.update(b);
+            b
+        }
+    }
+
+    /// Split a tree into its first `k` nodes and the rest
+    fn split(&mut self, t: usize, k: usize) -> (usize, usize) {
+        if t == NIL {
+            return (NIL, NIL);
+        }
+        let left = self.nodes[t].left;
+        if self.size(left) >= k {
+            let (a, b) = self.split(left, k);
+            self.nodes[t].left = b;
+            self.update(t);
+            (a, t)
+        } else {
+            let (a, b) = self.split(self.nodes[t].right, k - self.size(left) - 1);
+            self.nodes[t].right = a;
+            self.update(t);
+            (t, b)
+        }
+    }
+
+    fn set_root(&mut self, t: usize) {
+        self.root = t;
+        if t != NIL {
+            self.nodes[t].parent = NIL;
+        }
+    }
+
+    /// Put segment `s` in at position `k`
+    pub(crate) fn insert(&mut self, k: usize, s: usize) {
+        self.nodes[s] = Node {
+            left: NIL,
+            right: NIL,
+            parent: NIL,
+            size: 1,
+            ..self.nodes[s]
+        };
+        let (a, b) = self.split(self.root, k);
+        let a = self.merge(a, s);
+        let root = self.merge(a, b);
+        self.set_root(root);
+    }
+
+    pub(crate) fn remove(&mut self, s: usize) {
+        let (a, b) = self.split(self.root, self.rank(s));
+        let (_, c) = self.split(b, 1);
+        let root = self.merge(a, c);
+        self.set_root(root);
+    }
+
+    /// The position of segment `s`, which must be in the status
+    pub(crate) fn rank(&self, s: usize) -> usize {
+        let mut rank = self.size(self.nodes[s].left);
+        let mut t = s;
+        while self.nodes[t].parent != NIL {
+            let parent = self.nodes[t].parent;
+            if self.nodes[parent].right == t {
+                rank += self.size(self.nodes[parent].left) + 1;
+            }
+            t = parent;
+        }
+        rank
+    }
+
+    /// The segment at position `k`
+    pub(crate) fn get(&self, k: usize) -> Option<usize> {
+        (k < self.size(self.root)).then(|| self.select(k))
+    }
+
+    fn select(&self, mut k: usize) -> usize {
+        let mut t = self.root;
+        loop {
+            let left = self.size(self.nodes[t].left);
+            match k.cmp(&left) {
+                Ordering::Less => t = self.nodes[t].left,
+                Ordering::Equal => return t,
+                Ordering::Greater => {
+                    k -= left + 1;
+                    t = self.nodes[t].right;
+                }
+            }
+        }
+    }
+
+    /// The number of leading segments for which `pred` holds, given that it holds for
+    /// some first part of the status and for nothing after
+    pub(crate) fn partition_point(&self, pred: impl Fn(usize) -> bool) -> usize {
+        let (mut t, mut count) = (self.root, 0);
+        while t != NIL {
+            if pred(t) {
+                count += self.size(self.nodes[t].left) + 1;
+                t = self.nodes[t].right;
+            } else {
+                t = self.nodes[t].left;
+            }
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lcg;
+
+    fn brute_force(segments: &[LineSegment]) -> Vec<(usize, usize, SegmentIntersection)> {
+        let mut found = Vec::new();
+        for (i, a) in segments.iter().enumerate() {
+            for (j, b) in segments.iter().enumerate().skip(i + 1) {
+                match a.intersect(b) {
+                    SegmentIntersection::None => {}
+                    hit => found.push((i, j, hit)),
+                }
+            }
+        }
+        found
+    }
+
+    fn random_segments(n: usize, seed: u64, coord: impl Fn(&mut Lcg) -> f64) -> Vec<LineSegment> {
+        let mut rng = Lcg::new(seed);
+        let mut next = || coord(&mut rng);
+        (0..n)
+            .map(|_| {
+                let p1 = Point {
+                    x: next(),
+                    y: next(),
+                };
+                LineSegment::new(
+                    p1,
+                    Point {
+                        x: next(),
+                        y: next(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        // Arbitrary coordinates, where crossings are rounded
+        let scattered = random_segments(300, 5, |rng| rng.unit());
+        assert_eq!(brute_force(&scattered), segment_intersections(&scattered));
+        // Coordinates on a coarse grid, with many shared ends, collinear overlaps,
+        // vertical segments and several segments through one point
+        for seed in 0..20 {
+            let snapped = random_segments(60, seed, |rng| rng.below(8) as f64);
+            assert_eq!(brute_force(&snapped), segment_intersections(&snapped));
+        }
+    }
+
+    #[test]
+    fn test_through_rounded_crossings() {
+        // Segments between the ends of a few others and the rounded points where they
+        // cross, so that many pass within rounding of each other's ends
+        for seed in 0..300 {
+            let base = random_segments(6, seed, |rng| rng.unit());
+            let mut points: Vec<Point> = base.iter().flat_map(|s| [s.p1(), s.p2()]).collect();
+            for (_, _, hit) in segment_intersections(&base) {
+                if let SegmentIntersection::Point(p) = hit {
+                    points.push(p);
+                }
+            }
+            let n = points.len();
+            let segments: Vec<LineSegment> = (0..40)
+                .map(|k| LineSegment::new(points[k % n], points[(k * 7 + 3 + k / n) % n]))
+                .filter(|s| s.p1() != s.p2())
+                .collect();
+            assert_eq!(brute_force(&segments), segment_intersections(&segments));
+        }
+    }
+
+    #[test]
+    fn test_street_grid() {
+        // Ten streets each way, the edges of which meet end to end at every junction
+        let mut segments = Vec::new();
+        for i in 0..10 {
+            for j in 0..9 {
+                let (a, b) = (f64::from(i), f64::from(j));
+                segments.push(LineSegment::new(
+                    Point { x: a, y: b },
+                    Point { x: a, y: b + 1.0 },
+                ));
+                segments.push(LineSegment::new(
+                    Point { x: b, y: a },
+                    Point { x: b + 1.0, y: a },
+                ));
+            }
+        }
+        let found = segment_intersections(&segments);
+        assert_eq!(brute_force(&segments), found);
+        assert!(found
+            .iter()
+            .all(|(_, _, hit)| matches!(hit, SegmentIntersection::Point(_))));
+    }
+
+    #[test]
+    fn test_degenerate_segments() {
+        assert!(segment_intersections::<f64>(&[]).is_empty());
+        let point = Point { x: 1.0, y: 1.0 };
+        let segments = [
+            LineSegment::new(Point { x: 0.0, y: 0.0 }, Point { x: 4.0, y: 4.0 }),
+            // A single point on the first segment
+            LineSegment::new(point, point),
+            // Collinear with the first, and overlapping it
+            LineSegment::new(Point { x: 5.0, y: 5.0 }, Point { x: 2.0, y: 2.0 }),
+            // Vertical, meeting the first at one end
+            LineSegment::new(Point { x: 4.0, y: 4.0 }, Point { x: 4.0, y: 0.0 }),
+        ];
+        let found = segment_intersections(&segments);
+        assert_eq!(brute_force(&segments), found);
+        assert_eq!(4, found.len());
+        assert_eq!((0, 1, SegmentIntersection::Point(point)), found[0])
+    }
+}
@@ -0,0 +1,228 @@
+use alloc::vec::Vec;
+
+use num_traits::Float;
+
+use crate::delaunay::delaunay_triangulation;
+use crate::{Point, Rect, Scalar, TriangleMesh};
+
+/// A triangulated irregular network: a surface over a triangle mesh with a value at
+/// each point, which are interpolated linearly across each triangle. The triangles
+/// are sorted into a grid of buckets over the mesh, so that finding the one under a
+/// query point only means checking the few that share its bucket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tin<T = f64> {
+    mesh: TriangleMesh<T>,
+    values: Vec<T>,
+    bounds: Option<Rect<T>>,
+    columns: usize,
+    rows: usize,
+    /// Where each bucket's triangles start in `members`, with one more at the end
+    starts: Vec<usize>,
+    members: Vec<usize>,
+}
+
+impl<T: Scalar> Tin<T> {
+    /// The surface over `mesh` taking `values[i]` at `mesh.points[i]`. Sorting the
+    /// triangles into buckets takes time in proportion to their number, provided they
+    /// are not too uneven in size. Panics if there is not one value for each point.
+    pub fn new(mesh: TriangleMesh<T>, values: Vec<T>) -> Tin<T> {
+        assert!(
+            values.len() == mesh.points.len(),
+            "values must have one entry per point, got {} for {} points",
+            values.len(),
+            mesh.points.len()
+        );
+        let used = mesh.triangles.iter().flatten().map(|&k| mesh.points[k]);
+        let bounds = Rect::from_points(used);
+        let n = mesh.triangles.len().max(1);
+        let (columns, rows) = match bounds {
+            Some(b) if b.width() > T::zero() && b.height() > T::zero() => {
+                // About one bucket for each triangle, and roughly square
+                let aspect = (b.width() / b.height()).as_f64();
+                let columns = (Float::ceil(Float::sqrt(n as f64 * aspect)) as usize).clamp(1, n);
+                (columns, n.div_ceil(columns))
+            }
+            _ => (1, 1),
+        };
+        let mut tin = Tin {
+            mesh,
+            values,
+            bounds,
+            columns,
+            rows,
+            starts: Vec::new(),
+            members: Vec::new(),
+        };
+        let mut buckets = alloc::vec![Vec::new(); columns * rows];
+        for t in 0..tin.mesh.triangles.len() {
+            let corners = tin.mesh.triangles[t].map(|k| tin.mesh.points[k]);
+            let (lo, hi) = match Rect::from_points(corners) {
+                Some(r) => (tin.bucket(r.min()), tin.bucket(r.max())),
+                None => continue,
+            };
+            for row in lo.1..=hi.1 {
+                for column in lo.0..=hi.0 {
+                    buckets[row * columns + column].push(t);
+                }
+            }
+        }
+        for bucket in buckets {
+            tin.starts.push(tin.members.len());
+            tin.members.extend(bucket);
+        }
+        tin.starts.push(tin.members.len());
+        tin
+    }
+
+    /// The surface over the Delaunay triangulation of `points`, taking `values[i]` at
+    /// `points[i]`. Panics if there is not one value for each point.
+    /// ```
+    /// use rsgeo::{Point, Tin};
+    /// let points = [
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 2.0}, Point{x: 0.0, y: 2.0},
+    /// ];
+    /// // Heights on a plane rising to the east
+    /// let tin = Tin::from_samples(&points, &[0.0, 4.0, 4.0, 0.0]);
+    /// assert_eq!(tin.interpolate_at(Point{x: 0.5, y: 1.5}), Some(1.0));
+    /// assert_eq!(tin.interpolate_at(Point{x: 3.0, y: 1.0}), None);
+    /// ```
+    pub fn from_samples(points: &[Point<T>], values: &[T]) -> Tin<T> {
+        let mesh = TriangleMesh {
+            points: points.to_vec(),
+            triangles: delaunay_triangulation(points).triangles,
+        };
+        Tin::new(mesh, values.to_vec())
+    }
+
+    /// The mesh the surface is over
+    pub fn mesh(&self) -> &TriangleMesh<T> {
+        &self.mesh
+    }
+
+    /// The value at each point of the mesh
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The value of the surface at `p`, weighting the values at the corners of the
+    /// triangle under it by the barycentric coordinates of `p`. On an edge between two
+    /// triangles either may be used, which gives the same value up to rounding. None
+    /// if `p` is not on the mesh.
+    pub fn interpolate_at(&self, p: Point<T>) -> Option<T> {
+        let t = self.locate(p)?;
+        let weights = self.mesh.triangle(t).barycentric(p)?;
+        let v = self.mesh.triangles[t].map(|k| self.values[k]);
+        Some(weights[0] * v[0] + weights[1] * v[1] + weights[2] * v[2])
+    }
+
+    /// A triangle that is not degenerate with `p` inside it or on its boundary
+    pub(crate) fn locate(&self, p: Point<T>) -> Option<usize> {
+        if !self.bounds?.contains(p) {
+            return None;
+        }
+        let (column, row) = self.bucket(p);
+        let bucket = row * self.columns + column;
+        self.members[self.starts[bucket]..self.starts[bucket + 1]]
+            .iter()
+            .copied()
+            .find(|&t| {
+                let triangle = self.mesh.triangle(t);
+                !triangle.is_degenerate() && triangle.contains(p)
+            })
+    }
+
+    /// The column and row of the bucket holding `p`, which is clamped to the grid
+    fn bucket(&self, p: Point<T>) -> (usize, usize) {
+        let b = match self.bounds {
+            Some(b) => b,
+            None => return (0, 0),
+        };
+        let index = |offset: T, size: T, count: usize| {
+            if size <= T::zero() {
+                return 0;
+            }
+            let i = (offset / size * T::from_f64(count as f64)).floor();
+            (i.max(T::zero()).as_f64() as usize).min(count - 1)
+        };
+        (
+            index(p.x - b.min().x, b.width(), self.columns),
+            index(p.y - b.min().y, b.height(), self.rows),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{scattered, Angle, Polygon};
+
+    fn plane(p: Point) -> f64 {
+        2.0 * p.x - 3.0 * p.y + 1.0
+    }
+
+    #[test]
+    fn test_reproduces_plane() {
+        let points = scattered(300, 2);
+        let values: Vec<f64> = points.iter().map(|&p| plane(p)).collect();
+        let tin = Tin::from_samples(&points, &values);
+        let hull = crate::convex_hull(&points, false);
+        for q in scattered(2000, 9) {
+            match tin.interpolate_at(q) {
+                Some(z) => assert!((z - plane(q)).abs() < 1e-9),
+                None => assert!(!hull.contains(q)),
+            }
+        }
+        // Each sample is given back, up to rounding
+        for (&p, &v) in points.iter().zip(&values) {
+            assert!((tin.interpolate_at(p).unwrap() - v).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_over_quality_mesh() {
+        let frame = Polygon::with_holes(
+            vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 6.0, y: 0.0 },
+                Point { x: 6.0, y: 6.0 },
+                Point { x: 0.0, y: 6.0 },
+            ],
+            vec![vec![
+                Point { x: 2.0, y: 2.0 },
+                Point { x: 4.0, y: 2.0 },
+                Point { x: 4.0, y: 4.0 },
+                Point { x: 2.0, y: 4.0 },
+            ]],
+        );
+        let mesh = frame.quality_mesh(Angle::from_degrees(25.0), Some(0.2));
+        let values: Vec<f64> = mesh.points.iter().map(|p| p.x * p.y).collect();
+        let tin = Tin::new(mesh, values);
+        // Nothing in the hole, and close to the surface elsewhere
+        assert_eq!(None, tin.interpolate_at(Point { x: 3.0, y: 3.0 }));
+        let z = tin.interpolate_at(Point { x: 1.0, y: 5.0 }).unwrap();
+        assert!((z - 5.0).abs() < 0.2);
+        assert_eq!(Some(36.0), tin.interpolate_at(Point { x: 6.0, y: 6.0 }));
+    }
+
+    #[test]
+    fn test_degenerate_samples() {
+        let line: Vec<Point> = (0..4)
+            .map(|i| Point {
+                x: i as f64,
+                y: 0.0,
+            })
+            .collect();
+        let tin = Tin::from_samples(&line, &[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(None, tin.interpolate_at(Point { x: 1.0, y: 0.0 }));
+        assert_eq!(
+            None,
+            Tin::<f64>::from_samples(&[], &[]).interpolate_at(Point::origin())
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_values_must_match() {
+        Tin::from_samples(&scattered(5, 1), &[1.0, 2.0]);
+    }
+}
@@ -0,0 +1,251 @@
+//! Floating point comparison support. This holds the `Tolerance` used to select a
+//! comparison mode per call, units-in-last-place comparisons, and implementations of
+//! the `approx` crate's comparison traits, so that geometry can be compared with
+//! `assert_abs_diff_eq!`, `assert_relative_eq!` and `assert_ulps_eq!` using whatever
+//! tolerances suit the caller's domain.
+
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+use crate::{scalar_isclose_with, LineSegment, Point, Scalar};
+
+/// How two floating point values should be compared for closeness
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tolerance<T = f64> {
+    /// Absolute and relative tolerance: `|a - b| <= atol + rtol * |b|`
+    AbsRel { atol: T, rtol: T },
+    /// At most this many representable values may lie between the two values. This
+    /// scales with the magnitude of the values, so it behaves the same for very large
+    /// and very small coordinates. Values either side of zero are as many ulps apart as
+    /// the representable values between them, with the two zeros counted as one, so
+    /// tiny values of opposite sign can be close while larger ones are not.
+    /// ```
+    /// use rsgeo::Tolerance;
+    /// let tiny = f64::from_bits(1);
+    /// assert!(Tolerance::Ulps(2).isclose(-tiny, tiny));
+    /// assert!(!Tolerance::Ulps(1).isclose(-tiny, tiny));
+    /// assert!(!Tolerance::Ulps(1000).isclose(-1e-300, 1e-300));
+    /// ```
+    Ulps(u32),
+}
+
+impl<T: Scalar> Default for Tolerance<T> {
+    /// The absolute and relative tolerance used by the `isclose` methods
+    fn default() -> Self {
+        Tolerance::AbsRel {
+            atol: T::DEFAULT_ATOL,
+            rtol: T::DEFAULT_RTOL,
+        }
+    }
+}
+
+impl<T: Scalar> Tolerance<T> {
+    /// Check whether `a` and `b` are close under this tolerance
+    pub fn isclose(&self, a: T, b: T) -> bool {
+        match *self {
+            Tolerance::AbsRel { atol, rtol } => scalar_isclose_with(a, b, atol, rtol),
+            Tolerance::Ulps(max_ulps) => ulps_distance(a, b) <= u64::from(max_ulps),
+        }
+    }
+}
+
+/// The number of representable values between `a` and `b`, at the precision of their
+/// type. The two zeros are zero ulps apart, and any comparison with NaN is `u64::MAX`
+/// ulps apart.
+/// ```
+/// assert_eq!(rsgeo::ulps_distance(1.0, 1.0 + f64::EPSILON), 1);
+/// assert_eq!(rsgeo::ulps_distance(-0.0, 0.0), 0);
+/// ```
+pub fn ulps_distance<T: Scalar>(a: T, b: T) -> u64 {
+    a.ulps_distance(b)
+}
+
+impl<T: Scalar> Point<T> {
+    /// Check if two points are close to eachother using the given comparison mode
+    /// ```
+    /// use rsgeo::{Point, Tolerance};
+    /// let p = Point{x: 0.1 + 0.2, y: 1e300};
+    /// let q = Point{x: 0.3, y: 1e300};
+    /// assert!(p.isclose_by(q, Tolerance::Ulps(1)));
+    /// assert!(!p.isclose_by(q, Tolerance::Ulps(0)));
+    /// ```
+    pub fn isclose_by(&self, other: Point<T>, tol: Tolerance<T>) -> bool {
+        tol.isclose(self.x, other.x) && tol.isclose(self.y, other.y)
+    }
+
+    /// Check if both components of the two points are at most `max_ulps` representable
+    /// values apart. See `Tolerance::Ulps`.
+    pub fn isclose_ulps(&self, other: Point<T>, max_ulps: u32) -> bool {
+        self.isclose_by(other, Tolerance::Ulps(max_ulps))
+    }
+}
+
+impl<T: Scalar> LineSegment<T> {
+    /// Check if both endpoints are close using the given comparison mode
+    pub fn isclose_by(&self, other: &LineSegment<T>, tol: Tolerance<T>) -> bool {
+        self.p1().isclose_by(other.p1(), tol) && self.p2().isclose_by(other.p2(), tol)
+    }
+
+    /// Check if both endpoints are at most `max_ulps` representable values apart
+    pub fn isclose_ulps(&self, other: &LineSegment<T>, max_ulps: u32) -> bool {
+        self.isclose_by(other, Tolerance::Ulps(max_ulps))
+    }
+}
+
+impl<T: Scalar> AbsDiffEq for Point<T> {
+    type Epsilon = T;
+
+    fn default_epsilon() -> Self::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.x.abs_diff_eq(&other.x, epsilon) && self.y.abs_diff_eq(&other.y, epsilon)
+    }
+}
+
+impl<T: Scalar> RelativeEq for Point<T> {
+    fn default_max_relative() -> Self::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        self.x.relative_eq(&other.x, epsilon, max_relative)
+            && self.y.relative_eq(&other.y, epsilon, max_relative)
+    }
+}
+
+impl<T: Scalar> UlpsEq for Point<T> {
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        self.x.ulps_eq(&other.x, epsilon, max_ulps) && self.y.ulps_eq(&other.y, epsilon, max_ulps)
+    }
+}
+
+impl<T: Scalar> AbsDiffEq for LineSegment<T> {
+    type Epsilon = T;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Point::<T>::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.p1().abs_diff_eq(&other.p1(), epsilon) && self.p2().abs_diff_eq(&other.p2(), epsilon)
+    }
+}
+
+impl<T: Scalar> RelativeEq for LineSegment<T> {
+    fn default_max_relative() -> Self::Epsilon {
+        Point::<T>::default_max_relative()
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        self.p1().relative_eq(&other.p1(), epsilon, max_relative)
+            && self.p2().relative_eq(&other.p2(), epsilon, max_relative)
+    }
+}
+
+impl<T: Scalar> UlpsEq for LineSegment<T> {
+    fn default_max_ulps() -> u32 {
+        Point::<T>::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        self.p1().ulps_eq(&other.p1(), epsilon, max_ulps)
+            && self.p2().ulps_eq(&other.p2(), epsilon, max_ulps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::{assert_abs_diff_eq, assert_abs_diff_ne, assert_relative_eq, assert_ulps_eq};
+
+    #[test]
+    fn test_ulps_distance_across_zero() {
+        let tiny = f64::from_bits(1);
+        assert_eq!(1, ulps_distance(0.0, tiny));
+        assert_eq!(1, ulps_distance(-0.0, tiny));
+        assert_eq!(2, ulps_distance(-tiny, tiny))
+    }
+
+    #[test]
+    fn test_ulps_distance_nan_and_infinity() {
+        assert_eq!(u64::MAX, ulps_distance(f64::NAN, f64::NAN));
+        assert_eq!(1, ulps_distance(f64::MAX, f64::INFINITY));
+        assert_eq!(0, ulps_distance(f64::INFINITY, f64::INFINITY))
+    }
+
+    #[test]
+    fn test_ulps_is_scale_invariant() {
+        for &scale in &[1e-200, 1.0, 1e200] {
+            let a: f64 = scale;
+            let b = f64::from_bits(a.to_bits() + 3);
+            assert!(Point { x: a, y: a }.isclose_ulps(Point { x: b, y: a }, 3));
+            assert!(!Point { x: a, y: a }.isclose_ulps(Point { x: b, y: a }, 2))
+        }
+    }
+
+    #[test]
+    fn test_default_tolerance_matches_isclose() {
+        let p = Point { x: 1.0, y: 2.0 };
+        let q = Point {
+            x: 1.0 + 1e-9,
+            y: 2.0,
+        };
+        assert_eq!(p.isclose(q), p.isclose_by(q, Tolerance::default()));
+        let far = Point { x: 1.1, y: 2.0 };
+        assert_eq!(p.isclose(far), p.isclose_by(far, Tolerance::default()));
+        let seg = LineSegment::new(p, far);
+        assert!(seg.isclose_ulps(&seg, 0))
+    }
+
+    #[test]
+    fn test_point_abs_diff_eq() {
+        let p = Point { x: 1.0, y: 2.0 };
+        let q = Point { x: 1.05, y: 2.0 };
+        assert_abs_diff_eq!(p, q, epsilon = 0.1);
+        assert_abs_diff_ne!(p, q, epsilon = 0.01)
+    }
+
+    #[test]
+    fn test_point_relative_eq_large_coordinates() {
+        let p = Point { x: 1e12, y: -1e12 };
+        let q = Point {
+            x: 1e12 + 1.0,
+            y: -1e12,
+        };
+        assert_relative_eq!(p, q, max_relative = 1e-9)
+    }
+
+    #[test]
+    fn test_point_ulps_eq() {
+        let p = Point {
+            x: 0.1 + 0.2,
+            y: 1.0,
+        };
+        let q = Point { x: 0.3, y: 1.0 };
+        assert_ulps_eq!(p, q)
+    }
+
+    #[test]
+    fn test_segment_relative_eq() {
+        let s1 = LineSegment::new(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 });
+        let s2 = LineSegment::new(Point { x: 1e-9, y: 0.0 }, Point { x: 1.0, y: 1.0 });
+        assert_relative_eq!(s1, s2, epsilon = 1e-8);
+        assert!(s1.isclose_with(&s2, 1e-8, 0.0));
+        assert!(!s1.isclose_with(&s2, 1e-10, 0.0))
+    }
+}
@@ -0,0 +1,626 @@
+//! Spatial predicates between any two of `Point`, `LineSegment`, `Polyline`,
+//! `Polygon`, `Circle` and `Rect`, so that `a.intersects(&b)` works whichever two
+//! shapes you hold.
+//!
+//! Every shape is a closed set, so its boundary is part of it. The boundary of a
+//! segment or polyline is its two endpoints, and a closed polyline has none. The
+//! boundary of a shape that covers an area is its outline, including the outlines of
+//! any holes. A point has no boundary. The interior is whatever is left, and a
+//! degenerate shape is treated as what it really is: a circle of zero radius is a point
+//! and a rectangle of zero width is a segment.
+//!
+//! - `a.intersects(&b)`: the shapes share at least one point
+//! - `a.contains(&b)`: every point of `b` is in `a`, boundary included
+//! - `a.within(&b)`: `b.contains(&a)`
+//! - `a.touches(&b)`: the shapes meet, but their interiors do not
+//!
+//! `Polygon`, `Circle` and `Rect` have inherent `contains` methods that take a point,
+//! which method call syntax picks first. For other shapes use `b.within(&a)` or
+//! `Contains::contains(&a, &b)`.
+//!
+//! Intersection tests between points, segments, circles and rectangles don't allocate,
+//! and are available without the `alloc` feature.
+//! ```
+//! use rsgeo::{Circle, Contains, LineSegment, Point, Polygon, Rect, Touches, Within};
+//! let square = Polygon::new(vec![
+//!     Point{x: 0.0, y: 0.0}, Point{x: 2.0, y: 0.0}, Point{x: 2.0, y: 2.0}, Point{x: 0.0, y: 2.0},
+//! ]);
+//! let edge: LineSegment = "(0, 0) -> (2, 0)".parse().unwrap();
+//! assert!(edge.within(&square) && edge.touches(&square));
+//! let disk = Circle::new(Point{x: 3.0, y: 1.0}, 1.0);
+//! assert!(disk.touches(&square) && !Contains::contains(&square, &disk));
+//! assert!(Rect::from_corners(Point{x: 2.0, y: 0.0}, Point{x: 4.0, y: 2.0}).intersects(&disk));
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, vec::Vec};
+
+#[cfg(feature = "alloc")]
+use crate::polygon::{ring_edges, ring_orientation};
+#[cfg(feature = "alloc")]
+use crate::predicates::Orientation;
+use crate::{Circle, LineSegment, Point, Rect, Scalar, SegmentIntersection};
+#[cfg(feature = "alloc")]
+use crate::{Polygon, Polyline};
+
+pub trait Intersects<Rhs = Self> {
+    /// Whether the shapes share at least one point
+    fn intersects(&self, other: &Rhs) -> bool;
+}
+
+pub trait Contains<Rhs = Self> {
+    /// Whether every point of `other` lies in the shape or on its boundary
+    fn contains(&self, other: &Rhs) -> bool;
+}
+
+pub trait Within<Rhs = Self> {
+    /// Whether every point of the shape lies in `other` or on its boundary
+    fn within(&self, other: &Rhs) -> bool;
+}
+
+pub trait Touches<Rhs = Self> {
+    /// Whether the shapes meet only where at least one of them has its boundary
+    fn touches(&self, other: &Rhs) -> bool;
+}
+
+impl<A, B: Contains<A>> Within<B> for A {
+    fn within(&self, other: &B) -> bool {
+        Contains::contains(other, self)
+    }
+}
+
+/// A fixed-size shape reduced to the simplest kind that describes it
+#[derive(Clone, Copy)]
+enum Simple<T> {
+    Point(Point<T>),
+    Segment(LineSegment<T>),
+    Disk(Circle<T>),
+    Box(Rect<T>),
+}
+
+trait AsSimple<T> {
+    fn simple(&self) -> Simple<T>;
+}
+
+impl<T: Scalar> AsSimple<T> for Point<T> {
+    fn simple(&self) -> Simple<T> {
+        Simple::Point(*self)
+    }
+}
+
+impl<T: Scalar> AsSimple<T> for LineSegment<T> {
+    fn simple(&self) -> Simple<T> {
+        if self.p1() == self.p2() {
+            Simple::Point(self.p1())
+        } else {
+            Simple::Segment(*self)
+        }
+    }
+}
+
+impl<T: Scalar> AsSimple<T> for Circle<T> {
+    fn simple(&self) -> Simple<T> {
+        if self.radius == T::zero() {
+            Simple::Point(self.center)
+        } else {
+            Simple::Disk(*self)
+        }
+    }
+}
+
+impl<T: Scalar> AsSimple<T> for Rect<T> {
+    fn simple(&self) -> Simple<T> {
+        if self.width() == T::zero() || self.height() == T::zero() {
+            LineSegment::new(self.min(), self.max()).simple()
+        } else {
+            Simple::Box(*self)
+        }
+    }
+}
+
+fn meets<T: Scalar>(a: &LineSegment<T>, b: &LineSegment<T>) -> bool {
+    !matches!(a.intersect(b), SegmentIntersection::None)
+}
+
+fn simple_covers<T: Scalar>(s: &Simple<T>, p: Point<T>) -> bool {
+    match s {
+        Simple::Point(q) => *q == p,
+        Simple::Segment(seg) => seg.contains_point(p, T::zero()),
+        Simple::Disk(c) => c.contains(p),
+        Simple::Box(r) => r.contains(p),
+    }
+}
+
+fn simple_intersects<T: Scalar>(a: &Simple<T>, b: &Simple<T>) -> bool {
+    match (*a, *b) {
+        (Simple::Point(p), s) | (s, Simple::Point(p)) => simple_covers(&s, p),
+        (Simple::Segment(s), Simple::Segment(t)) => meets(&s, &t),
+        (Simple::Segment(s), Simple::Disk(c)) | (Simple::Disk(c), Simple::Segment(s)) => {
+            s.distance_squared_to_point(c.center) <= c.radius * c.radius
+        }
+        (Simple::Segment(s), Simple::Box(r)) | (Simple::Box(r), Simple::Segment(s)) => {
+            r.contains(s.p1()) || r.edges().any(|e| meets(&s, &e))
+        }
+        (Simple::Disk(c), Simple::Box(r)) | (Simple::Box(r), Simple::Disk(c)) => {
+            let (lo, hi) = (r.min(), r.max());
+            c.contains(Point {
+                x: c.center.x.max(lo.x).min(hi.x),
+                y: c.center.y.max(lo.y).min(hi.y),
+            })
+        }
+        (Simple::Disk(c), Simple::Disk(d)) => {
+            let reach = c.radius + d.radius;
+            c.center.distance_squared(&d.center) <= reach * reach
+        }
+        (Simple::Box(r), Simple::Box(s)) => {
+            r.min().x <= s.max().x
+                && s.min().x <= r.max().x
+                && r.min().y <= s.max().y
+                && s.min().y <= r.max().y
+        }
+    }
+}
+
+macro_rules! impl_simple_intersects {
+    ($a:ident, $b:ident) => {
+        impl<T: Scalar> Intersects<$b<T>> for $a<T> {
+            fn intersects(&self, other: &$b<T>) -> bool {
+                simple_intersects(&self.simple(), &other.simple())
+            }
+        }
+    };
+}
+
+for_pairs!(impl_simple_intersects;
+    [Point, LineSegment, Circle, Rect] x [Point, LineSegment, Circle, Rect]);
+
+/// Any shape reduced to the simplest kind that describes it
+#[cfg(feature = "alloc")]
+pub(crate) enum Shape<'a, T: Scalar> {
+    Empty,
+    Point(Point<T>),
+    /// The segments of nonzero length, and the endpoints that form the boundary
+    Curve(Vec<LineSegment<T>>, Vec<Point<T>>),
+    Region(Cow<'a, Polygon<T>>),
+    /// A circle of nonzero radius
+    Disk(Circle<T>),
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) trait AsShape<T: Scalar> {
+    fn shape(&self) -> Shape<'_, T>;
+}
+
+#[cfg(feature = "alloc")]
+fn simple_shape<'a, T: Scalar>(simple: Simple<T>) -> Shape<'a, T> {
+    match simple {
+        Simple::Point(p) => Shape::Point(p),
+        Simple::Segment(s) => Shape::Curve(alloc::vec![s], alloc::vec![s.p1(), s.p2()]),
+        Simple::Disk(c) => Shape::Disk(c),
+        Simple::Box(r) => Shape::Region(Cow::Owned(Polygon::new(r.corners().to_vec()))),
+    }
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! impl_simple_shape {
+    ($($t:ident),*) => {
+        $(
+            impl<T: Scalar> AsShape<T> for $t<T> {
+                fn shape(&self) -> Shape<'_, T> {
+                    simple_shape(self.simple())
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "alloc")]
+impl_simple_shape!(Point, LineSegment, Circle, Rect);
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> AsShape<T> for Polyline<T> {
+    fn shape(&self) -> Shape<'_, T> {
+        let segments: Vec<_> = self.segments().filter(|s| s.p1() != s.p2()).collect();
+        match (self.points().first(), self.points().last()) {
+            (Some(&first), _) if segments.is_empty() => Shape::Point(first),
+            (Some(&first), Some(&last)) if first != last => {
+                Shape::Curve(segments, alloc::vec![first, last])
+            }
+            (Some(_), _) => Shape::Curve(segments, Vec::new()),
+            _ => Shape::Empty,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Scalar> AsShape<T> for Polygon<T> {
+    fn shape(&self) -> Shape<'_, T> {
+        if self.vertices().is_empty() {
+            Shape::Empty
+        } else {
+            Shape::Region(Cow::Borrowed(self))
+        }
+    }
+}
+
+/// The edges of every ring of the polygon
+#[cfg(feature = "alloc")]
+pub(crate) fn boundary<T: Scalar>(poly: &Polygon<T>) -> impl Iterator<Item = LineSegment<T>> + '_ {
+    poly.rings().flat_map(ring_edges)
+}
+
+/// The boundary edges of the polygon, each with 1 if the interior lies to its left and
+/// -1 if it lies to its right
+#[cfg(feature = "alloc")]
+fn sided_boundary<T: Scalar>(poly: &Polygon<T>) -> impl Iterator<Item = (LineSegment<T>, T)> + '_ {
+    poly.rings().enumerate().flat_map(|(i, ring)| {
+        let ccw = ring_orientation(ring) == Orientation::CounterClockwise;
+        // Holes have the polygon's interior on their outside
+        let side = if ccw == (i == 0) { T::one() } else { -T::one() };
+        ring_edges(ring).map(move |e| (e, side))
+    })
+}
+
+#[cfg(feature = "alloc")]
+fn boundary_distance_squared<T: Scalar>(poly: &Polygon<T>, p: Point<T>) -> T {
+    boundary(poly)
+        .map(|e| e.distance_squared_to_point(p))
+        .fold(T::infinity(), T::min)
+}
+
+/// For each stretch of boundary that the polygons share, whether their interiors lie on
+/// the same side of it
+#[cfg(feature = "alloc")]
+pub(crate) fn shared_edge_sides<T: Scalar>(a: &Polygon<T>, b: &Polygon<T>) -> Vec<bool> {
+    let mut sides = Vec::new();
+    for (e, side_e) in sided_boundary(a) {
+        for (f, side_f) in sided_boundary(b) {
+            if let SegmentIntersection::Segment(_) = e.intersect(&f) {
+                let along = e.direction().dot_product(&f.direction());
+                sides.push(side_e * side_f * along > T::zero());
+            }
+        }
+    }
+    sides
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, PartialEq)]
+enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Split `seg` wherever it meets the boundary of `poly` and locate each piece. A piece
+/// either runs along the boundary or meets it only at its ends, in which case it lies
+/// entirely inside or outside, as its midpoint does.
+#[cfg(feature = "alloc")]
+fn locate_pieces<T: Scalar>(poly: &Polygon<T>, seg: &LineSegment<T>) -> Vec<Location> {
+    let d = seg.direction();
+    let len2 = d.dot_product(&d);
+    let param = |q: Point<T>| {
+        ((q - seg.p1()).dot_product(&d) / len2)
+            .max(T::zero())
+            .min(T::one())
+    };
+    let mut cuts = alloc::vec![T::zero(), T::one()];
+    let mut along = Vec::new();
+    for e in boundary(poly) {
+        match seg.intersect(&e) {
+            SegmentIntersection::None => {}
+            SegmentIntersection::Point(q) => cuts.push(param(q)),
+            SegmentIntersection::Segment(s) => {
+                let (lo, hi) = (param(s.p1()), param(s.p2()));
+                cuts.push(lo);
+                cuts.push(hi);
+                along.push((lo.min(hi), lo.max(hi)));
+            }
+        }
+    }
+    cuts.sort_by(|a, b| a.total_cmp(b));
+    cuts.dedup();
+    cuts.windows(2)
+        .map(|w| {
+            if along.iter().any(|&(lo, hi)| lo <= w[0] && w[1] <= hi) {
+                Location::Boundary
+            } else if poly.contains(seg.p1() + d * ((w[0] + w[1]) / T::from_f64(2.0))) {
+                Location::Inside
+            } else {
+                Location::Outside
+            }
+        })
+        .collect()
+}
+
+/// Whether the segments of a curve cover all of `seg`. Only collinear overlaps cover
+/// any length, and measured by their position along `seg` they must leave no gap.
+#[cfg(feature = "alloc")]
+fn curve_covers<T: Scalar>(curve: &[LineSegment<T>], seg: &LineSegment<T>) -> bool {
+    let d = seg.direction();
+    let len2 = d.dot_product(&d);
+    let param = |q: Point<T>| (q - seg.p1()).dot_product(&d) / len2;
+    let mut overlaps: Vec<_> = curve
+        .iter()
+        .filter_map(|e| match seg.intersect(e) {
+            SegmentIntersection::Segment(s) => Some((param(s.p1()), param(s.p2()))),
+            _ => None,
+        })
+        .collect();
+    overlaps.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut reach = T::zero();
+    for (lo, hi) in overlaps {
+        if lo > reach {
+            return false;
+        }
+        reach = reach.max(hi);
+    }
+    reach >= T::one()
+}
+
+#[cfg(feature = "alloc")]
+fn covers<T: Scalar>(shape: &Shape<T>, p: Point<T>) -> bool {
+    match shape {
+        Shape::Empty => false,
+        Shape::Point(q) => *q == p,
+        Shape::Curve(segments, _) => segments.iter().any(|s| s.contains_point(p, T::zero())),
+        Shape::Region(r) => r.contains(p),
+        Shape::Disk(c) => c.contains(p),
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn on_boundary<T: Scalar>(shape: &Shape<T>, p: Point<T>) -> bool {
+    match shape {
+        Shape::Empty | Shape::Point(_) => false,
+        Shape::Curve(_, ends) => ends.contains(&p),
+        Shape::Region(r) => boundary(r).any(|e| e.contains_point(p, T::zero())),
+        Shape::Disk(c) => c.center.distance_squared(&p) == c.radius * c.radius,
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn shape_intersects<T: Scalar>(a: &Shape<T>, b: &Shape<T>) -> bool {
+    match (a, b) {
+        (Shape::Empty, _) | (_, Shape::Empty) => false,
+        (Shape::Point(p), s) | (s, Shape::Point(p)) => covers(s, *p),
+        (Shape::Curve(x, _), Shape::Curve(y, _)) => x.iter().any(|s| y.iter().any(|t| meets(s, t))),
+        (Shape::Curve(c, _), Shape::Region(r)) | (Shape::Region(r), Shape::Curve(c, _)) => c
+            .iter()
+            .any(|s| r.contains(s.p1()) || boundary(r).any(|e| meets(s, &e))),
+        (Shape::Curve(c, _), Shape::Disk(d)) | (Shape::Disk(d), Shape::Curve(c, _)) => c
+            .iter()
+            .any(|s| s.distance_squared_to_point(d.center) <= d.radius * d.radius),
+        (Shape::Region(r), Shape::Region(s)) => {
+            // Without crossing boundaries, one polygon meets the other only by lying
+            // inside it
+            boundary(r).any(|e| boundary(s).any(|f| meets(&e, &f)))
+                || r.contains(s.vertices()[0])
+                || s.contains(r.vertices()[0])
+        }
+        (Shape::Region(r), Shape::Disk(d)) | (Shape::Disk(d), Shape::Region(r)) => {
+            r.contains(d.center) || boundary_distance_squared(r, d.center) <= d.radius * d.radius
+        }
+        (Shape::Disk(c), Shape::Disk(d)) => simple_intersects(&Simple::Disk(*c), &Simple::Disk(*d)),
+    }
+}
+
+/// Whether `b` lies entirely in `a`
+#[cfg(feature = "alloc")]
+fn shape_contains<T: Scalar>(a: &Shape<T>, b: &Shape<T>) -> bool {
+    let never_outside =
+        |r: &Polygon<T>, s: &LineSegment<T>| !locate_pieces(r, s).contains(&Location::Outside);
+    let never_inside =
+        |r: &Polygon<T>, s: &LineSegment<T>| !locate_pieces(r, s).contains(&Location::Inside);
+    match (a, b) {
+        (Shape::Empty, _) | (_, Shape::Empty) => false,
+        (s, Shape::Point(p)) => covers(s, *p),
+        // Anything else has an extent that a point, or a curve, cannot hold
+        (Shape::Point(_), _) => false,
+        (Shape::Curve(x, _), Shape::Curve(y, _)) => y.iter().all(|s| curve_covers(x, s)),
+        (Shape::Curve(..), _) => false,
+        (Shape::Region(r), Shape::Curve(c, _)) => c.iter().all(|s| never_outside(r, s)),
+        (Shape::Region(r), Shape::Region(s)) => {
+            // The boundary of `s` must stay in `r`, the boundary of `r` must not cut
+            // through `s`, and where they run together `s` must be on the inside
+            boundary(s).all(|e| never_outside(r, &e))
+                && boundary(r).all(|e| never_inside(s, &e))
+                && !shared_edge_sides(r, s).contains(&false)
+        }
+        (Shape::Region(r), Shape::Disk(d)) => {
+            r.contains(d.center) && boundary_distance_squared(r, d.center) >= d.radius * d.radius
+        }
+        (Shape::Disk(d), Shape::Curve(c, _)) => {
+            c.iter().all(|s| d.contains(s.p1()) && d.contains(s.p2()))
+        }
+        (Shape::Disk(d), Shape::Region(r)) => r.vertices().iter().all(|&v| d.contains(v)),
+        (Shape::Disk(c), Shape::Disk(d)) => c.center.distance(&d.center) + d.radius <= c.radius,
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn shape_touches<T: Scalar>(a: &Shape<T>, b: &Shape<T>) -> bool {
+    let never_inside =
+        |r: &Polygon<T>, s: &LineSegment<T>| !locate_pieces(r, s).contains(&Location::Inside);
+    match (a, b) {
+        (Shape::Empty, _) | (_, Shape::Empty) | (Shape::Point(_), Shape::Point(_)) => false,
+        (Shape::Point(p), s) | (s, Shape::Point(p)) => on_boundary(s, *p),
+        (Shape::Curve(x, x_ends), Shape::Curve(y, y_ends)) => {
+            let mut met = false;
+            for s in x {
+                for t in y {
+                    match s.intersect(t) {
+                        SegmentIntersection::None => {}
+                        SegmentIntersection::Segment(_) => return false,
+                        SegmentIntersection::Point(q) => {
+                            if !x_ends.contains(&q) && !y_ends.contains(&q) {
+                                return false;
+                            }
+                            met = true;
+                        }
+                    }
+                }
+            }
+            met
+        }
+        (Shape::Curve(c, _), Shape::Region(r)) | (Shape::Region(r), Shape::Curve(c, _)) => {
+            shape_intersects(a, b) && c.iter().all(|s| never_inside(r, s))
+        }
+        (Shape::Curve(c, _), Shape::Disk(d)) | (Shape::Disk(d), Shape::Curve(c, _)) => {
+            let nearest = c
+                .iter()
+                .map(|s| s.distance_squared_to_point(d.center))
+                .fold(T::infinity(), T::min);
+            nearest == d.radius * d.radius
+        }
+        (Shape::Region(r), Shape::Region(s)) => {
+            shape_intersects(a, b)
+                && boundary(r).all(|e| never_inside(s, &e))
+                && boundary(s).all(|e| never_inside(r, &e))
+                && !shared_edge_sides(r, s).contains(&true)
+        }
+        (Shape::Region(r), Shape::Disk(d)) | (Shape::Disk(d), Shape::Region(r)) => {
+            boundary_distance_squared(r, d.center) == d.radius * d.radius && !r.contains(d.center)
+        }
+        (Shape::Disk(c), Shape::Disk(d)) => {
+            let reach = c.radius + d.radius;
+            c.center.distance_squared(&d.center) == reach * reach
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! impl_shape_intersects {
+    ($a:ident, $b:ident) => {
+        impl<T: Scalar> Intersects<$b<T>> for $a<T> {
+            fn intersects(&self, other: &$b<T>) -> bool {
+                shape_intersects(&self.shape(), &other.shape())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! impl_shape_predicates {
+    ($a:ident, $b:ident) => {
+        impl<T: Scalar> Contains<$b<T>> for $a<T> {
+            fn contains(&self, other: &$b<T>) -> bool {
+                shape_contains(&self.shape(), &other.shape())
+            }
+        }
+
+        impl<T: Scalar> Touches<$b<T>> for $a<T> {
+            fn touches(&self, other: &$b<T>) -> bool {
+                shape_touches(&self.shape(), &other.shape())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "alloc")]
+for_pairs!(impl_shape_intersects;
+    [Polyline, Polygon] x [Point, LineSegment, Polyline, Polygon, Circle, Rect]);
+#[cfg(feature = "alloc")]
+for_pairs!(impl_shape_intersects; [Point, LineSegment, Circle, Rect] x [Polyline, Polygon]);
+#[cfg(feature = "alloc")]
+for_pairs!(impl_shape_predicates;
+    [Point, LineSegment, Polyline, Polygon, Circle, Rect]
+    x [Point, LineSegment, Polyline, Polygon, Circle, Rect]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(lo: f64, hi: f64) -> Vec<Point> {
+        vec![
+            Point { x: lo, y: lo },
+            Point { x: hi, y: lo },
+            Point { x: hi, y: hi },
+            Point { x: lo, y: hi },
+        ]
+    }
+
+    fn seg(s: &str) -> LineSegment {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_intersects_is_symmetric() {
+        let poly = Polygon::new(square(0.0, 2.0));
+        let line = Polyline::new(vec![Point { x: 3.0, y: 3.0 }, Point { x: 2.0, y: 1.0 }]);
+        let far = Circle::new(Point { x: 5.0, y: 2.4 }, 1.0);
+        let rect = Rect::from_corners(Point { x: 1.0, y: 1.0 }, Point { x: 6.0, y: 1.5 });
+        assert!(poly.intersects(&line) && line.intersects(&poly));
+        assert!(!poly.intersects(&far) && !far.intersects(&poly));
+        assert!(rect.intersects(&far) && far.intersects(&rect));
+        assert!(rect.intersects(&line) && line.intersects(&rect));
+        assert!(!line.intersects(&far) && !far.intersects(&line));
+        // A segment inside the polygon meets it without crossing its boundary
+        assert!(seg("(0.5, 0.5) -> (1, 1)").intersects(&poly))
+    }
+
+    #[test]
+    fn test_boundaries_touch() {
+        let poly = Polygon::new(square(0.0, 2.0));
+        let corner = Point { x: 2.0, y: 2.0 };
+        assert!(corner.touches(&poly) && corner.within(&poly));
+        assert!(!Point { x: 1.0, y: 1.0 }.touches(&poly));
+        // Squares sharing an edge touch, overlapping ones do not
+        let beside = Polygon::new(square(2.0, 3.0));
+        assert!(poly.touches(&beside) && !beside.within(&poly));
+        let overlapping = Rect::from_corners(Point { x: 1.0, y: 1.0 }, corner);
+        assert!(!poly.touches(&overlapping) && overlapping.within(&poly));
+        // A tangent circle touches, and a circle inscribed in the square is within it
+        assert!(Circle::new(Point { x: 3.0, y: 1.0 }, 1.0).touches(&poly));
+        let inscribed = Circle::new(Point { x: 1.0, y: 1.0 }, 1.0);
+        assert!(inscribed.within(&poly) && !inscribed.touches(&poly))
+    }
+
+    #[test]
+    fn test_holes() {
+        let hole = square(1.0, 3.0);
+        let frame = Polygon::with_holes(square(0.0, 4.0), vec![hole.clone()]);
+        let plug = Polygon::new(hole);
+        assert!(plug.touches(&frame) && !plug.within(&frame));
+        assert!(!Circle::new(Point { x: 2.0, y: 2.0 }, 0.5).intersects(&frame));
+        assert!(Circle::new(Point { x: 2.0, y: 2.0 }, 1.0).touches(&frame));
+        // The frame's outline is inside the square it was cut from, but not all of it
+        let block = Polygon::new(square(0.0, 4.0));
+        assert!(frame.within(&block) && !Contains::contains(&frame, &block));
+        assert!(seg("(0.5, 0.5) -> (3.5, 0.5)").within(&frame));
+        assert!(!seg("(0.5, 0.5) -> (3.5, 3.5)").within(&frame))
+    }
+
+    #[test]
+    fn test_curves() {
+        let zigzag = Polyline::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 3.0, y: 1.0 },
+        ]);
+        // A segment spanning several collinear pieces is still covered
+        assert!(zigzag.contains(&seg("(0.5, 0) -> (2, 0)")));
+        assert!(!zigzag.contains(&seg("(0.5, 0) -> (2.5, 0)")));
+        assert!(seg("(3, 1) -> (4, 0)").touches(&zigzag));
+        assert!(!seg("(1, -1) -> (1, 1)").touches(&zigzag));
+        assert!(seg("(1, -1) -> (1, 0)").touches(&zigzag));
+        // A closed polyline has no boundary to touch at
+        let ring: Polyline = square(0.0, 1.0)
+            .into_iter()
+            .chain(Some(Point::origin()))
+            .collect();
+        assert!(!Point::origin().touches(&ring) && Point::origin().within(&ring))
+    }
+
+    #[test]
+    fn test_degenerate_shapes() {
+        let flat = Rect::from_corners(Point { x: 0.0, y: 1.0 }, Point { x: 2.0, y: 1.0 });
+        let poly = Polygon::new(square(0.0, 1.0));
+        assert!(flat.touches(&poly) && !flat.within(&poly));
+        assert!(seg("(1, 1) -> (2, 1)").within(&flat));
+        let dot = Circle::new(Point { x: 1.0, y: 1.0 }, 0.0);
+        assert!(dot.touches(&poly) && dot.within(&flat));
+        assert!(!Polyline::<f64>::default().intersects(&poly))
+    }
+}
@@ -0,0 +1,212 @@
+use crate::predicates::orient2d;
+use crate::{Circle, LineSegment, Point, Scalar};
+
+/// A triangle with corners `a`, `b` and `c`, in either winding order. The boundary
+/// counts as part of the triangle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Triangle<T = f64> {
+    pub a: Point<T>,
+    pub b: Point<T>,
+    pub c: Point<T>,
+}
+
+impl<T: Scalar> Triangle<T> {
+    pub fn new(a: Point<T>, b: Point<T>, c: Point<T>) -> Triangle<T> {
+        Triangle { a, b, c }
+    }
+
+    /// The area, positive when the corners run counter-clockwise and negative when
+    /// they run clockwise
+    /// ```
+    /// use rsgeo::{Point, Triangle};
+    /// let t = Triangle::new(Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 0.0, y: 3.0});
+    /// assert_eq!(t.signed_area(), 6.0);
+    /// let flipped = Triangle::new(t.a, t.c, t.b);
+    /// assert_eq!(flipped.signed_area(), -6.0);
+    /// assert_eq!(flipped.area(), 6.0);
+    /// ```
+    pub fn signed_area(&self) -> T {
+        (self.b - self.a).cross(&(self.c - self.a)) / T::from_f64(2.0)
+    }
+
+    pub fn area(&self) -> T {
+        self.signed_area().abs()
+    }
+
+    pub fn perimeter(&self) -> T {
+        self.a.distance(&self.b) + self.b.distance(&self.c) + self.c.distance(&self.a)
+    }
+
+    /// The center of mass, where the medians meet
+    pub fn centroid(&self) -> Point<T> {
+        (self.a + self.b + self.c) / T::from_f64(3.0)
+    }
+
+    /// The edges `ab`, `bc` and `ca`
+    pub fn edges(&self) -> [LineSegment<T>; 3] {
+        [
+            LineSegment::new(self.a, self.b),
+            LineSegment::new(self.b, self.c),
+            LineSegment::new(self.c, self.a),
+        ]
+    }
+
+    /// Whether the corners are collinear, which is decided exactly
+    pub fn is_degenerate(&self) -> bool {
+        orient2d(self.a, self.b, self.c) == 0.0
+    }
+
+    /// The barycentric coordinates `[u, v, w]` of `p`, so that
+    /// `p = u * a + v * b + w * c` and `u + v + w = 1`. Returns None for a degenerate
+    /// triangle.
+    /// ```
+    /// use rsgeo::{Point, Triangle};
+    /// let t = Triangle::new(Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 0.0, y: 4.0});
+    /// assert_eq!(t.barycentric(Point{x: 1.0, y: 2.0}), Some([0.25, 0.25, 0.5]));
+    /// ```
+    pub fn barycentric(&self, p: Point<T>) -> Option<[T; 3]> {
+        if self.is_degenerate() {
+            return None;
+        }
+        let ab = self.b - self.a;
+        let ac = self.c - self.a;
+        let ap = p - self.a;
+        let d = ab.cross(&ac);
+        let v = ap.cross(&ac) / d;
+        let w = ab.cross(&ap) / d;
+        Some([T::one() - v - w, v, w])
+    }
+
+    /// Whether `p` is inside the triangle or on its boundary, i.e. whether none of its
+    /// barycentric coordinates are negative. Each coordinate has the sign of the
+    /// triangle formed by `p` and the opposite edge, so the signs are decided exactly
+    /// with `orient2d` rather than from the rounded coordinates. A degenerate triangle
+    /// contains the points on its edges.
+    /// ```
+    /// use rsgeo::{Point, Triangle};
+    /// let t = Triangle::new(Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 0.0, y: 4.0});
+    /// assert!(t.contains(Point{x: 2.0, y: 2.0}));
+    /// assert!(!t.contains(Point{x: 2.0, y: 2.1}));
+    /// ```
+    pub fn contains(&self, p: Point<T>) -> bool {
+        let whole = orient2d(self.a, self.b, self.c);
+        if whole == 0.0 {
+            return self.edges().iter().any(|e| e.contains_point(p, T::zero()));
+        }
+        [
+            orient2d(p, self.b, self.c),
+            orient2d(self.a, p, self.c),
+            orient2d(self.a, self.b, p),
+        ]
+        .iter()
+        .all(|&part| part == 0.0 || (part > 0.0) == (whole > 0.0))
+    }
+
+    /// The circle through all three corners, or None for a degenerate triangle
+    pub fn circumcircle(&self) -> Option<Circle<T>> {
+        Circle::from_three_points(self.a, self.b, self.c)
+    }
+
+    /// The largest circle inside the triangle, which touches all three edges. Returns
+    /// None for a degenerate triangle.
+    /// ```
+    /// use rsgeo::{Point, Triangle};
+    /// // A 3-4-5 right triangle has an inradius of 1
+    /// let t = Triangle::new(Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 0.0, y: 3.0});
+    /// let c = t.incircle().unwrap();
+    /// assert_eq!(c.center, Point{x: 1.0, y: 1.0});
+    /// assert_eq!(c.radius, 1.0);
+    /// ```
+    pub fn incircle(&self) -> Option<Circle<T>> {
+        if self.is_degenerate() {
+            return None;
+        }
+        // The incenter weights each corner by the length of the opposite side
+        let la = self.b.distance(&self.c);
+        let lb = self.c.distance(&self.a);
+        let lc = self.a.distance(&self.b);
+        let perimeter = la + lb + lc;
+        let center = (self.a * la + self.b * lb + self.c * lc) / perimeter;
+        let radius = T::from_f64(2.0) * self.area() / perimeter;
+        Some(Circle::new(center, radius))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalene() -> Triangle {
+        Triangle::new(
+            Point { x: 1.0, y: -1.0 },
+            Point { x: 7.0, y: 2.0 },
+            Point { x: 2.0, y: 5.0 },
+        )
+    }
+
+    #[test]
+    fn test_barycentric_reconstructs_point() {
+        let t = scalene();
+        let p = Point { x: 3.5, y: 1.25 };
+        let [u, v, w] = t.barycentric(p).unwrap();
+        assert!(crate::scalar_isclose(1.0, u + v + w));
+        assert!((t.a * u + t.b * v + t.c * w).isclose(p));
+        let [u, v, w] = t.barycentric(t.centroid()).unwrap();
+        assert!(crate::scalar_isclose(u, v) && crate::scalar_isclose(v, w))
+    }
+
+    #[test]
+    fn test_contains_matches_barycentric() {
+        let t = scalene();
+        let flipped = Triangle::new(t.a, t.c, t.b);
+        for i in 0..20 {
+            for j in 0..20 {
+                let p = Point {
+                    x: f64::from(i) * 0.4,
+                    y: f64::from(j) * 0.4 - 1.5,
+                };
+                let inside = t.barycentric(p).unwrap().iter().all(|&x| x >= 0.0);
+                assert_eq!(inside, t.contains(p));
+                assert_eq!(inside, flipped.contains(p));
+            }
+        }
+        assert!(t.contains(t.a) && t.contains(t.b.midpoint(&t.c)))
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let t = Triangle::new(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 3.0, y: 3.0 },
+        );
+        assert!(t.is_degenerate());
+        assert_eq!(0.0, t.area());
+        assert_eq!(None, t.barycentric(Point { x: 0.5, y: 0.5 }));
+        assert_eq!(None, t.circumcircle());
+        assert_eq!(None, t.incircle());
+        assert!(t.contains(Point { x: 2.0, y: 2.0 }));
+        assert!(!t.contains(Point { x: 2.0, y: 2.1 }))
+    }
+
+    #[test]
+    fn test_circumcircle_and_incircle() {
+        let t = scalene();
+        let outer = t.circumcircle().unwrap();
+        for p in &[t.a, t.b, t.c] {
+            assert!(crate::scalar_isclose(
+                outer.radius,
+                outer.center.distance(p)
+            ));
+        }
+        let inner = t.incircle().unwrap();
+        assert!(t.contains(inner.center));
+        for e in &t.edges() {
+            assert!(crate::scalar_isclose(
+                inner.radius,
+                e.distance_to_point(inner.center)
+            ));
+        }
+    }
+}
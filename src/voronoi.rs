@@ -0,0 +1,411 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::clip::clip_ring;
+use crate::delaunay::delaunay_triangulation;
+use crate::{
+    clip_polygon_convex, BooleanOps, BoundingRect, Circle, MultiPolygon, OrderedPoint, Point,
+    Polygon, Scalar,
+};
+
+/// The Voronoi diagram of a set of sites, which divides the plane into a cell for
+/// each site holding the points nearer to it than to any other
+#[derive(Debug, Clone, PartialEq)]
+pub struct Voronoi<T = f64> {
+    pub sites: Vec<Point<T>>,
+    /// The points equally near three or more sites, where the cells meet
+    pub vertices: Vec<Point<T>>,
+    pub edges: Vec<VoronoiEdge<T>>,
+    /// The cell of each site, in the same order as the sites
+    pub cells: Vec<VoronoiCell>,
+}
+
+/// A piece of the boundary between two Voronoi cells, which lies along the
+/// perpendicular bisector of their sites
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoronoiEdge<T = f64> {
+    /// The sites either side of the edge, with the first on its left
+    pub sites: [usize; 2],
+    /// The vertices the edge runs between. A ray out to infinity has only a start,
+    /// and when all the sites are on one line the edges are whole lines with neither.
+    pub ends: [Option<usize>; 2],
+    /// The direction the edge runs in, which is not normalized
+    pub direction: Point<T>,
+}
+
+/// The cell of one site, given by indices into the vertices and edges of the diagram
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VoronoiCell {
+    /// The corners of the cell counter-clockwise
+    pub vertices: Vec<usize>,
+    /// The edges around the cell counter-clockwise. An unbounded cell comes in from
+    /// infinity along the first and goes out along the last.
+    pub edges: Vec<usize>,
+    /// Whether the cell is finite, which it is unless its site is on the convex hull
+    pub bounded: bool,
+}
+
+impl<T: Scalar> Voronoi<T> {
+    /// The cell of `site` as a counter-clockwise polygon, or None if it is unbounded
+    pub fn cell_polygon(&self, site: usize) -> Option<Polygon<T>> {
+        let cell = &self.cells[site];
+        if !cell.bounded {
+            return None;
+        }
+        Some(Polygon::new(
+            cell.vertices.iter().map(|&v| self.vertices[v]).collect(),
+        ))
+    }
+
+    /// The cell of each site cut down to the part inside `bounds`, so that every cell
+    /// is finite. Unbounded cells are first cut from a rectangle around the bounds by
+    /// the bisectors along their edges. Against convex bounds each cell is then cut by
+    /// `clip_polygon_convex`, and otherwise it is intersected with the bounds by
+    /// `BooleanOps`, which may leave it in several pieces. Cells that miss the bounds,
+    /// and those of repeated sites, are empty.
+    /// ```
+    /// use rsgeo::{voronoi_diagram, Area, Point, Polygon};
+    /// let sites = [Point{x: 1.0, y: 1.0}, Point{x: 3.0, y: 1.0}];
+    /// let bounds = Polygon::new(vec![
+    ///     Point{x: 0.0, y: 0.0}, Point{x: 4.0, y: 0.0}, Point{x: 4.0, y: 3.0}, Point{x: 0.0, y: 3.0},
+    /// ]);
+    /// let cells = voronoi_diagram(&sites).clipped_cells(&bounds);
+    /// // Each site gets the half of the rectangle on its side of x = 2
+    /// assert_eq!(cells[0].area(), 6.0);
+    /// assert_eq!(cells[1].area(), 6.0);
+    /// ```
+    pub fn clipped_cells(&self, bounds: &Polygon<T>) -> Vec<MultiPolygon<T>> {
+        let rect = match bounds.bounding_rect() {
+            Some(rect) => rect,
+            None => return alloc::vec![MultiPolygon::default(); self.sites.len()],
+        };
+        // Any rectangle holding the bounds will do in place of the whole plane
+        let frame = rect
+            .expand(rect.width() + rect.height() + T::one())
+            .corners();
+        let convex = bounds.is_convex();
+        (0..self.sites.len())
+            .map(|s| {
+                let cell = match self.cell_polygon(s) {
+                    Some(cell) => cell,
+                    None => Polygon::new(self.unbounded_cell(s, &frame)),
+                };
+                if cell.vertices().is_empty() {
+                    MultiPolygon::default()
+                } else if convex {
+                    let piece = clip_polygon_convex(&cell, bounds);
+                    if piece.vertices().is_empty() {
+                        MultiPolygon::default()
+                    } else {
+                        piece.into()
+                    }
+                } else {
+                    cell.intersection(bounds)
+                }
+            })
+            .collect()
+    }
+
+    /// The part of the unbounded cell of `site` inside the convex `frame`. A cell with
+    /// no edges is the whole plane if its site comes first, as every site is then at
+    /// the same place, and otherwise belongs to a repeated site and is empty.
+    fn unbounded_cell(&self, site: usize, frame: &[Point<T>]) -> Vec<Point<T>> {
+        let cell = &self.cells[site];
+        if cell.edges.is_empty() && site > 0 {
+            return Vec::new();
+        }
+        // The side of each edge's bisector that is nearer the site, on the left
+        let halves: Vec<(Point<T>, Point<T>)> = cell
+            .edges
+            .iter()
+            .map(|&e| {
+                let edge = &self.edges[e];
+                let [left, right] = edge.sites;
+                let mid = self.sites[left].midpoint(&self.sites[right]);
+                if left == site {
+                    (mid, mid + edge.direction)
+                } else {
+                    (mid + edge.direction, mid)
+                }
+            })
+            .collect();
+        clip_ring(frame, &halves)
+    }
+
+    /// Divide the plane between sites that all lie on one line, so that the edges are
+    /// the parallel lines between each site and the next
+    fn along_line(&mut self) {
+        let mut first = BTreeMap::new();
+        for (i, &p) in self.sites.iter().enumerate() {
+            first.entry(OrderedPoint::from(p)).or_insert(i);
+        }
+        // Ordered points on a line run along it
+        let order: Vec<usize> = first.into_values().collect();
+        for pair in order.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            self.cells[a].edges.push(self.edges.len());
+            self.cells[b].edges.push(self.edges.len());
+            self.edges.push(VoronoiEdge {
+                sites: [b, a],
+                ends: [None, None],
+                direction: (self.sites[a] - self.sites[b]).perp(),
+            });
+        }
+    }
+}
+
+/// The Voronoi diagram of `sites`, found as the dual of their Delaunay triangulation
+/// in O(n log n) time. Each Voronoi vertex is the center of the circle through the
+/// corners of a Delaunay triangle, and where several triangles share a circle their
+/// centers are merged into one vertex. A site that repeats an earlier one gets an
+/// empty cell.
+/// ```
+/// use rsgeo::{voronoi_diagram, Point};
+/// let sites: Vec<Point> = (0..9)
+///     .map(|i| Point{x: (i % 3) as f64, y: (i / 3) as f64})
+///     .collect();
+/// let voronoi = voronoi_diagram(&sites);
+/// // Only the middle site is surrounded, and its cell is a unit square
+/// assert_eq!(voronoi.vertices.len(), 4);
+/// assert_eq!(voronoi.cells.iter().filter(|c| c.bounded).count(), 1);
+/// assert_eq!(voronoi.cell_polygon(4).unwrap().area(), 1.0);
+/// ```
+pub fn voronoi_diagram<T: Scalar>(sites: &[Point<T>]) -> Voronoi<T> {
+    let mesh = delaunay_triangulation(sites);
+    let mut voronoi = Voronoi {
+        sites: sites.to_vec(),
+        vertices: Vec::new(),
+        edges: Vec::new(),
+        cells: alloc::vec![VoronoiCell::default(); sites.len()],
+    };
+    if mesh.triangles.is_empty() {
+        voronoi.along_line();
+        return voronoi;
+    }
+    let mut merged: BTreeMap<OrderedPoint<T>, usize> = BTreeMap::new();
+    let vertex: Vec<usize> = mesh
+        .triangles
+        .iter()
+        .map(|&[a, b, c]| {
+            // Delaunay triangles are never flat, so always have a circumcircle
+            let center = Circle::from_three_points(sites[a], sites[b], sites[c])
+                .unwrap()
+                .center;
+            let next = voronoi.vertices.len();
+            let i = *merged.entry(center.into()).or_insert(next);
+            if i == next {
+                voronoi.vertices.push(center);
+            }
+            i
+        })
+        .collect();
+    // The Voronoi edge across each Delaunay edge, running from the triangle on its
+    // left to the one on its right, or out past the hull
+    let mut dual = BTreeMap::new();
+    for (t, v) in mesh.triangles.iter().enumerate() {
+        for i in 0..3 {
+            let n = mesh.neighbors[t][i];
+            let ends = [Some(vertex[t]), n.map(|n| vertex[n])];
+            if n.is_some_and(|n| n < t) || ends[0] == ends[1] {
+                continue;
+            }
+            let (a, b) = (v[(i + 1) % 3], v[(i + 2) % 3]);
+            dual.insert((a.min(b), a.max(b)), voronoi.edges.len());
+            voronoi.edges.push(VoronoiEdge {
+                sites: [b, a],
+                ends,
+                direction: (sites[a] - sites[b]).perp(),
+            });
+        }
+    }
+    let edge = |a: usize, b: usize| dual.get(&(a.min(b), a.max(b))).copied();
+    for (s, fan) in mesh.fans(sites.len()).into_iter().enumerate() {
+        let &(first, i) = match fan.first() {
+            Some(first) => first,
+            None => continue,
+        };
+        let cell = &mut voronoi.cells[s];
+        cell.bounded = mesh.neighbors[first][(i + 2) % 3].is_some();
+        if !cell.bounded {
+            cell.edges
+                .extend(edge(s, mesh.triangles[first][(i + 1) % 3]));
+        }
+        for &(t, i) in &fan {
+            if cell.vertices.last() != Some(&vertex[t]) {
+                cell.vertices.push(vertex[t]);
+            }
+            cell.edges.extend(edge(s, mesh.triangles[t][(i + 2) % 3]));
+        }
+        if cell.bounded && cell.vertices.len() > 1 && cell.vertices.first() == cell.vertices.last()
+        {
+            cell.vertices.pop();
+        }
+    }
+    voronoi
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{convex_hull, scalar_isclose, scattered};
+
+    /// Whether `p` is at least as near to site `s` as to any other, allowing for
+    /// rounding
+    fn nearest(sites: &[Point], s: usize, p: Point) -> bool {
+        let d = p.distance(&sites[s]);
+        sites.iter().all(|q| d <= p.distance(q) + 1e-9)
+    }
+
+    #[test]
+    fn test_cells_are_nearest() {
+        let sites = scattered(200, 11);
+        let voronoi = voronoi_diagram(&sites);
+        let hull = convex_hull(&sites, true).vertices().len();
+        assert_eq!(hull, voronoi.cells.iter().filter(|c| !c.bounded).count());
+        for (s, cell) in voronoi.cells.iter().enumerate() {
+            for &v in &cell.vertices {
+                assert!(nearest(&sites, s, voronoi.vertices[v]));
+            }
+            if let Some(polygon) = voronoi.cell_polygon(s) {
+                assert!(polygon.is_convex());
+                assert!(polygon.contains(sites[s]));
+                assert_eq!(cell.vertices.len(), cell.edges.len());
+            } else {
+                assert_eq!(cell.vertices.len() + 1, cell.edges.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_edges_between_sites() {
+        let sites = scattered(100, 4);
+        let voronoi = voronoi_diagram(&sites);
+        for (e, edge) in voronoi.edges.iter().enumerate() {
+            let [left, right] = edge.sites;
+            assert!(voronoi.cells[left].edges.contains(&e));
+            assert!(voronoi.cells[right].edges.contains(&e));
+            let start = voronoi.vertices[edge.ends[0].unwrap()];
+            // A point partway along the edge, or out along a ray
+            let p = match edge.ends[1] {
+                Some(end) => start.midpoint(&voronoi.vertices[end]),
+                None => start + edge.direction,
+            };
+            assert!((p - start).cross(&(sites[left] - start)) > 0.0);
+            assert!(nearest(&sites, left, p) && nearest(&sites, right, p));
+        }
+    }
+
+    #[test]
+    fn test_cocircular_sites() {
+        let mut sites: Vec<Point> = (0..25)
+            .map(|i| Point {
+                x: (i % 5) as f64,
+                y: (i / 5) as f64,
+            })
+            .collect();
+        sites.push(sites[12]);
+        let voronoi = voronoi_diagram(&sites);
+        // One vertex at the middle of each square, and one edge for each pair of sites
+        // side by side, with none of zero length
+        assert_eq!(16, voronoi.vertices.len());
+        assert_eq!(40, voronoi.edges.len());
+        for s in [6, 7, 8, 11, 12, 13, 16, 17, 18] {
+            assert_eq!(4, voronoi.cells[s].vertices.len());
+            assert_eq!(1.0, voronoi.cell_polygon(s).unwrap().area());
+        }
+        assert_eq!(VoronoiCell::default(), voronoi.cells[25]);
+    }
+
+    #[test]
+    fn test_sites_on_a_line() {
+        let sites: Vec<Point> = [3.0, 1.0, 2.0, 1.0]
+            .iter()
+            .map(|&t| Point { x: t, y: 2.0 * t })
+            .collect();
+        let voronoi = voronoi_diagram(&sites);
+        assert!(voronoi.vertices.is_empty());
+        assert_eq!(2, voronoi.edges.len());
+        assert!(voronoi.edges.iter().all(|e| e.ends == [None, None]));
+        assert_eq!(vec![1], voronoi.cells[0].edges);
+        assert_eq!(2, voronoi.cells[2].edges.len());
+        assert!(voronoi.cells.iter().all(|c| !c.bounded));
+        assert!(voronoi_diagram(&sites[..1]).edges.is_empty());
+    }
+
+    fn rectangle(w: f64, h: f64) -> Polygon {
+        Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: w, y: 0.0 },
+            Point { x: w, y: h },
+            Point { x: 0.0, y: h },
+        ])
+    }
+
+    #[test]
+    fn test_clipped_cells_cover_bounds() {
+        let mut sites = scattered(150, 9);
+        // Sites outside the bounds can still reach into them, or miss them entirely
+        sites.push(Point { x: 5.0, y: -0.5 });
+        sites.push(Point { x: 40.0, y: 40.0 });
+        let voronoi = voronoi_diagram(&sites);
+        let bounds = rectangle(10.0, 10.0);
+        let cells = voronoi.clipped_cells(&bounds);
+        let total: f64 = cells.iter().map(|c| c.area()).sum();
+        assert!(scalar_isclose(total, 100.0));
+        for (s, cell) in cells.iter().enumerate().take(150) {
+            assert_eq!(1, cell.polygons().len());
+            assert!(cell.polygons()[0].contains(sites[s]));
+            if let Some(polygon) = voronoi.cell_polygon(s) {
+                if polygon.vertices().iter().all(|&v| bounds.contains(v)) {
+                    assert!(scalar_isclose(polygon.area(), cell.area()));
+                }
+            }
+        }
+        assert!(cells[150].area() > 0.0);
+        assert!(cells[151].polygons().is_empty());
+    }
+
+    #[test]
+    fn test_clipped_to_concave_bounds() {
+        // A U whose arms are both nearest the site above the gap between them
+        let bounds = Polygon::new(vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 6.0, y: 0.0 },
+            Point { x: 6.0, y: 4.0 },
+            Point { x: 4.0, y: 4.0 },
+            Point { x: 4.0, y: 1.0 },
+            Point { x: 2.0, y: 1.0 },
+            Point { x: 2.0, y: 4.0 },
+            Point { x: 0.0, y: 4.0 },
+        ]);
+        let sites = [Point { x: 3.0, y: 3.0 }, Point { x: 3.0, y: 0.5 }];
+        let cells = voronoi_diagram(&sites).clipped_cells(&bounds);
+        assert_eq!(2, cells[0].polygons().len());
+        assert_eq!(1, cells[1].polygons().len());
+        assert!(scalar_isclose(
+            cells[0].area() + cells[1].area(),
+            bounds.area()
+        ));
+        // The boundary between them is the line y = 1.75
+        assert!(scalar_isclose(cells[1].area(), 6.0 * 1.75 - 2.0 * 0.75));
+    }
+
+    #[test]
+    fn test_clipped_cells_on_a_line() {
+        let bounds = rectangle(6.0, 2.0);
+        let sites = [
+            Point { x: 1.0, y: 1.0 },
+            Point { x: 3.0, y: 1.0 },
+            Point { x: 5.0, y: 1.0 },
+            Point { x: 3.0, y: 1.0 },
+        ];
+        let areas: Vec<f64> = voronoi_diagram(&sites)
+            .clipped_cells(&bounds)
+            .iter()
+            .map(|c| c.area())
+            .collect();
+        assert_eq!(vec![4.0, 4.0, 4.0, 0.0], areas);
+        let lone = voronoi_diagram(&sites[..1]).clipped_cells(&bounds);
+        assert_eq!(12.0, lone[0].area());
+    }
+}